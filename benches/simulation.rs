@@ -0,0 +1,136 @@
+//! How fast whole games can be simulated and copies of a game played out, and how many heap allocations each
+//! takes.  Compare the default build against the `perf` feature, which keeps hands and the current trick
+//! inline, so that copying a game allocates less:
+//!
+//! ```text
+//! cargo bench --bench simulation
+//! cargo bench --bench simulation --features perf
+//! ```
+
+extern crate spades;
+
+use spades::{Bet, Card, Game, GameLength, GameOptions, Uid};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// The system allocator, counting every allocation made through it.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+const GAMES: u64 = 2000;
+const ROUNDS: u32 = 5;
+const ROLLOUTS: usize = 20000;
+
+/// Plays a game of `ROUNDS` rounds, everyone betting 3 and playing their first legal card.  Returns the
+/// cards played and the allocations made while playing them.
+fn play_game(seed: u64) -> (usize, usize) {
+    let mut g = Game::default();
+    g.assign_players(Uid(1), [Uid(10), Uid(11), Uid(12), Uid(13)]);
+    g.set_options(GameOptions {
+        length: GameLength::fixed_rounds(ROUNDS).unwrap(),
+        ..GameOptions::default()
+    })
+    .unwrap();
+    g.set_shuffle_seed(Some(seed));
+    g.start_game();
+    let (mut cards, mut allocations) = (0, 0);
+    while g.state().is_betting() || g.playable_cards().next().is_some() {
+        if g.state().is_betting() {
+            g.place_bet(Bet::Amount(3));
+            continue;
+        }
+        let card = g.playable_cards().next().unwrap();
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        g.play_card(card);
+        allocations += ALLOCATIONS.load(Ordering::Relaxed) - before;
+        cards += 1;
+    }
+    (cards, allocations)
+}
+
+fn next_card(g: &Game) -> Option<Card> {
+    g.playable_cards().next()
+}
+
+/// Copies a game halfway through its first round and plays the copy to the end of the round, the way a
+/// search tries out a move, `ROLLOUTS` times.  Returns the allocations made.
+fn rollouts() -> usize {
+    let mut g = Game::default();
+    g.assign_players(Uid(1), [Uid(10), Uid(11), Uid(12), Uid(13)]);
+    g.set_shuffle_seed(Some(1));
+    g.start_game();
+    for _ in 0..4 {
+        g.place_bet(Bet::Amount(3));
+    }
+    for _ in 0..24 {
+        let card = g.playable_cards().next().unwrap();
+        g.play_card(card);
+    }
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..ROLLOUTS {
+        let mut rollout = g.clone();
+        // the round is over once there is nothing left to play, with the next round's betting begun
+        while let Some(card) = next_card(&rollout) {
+            rollout.play_card(card);
+        }
+    }
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn main() {
+    let feature = if cfg!(feature = "perf") {
+        "perf"
+    } else {
+        "default"
+    };
+    let (mut cards, mut allocations) = (0, 0);
+    let started = Instant::now();
+    for seed in 0..GAMES {
+        let (played, allocated) = play_game(seed);
+        cards += played;
+        allocations += allocated;
+    }
+    let elapsed = started.elapsed();
+    println!(
+        "{}: {} games of {} rounds in {:.2?}, {:.0} games/s; {:.2} allocations per card played",
+        feature,
+        GAMES,
+        ROUNDS,
+        elapsed,
+        GAMES as f64 / elapsed.as_secs_f64(),
+        allocations as f64 / cards as f64
+    );
+
+    let started = Instant::now();
+    let allocations = rollouts();
+    let elapsed = started.elapsed();
+    println!(
+        "{}: {} rollouts of half a round in {:.2?}, {:.0} rollouts/s; {:.1} allocations per rollout",
+        feature,
+        ROLLOUTS,
+        elapsed,
+        ROLLOUTS as f64 / elapsed.as_secs_f64(),
+        allocations as f64 / ROLLOUTS as f64
+    );
+}
@@ -0,0 +1,73 @@
+//! Serves games of spades over HTTP with the crate's REST adapter.
+//!
+//! Run with `cargo run --example http_server --features http`, then for instance:
+//!
+//! ```text
+//! curl -X POST localhost:9002/games -d '{}'
+//! curl localhost:9002/games/1/view -H 'Authorization: Bearer <token>'
+//! curl -X POST localhost:9002/games/1/actions -H 'Authorization: Bearer <token>' -d '{"Bet": {"Amount": 3}}'
+//! ```
+//!
+//! The routes are described in the `spades::http` module.  This server reads one request per connection and
+//! is only meant to show the wiring; a real deployment would put `GameServer::handle` behind its web
+//! framework of choice.
+extern crate spades;
+
+use spades::http::GameServer;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+const ADDRESS: &str = "127.0.0.1:9002";
+
+fn serve(server: &mut GameServer, stream: TcpStream) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let path = parts.next().unwrap_or("/").to_string();
+
+    let mut authorization = None;
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            match name.trim().to_ascii_lowercase().as_str() {
+                "authorization" => authorization = Some(value.trim().to_string()),
+                "content-length" => length = value.trim().parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8_lossy(&body);
+
+    let response = server.handle(&method, &path, authorization.as_deref(), &body);
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        if response.status < 400 { "OK" } else { "Error" },
+        response.body.len(),
+        response.body
+    )
+}
+
+fn main() -> io::Result<()> {
+    let listener = TcpListener::bind(ADDRESS)?;
+    println!("serving spades on http://{}", ADDRESS);
+    let mut server = GameServer::new();
+    for stream in listener.incoming() {
+        if let Err(err) = stream.and_then(|stream| serve(&mut server, stream)) {
+            eprintln!("request failed: {}", err);
+        }
+    }
+    Ok(())
+}
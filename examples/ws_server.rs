@@ -0,0 +1,320 @@
+//! Hosts a single game of spades over WebSockets.
+//!
+//! Run with `cargo run --example ws_server`, then connect four clients to `ws://127.0.0.1:9001`.
+//!
+//! Every message is a JSON object with a `type` field.  Clients send:
+//!
+//! * `{"type": "join", "player": 1}` to take a seat; the seats are `Uid(1)` to `Uid(4)`.  The first join is
+//!   answered with `{"type": "joined", "player": 1, "token": "..."}`, and reconnecting to the seat takes
+//!   `{"type": "join", "player": 1, "token": "..."}` with that token
+//! * `{"type": "show_hand"}` to see their hand while betting, which rules out a blind nil bet
+//! * `{"type": "bet", "bet": {"Amount": 3}}`, `{"type": "bet", "bet": "Nil"}` or `{"type": "bet", "bet": "BlindNil"}`
+//! * `{"type": "play", "card": 31}` where the card uses the crate's serde encoding (`rank + 15 * suit`)
+//!
+//! The server answers with `{"type": "view", ...}` messages, holding only what that player is allowed to see,
+//! and `{"type": "error", "message": "..."}` when an action is rejected.  Whenever the game moves on, every
+//! connected player is sent a fresh view.  Views leave out the player's hand during betting until they ask
+//! for it.  A player who drops can reconnect by sending `join` again with their seat's token; the new
+//! connection replaces the old one and immediately receives the current view.
+extern crate rand;
+extern crate serde;
+extern crate serde_json;
+extern crate spades;
+extern crate tungstenite;
+
+use spades::{Bet, Card, Game, SpadesError, State, Uid};
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tungstenite::{Message, WebSocket};
+
+const ADDRESS: &str = "127.0.0.1:9001";
+const PLAYER_IDS: [Uid; 4] = [Uid(1), Uid(2), Uid(3), Uid(4)];
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Join {
+        player: u64,
+        #[serde(default)]
+        token: Option<String>,
+    },
+    ShowHand,
+    Bet {
+        bet: Bet,
+    },
+    Play {
+        card: Card,
+    },
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    Joined { player: Uid, token: String },
+    View(PlayerView),
+    Error { message: String },
+}
+
+/// What a single player is allowed to know about the table.
+#[derive(Debug, serde::Serialize)]
+struct PlayerView {
+    player: Uid,
+    state: String,
+    current_player: Option<Uid>,
+    your_turn: bool,
+    /// Left out while betting until the player asks for it, so they can still bet blind nil.
+    hand: Option<Vec<Card>>,
+    leading_suit: Option<String>,
+    scores: Option<[i32; 2]>,
+    bags: Option<[u8; 2]>,
+    winners: Option<(Uid, Uid)>,
+}
+
+impl PlayerView {
+    fn new(game: &Game, player: Uid, show_hand: bool) -> PlayerView {
+        let current_player = game.current_player_id().ok();
+        PlayerView {
+            player,
+            state: game.state().to_string(),
+            current_player,
+            your_turn: current_player == Some(player),
+            hand: if show_hand {
+                game.hand_from_player_id(player)
+                    .ok()
+                    .map(|hand| hand.to_vec())
+            } else {
+                None
+            },
+            leading_suit: game
+                .leading_suit()
+                .ok()
+                .and_then(|suit| suit)
+                .map(|suit| suit.to_string()),
+            scores: match (game.team_all_rounds_score(0), game.team_all_rounds_score(1)) {
+                (Ok(a), Ok(b)) => Some([a, b]),
+                _ => None,
+            },
+            bags: match (game.team_all_rounds_bags(0), game.team_all_rounds_bags(1)) {
+                (Ok(a), Ok(b)) => Some([a, b]),
+                _ => None,
+            },
+            winners: game.winner_ids().ok(),
+        }
+    }
+}
+
+/// The game plus an outgoing channel for every connected seat.
+struct Table {
+    game: Game,
+    connections: HashMap<Uid, Sender<String>>,
+    /// The token handed out on each seat's first join, needed to reconnect to it.
+    tokens: HashMap<Uid, String>,
+    /// Players who asked for their hand while betting this round.
+    shown: HashSet<Uid>,
+}
+
+impl Table {
+    /// The player's view.  Any hand in it is revealed to them as far as the game is concerned.
+    fn view(&mut self, player: Uid) -> PlayerView {
+        let show_hand = !self.game.state().is_betting() || self.shown.contains(&player);
+        if show_hand {
+            let _ = self.game.reveal_hand(&player);
+        }
+        PlayerView::new(&self.game, player, show_hand)
+    }
+
+    fn send_view(&mut self, player: Uid) {
+        let view = self.view(player);
+        self.send(player, &ServerMessage::View(view));
+    }
+
+    fn send(&self, player: Uid, message: &ServerMessage) {
+        if let Some(connection) = self.connections.get(&player) {
+            let text = serde_json::to_string(message).expect("server messages always serialize");
+            // a closed channel means the player disconnected; they get a fresh view on reconnect
+            let _ = connection.send(text);
+        }
+    }
+
+    fn broadcast_views(&mut self) {
+        let players: Vec<Uid> = self.connections.keys().cloned().collect();
+        for player in players {
+            self.send_view(player);
+        }
+    }
+
+    /// Checks the token of a player joining, handing out a new one on the seat's first join.
+    fn join(&mut self, player: Uid, token: Option<String>) -> Result<(), String> {
+        if self.game.hand_from_player_id(player) == Err(SpadesError::InvalidUuid) {
+            return Err(SpadesError::InvalidUuid.to_string());
+        }
+        match (self.tokens.get(&player), token) {
+            (Some(expected), Some(ref token)) if expected == token => Ok(()),
+            (Some(_), _) => Err(format!("seat {} is taken", player.0)),
+            (None, _) => {
+                let token = format!("{:016x}", rand::random::<u64>());
+                self.tokens.insert(player, token.clone());
+                Ok(())
+            }
+        }
+    }
+
+    /// Applies one action on behalf of `player`, who must be the player the game is waiting on.
+    fn act(&mut self, player: Uid, message: ClientMessage) -> Result<(), String> {
+        if self.game.current_player_id() != Ok(player) {
+            return Err(match self.game.current_player_id() {
+                Ok(expected) => format!("it is player {}'s turn", expected.0),
+                Err(err) => err.to_string(),
+            });
+        }
+        match message {
+            ClientMessage::Join { .. } | ClientMessage::ShowHand => unreachable!(),
+            ClientMessage::Bet { bet } => {
+                if let Some(err) = self.game.can_place_bet(bet) {
+                    return Err(err.to_string());
+                }
+                self.game.place_bet(bet);
+            }
+            ClientMessage::Play { card } => {
+                if let Some(err) = self.game.can_play_card(card) {
+                    return Err(err.to_string());
+                }
+                self.game.play_card(card);
+                if self.game.state() == State::Betting(0) {
+                    // a new round was dealt
+                    self.shown.clear();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut game = Game::default();
+    game.assign_players(Uid(1000), PLAYER_IDS);
+    game.start_game();
+    let table = Arc::new(Mutex::new(Table {
+        game,
+        connections: HashMap::new(),
+        tokens: HashMap::new(),
+        shown: HashSet::new(),
+    }));
+
+    let listener = TcpListener::bind(ADDRESS).expect("could not bind the server address");
+    println!("listening on ws://{}", ADDRESS);
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let table = Arc::clone(&table);
+                thread::spawn(move || {
+                    if let Err(err) = serve(stream, &table) {
+                        println!("connection closed: {}", err);
+                    }
+                });
+            }
+            Err(err) => println!("failed to accept connection: {}", err),
+        }
+    }
+}
+
+/// Runs one client connection until it closes.
+///
+/// Reads use a short timeout so the same thread can also forward the views queued for this player.
+fn serve(stream: TcpStream, table: &Arc<Mutex<Table>>) -> Result<(), tungstenite::Error> {
+    let mut socket = tungstenite::accept(stream).map_err(|err| match err {
+        tungstenite::HandshakeError::Failure(err) => err,
+        tungstenite::HandshakeError::Interrupted(_) => tungstenite::Error::Io(io::Error::new(
+            io::ErrorKind::TimedOut,
+            "handshake interrupted",
+        )),
+    })?;
+    socket
+        .get_ref()
+        .set_read_timeout(Some(Duration::from_millis(50)))?;
+    let mut seat: Option<(Uid, Receiver<String>)> = None;
+
+    loop {
+        if let Some((_, outgoing)) = &seat {
+            loop {
+                match outgoing.try_recv() {
+                    Ok(text) => socket.send(Message::text(text))?,
+                    Err(TryRecvError::Empty) => break,
+                    // a newer connection has taken over this seat
+                    Err(TryRecvError::Disconnected) => return socket.close(None),
+                }
+            }
+        }
+
+        let text = match socket.read() {
+            Ok(Message::Text(text)) => text.to_string(),
+            Ok(Message::Close(_)) => return Ok(()),
+            Ok(_) => continue,
+            Err(tungstenite::Error::Io(ref err))
+                if err.kind() == io::ErrorKind::WouldBlock
+                    || err.kind() == io::ErrorKind::TimedOut =>
+            {
+                continue
+            }
+            Err(err) => return Err(err),
+        };
+
+        let message = match serde_json::from_str::<ClientMessage>(&text) {
+            Ok(message) => message,
+            Err(err) => {
+                reply_error(&mut socket, format!("malformed message: {}", err))?;
+                continue;
+            }
+        };
+
+        let mut table = table.lock().unwrap();
+        match (message, &seat) {
+            (ClientMessage::Join { player, token }, _) => {
+                let player = Uid(player);
+                if let Err(err) = table.join(player, token) {
+                    drop(table);
+                    reply_error(&mut socket, err)?;
+                    continue;
+                }
+                // joining again with the seat's token takes it over from any earlier connection
+                let (sender, receiver) = channel();
+                table.connections.insert(player, sender);
+                let token = table.tokens[&player].clone();
+                table.send(player, &ServerMessage::Joined { player, token });
+                table.send_view(player);
+                seat = Some((player, receiver));
+            }
+            (ClientMessage::ShowHand, Some((player, _))) => {
+                table.shown.insert(*player);
+                table.send_view(*player);
+            }
+            (message, Some((player, _))) => match table.act(*player, message) {
+                Ok(()) => {
+                    table.broadcast_views();
+                    if table.game.state() == State::GameCompleted {
+                        println!("game over, winners: {:?}", table.game.winner_ids());
+                    }
+                }
+                Err(err) => table.send(*player, &ServerMessage::Error { message: err }),
+            },
+            (_, None) => {
+                drop(table);
+                reply_error(&mut socket, "join a seat first".to_string())?;
+            }
+        }
+    }
+}
+
+fn reply_error(
+    socket: &mut WebSocket<TcpStream>,
+    message: String,
+) -> Result<(), tungstenite::Error> {
+    let text = serde_json::to_string(&ServerMessage::Error { message })
+        .expect("server messages always serialize");
+    socket.send(Message::text(text))
+}
@@ -0,0 +1,16 @@
+//! Generates the messages of `proto/spades.proto`, and the server or client for each feature enabled.  The
+//! schema is parsed by protox, so building needs no `protoc`.
+
+use std::env;
+
+fn main() {
+    let proto = "../proto";
+    println!("cargo:rerun-if-changed={}/spades.proto", proto);
+    let descriptors =
+        protox::compile(["spades.proto"], [proto]).expect("proto/spades.proto to be valid");
+    tonic_prost_build::configure()
+        .build_server(env::var_os("CARGO_FEATURE_SERVER").is_some())
+        .build_client(env::var_os("CARGO_FEATURE_CLIENT").is_some())
+        .compile_fds(descriptors)
+        .expect("code to be generated for proto/spades.proto");
+}
@@ -0,0 +1,173 @@
+//! Conversions between the engine's types and the messages of `proto/spades.proto`.
+
+use crate::proto::{self, bet, game_event};
+use spades::http::HttpError;
+use spades::{Bet, Card, GameEvent, PlayerView, SequencedEvent};
+use tonic::{Code, Status};
+
+/// The status answering a call that `GameServer` turned down with `err`.
+pub fn status(err: HttpError) -> Status {
+    let code = match err {
+        HttpError::BadRequest(_) => Code::InvalidArgument,
+        HttpError::Unauthorized => Code::Unauthenticated,
+        HttpError::NotYourTurn => Code::PermissionDenied,
+        HttpError::NotFound => Code::NotFound,
+        HttpError::MethodNotAllowed => Code::Unimplemented,
+        HttpError::Rejected(_) => Code::FailedPrecondition,
+    };
+    Status::new(code, err.to_string())
+}
+
+impl From<Card> for proto::Card {
+    fn from(card: Card) -> proto::Card {
+        proto::Card {
+            value: card.rank as u32 + 15 * card.suit as u32,
+        }
+    }
+}
+
+impl TryFrom<proto::Card> for Card {
+    type Error = Status;
+
+    /// Read as the crate's serde encoding of a card, which the message shares.
+    fn try_from(card: proto::Card) -> Result<Card, Status> {
+        serde_json::from_value(card.value.into())
+            .map_err(|_| Status::invalid_argument(format!("no card {}", card.value)))
+    }
+}
+
+impl From<Bet> for proto::Bet {
+    fn from(bet: Bet) -> proto::Bet {
+        let bet = match bet {
+            Bet::Amount(tricks) => bet::Bet::Amount(u32::from(tricks)),
+            Bet::Nil => bet::Bet::Nil(true),
+            Bet::BlindNil => bet::Bet::BlindNil(true),
+            Bet::Board => bet::Bet::Board(true),
+            Bet::TenForTwoHundred => bet::Bet::TenForTwoHundred(true),
+        };
+        proto::Bet { bet: Some(bet) }
+    }
+}
+
+impl TryFrom<proto::Bet> for Bet {
+    type Error = Status;
+
+    /// Whichever bet is set, whatever the value of a flag such as `nil`.  The engine checks the amount.
+    fn try_from(bet: proto::Bet) -> Result<Bet, Status> {
+        match bet.bet {
+            Some(bet::Bet::Amount(tricks)) => u8::try_from(tricks)
+                .map(Bet::Amount)
+                .map_err(|_| Status::invalid_argument(format!("a bet of {} tricks", tricks))),
+            Some(bet::Bet::Nil(_)) => Ok(Bet::Nil),
+            Some(bet::Bet::BlindNil(_)) => Ok(Bet::BlindNil),
+            Some(bet::Bet::Board(_)) => Ok(Bet::Board),
+            Some(bet::Bet::TenForTwoHundred(_)) => Ok(Bet::TenForTwoHundred),
+            None => Err(Status::invalid_argument("no bet given")),
+        }
+    }
+}
+
+fn cards(cards: &[Card]) -> Vec<proto::Card> {
+    cards.iter().map(|&card| card.into()).collect()
+}
+
+impl<'a> From<&PlayerView<'a>> for proto::PlayerView {
+    fn from(view: &PlayerView<'a>) -> proto::PlayerView {
+        let table = &view.table;
+        proto::PlayerView {
+            player: view.player.0,
+            seat: view.seat.index() as u32,
+            hand: cards(&view.hand),
+            playable: cards(&view.playable),
+            tricks: view
+                .tricks
+                .iter()
+                .map(|&tricks| u32::from(tricks))
+                .collect(),
+            current_player: table.current_player.map(|player| player.0),
+            state_json: serde_json::to_string(&table.state).unwrap_or_default(),
+            bets: table
+                .bets
+                .iter()
+                .map(|bet| proto::OptionalBet {
+                    bet: bet.map(proto::Bet::from),
+                })
+                .collect(),
+            current_trick: cards(&table.current_trick),
+            scores: table.scores.to_vec(),
+            bags: table.bags.iter().map(|&bags| u32::from(bags)).collect(),
+            trick_plays: table
+                .trick_plays
+                .iter()
+                .map(|play| proto::TrickPlay {
+                    seat: play.seat.index() as u32,
+                    card: Some(play.card.into()),
+                    winning: play.winning,
+                })
+                .collect(),
+            winning: table.winning.map(|(seat, card)| proto::TrickPlay {
+                seat: seat.index() as u32,
+                card: Some(card.into()),
+                winning: true,
+            }),
+        }
+    }
+}
+
+impl From<&SequencedEvent> for proto::GameEvent {
+    fn from(event: &SequencedEvent) -> proto::GameEvent {
+        let message = match &event.event {
+            GameEvent::Dealt => game_event::Event::Dealt(proto::Dealt {}),
+            GameEvent::Bet { player, bet } => game_event::Event::Bet(proto::BetPlaced {
+                player: player.0,
+                bet: Some((*bet).into()),
+            }),
+            GameEvent::CardPlayed { player, card } => {
+                game_event::Event::CardPlayed(proto::CardPlayed {
+                    player: player.0,
+                    card: Some((*card).into()),
+                })
+            }
+            GameEvent::RoundScored { tricks, scores } => {
+                game_event::Event::RoundScored(proto::RoundScored {
+                    tricks: tricks.iter().map(|&tricks| u32::from(tricks)).collect(),
+                    points: scores.iter().map(|score| score.points).collect(),
+                })
+            }
+            other => game_event::Event::OtherJson(serde_json::to_string(other).unwrap_or_default()),
+        };
+        proto::GameEvent {
+            seq: event.seq,
+            event: Some(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::proto;
+    use spades::{full_deck, Bet, Card};
+
+    #[test]
+    fn cards_and_bets_round_trip() {
+        for card in full_deck() {
+            assert_eq!(Some(card), Card::try_from(proto::Card::from(card)).ok());
+        }
+        assert!(Card::try_from(proto::Card { value: 1 }).is_err());
+        assert!(Card::try_from(proto::Card { value: 60 }).is_err());
+        for bet in [
+            Bet::Amount(4),
+            Bet::Nil,
+            Bet::BlindNil,
+            Bet::Board,
+            Bet::TenForTwoHundred,
+        ] {
+            assert_eq!(Some(bet), Bet::try_from(proto::Bet::from(bet)).ok());
+        }
+        assert!(Bet::try_from(proto::Bet { bet: None }).is_err());
+        assert!(Bet::try_from(proto::Bet {
+            bet: Some(proto::bet::Bet::Amount(300))
+        })
+        .is_err());
+    }
+}
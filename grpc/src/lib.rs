@@ -0,0 +1,30 @@
+//! The game service of `proto/spades.proto`, for backends that talk gRPC, built with tonic.
+//!
+//! With the `server` feature, [`SpadesService`] answers each call from a
+//! [`GameServer`](spades::http::GameServer), just as the REST adapter of the `spades` crate's `http` feature
+//! does: a game is created with a token for each player, and every other call is made for the player whose
+//! token is sent as `authorization: Bearer <token>` metadata.  Serve it with
+//! `tonic::transport::Server::builder().add_service(SpadesServer::new(SpadesService::new()))`.
+//!
+//! With the `client` feature, [`SpadesClient`] calls such a server.  Both features are on by default.
+//!
+//! Errors are answered with the status codes the schema gives: `INVALID_ARGUMENT` for a request that can't
+//! be read, `UNAUTHENTICATED` for a missing or unknown token, `PERMISSION_DENIED` out of turn, `NOT_FOUND`
+//! for an unknown game and `FAILED_PRECONDITION` for an action the game doesn't allow.
+
+/// The messages of `proto/spades.proto`, with the generated server and client.
+pub mod proto {
+    tonic::include_proto!("spades.v1");
+}
+
+mod convert;
+#[cfg(feature = "server")]
+mod service;
+
+pub use convert::status;
+#[cfg(feature = "client")]
+pub use proto::spades_client::SpadesClient;
+#[cfg(feature = "server")]
+pub use proto::spades_server::SpadesServer;
+#[cfg(feature = "server")]
+pub use service::SpadesService;
@@ -0,0 +1,231 @@
+//! [`SpadesService`], answering the calls of `proto/spades.proto` from a `GameServer`.
+
+use crate::convert::status;
+use crate::proto::spades_server::Spades;
+use crate::proto::{
+    CreateGameRequest, CreateGameResponse, GameEvent as EventMessage, GetViewRequest,
+    PlaceBetRequest, PlayCardRequest, PlayerView, StreamEventsRequest,
+};
+use spades::http::{CreateGame, GameServer};
+use spades::ml::Action;
+use spades::{BidVisibility, Game, GameEvent, GameOptions, SequencedEvent, State, Uid};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::sync::{mpsc, watch};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+/// How many events a stream holds for a client reading them more slowly than they happen.
+const STREAM_BUFFER: usize = 64;
+
+/// The games being served over gRPC.  Clones share the same games, so one can be kept to look at them while
+/// another is served.
+#[derive(Debug, Clone)]
+pub struct SpadesService {
+    games: Arc<Mutex<GameServer>>,
+    /// Counts the changes made to any game, waking the event streams to look for new events.
+    changes: Arc<watch::Sender<u64>>,
+}
+
+impl Default for SpadesService {
+    fn default() -> SpadesService {
+        SpadesService::new()
+    }
+}
+
+/// The token sent as `authorization: Bearer <token>` metadata, or on its own.
+fn token<T>(request: &Request<T>) -> Option<String> {
+    let value = request
+        .metadata()
+        .get("authorization")?
+        .to_str()
+        .ok()?
+        .trim();
+    Some(
+        value
+            .strip_prefix("Bearer ")
+            .unwrap_or(value)
+            .trim()
+            .to_string(),
+    )
+}
+
+/// The games, even if a call panicked while holding them: every change to a game is made whole or not at
+/// all, so they are still consistent.
+fn lock(games: &Mutex<GameServer>) -> MutexGuard<'_, GameServer> {
+    games.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The events numbered `from` on that `player` may see, and the number of the first event still to be looked
+/// at.  The shuffle seed would give every hand away and another player's discards are theirs alone, so both
+/// are left out.  While bets are [hidden](spades::BidVisibility::AfterBetting) until everyone has bet, the
+/// events stop short of the round's first bet.
+fn visible_events(game: &Game, player: Uid, from: u64) -> (Vec<SequencedEvent>, u64) {
+    let hiding_bets =
+        game.options().bid_visibility == BidVisibility::AfterBetting && game.state().is_betting();
+    let mut visible = vec![];
+    let mut next = from;
+    for event in game.events_since(from.saturating_sub(1)) {
+        match &event.event {
+            GameEvent::Bet { .. } if hiding_bets => break,
+            GameEvent::ShuffleSeed(_) => {}
+            GameEvent::Discarded {
+                player: discarding, ..
+            } if *discarding != player => {}
+            _ => visible.push(event.clone()),
+        }
+        next = event.seq + 1;
+    }
+    (visible, next)
+}
+
+impl SpadesService {
+    pub fn new() -> SpadesService {
+        SpadesService {
+            games: Arc::default(),
+            changes: Arc::new(watch::Sender::new(0)),
+        }
+    }
+
+    /// The game with this id, as it is now, if it is being served.
+    pub fn game(&self, id: u64) -> Option<Game> {
+        lock(&self.games).game(Uid(id)).cloned()
+    }
+
+    /// Takes `action` for the player holding the request's token and answers with their new view.
+    fn act<T>(
+        &self,
+        request: &Request<T>,
+        game: u64,
+        action: Action,
+    ) -> Result<PlayerView, Status> {
+        let token = token(request);
+        let mut games = lock(&self.games);
+        let view = games
+            .act(Uid(game), token.as_deref(), action)
+            .map(|view| PlayerView::from(&view))
+            .map_err(status)?;
+        drop(games);
+        self.changes.send_modify(|count| *count += 1);
+        Ok(view)
+    }
+}
+
+#[tonic::async_trait]
+impl Spades for SpadesService {
+    async fn create_game(
+        &self,
+        request: Request<CreateGameRequest>,
+    ) -> Result<Response<CreateGameResponse>, Status> {
+        let request = request.into_inner();
+        let players = match request.players.as_slice() {
+            [] => None,
+            &[north, east, south, west] => Some([Uid(north), Uid(east), Uid(south), Uid(west)]),
+            players => {
+                return Err(Status::invalid_argument(format!(
+                    "{} players given for 4 seats",
+                    players.len()
+                )))
+            }
+        };
+        let options = match request.options_json {
+            Some(json) => Some(
+                serde_json::from_str::<GameOptions>(&json)
+                    .map_err(|err| Status::invalid_argument(err.to_string()))?,
+            ),
+            None => None,
+        };
+        let created = lock(&self.games)
+            .create_game(CreateGame {
+                players,
+                options,
+                shuffle_seed: request.shuffle_seed,
+            })
+            .map_err(status)?;
+        Ok(Response::new(CreateGameResponse {
+            game: created.game.0,
+            players: created.players.iter().map(|player| player.0).collect(),
+            tokens: created.tokens.to_vec(),
+        }))
+    }
+
+    /// The view of the player holding the token, which counts as them seeing their hand.
+    async fn get_view(
+        &self,
+        request: Request<GetViewRequest>,
+    ) -> Result<Response<PlayerView>, Status> {
+        let token = token(&request);
+        let mut games = lock(&self.games);
+        let view = games
+            .view(Uid(request.get_ref().game), token.as_deref())
+            .map_err(status)?;
+        Ok(Response::new(PlayerView::from(&view)))
+    }
+
+    async fn place_bet(
+        &self,
+        request: Request<PlaceBetRequest>,
+    ) -> Result<Response<PlayerView>, Status> {
+        let bet = request
+            .get_ref()
+            .bet
+            .ok_or_else(|| Status::invalid_argument("no bet given"))?
+            .try_into()?;
+        self.act(&request, request.get_ref().game, Action::Bet(bet))
+            .map(Response::new)
+    }
+
+    async fn play_card(
+        &self,
+        request: Request<PlayCardRequest>,
+    ) -> Result<Response<PlayerView>, Status> {
+        let card = request
+            .get_ref()
+            .card
+            .ok_or_else(|| Status::invalid_argument("no card given"))?
+            .try_into()?;
+        self.act(&request, request.get_ref().game, Action::Play(card))
+            .map(Response::new)
+    }
+
+    type StreamEventsStream = ReceiverStream<Result<EventMessage, Status>>;
+
+    /// The events the token's player may see, from `from_seq` on, until the game is over or the client goes
+    /// away.
+    async fn stream_events(
+        &self,
+        request: Request<StreamEventsRequest>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        let id = Uid(request.get_ref().game);
+        let player = lock(&self.games)
+            .player(id, token(&request).as_deref())
+            .map_err(status)?;
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+        let games = Arc::clone(&self.games);
+        let mut changes = self.changes.subscribe();
+        let mut next = request.get_ref().from_seq;
+        tokio::spawn(async move {
+            loop {
+                // seen before looking, so that a change made while sending isn't missed
+                changes.borrow_and_update();
+                let (events, over) = match lock(&games).game(id) {
+                    Some(game) => {
+                        let (events, after) = visible_events(game, player, next);
+                        next = after;
+                        let over = matches!(game.state(), State::GameCompleted | State::Abandoned);
+                        (events, over)
+                    }
+                    None => return,
+                };
+                for event in &events {
+                    if sender.send(Ok(event.into())).await.is_err() {
+                        return;
+                    }
+                }
+                if over || changes.changed().await.is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
@@ -0,0 +1,251 @@
+//! A client and server talking over a local socket: creating a game, betting, playing and following its
+//! events.
+
+use spades_grpc::proto::{
+    bet, game_event, Bet, CreateGameRequest, GetViewRequest, PlaceBetRequest, PlayCardRequest,
+    StreamEventsRequest,
+};
+use spades_grpc::{SpadesClient, SpadesServer, SpadesService};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::time::timeout;
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_stream::StreamExt;
+use tonic::transport::{Channel, Server};
+use tonic::{Code, Request};
+
+async fn serve(service: SpadesService) -> SpadesClient<Channel> {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(
+        Server::builder()
+            .add_service(SpadesServer::new(service))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    SpadesClient::connect(format!("http://{}", address))
+        .await
+        .unwrap()
+}
+
+fn with_token<T>(message: T, token: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request.metadata_mut().insert(
+        "authorization",
+        format!("Bearer {}", token).parse().unwrap(),
+    );
+    request
+}
+
+fn amount(tricks: u32) -> Option<Bet> {
+    Some(Bet {
+        bet: Some(bet::Bet::Amount(tricks)),
+    })
+}
+
+#[tokio::test]
+async fn players_bet_play_and_follow_events() {
+    let service = SpadesService::new();
+    let mut client = serve(service.clone()).await;
+    let created = client
+        .create_game(CreateGameRequest {
+            players: vec![],
+            shuffle_seed: Some(3),
+            options_json: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(vec![1, 2, 3, 4], created.players);
+    let game = created.game;
+
+    let mut events = client
+        .stream_events(with_token(
+            StreamEventsRequest { game, from_seq: 0 },
+            &created.tokens[2],
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let view = client
+        .get_view(with_token(GetViewRequest { game }, &created.tokens[0]))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(13, view.hand.len());
+    assert_eq!(Some(1), view.current_player);
+
+    let out_of_turn = client
+        .place_bet(with_token(
+            PlaceBetRequest {
+                game,
+                bet: amount(3),
+            },
+            &created.tokens[1],
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(Code::PermissionDenied, out_of_turn.code());
+    let rejected = client
+        .place_bet(with_token(
+            PlaceBetRequest {
+                game,
+                bet: amount(14),
+            },
+            &created.tokens[0],
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(Code::FailedPrecondition, rejected.code());
+    let unknown = client
+        .get_view(with_token(GetViewRequest { game }, "nope"))
+        .await
+        .unwrap_err();
+    assert_eq!(Code::Unauthenticated, unknown.code());
+
+    for token in &created.tokens {
+        client
+            .place_bet(with_token(
+                PlaceBetRequest {
+                    game,
+                    bet: amount(3),
+                },
+                token,
+            ))
+            .await
+            .unwrap();
+    }
+    let view = client
+        .get_view(with_token(GetViewRequest { game }, &created.tokens[0]))
+        .await
+        .unwrap()
+        .into_inner();
+    let card = view.playable[0];
+    let view = client
+        .play_card(with_token(
+            PlayCardRequest {
+                game,
+                card: Some(card),
+            },
+            &created.tokens[0],
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(vec![card], view.current_trick);
+    assert_eq!(Some(2), view.current_player);
+
+    // dealt, four bets and the card, with the shuffle seed left out
+    let mut kinds = vec![];
+    while kinds.len() < 6 {
+        let event = events.next().await.unwrap().unwrap();
+        kinds.push(match event.event.unwrap() {
+            game_event::Event::Dealt(_) => "dealt",
+            game_event::Event::Bet(_) => "bet",
+            game_event::Event::CardPlayed(played) => {
+                assert_eq!(Some(card), played.card);
+                "card"
+            }
+            game_event::Event::RoundScored(_) => "scored",
+            game_event::Event::OtherJson(json) => panic!("unexpected event {}", json),
+        });
+    }
+    assert_eq!(vec!["dealt", "bet", "bet", "bet", "bet", "card"], kinds);
+    assert_eq!(
+        1,
+        service.game(game).unwrap().snapshot().current_trick.len()
+    );
+}
+
+#[tokio::test]
+async fn hidden_bets_are_streamed_once_everyone_has_bet() {
+    let mut client = serve(SpadesService::new()).await;
+    let options = r#"{"bid_review": false, "minimum_team_bid": null, "length": {"TargetScore": 500},
+        "bag_penalty": null, "audit_deals": false, "bid_visibility": "AfterBetting"}"#;
+    let created = client
+        .create_game(CreateGameRequest {
+            players: vec![10, 11, 12, 13],
+            shuffle_seed: None,
+            options_json: Some(options.to_string()),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let game = created.game;
+    let mut events = client
+        .stream_events(with_token(
+            StreamEventsRequest { game, from_seq: 0 },
+            &created.tokens[1],
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    let first = events.next().await.unwrap().unwrap();
+    assert!(matches!(first.event, Some(game_event::Event::Dealt(_))));
+
+    for token in &created.tokens[..3] {
+        client
+            .place_bet(with_token(
+                PlaceBetRequest {
+                    game,
+                    bet: amount(3),
+                },
+                token,
+            ))
+            .await
+            .unwrap();
+    }
+    let view = client
+        .get_view(with_token(GetViewRequest { game }, &created.tokens[1]))
+        .await
+        .unwrap()
+        .into_inner();
+    let bets: Vec<bool> = view.bets.iter().map(|bet| bet.bet.is_some()).collect();
+    assert_eq!(vec![false, true, false, false], bets);
+    assert!(timeout(Duration::from_millis(100), events.next())
+        .await
+        .is_err());
+
+    client
+        .place_bet(with_token(
+            PlaceBetRequest {
+                game,
+                bet: amount(3),
+            },
+            &created.tokens[3],
+        ))
+        .await
+        .unwrap();
+    for _ in 0..4 {
+        let event = events.next().await.unwrap().unwrap();
+        assert!(matches!(event.event, Some(game_event::Event::Bet(_))));
+    }
+}
+
+#[tokio::test]
+async fn games_that_cant_be_served_are_refused() {
+    let mut client = serve(SpadesService::new()).await;
+    let twice = client
+        .create_game(CreateGameRequest {
+            players: vec![7, 7, 8, 9],
+            shuffle_seed: None,
+            options_json: None,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(Code::InvalidArgument, twice.code());
+    let three = client
+        .create_game(CreateGameRequest {
+            players: vec![7, 8, 9],
+            shuffle_seed: None,
+            options_json: None,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(Code::InvalidArgument, three.code());
+    let missing = client
+        .get_view(with_token(GetViewRequest { game: 99 }, "token"))
+        .await
+        .unwrap_err();
+    assert_eq!(Code::NotFound, missing.code());
+}
@@ -0,0 +1,127 @@
+use diagnostics::GameEvent;
+use game_state::State;
+use result::SpadesError;
+use Game;
+
+/// Points given to or taken from a team by a ruling, such as a penalty for table talk, see
+/// [`Game::apply_adjustment`](struct.Game.html#method.apply_adjustment).
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct Adjustment {
+    pub team: usize,
+    pub points: i32,
+    pub reason: String,
+    /// The round being played when the adjustment was made, counting from 0.  The adjustment shows in the
+    /// [`score_history`](struct.Game.html#method.score_history) from the end of this round on.
+    pub round: usize,
+}
+
+impl Game {
+    /// Adds `delta_points` to the cumulative score of team `team`, 0 or 1, for `reason`.
+    ///
+    /// Whether the game is over is decided at the end of each round, so an adjustment never ends the game by
+    /// itself: it counts towards the decision at the end of the round being played, or of the next round if
+    /// made between rounds.  Returns `SpadesError::ImproperGameStage` for a team other than 0 or 1, and an
+    /// error if the game hasn't started, is over or has been abandoned.
+    pub fn apply_adjustment(
+        &mut self,
+        team: usize,
+        delta_points: i32,
+        reason: &str,
+    ) -> Result<(), SpadesError> {
+        match self.state {
+            State::GameNotStarted => return Err(SpadesError::GameNotStarted),
+            State::GameCompleted => return Err(SpadesError::GameCompleted),
+            State::Abandoned => return Err(SpadesError::GameAbandoned),
+            _ if team > 1 => return Err(SpadesError::ImproperGameStage),
+            _ => {}
+        }
+        let adjustment = Adjustment {
+            team,
+            points: delta_points,
+            reason: reason.to_string(),
+            round: self.scoring.rounds_played(),
+        };
+        self.scoring.adjust_points(team, delta_points);
+        self.adjustments.push(adjustment.clone());
+        self.record_event(GameEvent::ScoreAdjusted(adjustment));
+        self.check_invariants();
+        Ok(())
+    }
+
+    /// Every adjustment made to the game's scores, oldest first.
+    pub fn adjustments(&self) -> &[Adjustment] {
+        &self.adjustments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Adjustment;
+    use test_support::seeded_game;
+    use {Bet, Game, GameEvent, SpadesError, State};
+
+    fn play_round(g: &mut Game) {
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        while g.state() != State::Betting(0) && g.state() != State::GameCompleted {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+    }
+
+    #[test]
+    fn test_adjustment_changes_cumulative_score() {
+        let mut g = Game::default();
+        assert_eq!(
+            Err(SpadesError::GameNotStarted),
+            g.apply_adjustment(0, -10, "table talk")
+        );
+        let mut g = seeded_game(8);
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            g.apply_adjustment(2, -10, "table talk")
+        );
+        assert_eq!(Ok(()), g.apply_adjustment(1, -10, "table talk"));
+        assert_eq!(Ok(-10), g.team_all_rounds_score(1));
+        let adjustment = Adjustment {
+            team: 1,
+            points: -10,
+            reason: "table talk".to_string(),
+            round: 0,
+        };
+        assert_eq!(vec![adjustment.clone()], g.adjustments().to_vec());
+        assert_eq!(
+            Some(&GameEvent::ScoreAdjusted(adjustment)),
+            g.history().last()
+        );
+
+        play_round(&mut g);
+        let scores = [0, 1].map(|team| g.team_all_rounds_score(team).unwrap());
+        assert_eq!(vec![scores], g.score_history());
+        assert_eq!(
+            Ok(()),
+            g.apply_adjustment(0, 5, "slow play by the other side")
+        );
+        assert_eq!(vec![scores], g.score_history());
+        play_round(&mut g);
+        let scores = [0, 1].map(|team| g.team_all_rounds_score(team).unwrap());
+        assert_eq!(Some(&scores), g.score_history().last());
+    }
+
+    #[test]
+    fn test_adjustment_ends_game_at_round_end() {
+        let mut g = seeded_game(8);
+        play_round(&mut g);
+        assert_eq!(Ok(()), g.apply_adjustment(0, 1000, "award"));
+        assert_eq!(State::Betting(0), g.state());
+        play_round(&mut g);
+        assert_eq!(State::GameCompleted, g.state());
+        assert_eq!(
+            Err(SpadesError::GameCompleted),
+            g.apply_adjustment(1, 1000, "too late")
+        );
+    }
+}
@@ -0,0 +1,488 @@
+use cards::{Card, Suit};
+use game_result::GameResult;
+use game_state::State;
+use lifecycle::AbandonReason;
+use result::SpadesError;
+use scoring::Bet;
+use seat::Seat;
+use snapshot::GameSnapshot;
+use std::borrow::Cow;
+use {Game, PlayerId, Uid};
+
+/// What one player can see of a game: the public table and their own hand, as returned by
+/// [`Game::player_view`](struct.Game.html#method.player_view).
+/// The hand is borrowed from the game unless it is put in another [order](enum.HandOrder.html), so a view is
+/// cheap to make and has to be dropped before the game is changed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize)]
+pub struct PlayerView<'a> {
+    pub player: Uid,
+    pub seat: Seat,
+    pub hand: Cow<'a, [Card]>,
+    /// The cards in `hand` the player may play now, in the same order; empty unless it is their turn in the
+    /// trick stage.
+    pub playable: Vec<Card>,
+    /// Tricks each player has taken this round, in seat order.
+    pub tricks: [u8; 4],
+    pub table: GameSnapshot,
+}
+
+/// The order the cards of a [`PlayerView`](struct.PlayerView.html) are put in, see
+/// [`Game::player_view_in_order`](struct.Game.html#method.player_view_in_order).  Within a suit, cards go
+/// from the two up to the ace.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum HandOrder {
+    /// By suit, in the order clubs, diamonds, hearts, spades, as the game keeps hands.
+    #[default]
+    SuitThenRank,
+    /// By rank, then by suit in the order clubs, diamonds, hearts, spades.
+    RankThenSuit,
+    /// By suit, alternating black and red: spades, hearts, clubs, diamonds.
+    AlternatingColors,
+    /// By suit, alternating red and black with trumps at the end: diamonds, clubs, hearts, spades.
+    SpadesLast,
+}
+
+impl HandOrder {
+    /// Puts `cards` in this order.
+    pub fn sort(self, cards: &mut [Card]) {
+        let suit_order = |suits: [Suit; 4]| {
+            move |card: &Card| (suits.iter().position(|suit| *suit == card.suit), card.rank)
+        };
+        match self {
+            HandOrder::SuitThenRank => cards.sort(),
+            HandOrder::RankThenSuit => cards.sort_by_key(|card| (card.rank, card.suit)),
+            HandOrder::AlternatingColors => cards.sort_by_key(suit_order([
+                Suit::Spades,
+                Suit::Hearts,
+                Suit::Clubs,
+                Suit::Diamonds,
+            ])),
+            HandOrder::SpadesLast => cards.sort_by_key(suit_order([
+                Suit::Diamonds,
+                Suit::Clubs,
+                Suit::Hearts,
+                Suit::Spades,
+            ])),
+        }
+    }
+}
+
+/// What a player can see of one seat, as arranged by [`PlayerView::relative`](struct.PlayerView.html#method.relative).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct SeatView {
+    pub seat: Seat,
+    pub player: Uid,
+    /// The bet made this round, or `None` if the player hasn't bet yet.
+    pub bet: Option<Bet>,
+    /// Tricks taken this round.
+    pub tricks: u8,
+    /// The card the player has played to the trick in progress, if any.
+    pub card_in_trick: Option<Card>,
+    /// It is this player's turn.
+    pub to_act: bool,
+}
+
+/// The four seats of a table as one player sees them: themselves, the opponent on their left, their partner
+/// across the table and the opponent on their right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct RelativeTable {
+    pub me: SeatView,
+    pub left: SeatView,
+    pub partner: SeatView,
+    pub right: SeatView,
+}
+
+impl PlayerView<'_> {
+    /// The table arranged around the viewing player, so a client can draw it without working out which seat
+    /// sits where.
+    pub fn relative(&self) -> RelativeTable {
+        let table = &self.table;
+        let to_act = table
+            .current_player
+            .and_then(|id| table.player_ids.iter().position(|player| *player == id));
+        // the player to act is next in the trick, so it was led as many seats to their right as cards played
+        let leader = to_act.map(|index| (index + 4 - table.current_trick.len()) % 4);
+        let seat_view = |seat: Seat| {
+            let index = seat.index();
+            SeatView {
+                seat,
+                player: table.player_ids[index],
+                bet: table.bets[index],
+                tricks: self.tricks[index],
+                card_in_trick: leader
+                    .and_then(|leader| table.current_trick.get((index + 4 - leader) % 4).cloned()),
+                to_act: to_act == Some(index),
+            }
+        };
+        RelativeTable {
+            me: seat_view(self.seat),
+            left: seat_view(self.seat.left()),
+            partner: seat_view(self.seat.partner()),
+            right: seat_view(self.seat.right()),
+        }
+    }
+}
+
+/// Decides bets and cards for one player, so a whole game can be played with [`run_game`](fn.run_game.html).
+pub trait PlayerAgent {
+    /// Asked before the player's hand is revealed to them each round; returning true bets blind nil.
+    fn bet_blind_nil(&mut self, _seat: Seat, _table: &GameSnapshot) -> bool {
+        false
+    }
+
+    fn bet(&mut self, view: &PlayerView) -> Bet;
+
+    /// One of `view.playable`.
+    fn play_card(&mut self, view: &PlayerView) -> Card;
+
+    /// `count` cards of `view.hand` to put back after picking up the kitty, see
+    /// [`KittyConfig`](struct.KittyConfig.html).  Discards the lowest cards unless overridden.
+    fn discard(&mut self, view: &PlayerView, count: usize) -> Vec<Card> {
+        let mut hand = view.hand.to_vec();
+        hand.sort_by_key(|card| (card.rank, card.suit));
+        hand.truncate(count);
+        hand
+    }
+
+    /// A new bet to make while reviewing the bets, or `None` to keep the bet already made, see
+    /// [`GameOptions::bid_review`](struct.GameOptions.html#structfield.bid_review).  Keeps it unless overridden.
+    fn review_bet(&mut self, _view: &PlayerView) -> Option<Bet> {
+        None
+    }
+}
+
+impl Game {
+    /// What the player with this id can see of the game.  Like
+    /// [`hand_of_current_player`](#method.hand_of_current_player), this does not count as the player seeing
+    /// their hand.
+    pub fn player_view<P: PlayerId + ?Sized>(
+        &self,
+        player_id: &P,
+    ) -> Result<PlayerView<'_>, SpadesError> {
+        self.player_view_in_order(player_id, HandOrder::default())
+    }
+
+    /// Like [`player_view`](#method.player_view), with the hand and playable cards put in `order`, so every
+    /// client gets the order its players expect without sorting the cards again.
+    pub fn player_view_in_order<P: PlayerId + ?Sized>(
+        &self,
+        player_id: &P,
+        order: HandOrder,
+    ) -> Result<PlayerView<'_>, SpadesError> {
+        let seat = self.seat_of(player_id)?;
+        let player = &self.player[seat.index()];
+        let mut playable: Vec<Card> = match self.state {
+            State::Trick(_) if self.current_player_index == seat.index() => {
+                self.playable_cards().collect()
+            }
+            _ => vec![],
+        };
+        let hand = match order {
+            HandOrder::SuitThenRank => Cow::Borrowed(&player.hand[..]),
+            _ => {
+                let mut hand = player.hand.to_vec();
+                order.sort(&mut hand);
+                order.sort(&mut playable);
+                Cow::Owned(hand)
+            }
+        };
+        let mut table = self.snapshot();
+        table.bets = self.shown_bets(Some(seat));
+        Ok(PlayerView {
+            player: player.id,
+            seat,
+            hand,
+            playable,
+            tricks: if self.scoring.is_in_betting_stage() {
+                [0; 4]
+            } else {
+                self.scoring.player_tricks()
+            },
+            table,
+        })
+    }
+}
+
+/// How many rounds [`run_game`](fn.run_game.html) plays before giving up on a game.
+pub const ROUND_LIMIT: usize = 200;
+
+/// Plays `game` to the end, asking `agents[i]` for the actions of player `i`, and starts it first if it
+/// hasn't been started.
+///
+/// Stops early if the game is paused or a claim is made.  If an agent picks a bet or card that isn't allowed,
+/// the game is abandoned with `AbandonReason::IllegalAction`, and if neither team has won after
+/// `ROUND_LIMIT` rounds, with `AbandonReason::RoundLimit`.  Returns the error, leaving the game as it stands,
+/// if the game refuses one of the steps taken on the players' behalf.
+pub fn run_game(
+    game: &mut Game,
+    agents: [&mut dyn PlayerAgent; 4],
+) -> Result<GameResult, SpadesError> {
+    run_game_with_round_limit(game, agents, ROUND_LIMIT)
+}
+
+/// Like [`run_game`](fn.run_game.html), but giving up after `round_limit` rounds.
+pub fn run_game_with_round_limit(
+    game: &mut Game,
+    agents: [&mut dyn PlayerAgent; 4],
+    round_limit: usize,
+) -> Result<GameResult, SpadesError> {
+    let mut agents = agents;
+    if game.state == State::GameNotStarted {
+        game.start_game();
+    }
+    while game.pending_claim.is_none() {
+        if game.state.is_playing() && game.scoring.rounds_played() >= round_limit {
+            game.abandon(AbandonReason::RoundLimit(round_limit))?;
+            break;
+        }
+        let index = game.current_player_index;
+        let player = game.player[index].id;
+        let agent = &mut agents[index];
+        let legal = match game.state {
+            State::Betting(_) => {
+                let view = game.player_view(&player)?;
+                let blind = agent.bet_blind_nil(view.seat, &view.table)
+                    && game.can_place_bet(Bet::BlindNil).is_none();
+                let bet = if blind {
+                    Bet::BlindNil
+                } else {
+                    agent.bet(&view)
+                };
+                if !blind {
+                    game.reveal_hand(&player)?;
+                }
+                game.place_bet(bet).is_some()
+            }
+            State::Exchange => {
+                let view = game.player_view(&player)?;
+                let count = game.kitty_discards();
+                let cards = agent.discard(&view, count);
+                game.discard(&cards).is_ok()
+            }
+            State::BidReview => {
+                let amendment = agent.review_bet(&game.player_view(&player)?);
+                game.review_bet(amendment).is_ok()
+            }
+            State::Trick(_) => {
+                let card = agent.play_card(&game.player_view(&player)?);
+                game.reveal_hand(&player)?;
+                game.play_card(card).is_some()
+            }
+            State::GameNotStarted | State::GameCompleted | State::Paused | State::Abandoned => {
+                break
+            }
+        };
+        if !legal {
+            game.abandon(AbandonReason::IllegalAction(player))?;
+        }
+    }
+    Ok(game.game_result())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        run_game, run_game_with_round_limit, HandOrder, PlayerAgent, PlayerView, ROUND_LIMIT,
+    };
+    use cards::{new_deck, Rank, Suit};
+    use lifecycle::{AbandonReason, PrematureEnd};
+    use std::borrow::Cow;
+    use test_support::seated_game;
+    use {Bet, Card, Seat, State, Uid};
+
+    /// Bets 3 and plays the first card it may.
+    struct FirstCard;
+
+    impl PlayerAgent for FirstCard {
+        fn bet(&mut self, _view: &PlayerView) -> Bet {
+            Bet::Amount(3)
+        }
+
+        fn play_card(&mut self, view: &PlayerView) -> Card {
+            view.playable[0]
+        }
+    }
+
+    /// Bets 7, so that both teams bid 14 between them and are set every round.
+    struct Overbid;
+
+    impl PlayerAgent for Overbid {
+        fn bet(&mut self, _view: &PlayerView) -> Bet {
+            Bet::Amount(7)
+        }
+
+        fn play_card(&mut self, view: &PlayerView) -> Card {
+            view.playable[0]
+        }
+    }
+
+    /// Plays a card it doesn't hold.
+    struct Cheat;
+
+    impl PlayerAgent for Cheat {
+        fn bet(&mut self, _view: &PlayerView) -> Bet {
+            Bet::Amount(3)
+        }
+
+        fn play_card(&mut self, view: &PlayerView) -> Card {
+            new_deck()
+                .into_iter()
+                .find(|card| !view.hand.contains(card))
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn test_run_game_plays_to_completion() {
+        let mut g = seated_game();
+        let (mut a, mut b, mut c, mut d) = (FirstCard, FirstCard, FirstCard, FirstCard);
+        let result = run_game(&mut g, [&mut a, &mut b, &mut c, &mut d]).unwrap();
+        assert_eq!(State::GameCompleted, g.state());
+        assert_eq!(g.winner_ids().ok(), result.winners);
+        assert_eq!(Ok(result.scores[0]), g.team_all_rounds_score(0));
+        assert_eq!(None, result.premature_end);
+    }
+
+    #[test]
+    fn test_illegal_action_abandons_game() {
+        let mut g = seated_game();
+        let (mut a, mut b, mut c) = (FirstCard, FirstCard, FirstCard);
+        let result = run_game(&mut g, [&mut a, &mut Cheat, &mut b, &mut c]).unwrap();
+        assert_eq!(State::Abandoned, g.state());
+        assert_eq!(None, result.winners);
+        assert_eq!(
+            Some(PrematureEnd::Abandoned(AbandonReason::IllegalAction(Uid(
+                11
+            )))),
+            result.premature_end
+        );
+    }
+
+    #[test]
+    fn test_game_nobody_can_win_is_abandoned_at_round_limit() {
+        let mut g = seated_game();
+        let (mut a, mut b, mut c, mut d) = (Overbid, Overbid, Overbid, Overbid);
+        let result =
+            run_game_with_round_limit(&mut g, [&mut a, &mut b, &mut c, &mut d], 5).unwrap();
+        assert_eq!(State::Abandoned, g.state());
+        assert_eq!(None, result.winners);
+        assert_eq!([-700, -700], result.scores);
+        assert_eq!(
+            Some(PrematureEnd::Abandoned(AbandonReason::RoundLimit(5))),
+            result.premature_end
+        );
+
+        let mut g = seated_game();
+        let result = run_game(&mut g, [&mut a, &mut b, &mut c, &mut d]).unwrap();
+        assert_eq!(
+            Some(PrematureEnd::Abandoned(AbandonReason::RoundLimit(
+                ROUND_LIMIT
+            ))),
+            result.premature_end
+        );
+    }
+
+    #[test]
+    fn test_player_view_does_not_reveal_hand() {
+        let mut g = seated_game();
+        g.start_game();
+        let view = g.player_view(&Uid(10)).unwrap();
+        assert_eq!(Seat::North, view.seat);
+        assert_eq!(13, view.hand.len());
+        assert!(view.playable.is_empty());
+        assert_eq!(None, g.can_place_bet(Bet::BlindNil));
+    }
+
+    #[test]
+    fn test_hand_orders() {
+        let cards: Vec<Card> = [(3, 14), (0, 2), (2, 14), (1, 9), (3, 2), (0, 14)]
+            .iter()
+            .map(|(suit, rank)| Card {
+                suit: Suit::from(*suit),
+                rank: Rank::from(*rank),
+            })
+            .collect();
+        let sorted = |order: HandOrder| {
+            let mut hand = cards.clone();
+            order.sort(&mut hand);
+            hand.iter()
+                .map(|card| card.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        assert_eq!("2♣ A♣ 9♦ A♥ 2♠ A♠", sorted(HandOrder::SuitThenRank));
+        assert_eq!("2♣ 2♠ 9♦ A♣ A♥ A♠", sorted(HandOrder::RankThenSuit));
+        assert_eq!("2♠ A♠ A♥ 2♣ A♣ 9♦", sorted(HandOrder::AlternatingColors));
+        assert_eq!("9♦ 2♣ A♣ A♥ 2♠ A♠", sorted(HandOrder::SpadesLast));
+    }
+
+    #[test]
+    fn test_player_view_in_order() {
+        let mut g = seated_game();
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let view = g.player_view(&Uid(10)).unwrap();
+        assert!(matches!(view.hand, Cow::Borrowed(_)));
+        let mut hand = view.hand.to_vec();
+        let mut playable = view.playable.clone();
+        HandOrder::RankThenSuit.sort(&mut hand);
+        HandOrder::RankThenSuit.sort(&mut playable);
+        let view = g
+            .player_view_in_order(&Uid(10), HandOrder::RankThenSuit)
+            .unwrap();
+        assert_eq!(hand, view.hand.to_vec());
+        assert_eq!(playable, view.playable);
+    }
+
+    #[test]
+    fn test_relative_view_seats_around_player() {
+        let mut g = seated_game();
+        g.start_game();
+        for bet in 1..=4 {
+            g.place_bet(Bet::Amount(bet));
+        }
+        for _ in 0..5 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+        let leader = g.current_trick_leader().unwrap();
+        let led = g.snapshot().current_trick[0];
+
+        let view = g.player_view(&Uid(11)).unwrap();
+        let table = view.relative();
+        assert_eq!(Seat::East, table.me.seat);
+        assert_eq!(Uid(12), table.left.player);
+        assert_eq!(Uid(13), table.partner.player);
+        assert_eq!(Uid(10), table.right.player);
+        assert_eq!(Some(Bet::Amount(3)), table.left.bet);
+        assert_eq!(Some(Bet::Amount(1)), table.right.bet);
+        let seats = [table.me, table.left, table.partner, table.right];
+        let total: u8 = seats.iter().map(|seat| seat.tricks).sum();
+        assert_eq!(1, total);
+        let lead_seat = seats.iter().find(|seat| seat.player == leader).unwrap();
+        assert_eq!(Some(led), lead_seat.card_in_trick);
+        assert!(!lead_seat.to_act);
+        assert_eq!(
+            1,
+            seats
+                .iter()
+                .filter(|seat| seat.card_in_trick.is_some())
+                .count()
+        );
+        assert_eq!(1, seats.iter().filter(|seat| seat.to_act).count());
+    }
+}
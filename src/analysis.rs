@@ -0,0 +1,414 @@
+//! What a hand is worth: its shape, its high cards, and how many tricks it should take, as the basis for a
+//! bid; and, once a game is over, how well each player bid and which tricks mattered.
+//!
+//! ```
+//! use spades::analysis::describe;
+//! use spades::Game;
+//!
+//! let mut g = Game::default();
+//! g.start_game();
+//! let analysis = describe(g.hand_of_current_player().unwrap());
+//! assert_eq!(13, analysis.suit_lengths.iter().sum::<usize>());
+//! println!("{}", analysis.summary);
+//! ```
+
+use cards::{Card, Rank, Suit};
+use game_state::State;
+use result::SpadesError;
+use scoring::Bet;
+use seat::Seat;
+use {Game, Uid};
+
+/// The description of a hand returned by [`describe`](fn.describe.html).  The per-suit arrays are indexed
+/// by `Suit as usize`: clubs, diamonds, hearts, then spades.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct HandAnalysis {
+    pub suit_lengths: [usize; 4],
+    /// Aces, kings, queens and jacks held in each suit.
+    pub honors: [usize; 4],
+    /// Tricks the hand should take by itself, counting sure winners and spades long enough to win tricks
+    /// or trump other suits.
+    pub estimated_tricks: u8,
+    /// From 0 to 100, how likely the hand is to take no tricks at all.
+    pub nil_suitability: u8,
+    /// The above in words, such as "4 spades (A K), 3 hearts (Q), 4 diamonds, 2 clubs (K); about 3
+    /// tricks; poor for nil".
+    pub summary: String,
+}
+
+/// A nil suitability at least this high makes [`HandAnalysis::suggested_bet`](struct.HandAnalysis.html#method.suggested_bet)
+/// bid nil.
+pub const NIL_THRESHOLD: u8 = 70;
+
+impl HandAnalysis {
+    /// The bet the hand is worth: nil if it is suited to nil and expected to take at most one trick,
+    /// otherwise its estimated tricks, and at least one.
+    pub fn suggested_bet(&self) -> Bet {
+        if self.nil_suitability >= NIL_THRESHOLD && self.estimated_tricks <= 1 {
+            Bet::Nil
+        } else {
+            Bet::Amount(self.estimated_tricks.max(1))
+        }
+    }
+}
+
+/// Describes `hand`, which may hold any number of cards.
+pub fn describe(hand: &[Card]) -> HandAnalysis {
+    let mut suit_lengths = [0; 4];
+    let mut honors = [0; 4];
+    for card in hand {
+        suit_lengths[card.suit as usize] += 1;
+        if card.rank >= Rank::Jack {
+            honors[card.suit as usize] += 1;
+        }
+    }
+    let estimated_tricks = estimate_tricks(hand, &suit_lengths);
+    let nil_suitability = nil_suitability(hand, &suit_lengths);
+    let summary = summarize(hand, &suit_lengths, estimated_tricks, nil_suitability);
+    HandAnalysis {
+        suit_lengths,
+        honors,
+        estimated_tricks,
+        nil_suitability,
+        summary,
+    }
+}
+
+fn holds(hand: &[Card], suit: Suit, rank: Rank) -> bool {
+    hand.contains(&Card { suit, rank })
+}
+
+/// Counts in half tricks, then rounds down.
+fn estimate_tricks(hand: &[Card], suit_lengths: &[usize; 4]) -> u8 {
+    let mut halves = 0;
+    let spades = suit_lengths[Suit::Spades as usize];
+    let mut spare_spades = spades;
+    for suit in Suit::ALL.iter() {
+        let length = suit_lengths[*suit as usize];
+        // side suits rarely go round more than twice before someone trumps them
+        let side = *suit != Suit::Spades;
+        if holds(hand, *suit, Rank::Ace) && (!side || length <= 6) {
+            halves += 2;
+        }
+        if holds(hand, *suit, Rank::King) && length >= 2 && (!side || length <= 5) {
+            halves += 2;
+        }
+        if holds(hand, *suit, Rank::Queen) && length >= 3 && (!side || length <= 4) {
+            halves += if side { 1 } else { 2 };
+        }
+        if side && length <= 1 && spare_spades > 0 {
+            // a short suit lets the spades trump it
+            let ruffs = (2 - length).min(spare_spades);
+            halves += ruffs;
+            spare_spades -= ruffs;
+        }
+    }
+    // spades beyond the third take tricks once the others have run out
+    halves += 2 * spades.saturating_sub(3);
+    (halves / 2) as u8
+}
+
+fn nil_suitability(hand: &[Card], suit_lengths: &[usize; 4]) -> u8 {
+    let mut risk = 0;
+    for card in hand {
+        let length = suit_lengths[card.suit as usize];
+        // low cards in the same suit let a high card be ducked under someone else's
+        let cover = length.saturating_sub(1).min(3) as i32;
+        risk += match (card.suit, card.rank) {
+            (Suit::Spades, Rank::Ace) | (Suit::Spades, Rank::King) => 40,
+            (Suit::Spades, rank) if rank >= Rank::Ten => 20,
+            (_, Rank::Ace) => 30,
+            (_, Rank::King) => 22 - 5 * cover,
+            (_, Rank::Queen) => 15 - 4 * cover,
+            (_, Rank::Jack) => 8 - 2 * cover,
+            _ => 0,
+        };
+    }
+    let spades = suit_lengths[Suit::Spades as usize] as i32;
+    risk += 10 * (spades - 3).max(0);
+    (100 - risk).clamp(0, 100) as u8
+}
+
+fn summarize(hand: &[Card], suit_lengths: &[usize; 4], tricks: u8, nil: u8) -> String {
+    let suits = [
+        (Suit::Spades, "spade", "spades"),
+        (Suit::Hearts, "heart", "hearts"),
+        (Suit::Diamonds, "diamond", "diamonds"),
+        (Suit::Clubs, "club", "clubs"),
+    ];
+    let parts: Vec<String> = suits
+        .iter()
+        .map(|(suit, one, many)| {
+            let length = suit_lengths[*suit as usize];
+            let mut part = format!("{} {}", length, if length == 1 { one } else { many });
+            let high: Vec<String> = [Rank::Ace, Rank::King, Rank::Queen, Rank::Jack]
+                .iter()
+                .filter(|rank| holds(hand, *suit, **rank))
+                .map(|rank| rank.to_string())
+                .collect();
+            if !high.is_empty() {
+                part.push_str(&format!(" ({})", high.join(" ")));
+            }
+            part
+        })
+        .collect();
+    let nil = if nil >= NIL_THRESHOLD {
+        "good for nil"
+    } else if nil >= NIL_THRESHOLD / 2 {
+        "risky for nil"
+    } else {
+        "poor for nil"
+    };
+    format!(
+        "{}; about {} trick{}; {}",
+        parts.join(", "),
+        tricks,
+        if tricks == 1 { "" } else { "s" },
+        nil
+    )
+}
+
+/// How one player did over a finished game, as returned by [`report`](fn.report.html).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct AnalysisReport {
+    pub player: Uid,
+    pub seat: Seat,
+    /// For each round, the player's bet and the tricks they took.
+    pub rounds: Vec<(Bet, u8)>,
+    /// Tricks the player took beyond their bet that became their team's bags.
+    pub bags: u32,
+    /// Nil and blind nil bets made, and how many of them took a trick.
+    pub nil_attempts: u32,
+    pub nil_failures: u32,
+    /// The trick the player won that mattered most to the score, if they won any.
+    pub biggest_swing: Option<TrickSwing>,
+}
+
+/// A trick that changed a round's score, see [`AnalysisReport`](struct.AnalysisReport.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct TrickSwing {
+    /// Counting from 0, as in [`Game::round_history`](../struct.Game.html#method.round_history).
+    pub round: usize,
+    pub trick: usize,
+    /// How many points further ahead of the other team the winner's team came out of the round than if the
+    /// player on the winner's left had taken the trick; negative if winning it cost them.
+    pub points: i32,
+}
+
+/// A report for each player of a completed game, in seat order, worked out from the game's history.
+/// Returns `SpadesError::GameNotCompleted` for a game that isn't over.
+pub fn report(game: &Game) -> Result<Vec<AnalysisReport>, SpadesError> {
+    if game.state() != State::GameCompleted {
+        return Err(SpadesError::GameNotCompleted);
+    }
+    let mut reports: Vec<AnalysisReport> = Seat::ALL
+        .iter()
+        .map(|seat| AnalysisReport {
+            player: game.player[seat.index()].id,
+            seat: *seat,
+            rounds: vec![],
+            bags: 0,
+            nil_attempts: 0,
+            nil_failures: 0,
+            biggest_swing: None,
+        })
+        .collect();
+
+    let mut bags_before = [0, 0];
+    let history = game.round_history();
+    for (number, round) in history.iter().enumerate() {
+        let (tricks, scores) = match round.result {
+            Some(result) => result,
+            None => continue,
+        };
+        let bets = [
+            round.bets[0].unwrap_or(Bet::Amount(0)),
+            round.bets[1].unwrap_or(Bet::Amount(0)),
+            round.bets[2].unwrap_or(Bet::Amount(0)),
+            round.bets[3].unwrap_or(Bet::Amount(0)),
+        ];
+        for (seat, report) in reports.iter_mut().enumerate() {
+            report.rounds.push((bets[seat], tricks[seat]));
+            match bets[seat] {
+                Bet::Nil | Bet::BlindNil => {
+                    report.nil_attempts += 1;
+                    if tricks[seat] > 0 {
+                        report.nil_failures += 1;
+                    }
+                }
+                bet => {
+                    let over = tricks[seat].saturating_sub(bet.tricks_required());
+                    report.bags += over.min(scores[seat % 2].bags) as u32;
+                }
+            }
+        }
+
+        for (number_in_round, trick) in round.tricks.iter().enumerate() {
+            let winner = trick.winner.index();
+            let team = winner % 2;
+            let other = trick.winner.left().index();
+            let mut instead = tricks;
+            instead[winner] -= 1;
+            instead[other] += 1;
+            let mut rescored = game.scoring.score_bets(&bets, &instead, &bags_before);
+            if number + 1 == history.len() {
+                game.scoring.settle_bags(&mut rescored);
+            }
+            let points = (scores[team].points - scores[1 - team].points)
+                - (rescored[team].points - rescored[1 - team].points);
+            let report = &mut reports[winner];
+            if report
+                .biggest_swing
+                .is_none_or(|swing| points.abs() > swing.points.abs())
+            {
+                report.biggest_swing = Some(TrickSwing {
+                    round: number,
+                    trick: number_in_round,
+                    points,
+                });
+            }
+        }
+        bags_before = [scores[0].cumulative_bags, scores[1].cumulative_bags];
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{describe, report};
+    use cards::{Card, Rank, Suit};
+    use test_support::seated_game;
+    use {Bet, SpadesError, Uid};
+
+    fn hand(cards: &[(Suit, u8)]) -> Vec<Card> {
+        cards
+            .iter()
+            .map(|(suit, rank)| Card {
+                suit: *suit,
+                rank: Rank::from(*rank),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_strong_hand() {
+        let cards = hand(&[
+            (Suit::Spades, 14),
+            (Suit::Spades, 13),
+            (Suit::Spades, 12),
+            (Suit::Spades, 9),
+            (Suit::Spades, 5),
+            (Suit::Hearts, 14),
+            (Suit::Hearts, 13),
+            (Suit::Hearts, 4),
+            (Suit::Diamonds, 14),
+            (Suit::Diamonds, 3),
+            (Suit::Diamonds, 2),
+            (Suit::Clubs, 7),
+            (Suit::Clubs, 6),
+        ]);
+        let analysis = describe(&cards);
+        assert_eq!([2, 3, 3, 5], analysis.suit_lengths);
+        assert_eq!([0, 1, 2, 3], analysis.honors);
+        // three spade honors, two more long spades, and three side winners
+        assert_eq!(8, analysis.estimated_tricks);
+        assert_eq!(0, analysis.nil_suitability);
+        assert_eq!(Bet::Amount(8), analysis.suggested_bet());
+        assert_eq!(
+            "5 spades (A K Q), 3 hearts (A K), 3 diamonds (A), 2 clubs; about 8 tricks; poor for nil",
+            analysis.summary
+        );
+    }
+
+    #[test]
+    fn test_nil_hand() {
+        let cards = hand(&[
+            (Suit::Spades, 2),
+            (Suit::Spades, 4),
+            (Suit::Hearts, 2),
+            (Suit::Hearts, 3),
+            (Suit::Hearts, 5),
+            (Suit::Hearts, 9),
+            (Suit::Diamonds, 2),
+            (Suit::Diamonds, 4),
+            (Suit::Diamonds, 6),
+            (Suit::Diamonds, 11),
+            (Suit::Clubs, 3),
+            (Suit::Clubs, 5),
+            (Suit::Clubs, 8),
+        ]);
+        let analysis = describe(&cards);
+        assert_eq!(0, analysis.estimated_tricks);
+        assert!(analysis.nil_suitability >= 90);
+        assert_eq!(Bet::Nil, analysis.suggested_bet());
+        assert!(analysis.summary.ends_with("about 0 tricks; good for nil"));
+    }
+
+    #[test]
+    fn test_short_suits_with_spades_ruff() {
+        let cards = hand(&[
+            (Suit::Spades, 2),
+            (Suit::Spades, 3),
+            (Suit::Hearts, 2),
+            (Suit::Hearts, 3),
+            (Suit::Hearts, 4),
+            (Suit::Hearts, 5),
+            (Suit::Hearts, 6),
+            (Suit::Hearts, 7),
+            (Suit::Diamonds, 2),
+            (Suit::Diamonds, 3),
+            (Suit::Diamonds, 4),
+            (Suit::Diamonds, 5),
+            (Suit::Diamonds, 6),
+        ]);
+        let analysis = describe(&cards);
+        // the two spades can trump the missing clubs
+        assert_eq!(1, analysis.estimated_tricks);
+        // but nothing else can win a trick, and spades needn't be used to trump
+        assert_eq!(Bet::Nil, analysis.suggested_bet());
+        assert!(analysis
+            .summary
+            .starts_with("2 spades, 6 hearts, 5 diamonds, 0 clubs;"));
+    }
+
+    #[test]
+    fn test_report_after_game() {
+        let mut g = seated_game();
+        assert_eq!(Err(SpadesError::GameNotCompleted), report(&g));
+        g.set_shuffle_seed(Some(7));
+        g.start_game();
+        let mut rounds = 0;
+        while !g.is_over() {
+            for bet in [Bet::Nil, Bet::Amount(3), Bet::Amount(3), Bet::Amount(3)].iter() {
+                g.place_bet(*bet);
+            }
+            for _ in 0..52 {
+                let card = g.playable_cards().next().unwrap();
+                g.play_card(card);
+            }
+            rounds += 1;
+        }
+
+        let reports = report(&g).unwrap();
+        assert_eq!(4, reports.len());
+        assert_eq!(Uid(11), reports[1].player);
+        let history = g.round_history();
+        for (seat, report) in reports.iter().enumerate() {
+            assert_eq!(rounds, report.rounds.len());
+            let taken: u32 = report.rounds.iter().map(|(_, tricks)| *tricks as u32).sum();
+            let won = history
+                .iter()
+                .flat_map(|round| round.tricks.iter())
+                .filter(|trick| trick.winner.index() == seat)
+                .count() as u32;
+            assert_eq!(taken, won);
+            assert_eq!(taken > 0, report.biggest_swing.is_some());
+        }
+        assert_eq!(rounds as u32, reports[0].nil_attempts);
+        let failed = reports[0].rounds.iter().filter(|(_, t)| *t > 0).count() as u32;
+        assert_eq!(failed, reports[0].nil_failures);
+        assert_eq!(0, reports[1].nil_attempts);
+        let bags: u32 = g.team_all_rounds_bags(1).unwrap() as u32;
+        assert!(reports[1].bags + reports[3].bags >= bags);
+    }
+}
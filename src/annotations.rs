@@ -0,0 +1,104 @@
+use {Game, Uid};
+
+/// Commentary or a ruling attached to a game's event log with
+/// [`Game::annotate`](struct.Game.html#method.annotate).  Needs the `annotations` feature.
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct Annotation {
+    /// The sequence number of the latest event when the annotation was made, so a replay can show it right
+    /// after that event; 0 if it was made before any.
+    pub seq: u64,
+    pub author: Uid,
+    pub text: String,
+}
+
+impl Game {
+    /// Attaches `text` by `author`, who needn't be playing, to the event log after the latest event, and
+    /// returns that event's sequence number.  Annotations don't change the game, and are saved with it.
+    pub fn annotate(&mut self, author: Uid, text: &str) -> u64 {
+        let seq = self.last_seq();
+        self.event_log.annotations.push(Annotation {
+            seq,
+            author,
+            text: text.to_string(),
+        });
+        seq
+    }
+
+    /// Every annotation kept, oldest first.  Those attached to events a [checkpoint](#method.checkpoint)
+    /// drops are dropped with them.
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.event_log.annotations
+    }
+
+    /// The annotations made after event `seq`, to go with [`events_since`](#method.events_since) the same
+    /// `seq`.
+    pub fn annotations_since(&self, seq: u64) -> Vec<Annotation> {
+        self.event_log
+            .annotations
+            .iter()
+            .filter(|annotation| annotation.seq >= seq)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_support::{seated_game, seeded_game};
+    extern crate serde_json;
+
+    use super::Annotation;
+    use {Bet, Game, Uid};
+
+    fn play_round(g: &mut Game) {
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        for _ in 0..52 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+    }
+
+    #[test]
+    fn test_annotations_follow_the_event_log() {
+        let mut g = seated_game();
+        assert_eq!(0, g.annotate(Uid(99), "welcome"));
+        g.start_game();
+        g.place_bet(Bet::Amount(4));
+        let seq = g.annotate(Uid(98), "bold bid");
+        assert_eq!(g.last_seq(), seq);
+        let before = g.clone();
+        g.annotate(Uid(99), "table talk warning to North");
+        // annotations don't change the game
+        assert_eq!(before, g);
+
+        assert_eq!(3, g.annotations().len());
+        assert_eq!(
+            Annotation {
+                seq,
+                author: Uid(98),
+                text: "bold bid".to_string(),
+            },
+            g.annotations()[1]
+        );
+        assert_eq!(2, g.annotations_since(seq).len());
+        assert!(g.annotations_since(seq + 1).is_empty());
+
+        let saved: Game = serde_json::from_str(&serde_json::to_string(&g).unwrap()).unwrap();
+        assert_eq!(g.annotations(), saved.annotations());
+    }
+
+    #[test]
+    fn test_checkpoint_drops_annotations_with_their_events() {
+        let mut g = seeded_game(4);
+        g.annotate(Uid(99), "first round");
+        play_round(&mut g);
+        g.annotate(Uid(99), "second round");
+        g.checkpoint();
+        let texts: Vec<&str> = g.annotations().iter().map(|a| a.text.as_str()).collect();
+        assert_eq!(vec!["second round"], texts);
+    }
+}
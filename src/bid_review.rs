@@ -0,0 +1,124 @@
+use diagnostics::GameEvent;
+use game_state::State;
+use result::SpadesError;
+use scoring::Bet;
+use Game;
+
+/// The last bidder's partner, who reviews the bets when [`GameOptions::bid_review`](struct.GameOptions.html)
+/// is set.
+pub(crate) const REVIEWER: usize = 1;
+
+impl Game {
+    /// A method to determine whether the reviewing player may change their bet to `amendment`, or keep it
+    /// with `None`.  If it would not be possible, the reason why not will be returned in Some(SpadesError).
+    /// Blind nil can't be bid once the bets have been reviewed.
+    pub fn can_review_bet(&self, amendment: Option<Bet>) -> Option<SpadesError> {
+        match self.state {
+            State::GameNotStarted => Some(SpadesError::GameNotStarted),
+            State::GameCompleted => Some(SpadesError::GameCompleted),
+            State::Paused => Some(SpadesError::GamePaused),
+            State::Abandoned => Some(SpadesError::GameAbandoned),
+            State::Betting(_) | State::Trick(_) | State::Exchange => {
+                Some(SpadesError::ImproperGameStage)
+            }
+            State::BidReview if amendment == Some(Bet::BlindNil) => {
+                Some(SpadesError::BetImproperSeenHand)
+            }
+            State::BidReview => amendment.and_then(|bet| self.check_team_bid(bet)),
+        }
+    }
+
+    /// Change the reviewing player's bet to `amendment`, or keep it with `None`, which ends the review and
+    /// moves the game on to the first trick (or the kitty exchange).
+    pub fn review_bet(&mut self, amendment: Option<Bet>) -> Result<State, SpadesError> {
+        if let Some(err) = self.can_review_bet(amendment) {
+            return Err(err);
+        }
+        self.turn_started = None;
+        let from = self.scoring.bets()[self.current_player_index];
+        if let Some(to) = amendment.filter(|to| *to != from) {
+            self.record_event(GameEvent::BetAmended {
+                player: self.player[self.current_player_index].id,
+                from,
+                to,
+            });
+            self.scoring.add_bet(self.current_player_index, to);
+            self.round_bets[self.current_player_index] = Some(to);
+        }
+        self.finish_betting();
+        self.check_invariants();
+        Ok(self.state)
+    }
+
+    /// Starts the review once everyone has bet, if the table reviews bets.  Returns whether it started.
+    pub(crate) fn start_bid_review(&mut self) -> bool {
+        if !self.options.bid_review {
+            return false;
+        }
+        self.current_player_index = REVIEWER;
+        self.state = State::BidReview;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_support::seated_game;
+    use {Bet, BetResult, Game, GameEvent, GameOptions, SpadesError, State, Uid};
+
+    fn reviewing_game() -> Game {
+        let mut g = seated_game();
+        g.set_options(GameOptions {
+            bid_review: true,
+            ..GameOptions::default()
+        })
+        .unwrap();
+        g.start_game();
+        for _ in 0..3 {
+            assert_eq!(Some(BetResult::MadeBet), g.place_bet(Bet::Amount(3)));
+        }
+        assert_eq!(Some(BetResult::MadeBet), g.place_bet(Bet::Amount(2)));
+        g
+    }
+
+    #[test]
+    fn test_partner_of_last_bidder_amends_once() {
+        let mut g = reviewing_game();
+        assert_eq!(State::BidReview, g.state());
+        assert_eq!(Ok(Uid(11)), g.current_player_id());
+        assert_eq!(
+            Some(SpadesError::NotBettingStage { next: Uid(11) }),
+            g.can_place_bet(Bet::Amount(4))
+        );
+        assert_eq!(
+            Some(SpadesError::BetImproperSeenHand),
+            g.can_review_bet(Some(Bet::BlindNil))
+        );
+
+        assert_eq!(Ok(State::Trick(0)), g.review_bet(Some(Bet::Amount(5))));
+        assert_eq!(Ok(Uid(10)), g.current_player_id());
+        assert_eq!(Some(Bet::Amount(5)), g.bets_with_players()[1].1);
+        assert!(g.history().contains(&GameEvent::BetAmended {
+            player: Uid(11),
+            from: Bet::Amount(3),
+            to: Bet::Amount(5),
+        }));
+        assert_eq!(Some(Bet::Amount(5)), g.round_history()[0].bets[1]);
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            g.review_bet(Some(Bet::Amount(6)))
+        );
+    }
+
+    #[test]
+    fn test_keeping_bets_records_nothing() {
+        let mut g = reviewing_game();
+        let events = g.history().len();
+        assert_eq!(Ok(State::Trick(0)), g.review_bet(Some(Bet::Amount(3))));
+        assert_eq!(events, g.history().len());
+
+        let mut g = reviewing_game();
+        assert_eq!(Ok(State::Trick(0)), g.review_bet(None));
+        assert_eq!(events, g.history().len());
+    }
+}
@@ -2,7 +2,7 @@ extern crate rand;
 extern crate spades;
 
 use rand::{thread_rng, Rng};
-use spades::{Bet, Game, State, Uid};
+use spades::{run_game, Bet, Card, Game, GameOptions, PlayerAgent, PlayerView, Uid};
 
 fn main() {
     let game_id = Uid(12345);
@@ -10,8 +10,8 @@ fn main() {
     for _r in 0..1000 {
         // rounds
         //println!("round {}", r + 1);
-        let mut g = Game::default();
-        g.assign_players(game_id, player_ids);
+        let mut g = Game::with_options(game_id, GameOptions::default()).unwrap();
+        g.seat_players(player_ids).unwrap();
         play_complete_round(&mut g);
         /*
         println!("winners of final game: {:?}", g.get_winner_ids().unwrap());
@@ -33,50 +33,22 @@ fn main() {
     }
 }
 
-fn play_complete_round(g: &mut Game) {
-    let mut rng = thread_rng();
-    loop {
-        match g.state() {
-            State::GameNotStarted => {
-                g.start_game();
-            }
-            State::Betting(_player_index) => {
-                g.place_bet(Bet::Amount(3));
-            }
-            State::Trick(_player_index) => {
-                let hand = g.current_hand().unwrap().clone();
-                let mut times_through = 0;
-                let mut last_choice = None;
-                let mut last_err = None;
-                loop {
-                    times_through += 1;
-                    if times_through > 1000 {
-                        println!("{:?}", g);
-                        println!("{:?}", hand);
-                        println!("{:?}", last_choice);
-                        println!("{:?}", last_err);
-                        panic!("should have something to play");
-                    }
-                    if let Some(random_card) = rng.choose(hand.as_slice()) {
-                        // println!("player {} plays {}{}", playerindex, random_card.rank, random_card.suit);
-                        last_choice = Some(*random_card);
-                        if let Some(err) = g.can_play_card(*random_card) {
-                            // we're assuming the error was SpadesError::CardIncorrectSuit
-                            // println!("player {} tried to play {}{}, but it was the incorrect suit", playerindex, random_card.rank, random_card.suit);
-                            last_err = Some(err);
-                            continue;
-                        } else {
-                            g.play_card(*random_card);
-                            break;
-                        }
-                    } else {
-                        panic!("no valid card can be chosen");
-                    }
-                }
-            }
-            State::GameCompleted => {
-                return;
-            }
-        }
+/// Bets 3 and plays a random legal card.
+struct RandomPlayer;
+
+impl PlayerAgent for RandomPlayer {
+    fn bet(&mut self, _view: &PlayerView) -> Bet {
+        Bet::Amount(3)
+    }
+
+    fn play_card(&mut self, view: &PlayerView) -> Card {
+        *thread_rng()
+            .choose(view.playable.as_slice())
+            .expect("should have something to play")
     }
 }
+
+fn play_complete_round(g: &mut Game) {
+    let (mut a, mut b, mut c, mut d) = (RandomPlayer, RandomPlayer, RandomPlayer, RandomPlayer);
+    run_game(g, [&mut a, &mut b, &mut c, &mut d]).expect("the game should play to the end");
+}
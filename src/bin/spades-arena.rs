@@ -0,0 +1,219 @@
+//! Plays a round-robin tournament between built-in bots and prints how each did.
+//!
+//! ```text
+//! cargo run --release --features arena,rayon --bin spades-arena -- arena.toml --format json
+//! ```
+//!
+//! The configuration names the bots and their parameters (see `BotConfig`):
+//!
+//! ```toml
+//! games_per_pairing = 20
+//! seed = 7
+//!
+//! [[bot]]
+//! name = "careful"
+//! kind = "heuristic"
+//! bid_offset = -1
+//!
+//! [[bot]]
+//! name = "random"
+//! kind = "random"
+//! bet = 3
+//! ```
+//!
+//! With the `rayon` feature the games run in parallel.
+extern crate serde;
+extern crate serde_json;
+extern crate spades;
+extern crate toml;
+
+use spades::{BotConfig, Tournament, TournamentResults};
+use std::{env, fs, process};
+
+const USAGE: &str = "usage: spades-arena <config.toml> [--format table|json|csv]";
+
+#[derive(Debug, serde::Deserialize)]
+struct ArenaConfig {
+    #[serde(default = "default_games_per_pairing")]
+    games_per_pairing: usize,
+    #[serde(default)]
+    seed: u64,
+    bot: Vec<BotEntry>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct BotEntry {
+    name: String,
+    #[serde(flatten)]
+    config: BotConfig,
+}
+
+fn default_games_per_pairing() -> usize {
+    10
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+fn main() {
+    let mut path = None;
+    let mut format = Format::Table;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("table") => Format::Table,
+                    Some("json") => Format::Json,
+                    Some("csv") => Format::Csv,
+                    _ => fail(USAGE),
+                }
+            }
+            _ if path.is_none() && !arg.starts_with('-') => path = Some(arg),
+            _ => fail(USAGE),
+        }
+    }
+    let path = path.unwrap_or_else(|| fail(USAGE));
+
+    let text = fs::read_to_string(&path).unwrap_or_else(|e| fail(&format!("{}: {}", path, e)));
+    let config = parse_config(&text).unwrap_or_else(|e| fail(&format!("{}: {}", path, e)));
+    if config.bot.len() < 2 {
+        fail(&format!("{}: at least two bots are needed", path));
+    }
+
+    let mut tournament = Tournament::new(config.games_per_pairing);
+    tournament.set_seed(config.seed);
+    for entry in &config.bot {
+        let bot = entry.config;
+        tournament.add_entrant(&entry.name, move |game| bot.agent(game));
+    }
+    let results = tournament
+        .run()
+        .unwrap_or_else(|e| fail(&format!("{}: {}", path, e)));
+
+    match format {
+        Format::Table => print!("{}", table(&results)),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&results).unwrap()),
+        Format::Csv => print!("{}", csv(&results)),
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2)
+}
+
+fn parse_config(text: &str) -> Result<ArenaConfig, String> {
+    toml::from_str(text).map_err(|e| e.to_string())
+}
+
+fn win_rate(games: u32, wins: u32) -> f64 {
+    if games == 0 {
+        0.0
+    } else {
+        wins as f64 / games as f64
+    }
+}
+
+fn table(results: &TournamentResults) -> String {
+    let width = results
+        .entrants
+        .iter()
+        .map(|s| s.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(3);
+    let mut out = format!(
+        "{:<w$}  {:>5}  {:>5}  {:>6}  {:>10}  {:>10}  {:>10}\n",
+        "bot",
+        "games",
+        "wins",
+        "win %",
+        "unfinished",
+        "points for",
+        "against",
+        w = width
+    );
+    for stats in &results.entrants {
+        out += &format!(
+            "{:<w$}  {:>5}  {:>5}  {:>6.1}  {:>10}  {:>10}  {:>10}\n",
+            stats.name,
+            stats.games,
+            stats.wins,
+            win_rate(stats.games, stats.wins) * 100.0,
+            stats.unfinished,
+            stats.points_for,
+            stats.points_against,
+            w = width
+        );
+    }
+    out += "\nwins against:\n";
+    out += &format!("{:<w$}", "", w = width);
+    for stats in &results.entrants {
+        out += &format!("  {:>w$}", stats.name, w = width);
+    }
+    out += "\n";
+    for (stats, row) in results.entrants.iter().zip(&results.head_to_head) {
+        out += &format!("{:<w$}", stats.name, w = width);
+        for wins in row {
+            out += &format!("  {:>w$}", wins, w = width);
+        }
+        out += "\n";
+    }
+    out
+}
+
+fn csv(results: &TournamentResults) -> String {
+    let mut out = String::from("bot,games,wins,win_rate,unfinished,points_for,points_against\n");
+    for stats in &results.entrants {
+        out += &format!(
+            "{},{},{},{:.4},{},{},{}\n",
+            csv_field(&stats.name),
+            stats.games,
+            stats.wins,
+            win_rate(stats.games, stats.wins),
+            stats.unfinished,
+            stats.points_for,
+            stats.points_against
+        );
+    }
+    out
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_config;
+    use spades::BotConfig;
+
+    #[test]
+    fn test_example_config_parses() {
+        let config = parse_config(include_str!("../../examples/arena.toml")).unwrap();
+        assert_eq!(10, config.games_per_pairing);
+        assert_eq!(1, config.seed);
+        let names: Vec<&str> = config.bot.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(vec!["heuristic", "underbidder", "random"], names);
+        assert_eq!(BotConfig::Random { bet: 3 }, config.bot[2].config);
+    }
+
+    #[test]
+    fn test_malformed_configs_are_refused() {
+        assert!(parse_config("[[bot]]\nname = \"a\"\nkind = \"heuristic\"").is_ok());
+        assert!(parse_config("seed = 1").is_err());
+        assert!(parse_config("[[bot]]\nname = \"a\"\nkind = \"wizard\"").is_err());
+        assert!(parse_config("seed = 1\nseed = 2\nbot = []").is_err());
+        assert!(parse_config("seed = \"one\"\nbot = []").is_err());
+        assert!(parse_config("seed 1\nbot = []").is_err());
+    }
+}
@@ -0,0 +1,182 @@
+extern crate rand;
+
+use self::rand::prng::XorShiftRng;
+use self::rand::{Rng, SeedableRng};
+use agent::{PlayerAgent, PlayerView};
+use analysis::describe;
+use cards::{winning_position, Card};
+use scoring::Bet;
+
+/// Bets a fixed number of tricks and plays a legal card at random.
+#[derive(Debug, Clone)]
+pub struct RandomBot {
+    bet: u8,
+    rng: XorShiftRng,
+}
+
+impl RandomBot {
+    /// Bets `bet` tricks every round, drawing its cards from random numbers seeded by `seed`.
+    pub fn new(bet: u8, seed: u64) -> RandomBot {
+        RandomBot {
+            bet,
+            rng: XorShiftRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl PlayerAgent for RandomBot {
+    fn bet(&mut self, _view: &PlayerView) -> Bet {
+        Bet::amount(self.bet).unwrap_or_default()
+    }
+
+    fn play_card(&mut self, view: &PlayerView) -> Card {
+        *self
+            .rng
+            .choose(&view.playable)
+            .expect("should have something to play")
+    }
+}
+
+/// Bets what [`analysis::describe`](analysis/fn.describe.html) suggests, and plays to win each trick as
+/// cheaply as it can unless its partner is already winning it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HeuristicBot {
+    /// Tricks added to (or, if negative, taken off) the suggested bet.
+    pub bid_offset: i8,
+    /// Bets nil when the hand is suited to it; otherwise always bets at least one trick.
+    pub allow_nil: bool,
+}
+
+impl PlayerAgent for HeuristicBot {
+    fn bet(&mut self, view: &PlayerView) -> Bet {
+        match describe(&view.hand).suggested_bet() {
+            Bet::Nil if self.allow_nil => Bet::Nil,
+            bet => {
+                let tricks = bet.tricks_required() as i16 + self.bid_offset as i16;
+                Bet::Amount(tricks.clamp(1, 13) as u8)
+            }
+        }
+    }
+
+    fn play_card(&mut self, view: &PlayerView) -> Card {
+        let trick = &view.table.current_trick;
+        let mut playable = view.playable.clone();
+        // cheapest first: side suits before spades, low ranks before high
+        playable.sort_by_key(|card| (card.suit.is_trump(), card.rank));
+        let lowest = playable[0];
+        let leading = match trick.first() {
+            Some(card) => card.suit,
+            // lead the highest side suit card, keeping spades back
+            None => {
+                return *playable
+                    .iter()
+                    .rfind(|card| !card.suit.is_trump())
+                    .unwrap_or(&lowest)
+            }
+        };
+        let winner = winning_position(trick);
+        let partner_winning = trick.len() >= 2 && winner == trick.len() - 2;
+        if partner_winning {
+            return lowest;
+        }
+        playable
+            .iter()
+            .find(|card| card.beats(&trick[winner], leading))
+            .cloned()
+            .unwrap_or(lowest)
+    }
+}
+
+/// A built-in bot and its parameters, as read from a configuration file such as `spades-arena`'s.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BotConfig {
+    /// A [`RandomBot`](struct.RandomBot.html), betting 3 unless told otherwise.
+    Random {
+        #[serde(default = "default_random_bet")]
+        bet: u8,
+    },
+    /// A [`HeuristicBot`](struct.HeuristicBot.html), allowed to bet nil unless told otherwise.
+    Heuristic {
+        #[serde(default)]
+        bid_offset: i8,
+        #[serde(default = "default_allow_nil")]
+        allow_nil: bool,
+    },
+}
+
+fn default_random_bet() -> u8 {
+    3
+}
+
+fn default_allow_nil() -> bool {
+    true
+}
+
+impl BotConfig {
+    /// The bot for one seat of game number `game`, seeded from it where the bot uses random numbers.
+    pub fn agent(&self, game: usize) -> Box<dyn PlayerAgent> {
+        match *self {
+            BotConfig::Random { bet } => Box::new(RandomBot::new(bet, game as u64)),
+            BotConfig::Heuristic {
+                bid_offset,
+                allow_nil,
+            } => Box::new(HeuristicBot {
+                bid_offset,
+                allow_nil,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BotConfig;
+    use agent::run_game;
+    use test_support::seated_game;
+    use Game;
+
+    fn play(configs: [BotConfig; 2]) -> Game {
+        let mut g = seated_game();
+        g.set_shuffle_seed(Some(5));
+        let mut agents = [
+            configs[0].agent(0),
+            configs[1].agent(0),
+            configs[0].agent(0),
+            configs[1].agent(0),
+        ];
+        let [a, b, c, d] = &mut agents;
+        run_game(&mut g, [a.as_mut(), b.as_mut(), c.as_mut(), d.as_mut()]).unwrap();
+        g
+    }
+
+    #[test]
+    fn test_bots_finish_games() {
+        let heuristic = BotConfig::Heuristic {
+            bid_offset: 0,
+            allow_nil: true,
+        };
+        let random = BotConfig::Random { bet: 3 };
+        let g = play([heuristic, random]);
+        assert!(g.result().unwrap().winners.is_some());
+        // the same seeds play the same game
+        assert_eq!(g.history(), play([heuristic, random]).history());
+    }
+
+    #[test]
+    fn test_bot_config_defaults() {
+        assert_eq!(
+            BotConfig::Random { bet: 3 },
+            serde_json::from_str(r#"{"kind": "random"}"#).unwrap()
+        );
+        assert_eq!(
+            BotConfig::Heuristic {
+                bid_offset: -1,
+                allow_nil: true
+            },
+            serde_json::from_str(r#"{"kind": "heuristic", "bid_offset": -1}"#).unwrap()
+        );
+    }
+}
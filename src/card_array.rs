@@ -0,0 +1,242 @@
+//! A list of cards kept inline in a fixed-size array, used for hands and the current trick when the `perf`
+//! feature is on, so that dealing and playing cards never touch the heap.  It reads like a `Vec<Card>`
+//! through `Deref` to a slice, and saves as the same list of cards.
+
+extern crate serde;
+
+use self::serde::de::Error;
+use cards::Card;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::{Deref, DerefMut};
+use std::{fmt, slice};
+
+/// Up to `N` cards.  Adding a card to a full array panics; `N` is chosen so that a game's cards can't fill it.
+#[derive(Clone)]
+pub(crate) struct CardArray<const N: usize> {
+    cards: [Card; N],
+    len: usize,
+}
+
+impl<const N: usize> CardArray<N> {
+    pub(crate) fn new() -> CardArray<N> {
+        CardArray {
+            cards: [Card::default(); N],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn push(&mut self, card: Card) {
+        assert!(self.len < N, "more than {} cards", N);
+        self.cards[self.len] = card;
+        self.len += 1;
+    }
+
+    pub(crate) fn remove(&mut self, index: usize) -> Card {
+        assert!(
+            index < self.len,
+            "removal index {} past {} cards",
+            index,
+            self.len
+        );
+        let card = self.cards[index];
+        self.cards[index..self.len].rotate_left(1);
+        self.len -= 1;
+        card
+    }
+
+    pub(crate) fn extend_from_slice(&mut self, cards: &[Card]) {
+        self.extend(cards);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for CardArray<N> {
+    fn default() -> CardArray<N> {
+        CardArray::new()
+    }
+}
+
+impl<const N: usize> Deref for CardArray<N> {
+    type Target = [Card];
+
+    fn deref(&self) -> &[Card] {
+        &self.cards[..self.len]
+    }
+}
+
+impl<const N: usize> DerefMut for CardArray<N> {
+    fn deref_mut(&mut self) -> &mut [Card] {
+        &mut self.cards[..self.len]
+    }
+}
+
+impl<const N: usize> Extend<Card> for CardArray<N> {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, cards: I) {
+        for card in cards {
+            self.push(card);
+        }
+    }
+}
+
+impl<'a, const N: usize> Extend<&'a Card> for CardArray<N> {
+    fn extend<I: IntoIterator<Item = &'a Card>>(&mut self, cards: I) {
+        self.extend(cards.into_iter().cloned());
+    }
+}
+
+impl<const N: usize> FromIterator<Card> for CardArray<N> {
+    fn from_iter<I: IntoIterator<Item = Card>>(cards: I) -> CardArray<N> {
+        let mut array = CardArray::new();
+        array.extend(cards);
+        array
+    }
+}
+
+impl<const N: usize> From<Vec<Card>> for CardArray<N> {
+    fn from(cards: Vec<Card>) -> CardArray<N> {
+        cards.into_iter().collect()
+    }
+}
+
+impl<'a, const N: usize> From<&'a [Card]> for CardArray<N> {
+    fn from(cards: &'a [Card]) -> CardArray<N> {
+        cards.iter().cloned().collect()
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a CardArray<N> {
+    type Item = &'a Card;
+    type IntoIter = slice::Iter<'a, Card>;
+
+    fn into_iter(self) -> slice::Iter<'a, Card> {
+        self.iter()
+    }
+}
+
+impl<const N: usize> IntoIterator for CardArray<N> {
+    type Item = Card;
+    type IntoIter = IntoIter<N>;
+
+    fn into_iter(self) -> IntoIter<N> {
+        IntoIter {
+            array: self,
+            next: 0,
+        }
+    }
+}
+
+/// The cards of a `CardArray`, taken by value.
+pub(crate) struct IntoIter<const N: usize> {
+    array: CardArray<N>,
+    next: usize,
+}
+
+impl<const N: usize> Iterator for IntoIter<N> {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        let card = self.array.get(self.next).cloned();
+        self.next += 1;
+        card
+    }
+}
+
+impl<const N: usize> fmt::Debug for CardArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<const N: usize> PartialEq for CardArray<N> {
+    fn eq(&self, other: &CardArray<N>) -> bool {
+        **self == **other
+    }
+}
+
+impl<const N: usize> Eq for CardArray<N> {}
+
+impl<const N: usize> PartialEq<Vec<Card>> for CardArray<N> {
+    fn eq(&self, other: &Vec<Card>) -> bool {
+        **self == other[..]
+    }
+}
+
+impl<const N: usize> PartialEq<CardArray<N>> for Vec<Card> {
+    fn eq(&self, other: &CardArray<N>) -> bool {
+        self[..] == **other
+    }
+}
+
+impl<const N: usize> PartialOrd for CardArray<N> {
+    fn partial_cmp(&self, other: &CardArray<N>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for CardArray<N> {
+    fn cmp(&self, other: &CardArray<N>) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<const N: usize> Hash for CardArray<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+impl<const N: usize> serde::Serialize for CardArray<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<'de, const N: usize> serde::Deserialize<'de> for CardArray<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<CardArray<N>, D::Error> {
+        let cards = Vec::<Card>::deserialize(deserializer)?;
+        if cards.len() > N {
+            return Err(D::Error::invalid_length(
+                cards.len(),
+                &format!("at most {} cards", N).as_str(),
+            ));
+        }
+        Ok(cards.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cards::new_deck;
+
+    #[test]
+    fn test_edits_match_vec() {
+        let deck = new_deck();
+        let mut array: CardArray<8> = deck[..5].iter().cloned().collect();
+        let mut vec = deck[..5].to_vec();
+        array.remove(1);
+        vec.remove(1);
+        array.push(deck[7]);
+        vec.push(deck[7]);
+        array.extend_from_slice(&deck[10..12]);
+        vec.extend_from_slice(&deck[10..12]);
+        assert_eq!(array, vec);
+        assert_eq!(vec, array.into_iter().collect::<Vec<Card>>());
+    }
+
+    #[test]
+    fn test_saves_as_a_list_of_cards() {
+        let deck = new_deck();
+        let array: CardArray<4> = deck[..3].iter().cloned().collect();
+        let json = serde_json::to_string(&array).unwrap();
+        assert_eq!(serde_json::to_string(&deck[..3]).unwrap(), json);
+        assert_eq!(array, serde_json::from_str::<CardArray<4>>(&json).unwrap());
+        let too_many = serde_json::to_string(&deck[..5]).unwrap();
+        assert!(serde_json::from_str::<CardArray<4>>(&too_many).is_err());
+    }
+}
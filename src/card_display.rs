@@ -0,0 +1,244 @@
+use cards::{Card, Rank, Suit};
+use deal::RANK_CHARS;
+use std::fmt;
+
+/// How [`Card::fmt_with`](struct.Card.html#method.fmt_with) and [`HandDisplay`](struct.HandDisplay.html)
+/// write cards.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum CardStyle {
+    /// Rank then suit letter, with ten written `T`: `QS`, `TH`.
+    Ascii,
+    /// Rank then suit symbol, as `Display` writes a card: `Q♠`, `10♥`.
+    #[default]
+    Unicode,
+    /// The card's own glyph from the Unicode playing cards block: `🂭`.
+    Glyph,
+}
+
+/// A card written in a [`CardStyle`](enum.CardStyle.html), returned by
+/// [`Card::fmt_with`](struct.Card.html#method.fmt_with).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CardDisplay {
+    card: Card,
+    style: CardStyle,
+}
+
+impl Card {
+    /// Displays the card in `style`.
+    pub fn fmt_with(&self, style: CardStyle) -> CardDisplay {
+        CardDisplay { card: *self, style }
+    }
+}
+
+fn rank_char(rank: Rank) -> char {
+    RANK_CHARS
+        .iter()
+        .find(|(r, _)| *r == rank)
+        .map_or('?', |(_, c)| *c)
+}
+
+fn suit_char(suit: Suit) -> char {
+    match suit {
+        Suit::Clubs => 'C',
+        Suit::Diamonds => 'D',
+        Suit::Hearts => 'H',
+        Suit::Spades => 'S',
+    }
+}
+
+fn glyph(card: Card) -> char {
+    let base = match card.suit {
+        Suit::Spades => 0x1F0A0,
+        Suit::Hearts => 0x1F0B0,
+        Suit::Diamonds => 0x1F0C0,
+        Suit::Clubs => 0x1F0D0,
+    };
+    let offset = match card.rank {
+        Rank::Ace => 1,
+        // the knight sits between the jack and the queen
+        Rank::Queen => 13,
+        Rank::King => 14,
+        rank => rank as u32,
+    };
+    std::char::from_u32(base + offset).unwrap_or('?')
+}
+
+impl fmt::Display for CardDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.style {
+            CardStyle::Ascii => write!(
+                f,
+                "{}{}",
+                rank_char(self.card.rank),
+                suit_char(self.card.suit)
+            ),
+            CardStyle::Unicode => write!(f, "{}", self.card),
+            CardStyle::Glyph => write!(f, "{}", glyph(self.card)),
+        }
+    }
+}
+
+/// Writes a hand of cards in a [`CardStyle`](enum.CardStyle.html), separated by spaces.
+///
+/// Grouped by suit, the hand takes a line per suit it holds, spades first, each starting with the suit.
+/// With a number of columns set, a line wraps after that many cards.
+///
+/// ```
+/// use spades::{Card, CardStyle, HandDisplay, Rank, Suit};
+///
+/// let cards = [
+///     Card { suit: Suit::Spades, rank: Rank::Ace },
+///     Card { suit: Suit::Hearts, rank: Rank::Ten },
+/// ];
+/// let display = HandDisplay::new(&cards).style(CardStyle::Ascii);
+/// assert_eq!("AS TH", display.to_string());
+/// assert_eq!("S: AS\nH: TH", display.group_by_suit(true).to_string());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandDisplay<'a> {
+    cards: &'a [Card],
+    style: CardStyle,
+    group_by_suit: bool,
+    columns: Option<usize>,
+}
+
+impl<'a> HandDisplay<'a> {
+    /// The hand in its own order, in the default style, on one line.
+    pub fn new(cards: &'a [Card]) -> HandDisplay<'a> {
+        HandDisplay {
+            cards,
+            style: CardStyle::default(),
+            group_by_suit: false,
+            columns: None,
+        }
+    }
+
+    pub fn style(mut self, style: CardStyle) -> HandDisplay<'a> {
+        self.style = style;
+        self
+    }
+
+    pub fn group_by_suit(mut self, group_by_suit: bool) -> HandDisplay<'a> {
+        self.group_by_suit = group_by_suit;
+        self
+    }
+
+    /// Wraps lines after `columns` cards, or never with `None` (or 0).
+    pub fn columns(mut self, columns: Option<usize>) -> HandDisplay<'a> {
+        self.columns = columns.filter(|columns| *columns > 0);
+        self
+    }
+
+    fn write_line<'b, I: Iterator<Item = &'b Card>>(
+        &self,
+        f: &mut fmt::Formatter,
+        cards: I,
+    ) -> fmt::Result {
+        for (i, card) in cards.enumerate() {
+            if i > 0 {
+                match self.columns {
+                    Some(columns) if i % columns == 0 => writeln!(f)?,
+                    _ => write!(f, " ")?,
+                }
+            }
+            write!(f, "{}", card.fmt_with(self.style))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for HandDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.group_by_suit {
+            return self.write_line(f, self.cards.iter());
+        }
+        let mut first = true;
+        for suit in Suit::ALL.iter().rev() {
+            if !self.cards.iter().any(|card| card.suit == *suit) {
+                continue;
+            }
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            match self.style {
+                CardStyle::Ascii => write!(f, "{}: ", suit_char(*suit))?,
+                CardStyle::Unicode | CardStyle::Glyph => write!(f, "{}: ", suit)?,
+            }
+            self.write_line(f, self.cards.iter().filter(|card| card.suit == *suit))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CardStyle, HandDisplay};
+    use cards::{Card, Rank, Suit};
+
+    fn card(suit: Suit, rank: Rank) -> Card {
+        Card { suit, rank }
+    }
+
+    #[test]
+    fn test_card_styles() {
+        let qs = card(Suit::Spades, Rank::Queen);
+        let th = card(Suit::Hearts, Rank::Ten);
+        assert_eq!("QS", qs.fmt_with(CardStyle::Ascii).to_string());
+        assert_eq!("TH", th.fmt_with(CardStyle::Ascii).to_string());
+        assert_eq!(qs.to_string(), qs.fmt_with(CardStyle::Unicode).to_string());
+        assert_eq!("\u{1F0AD}", qs.fmt_with(CardStyle::Glyph).to_string());
+        assert_eq!(
+            "\u{1F0A1}",
+            card(Suit::Spades, Rank::Ace)
+                .fmt_with(CardStyle::Glyph)
+                .to_string()
+        );
+        assert_eq!(
+            "\u{1F0DB}",
+            card(Suit::Clubs, Rank::Jack)
+                .fmt_with(CardStyle::Glyph)
+                .to_string()
+        );
+        assert_eq!("\u{1F0BA}", th.fmt_with(CardStyle::Glyph).to_string());
+    }
+
+    #[test]
+    fn test_hand_display_layouts() {
+        let hand = [
+            card(Suit::Clubs, Rank::Two),
+            card(Suit::Spades, Rank::Ace),
+            card(Suit::Spades, Rank::Nine),
+            card(Suit::Spades, Rank::Four),
+            card(Suit::Diamonds, Rank::King),
+        ];
+        let display = HandDisplay::new(&hand).style(CardStyle::Ascii);
+        assert_eq!("2C AS 9S 4S KD", display.to_string());
+        assert_eq!("2C AS\n9S 4S\nKD", display.columns(Some(2)).to_string());
+        assert_eq!(
+            "S: AS 9S 4S\nD: KD\nC: 2C",
+            display.group_by_suit(true).to_string()
+        );
+        assert_eq!(
+            "S: AS 9S\n4S\nD: KD\nC: 2C",
+            display.group_by_suit(true).columns(Some(2)).to_string()
+        );
+        assert_eq!(
+            "\u{2660}: A\u{2660}\n\u{2663}: 2\u{2663}",
+            HandDisplay::new(&hand[..2]).group_by_suit(true).to_string()
+        );
+        assert_eq!("", HandDisplay::new(&[]).to_string());
+    }
+}
@@ -0,0 +1,160 @@
+use cards::{Card, Rank, Suit};
+
+/// Names for ranks and suits, for clients rendering cards in words.  [`Language`](enum.Language.html) covers
+/// a handful of languages; implement this for any other.
+pub trait CardNames {
+    fn rank_name(&self, rank: Rank) -> &str;
+
+    fn suit_name(&self, suit: Suit) -> &str;
+
+    /// The whole card, by default as in English: "Queen of Spades".
+    fn card_name(&self, card: Card) -> String {
+        format!(
+            "{} of {}",
+            self.rank_name(card.rank),
+            self.suit_name(card.suit)
+        )
+    }
+}
+
+/// The languages with built-in [`CardNames`](trait.CardNames.html).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+/// Rank names from two up to the ace, by language.
+const RANK_NAMES: [[&str; 13]; 4] = [
+    [
+        "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Ten", "Jack", "Queen",
+        "King", "Ace",
+    ],
+    [
+        "Deux", "Trois", "Quatre", "Cinq", "Six", "Sept", "Huit", "Neuf", "Dix", "Valet", "Dame",
+        "Roi", "As",
+    ],
+    [
+        "Zwei", "Drei", "Vier", "Fünf", "Sechs", "Sieben", "Acht", "Neun", "Zehn", "Bube", "Dame",
+        "König", "Ass",
+    ],
+    [
+        "Dos", "Tres", "Cuatro", "Cinco", "Seis", "Siete", "Ocho", "Nueve", "Diez", "Jota",
+        "Reina", "Rey", "As",
+    ],
+];
+
+/// Suit names in the order clubs, diamonds, hearts, spades, by language.
+const SUIT_NAMES: [[&str; 4]; 4] = [
+    ["Clubs", "Diamonds", "Hearts", "Spades"],
+    ["trèfle", "carreau", "cœur", "pique"],
+    ["Kreuz", "Karo", "Herz", "Pik"],
+    ["tréboles", "diamantes", "corazones", "picas"],
+];
+
+impl CardNames for Language {
+    fn rank_name(&self, rank: Rank) -> &str {
+        RANK_NAMES[*self as usize][rank as usize - 2]
+    }
+
+    fn suit_name(&self, suit: Suit) -> &str {
+        SUIT_NAMES[*self as usize][suit as usize]
+    }
+
+    fn card_name(&self, card: Card) -> String {
+        let (rank, suit) = (self.rank_name(card.rank), self.suit_name(card.suit));
+        match self {
+            Language::English => format!("{} of {}", rank, suit),
+            Language::French => format!("{} de {}", rank, suit),
+            Language::German => format!("{}-{}", suit, rank),
+            Language::Spanish => format!("{} de {}", rank, suit),
+        }
+    }
+}
+
+impl Rank {
+    /// The rank's English name, such as "Queen".
+    pub fn name(self) -> &'static str {
+        RANK_NAMES[Language::English as usize][self as usize - 2]
+    }
+}
+
+impl Suit {
+    /// The suit's English name, such as "Spades".
+    pub fn name(self) -> &'static str {
+        SUIT_NAMES[Language::English as usize][self as usize]
+    }
+}
+
+impl Card {
+    /// The card's English name, such as "Queen of Spades".
+    pub fn name(&self) -> String {
+        Language::English.card_name(*self)
+    }
+
+    /// The card's name as `names` gives it, such as "Dame de pique" for
+    /// [`Language::French`](enum.Language.html#variant.French).
+    pub fn name_in<N: CardNames + ?Sized>(&self, names: &N) -> String {
+        names.card_name(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CardNames, Language};
+    use cards::{Card, Rank, Suit};
+
+    const QUEEN_OF_SPADES: Card = Card {
+        suit: Suit::Spades,
+        rank: Rank::Queen,
+    };
+
+    #[test]
+    fn test_english_names() {
+        assert_eq!("Two", Rank::Two.name());
+        assert_eq!("Ace", Rank::Ace.name());
+        assert_eq!("Clubs", Suit::Clubs.name());
+        assert_eq!("Queen of Spades", QUEEN_OF_SPADES.name());
+    }
+
+    #[test]
+    fn test_languages() {
+        assert_eq!("Dame de pique", QUEEN_OF_SPADES.name_in(&Language::French));
+        assert_eq!("Pik-Dame", QUEEN_OF_SPADES.name_in(&Language::German));
+        assert_eq!(
+            "Reina de picas",
+            QUEEN_OF_SPADES.name_in(&Language::Spanish)
+        );
+        assert_eq!("Zehn", Language::German.rank_name(Rank::Ten));
+        assert_eq!("cœur", Language::French.suit_name(Suit::Hearts));
+    }
+
+    #[test]
+    fn test_custom_names() {
+        struct Initials;
+        impl CardNames for Initials {
+            fn rank_name(&self, rank: Rank) -> &str {
+                &rank.name()[..1]
+            }
+            fn suit_name(&self, suit: Suit) -> &str {
+                &suit.name()[..1]
+            }
+        }
+        assert_eq!("Q of S", QUEEN_OF_SPADES.name_in(&Initials));
+    }
+}
@@ -2,11 +2,27 @@
 
 extern crate rand;
 
-use self::rand::{thread_rng, Rng};
+use self::rand::prng::XorShiftRng;
+use self::rand::{thread_rng, Rng, SeedableRng};
+use kitty::check_kitty_size;
+use result::SpadesError;
+use shuffler::{Shuffler, ThreadRngShuffler};
 use std::cmp::Ordering;
 use std::fmt::{self, Display};
 
-#[derive(Default, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Debug, Hash)]
+#[derive(
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Ord,
+    PartialOrd,
+    Debug,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 pub enum Suit {
     #[default]
     Clubs = 0,
@@ -15,6 +31,43 @@ pub enum Suit {
     Spades = 3,
 }
 
+impl Suit {
+    /// Every suit, in the order clubs, diamonds, hearts, spades.
+    pub const ALL: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+    /// Spades are always trumps.
+    pub fn is_trump(self) -> bool {
+        self == Suit::Spades
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Suit::Clubs | Suit::Spades => Color::Black,
+            Suit::Diamonds | Suit::Hearts => Color::Red,
+        }
+    }
+}
+
+/// The color of a [`Suit`](enum.Suit.html).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum Color {
+    #[default]
+    Black,
+    Red,
+}
+
 impl fmt::Display for Suit {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -38,7 +91,19 @@ impl From<u8> for Suit {
     }
 }
 
-#[derive(Default, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Debug, Hash)]
+#[derive(
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Ord,
+    PartialOrd,
+    Debug,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 pub enum Rank {
     #[default]
     Two = 2,
@@ -77,6 +142,25 @@ impl From<u8> for Rank {
     }
 }
 
+impl Rank {
+    /// Every rank, from two up to the ace.
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+}
+
 impl fmt::Display for Rank {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -108,6 +192,44 @@ impl Card {
     fn new(suit: Suit, rank: Rank) -> Card {
         Card { suit, rank }
     }
+
+    pub fn color(&self) -> Color {
+        self.suit.color()
+    }
+
+    /// Whether this card would take a trick led in `leading` from `other`: by being a trump when `other`
+    /// isn't, by following the lead when `other` neither follows nor trumps, or by outranking `other` in the
+    /// same suit.  This is the comparison [`get_trick_winner`](fn.get_trick_winner.html) makes.
+    pub fn beats(&self, other: &Card, leading: Suit) -> bool {
+        let strength = |card: &Card| {
+            if card.suit.is_trump() {
+                2
+            } else if card.suit == leading {
+                1
+            } else {
+                0
+            }
+        };
+        if self.suit == other.suit {
+            self.rank > other.rank
+        } else {
+            strength(self) > strength(other)
+        }
+    }
+
+    /// How strong this card is in a trick led in `leading`, or as the lead itself when `leading` is `None`:
+    /// trumps outrank the led suit, which outranks the other suits, and rank decides within each of those.
+    /// Whenever [`beats`](#method.beats) holds between two cards, the winner has the higher power.
+    pub fn power(&self, leading: Option<Suit>) -> u8 {
+        let rank = self.rank as u8;
+        if self.suit.is_trump() {
+            rank + 26
+        } else if leading.is_none_or(|suit| suit == self.suit) {
+            rank + 13
+        } else {
+            rank
+        }
+    }
 }
 
 impl fmt::Display for Card {
@@ -125,10 +247,7 @@ impl Ord for Card {
 
 impl PartialOrd for Card {
     fn partial_cmp(&self, other: &Card) -> Option<Ordering> {
-        Some(
-            ((self.suit as u64) * 15 + (self.rank as u64))
-                .cmp(&(((other.suit as u64) * 15) + (other.rank as u64))),
-        )
+        Some(self.cmp(other))
     }
 }
 
@@ -147,16 +266,31 @@ impl<'de> serde::de::Visitor<'de> for U8Visitor {
     type Value = Card;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("an integer between 2 and 62")
+        formatter.write_str("a card encoded as rank + 15 * suit")
     }
 
     fn visit_u8<E>(self, value: u8) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
+        self.visit_u64(u64::from(value))
+    }
+
+    // self-describing formats such as JSON hand integers over as u64
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let (rank, suit) = (value % 15, value / 15);
+        if rank < 2 || suit > 3 {
+            return Err(E::invalid_value(
+                serde::de::Unexpected::Unsigned(value),
+                &self,
+            ));
+        }
         Ok(Card {
-            rank: (value % 15).into(),
-            suit: (value / 15).into(),
+            rank: (rank as u8).into(),
+            suit: (suit as u8).into(),
         })
     }
 }
@@ -175,83 +309,333 @@ fn test_ser_de() {
     let mut card = Card::new(Suit::Diamonds, Rank::King);
     serde_test::assert_tokens(&card, &[serde_test::Token::U8(15 + 13)]);
     card = Card::new(Suit::Clubs, Rank::Two);
-    serde_test::assert_tokens(&card, &[serde_test::Token::U8(0 + 2)]);
+    serde_test::assert_tokens(&card, &[serde_test::Token::U8(2)]);
     card = Card::new(Suit::Spades, Rank::Ace);
     serde_test::assert_tokens(&card, &[serde_test::Token::U8(15 * 3 + 14)]);
+    serde_test::assert_de_tokens(&card, &[serde_test::Token::U64(15 * 3 + 14)]);
 }
 
-/// Given four cards and a starting card, returns the winner of a trick.
+#[test]
+fn test_de_rejects_invalid_cards() {
+    serde_test::assert_de_tokens_error::<Card>(
+        &[serde_test::Token::U8(16)],
+        "invalid value: integer `16`, expected a card encoded as rank + 15 * suit",
+    );
+    serde_test::assert_de_tokens_error::<Card>(
+        &[serde_test::Token::U64(15 * 4 + 2)],
+        "invalid value: integer `62`, expected a card encoded as rank + 15 * suit",
+    );
+}
+
+/// Given the cards played so far in a trick and the index of the player who led it, returns the index of the
+/// player winning the trick.  Once all four cards are in, that is the winner of the trick.
 ///
 /// The rules used to determine the winner of a trick are as follows:
 /// * Spades trump all other suits
 /// * The suit the first player (given by index) plays sets the suit of the trick
 /// * The highest ranking spades card or card of suit of first player's card wins the trick.
+///
 /// Note: assumes leading card is valid (e.g., if non-spade led and not broken spades, this method doesn't care)
-pub fn get_trick_winner(leading_player_index: usize, others: &Vec<Card>) -> usize {
-    assert_eq!(4, others.len());
+///
+/// Returns `None` if `others` does not hold between 1 and 4 cards.
+pub fn get_trick_winner(leading_player_index: usize, others: &[Card]) -> Option<usize> {
+    if others.is_empty() || others.len() > 4 {
+        return None;
+    }
+    Some(trick_winner(leading_player_index, 4, others))
+}
+
+/// The seat winning `trick`, led by the player in seat `leader` at a table of `seats` players, for
+/// [`Table`](struct.Table.html)s as well as four-player games.  Panics if `trick` is empty.
+pub(crate) fn trick_winner(leader: usize, seats: usize, trick: &[Card]) -> usize {
+    (leader + winning_position(trick)) % seats
+}
+
+/// Puts `hand` in order of [`Card::power`](struct.Card.html#method.power) in a trick led in `leading`, the
+/// strongest card first.  Cards of equal power keep their order.
+pub fn sort_by_strength(hand: &mut [Card], leading: Option<Suit>) {
+    hand.sort_by_key(|card| std::cmp::Reverse(card.power(leading)));
+}
+
+/// The position in `trick` of the card winning it, by the rules of
+/// [`get_trick_winner`](fn.get_trick_winner.html), for tricks of any size.  Panics if `trick` is empty.
+pub(crate) fn winning_position(trick: &[Card]) -> usize {
+    let leading = trick[0].suit;
     let mut winning_index = 0;
-    let mut best_card = others[0];
-    for (i, other) in others.iter().enumerate() {
-        if other.suit == best_card.suit {
-            if other.rank as u8 > best_card.rank as u8 {
-                best_card = *other;
-                winning_index = i;
-            }
-        } else if other.suit == Suit::Spades {
-            best_card = *other;
+    for (i, other) in trick.iter().enumerate() {
+        if other.beats(&trick[winning_index], leading) {
             winning_index = i;
         }
     }
-    (winning_index + leading_player_index) % 4
+    winning_index
 }
 
 /// Returns a shuffled deck of [`deck::Card`](struct.Card.html)'s, with 52 elements.
 pub fn new_deck() -> Vec<Card> {
-    let ranks: Vec<Rank> = vec![
-        Rank::Two,
-        Rank::Three,
-        Rank::Four,
-        Rank::Five,
-        Rank::Six,
-        Rank::Seven,
-        Rank::Eight,
-        Rank::Nine,
-        Rank::Ten,
-        Rank::Jack,
-        Rank::Queen,
-        Rank::King,
-        Rank::Ace,
-    ];
-    let suits: Vec<Suit> = vec![Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+    let mut cards = full_deck().to_vec();
+    shuffle(&mut cards);
+    cards
+}
 
-    let mut cards = Vec::new();
-    for suit in suits {
-        for rank in &ranks {
-            cards.push(Card { suit, rank: *rank });
-        }
+/// The 52 cards in canonical order: suit by suit as in [`Suit::ALL`](enum.Suit.html#associatedconstant.ALL),
+/// and by rank within each suit, which is also their sorted order.
+pub const fn full_deck() -> [Card; 52] {
+    let mut cards = [Card {
+        suit: Suit::Clubs,
+        rank: Rank::Two,
+    }; 52];
+    let mut i = 0;
+    while i < 52 {
+        cards[i] = Card {
+            suit: Suit::ALL[i / 13],
+            rank: Rank::ALL[i % 13],
+        };
+        i += 1;
     }
-    shuffle(&mut cards);
     cards
 }
 
+/// A pile of cards, face down.  As when dealing, the top of the deck is the end of its cards, so iterating
+/// over a [new](#method.new) deck goes through the cards in canonical order while [drawing](#method.draw)
+/// takes the ace of spades first.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct Deck {
+    cards: Vec<Card>,
+}
+
+impl Deck {
+    /// A full deck, in the order of [`full_deck`](fn.full_deck.html).
+    pub fn new() -> Deck {
+        Deck {
+            cards: full_deck().to_vec(),
+        }
+    }
+
+    /// A full deck, shuffled.
+    pub fn shuffled() -> Deck {
+        let mut deck = Deck::new();
+        deck.shuffle_with(&ThreadRngShuffler);
+        deck
+    }
+
+    /// Shuffles the deck with `shuffler`.
+    pub fn shuffle_with<S: Shuffler + ?Sized>(&mut self, shuffler: &S) {
+        shuffler.shuffle(&mut self.cards);
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Takes the top card, if there is one left.
+    pub fn draw(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    /// The top card, without taking it.
+    pub fn peek(&self) -> Option<&Card> {
+        self.cards.last()
+    }
+
+    /// Lifts off the top `count` cards as a deck of their own, leaving the rest.  Returns `None`, leaving the
+    /// deck as it was, if there are fewer than `count` cards.
+    pub fn split(&mut self, count: usize) -> Option<Deck> {
+        let at = self.cards.len().checked_sub(count)?;
+        Some(Deck {
+            cards: self.cards.split_off(at),
+        })
+    }
+
+    /// The cards, from the bottom of the deck to the top.
+    pub fn iter(&self) -> std::slice::Iter<'_, Card> {
+        self.cards.iter()
+    }
+
+    /// The cards, from the bottom of the deck to the top, in one slice.
+    pub fn as_slice(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn into_vec(self) -> Vec<Card> {
+        self.cards
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Deck {
+        Deck::new()
+    }
+}
+
+impl From<Vec<Card>> for Deck {
+    fn from(cards: Vec<Card>) -> Deck {
+        Deck { cards }
+    }
+}
+
+impl IntoIterator for Deck {
+    type Item = Card;
+    type IntoIter = std::vec::IntoIter<Card>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Deck {
+    type Item = &'a Card;
+    type IntoIter = std::slice::Iter<'a, Card>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.iter()
+    }
+}
+
 /// Shuffles a `Vector` of cards in place, see [`rand::thread_rng::shuffle`](https://docs.rs/rand/0.5.4/rand/trait.Rng.html#method.shuffle).
 pub fn shuffle(cards: &mut [Card]) {
-    let mut rng = thread_rng();
-    rng.shuffle(cards);
+    ThreadRngShuffler.shuffle(cards);
+}
+
+/// The random numbers for a deal that has to come out the same every time, such as with
+/// [`Game::set_shuffle_seed`](struct.Game.html#method.set_shuffle_seed).
+pub(crate) fn seeded_rng(seed: u64) -> XorShiftRng {
+    XorShiftRng::seed_from_u64(seed)
+}
+
+/// How the cards are passed out, see [`DealConfig`](struct.DealConfig.html).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum DealPattern {
+    /// One card to each player in turn.
+    #[default]
+    OneAtATime,
+    /// This many cards to each player in turn (treated as 1 if 0), with a smaller last block if 13 is not a
+    /// multiple of it.
+    Blocks(usize),
 }
 
-/// Used to reshuffle a deck of cards, panics if the `cards` does not have 52 elements (should only be used on a "full" deck).
-pub fn deal_four_players(cards: &mut Vec<Card>) -> Vec<Vec<Card>> {
-    assert_eq!(cards.len(), 52);
-    shuffle(cards);
-    let mut hands = [vec![], vec![], vec![], vec![]];
+/// How a deal is done, set with [`Game::set_deal_config`](struct.Game.html#method.set_deal_config).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct DealConfig {
+    pub pattern: DealPattern,
+    /// After shuffling, cut the deck this many cards from the top (taken modulo 52).
+    pub cut: Option<usize>,
+    /// House rule: if any hand holds no spades, gather the cards and deal again.
+    pub redeal_if_void_of_spades: bool,
+}
+
+/// Used to reshuffle a deck of cards.  Returns `SpadesError::InvalidDeal` unless `cards` has 52 elements (should
+/// only be used on a "full" deck).
+pub fn deal_four_players(cards: &mut Vec<Card>) -> Result<Vec<Vec<Card>>, SpadesError> {
+    deal_four_players_with(cards, &DealConfig::default())
+}
+
+/// Like [`deal_four_players`](fn.deal_four_players.html), but dealing as `config` says.  Redeals, if the
+/// configuration asks for them, happen until every hand has a spade.
+pub fn deal_four_players_with(
+    cards: &mut Vec<Card>,
+    config: &DealConfig,
+) -> Result<Vec<Vec<Card>>, SpadesError> {
+    Ok(deal_with_kitty(cards, config, 0)?.0)
+}
+
+/// Like [`deal_four_players_with`](fn.deal_four_players_with.html), but first setting `kitty_size` cards
+/// from the top of the deck aside, which are returned with the hands.  Returns
+/// `SpadesError::InvalidKittySize` if the rest of the deck can't be dealt out evenly.
+pub fn deal_with_kitty(
+    cards: &mut Vec<Card>,
+    config: &DealConfig,
+    kitty_size: usize,
+) -> Result<(Vec<Vec<Card>>, Vec<Card>), SpadesError> {
+    deal_with_kitty_using(cards, config, kitty_size, &ThreadRngShuffler)
+}
+
+/// Like [`deal_with_kitty`](fn.deal_with_kitty.html), shuffling with `shuffler`.
+pub(crate) fn deal_with_kitty_using<S: Shuffler + ?Sized>(
+    cards: &mut Vec<Card>,
+    config: &DealConfig,
+    kitty_size: usize,
+    shuffler: &S,
+) -> Result<(Vec<Vec<Card>>, Vec<Card>), SpadesError> {
+    if cards.len() != 52 {
+        return Err(SpadesError::InvalidDeal);
+    }
+    check_kitty_size(kitty_size)?;
+    loop {
+        shuffler.shuffle(cards);
+        if let Some(cut) = config.cut {
+            // the top of the deck is the end of the vector, where cards are dealt from
+            let len = cards.len();
+            cards.rotate_right(cut % len);
+        }
+        let mut kitty = cards.split_off(cards.len() - kitty_size);
+        let hands = deal_pattern(cards, config.pattern);
+        if config.redeal_if_void_of_spades
+            && hands
+                .iter()
+                .any(|hand| !hand.iter().any(|card| card.suit == Suit::Spades))
+        {
+            for mut hand in hands {
+                cards.append(&mut hand);
+            }
+            cards.append(&mut kitty);
+            continue;
+        }
+        return Ok((hands, kitty));
+    }
+}
+
+fn deal_pattern(cards: &mut Vec<Card>, pattern: DealPattern) -> Vec<Vec<Card>> {
+    let block = match pattern {
+        DealPattern::OneAtATime => 1,
+        DealPattern::Blocks(size) => size.max(1),
+    };
+    let hand_size = cards.len() / 4;
+    let mut hands = [
+        Vec::with_capacity(hand_size),
+        Vec::with_capacity(hand_size),
+        Vec::with_capacity(hand_size),
+        Vec::with_capacity(hand_size),
+    ];
 
     let mut i = 0;
-    while let Some(card) = cards.pop() {
-        hands[i].push(card);
+    while !cards.is_empty() {
+        let count = block.min(hand_size - hands[i].len());
+        for _ in 0..count {
+            if let Some(card) = cards.pop() {
+                hands[i].push(card);
+            }
+        }
         i = (i + 1) % 4;
     }
-    hands.to_vec()
+    Vec::from(hands)
 }
 
 #[cfg(test)]
@@ -317,7 +701,11 @@ mod rank_tests {
 #[cfg(test)]
 mod tests {
 
-    use cards::{deal_four_players, get_trick_winner, new_deck, shuffle, Card, Rank, Suit};
+    use cards::{
+        deal_four_players, deal_four_players_with, deal_pattern, deal_with_kitty, full_deck,
+        get_trick_winner, new_deck, shuffle, sort_by_strength, Card, Color, DealConfig,
+        DealPattern, Deck, Rank, Suit,
+    };
     use std::fmt;
 
     #[test]
@@ -333,6 +721,7 @@ mod tests {
         let c6d = Card::new(Suit::Diamonds, Rank::Six); // NOTE! not in cards vec
         let mut cards = [ah, ks, qc, jd, c2d, c3d, c4d, c5d];
         let the_copy = cards;
+        #[allow(clippy::clone_on_copy)]
         let the_clone = cards.clone();
         assert_eq!(cards, the_copy);
         assert_eq!(cards, the_clone);
@@ -389,10 +778,10 @@ mod tests {
         let mut deck = Vec::new();
         for r in ranks {
             for s in suits {
-                deck.push(Card::new(s.into(), r.into()));
+                deck.push(Card::new(s, r));
             }
         }
-        let hands = deal_four_players(&mut deck);
+        let hands = deal_four_players(&mut deck).unwrap();
         assert_eq!(13, hands[0].len());
         assert_eq!(13, hands[1].len());
         assert_eq!(13, hands[2].len());
@@ -436,33 +825,205 @@ mod tests {
         let c3s = Card::new(Suit::Spades, Rank::Three);
 
         let hand1 = vec![c2d, c3d, jd, qc];
-        assert_eq!(2, get_trick_winner(0, &hand1));
-        assert_eq!(3, get_trick_winner(1, &hand1));
-        assert_eq!(0, get_trick_winner(2, &hand1));
-        assert_eq!(1, get_trick_winner(3, &hand1));
+        assert_eq!(Some(2), get_trick_winner(0, &hand1));
+        assert_eq!(Some(3), get_trick_winner(1, &hand1));
+        assert_eq!(Some(0), get_trick_winner(2, &hand1));
+        assert_eq!(Some(1), get_trick_winner(3, &hand1));
 
         let hand2 = vec![ah, ks, qc, jd];
-        assert_eq!(1, get_trick_winner(0, &hand2));
-        assert_eq!(2, get_trick_winner(1, &hand2));
-        assert_eq!(3, get_trick_winner(2, &hand2));
-        assert_eq!(0, get_trick_winner(3, &hand2));
+        assert_eq!(Some(1), get_trick_winner(0, &hand2));
+        assert_eq!(Some(2), get_trick_winner(1, &hand2));
+        assert_eq!(Some(3), get_trick_winner(2, &hand2));
+        assert_eq!(Some(0), get_trick_winner(3, &hand2));
 
         let hand3 = vec![c3d, qc, jd, ah];
-        assert_eq!(2, get_trick_winner(0, &hand3));
-        assert_eq!(3, get_trick_winner(1, &hand3));
-        assert_eq!(0, get_trick_winner(2, &hand3));
-        assert_eq!(1, get_trick_winner(3, &hand3));
+        assert_eq!(Some(2), get_trick_winner(0, &hand3));
+        assert_eq!(Some(3), get_trick_winner(1, &hand3));
+        assert_eq!(Some(0), get_trick_winner(2, &hand3));
+        assert_eq!(Some(1), get_trick_winner(3, &hand3));
 
         let hand4 = vec![ah, c3s, qc, jd];
-        assert_eq!(1, get_trick_winner(0, &hand4));
-        assert_eq!(2, get_trick_winner(1, &hand4));
-        assert_eq!(3, get_trick_winner(2, &hand4));
-        assert_eq!(0, get_trick_winner(3, &hand4));
+        assert_eq!(Some(1), get_trick_winner(0, &hand4));
+        assert_eq!(Some(2), get_trick_winner(1, &hand4));
+        assert_eq!(Some(3), get_trick_winner(2, &hand4));
+        assert_eq!(Some(0), get_trick_winner(3, &hand4));
 
         let hand5 = vec![ks, c3s, qc, jd];
-        assert_eq!(0, get_trick_winner(0, &hand5));
-        assert_eq!(1, get_trick_winner(1, &hand5));
-        assert_eq!(2, get_trick_winner(2, &hand5));
-        assert_eq!(3, get_trick_winner(3, &hand5));
+        assert_eq!(Some(0), get_trick_winner(0, &hand5));
+        assert_eq!(Some(1), get_trick_winner(1, &hand5));
+        assert_eq!(Some(2), get_trick_winner(2, &hand5));
+        assert_eq!(Some(3), get_trick_winner(3, &hand5));
+    }
+
+    #[test]
+    fn test_deal_in_blocks() {
+        let mut cards = new_deck();
+        let top: Vec<Card> = cards.iter().rev().cloned().collect();
+        let hands = deal_pattern(&mut cards, DealPattern::Blocks(4));
+        assert!(cards.is_empty());
+        assert_eq!(&top[0..4], &hands[0][0..4]);
+        assert_eq!(&top[4..8], &hands[1][0..4]);
+        assert_eq!(&top[16..20], &hands[0][4..8]);
+        // the last round of the deal is a single card each
+        assert_eq!(top[48], hands[0][12]);
+        assert_eq!(top[51], hands[3][12]);
+        for hand in hands.iter() {
+            assert_eq!(13, hand.len());
+        }
+    }
+
+    #[test]
+    fn test_deal_one_at_a_time() {
+        let mut cards = new_deck();
+        let top: Vec<Card> = cards.iter().rev().cloned().collect();
+        let hands = deal_pattern(&mut cards, DealPattern::Blocks(0));
+        assert_eq!(vec![top[0], top[4]], hands[0][0..2].to_vec());
+        assert_eq!(vec![top[3], top[7]], hands[3][0..2].to_vec());
+    }
+
+    #[test]
+    fn test_redeal_until_every_hand_has_spades() {
+        let config = DealConfig {
+            pattern: DealPattern::Blocks(3),
+            cut: Some(17),
+            redeal_if_void_of_spades: true,
+        };
+        for _ in 0..50 {
+            let mut cards = new_deck();
+            let hands = deal_four_players_with(&mut cards, &config).unwrap();
+            for hand in hands.iter() {
+                assert_eq!(13, hand.len());
+                assert!(hand.iter().any(|card| card.suit == Suit::Spades));
+            }
+        }
+    }
+
+    #[test]
+    fn test_deal_with_kitty() {
+        let mut cards = new_deck();
+        let (hands, kitty) = deal_with_kitty(&mut cards, &DealConfig::default(), 8).unwrap();
+        assert_eq!(8, kitty.len());
+        assert!(cards.is_empty());
+        let mut all: Vec<Card> = kitty.clone();
+        for hand in hands.iter() {
+            assert_eq!(11, hand.len());
+            all.extend(hand.iter().cloned());
+        }
+        all.sort();
+        let mut deck = new_deck();
+        deck.sort();
+        assert_eq!(deck, all);
+    }
+
+    #[test]
+    fn test_full_deck_is_sorted() {
+        let deck = full_deck();
+        assert_eq!(Card::new(Suit::Clubs, Rank::Two), deck[0]);
+        assert_eq!(Card::new(Suit::Diamonds, Rank::Two), deck[13]);
+        assert_eq!(Card::new(Suit::Spades, Rank::Ace), deck[51]);
+        assert!(deck.windows(2).all(|pair| pair[0] < pair[1]));
+        let mut cards = new_deck();
+        cards.sort();
+        assert_eq!(deck.to_vec(), cards);
+    }
+
+    #[test]
+    fn test_deck_draw_peek_split() {
+        let mut deck = Deck::new();
+        assert_eq!(
+            full_deck().to_vec(),
+            deck.iter().cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&Card::new(Suit::Spades, Rank::Ace)), deck.peek());
+        assert_eq!(Some(Card::new(Suit::Spades, Rank::Ace)), deck.draw());
+        assert_eq!(51, deck.len());
+
+        let top = deck.split(12).unwrap();
+        assert_eq!(39, deck.len());
+        assert!(top.iter().all(|card| card.suit == Suit::Spades));
+        assert_eq!(Some(&Card::new(Suit::Hearts, Rank::Ace)), deck.peek());
+        assert_eq!(12, top.into_iter().count());
+
+        let mut rest = deck.split(39).unwrap();
+        assert!(deck.is_empty());
+        assert_eq!(None, deck.draw());
+        assert_eq!(Some(Card::new(Suit::Hearts, Rank::Ace)), rest.draw());
+    }
+
+    #[test]
+    fn test_deck_split_too_deep() {
+        let mut deck = Deck::from(vec![Card::new(Suit::Hearts, Rank::Ace)]);
+        assert_eq!(None, deck.split(2));
+        assert_eq!(1, deck.len());
+        assert_eq!(Some(deck.clone()), deck.split(1));
+        assert!(deck.is_empty());
+    }
+
+    #[test]
+    fn test_card_beats() {
+        let ah = Card::new(Suit::Hearts, Rank::Ace);
+        let th = Card::new(Suit::Hearts, Rank::Ten);
+        let c2s = Card::new(Suit::Spades, Rank::Two);
+        let kd = Card::new(Suit::Diamonds, Rank::King);
+        assert!(ah.beats(&th, Suit::Hearts));
+        assert!(!th.beats(&ah, Suit::Hearts));
+        assert!(c2s.beats(&ah, Suit::Hearts));
+        assert!(!ah.beats(&c2s, Suit::Hearts));
+        assert!(th.beats(&kd, Suit::Hearts));
+        assert!(!kd.beats(&th, Suit::Hearts));
+        // neither follows the lead, so neither can take the trick from the other
+        assert!(!kd.beats(&th, Suit::Clubs));
+        assert!(!th.beats(&kd, Suit::Clubs));
+        assert!(!ah.beats(&ah, Suit::Hearts));
+
+        assert!(Suit::Spades.is_trump());
+        assert!(!Suit::Hearts.is_trump());
+        assert_eq!(Color::Red, kd.color());
+        assert_eq!(Color::Black, c2s.color());
+    }
+
+    #[test]
+    fn test_card_power_agrees_with_beats() {
+        for leading in Suit::ALL.iter() {
+            for a in full_deck().iter() {
+                for b in full_deck().iter() {
+                    if a.beats(b, *leading) {
+                        assert!(a.power(Some(*leading)) > b.power(Some(*leading)));
+                    }
+                }
+            }
+        }
+        let ah = Card::new(Suit::Hearts, Rank::Ace);
+        let c2s = Card::new(Suit::Spades, Rank::Two);
+        let kd = Card::new(Suit::Diamonds, Rank::King);
+        assert!(c2s.power(None) > ah.power(None));
+        assert!(ah.power(None) > kd.power(None));
+        assert!(kd.power(Some(Suit::Diamonds)) > ah.power(Some(Suit::Diamonds)));
+    }
+
+    #[test]
+    fn test_sort_by_strength() {
+        let mut hand = vec![
+            Card::new(Suit::Clubs, Rank::Ace),
+            Card::new(Suit::Hearts, Rank::Four),
+            Card::new(Suit::Spades, Rank::Two),
+            Card::new(Suit::Hearts, Rank::Queen),
+            Card::new(Suit::Diamonds, Rank::Three),
+        ];
+        sort_by_strength(&mut hand, Some(Suit::Hearts));
+        assert_eq!(
+            hand,
+            vec![
+                Card::new(Suit::Spades, Rank::Two),
+                Card::new(Suit::Hearts, Rank::Queen),
+                Card::new(Suit::Hearts, Rank::Four),
+                Card::new(Suit::Clubs, Rank::Ace),
+                Card::new(Suit::Diamonds, Rank::Three),
+            ]
+        );
+        sort_by_strength(&mut hand, None);
+        assert_eq!(hand[0], Card::new(Suit::Spades, Rank::Two));
+        assert_eq!(hand[1], Card::new(Suit::Clubs, Rank::Ace));
+        assert_eq!(hand[4], Card::new(Suit::Diamonds, Rank::Three));
     }
 }
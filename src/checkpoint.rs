@@ -0,0 +1,252 @@
+use claim::Claim;
+use diagnostics::{GameEvent, SequencedEvent};
+use game_state::State;
+use result::SpadesError;
+use std::sync::Arc;
+use Game;
+
+/// The whole game as it stood at one point of its history, as returned by
+/// [`Game::checkpoint`](struct.Game.html#method.checkpoint).  Saved along with the events after it, it
+/// brings the game back with [`Game::replay`](struct.Game.html#method.replay).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct Checkpoint {
+    /// The sequence number of the latest event before the checkpoint.
+    pub seq: u64,
+    pub game: Game,
+}
+
+impl Game {
+    /// Takes a checkpoint of the game and drops its history from before the round being played, so the
+    /// events kept (and saved) stay bounded over a long session.  Everything built on the history, such as
+    /// [`round_history`](#method.round_history) and [`revokes`](#method.revokes), only goes back to the
+    /// start of that round afterwards; [`events_since`](#method.events_since) returns only the events kept.
+    pub fn checkpoint(&mut self) -> Checkpoint {
+        self.drop_earlier_rounds();
+        Checkpoint {
+            seq: self.last_seq(),
+            game: self.clone(),
+        }
+    }
+
+    /// Drops the events from before the round being played, or all of them once the game is over, counting
+    /// the actions and rounds dropped.  The events kept are copied rather than shared with other clones.
+    pub(crate) fn drop_earlier_rounds(&mut self) {
+        let log = &mut self.event_log;
+        let keep_from = match self.state {
+            State::GameCompleted | State::Abandoned => log.events.len(),
+            _ => log
+                .events
+                .iter()
+                .rposition(|event| *event == GameEvent::Dealt)
+                .unwrap_or(0),
+        };
+        for event in log.events[..keep_from].iter() {
+            match event {
+                GameEvent::Bet { .. }
+                | GameEvent::Discarded { .. }
+                | GameEvent::CardPlayed { .. } => log.compacted_actions += 1,
+                GameEvent::RoundScored { .. } => log.compacted_rounds += 1,
+                _ => {}
+            }
+        }
+        log.events = Arc::new(log.events[keep_from..].to_vec());
+        log.first_seq += keep_from as u64;
+        #[cfg(feature = "annotations")]
+        {
+            let first_seq = log.first_seq;
+            log.annotations
+                .retain(|annotation| annotation.seq + 1 >= first_seq);
+        }
+    }
+
+    /// Brings a game back from a [`Checkpoint`](struct.Checkpoint.html) by acting out the `events` after
+    /// it, as returned by [`events_since`](#method.events_since) its `seq`.  Events the checkpoint already
+    /// has are skipped.
+    ///
+    /// Only what is recorded as events is acted out: turn clocks, pauses and abandoning are not.  Deals
+    /// aren't recorded either, so a game dealt at random can only be replayed up to its next deal.  Games
+    /// dealt from a [shuffle seed](#method.set_shuffle_seed) record it with each deal and replay in full,
+    /// even if the seed was changed after the checkpoint, as do games dealt from
+    /// [queued deals](#method.queue_deal).  Returns `SpadesError::ReplayMismatch` with the sequence number of the first event that didn't
+    /// come out the same, or that is missing.
+    pub fn replay(checkpoint: &Checkpoint, events: &[SequencedEvent]) -> Result<Game, SpadesError> {
+        let mut game = checkpoint.game.clone();
+        for (index, sequenced) in events.iter().enumerate() {
+            let seq = sequenced.seq;
+            if seq > game.last_seq() + 1 {
+                return Err(SpadesError::ReplayMismatch(game.last_seq() + 1));
+            }
+            if seq == game.last_seq() + 1 {
+                // deal whatever this action leads to from the seed it was dealt from
+                let seed = events[index + 1..]
+                    .iter()
+                    .take_while(|later| later.event.acting_player().is_none())
+                    .find_map(|later| match later.event {
+                        GameEvent::ShuffleSeed(seed) => Some(seed),
+                        _ => None,
+                    });
+                if seed.is_some() {
+                    game.shuffle_seed = seed;
+                }
+                game.act_out(&sequenced.event)
+                    .map_err(|_| SpadesError::ReplayMismatch(seq))?;
+            }
+            if game
+                .event_at(seq)
+                .is_some_and(|event| *event != sequenced.event)
+            {
+                return Err(SpadesError::ReplayMismatch(seq));
+            }
+        }
+        Ok(game)
+    }
+
+    /// The event numbered `seq`, if it is kept.
+    fn event_at(&self, seq: u64) -> Option<&GameEvent> {
+        let index = seq.checked_sub(self.event_log.first_seq)?;
+        self.event_log.events.get(index as usize)
+    }
+
+    /// Takes the action recorded by `event` again.
+    fn act_out(&mut self, event: &GameEvent) -> Result<(), SpadesError> {
+        let expect_player = |game: &Game, player| match game.current_player_id() {
+            Ok(current) if current == player => Ok(()),
+            _ => Err(SpadesError::ImproperGameStage),
+        };
+        // a review that kept the bets left no event
+        if self.state == State::BidReview && !matches!(event, GameEvent::BetAmended { .. }) {
+            self.review_bet(None)?;
+        }
+        match event {
+            GameEvent::Bet { player, bet } => {
+                expect_player(self, *player)?;
+                self.place_bet(*bet).ok_or(SpadesError::ImproperGameStage)?;
+            }
+            GameEvent::Discarded { player, cards } => {
+                expect_player(self, *player)?;
+                self.discard(cards)?;
+            }
+            GameEvent::CardPlayed { player, card } => {
+                expect_player(self, *player)?;
+                self.play_card(*card)
+                    .ok_or(SpadesError::ImproperGameStage)?;
+            }
+            GameEvent::BetAmended { player, to, .. } => {
+                expect_player(self, *player)?;
+                self.review_bet(Some(*to))?;
+            }
+            GameEvent::ClaimAccepted(claim) => {
+                match claim {
+                    Claim::Tricks { player } => self.claim_remaining(player)?,
+                    Claim::Concession { team } => self.concede(*team)?,
+                }
+                for player in self.awaiting_claim_response() {
+                    self.respond_to_claim(&player, true)?;
+                }
+            }
+            GameEvent::Misdeal { player } => self.declare_misdeal(player)?,
+            GameEvent::PlayerInfoChanged { player, info } => {
+                self.set_player_info(player, info.clone())?
+            }
+            GameEvent::OptionsAmended { to, .. } => self.amend_options(*to)?,
+            GameEvent::ScoreAdjusted(adjustment) => {
+                self.apply_adjustment(adjustment.team, adjustment.points, &adjustment.reason)?
+            }
+            GameEvent::BagsCarried(bags) => self.carry_over_bags(*bags)?,
+            GameEvent::RevokePenalized { revoke, points } => {
+                self.apply_revoke_penalty(revoke, *points)?
+            }
+            GameEvent::Dealt
+            | GameEvent::RoundScored { .. }
+            | GameEvent::DealCommitted(_)
+            | GameEvent::DealRevealed(_)
+            | GameEvent::ShuffleSeed(_) => return Err(SpadesError::ImproperGameStage),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Checkpoint;
+    use test_support::seeded_game;
+    use {Bet, Game, GameEvent, SpadesError};
+
+    fn play_round(g: &mut Game) {
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        for _ in 0..52 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_drops_finished_rounds() {
+        let mut g = seeded_game(4);
+        play_round(&mut g);
+        play_round(&mut g);
+        g.place_bet(Bet::Nil);
+        let last_seq = g.last_seq();
+        let rounds = g.round_history();
+        let result_actions = g.game_result().actions;
+
+        let checkpoint = g.checkpoint();
+        assert_eq!(last_seq, checkpoint.seq);
+        assert_eq!(last_seq, g.last_seq());
+        // the round being bet on is kept: its deal, the seed it was dealt from, and the bet
+        assert_eq!(3, g.history().len());
+        assert_eq!(vec![rounds[2].clone()], g.round_history());
+        assert_eq!(result_actions, g.game_result().actions);
+        assert_eq!(g, checkpoint.game);
+        assert_eq!(2, g.events_since(0).len() - 1);
+    }
+
+    #[test]
+    fn test_replay_from_checkpoint() {
+        let mut g = seeded_game(4);
+        play_round(&mut g);
+        let checkpoint = g.checkpoint();
+        let saved: Checkpoint =
+            serde_json::from_str(&serde_json::to_string(&checkpoint).unwrap()).unwrap();
+        play_round(&mut g);
+        g.place_bet(Bet::Amount(4));
+        let events = g.events_since(checkpoint.seq);
+
+        let replayed = Game::replay(&saved, &events).unwrap();
+        assert_eq!(g, replayed);
+        assert_eq!(g.last_seq(), replayed.last_seq());
+        assert_eq!(g.history(), replayed.history());
+
+        let mut gap = events.clone();
+        gap.remove(3);
+        assert_eq!(
+            Err(SpadesError::ReplayMismatch(events[3].seq)),
+            Game::replay(&saved, &gap)
+        );
+    }
+
+    #[test]
+    fn test_replay_deals_from_recorded_seeds() {
+        let mut g = seeded_game(4);
+        let checkpoint = g.checkpoint();
+        play_round(&mut g);
+        g.set_shuffle_seed(Some(40));
+        play_round(&mut g);
+        let seeds: Vec<u64> = g
+            .history()
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::ShuffleSeed(seed) => Some(*seed),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(vec![4, 4, 40], seeds);
+
+        // the checkpoint only knows the first seed; the log tells it about the second
+        let replayed = Game::replay(&checkpoint, &g.events_since(checkpoint.seq)).unwrap();
+        assert_eq!(g.history(), replayed.history());
+        assert_eq!(g.dealt_hands(), replayed.dealt_hands());
+    }
+}
@@ -0,0 +1,402 @@
+use cards::{Card, Suit};
+use diagnostics::GameEvent;
+use game_state::State;
+use result::SpadesError;
+use seat::Seat;
+use {check_team, Game, PlayCardResult, PlayerId, Uid};
+
+/// A proposal to end the current round early by giving every remaining trick to one side, see
+/// [`Game::claim_remaining`](struct.Game.html#method.claim_remaining) and
+/// [`Game::concede`](struct.Game.html#method.concede).
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum Claim {
+    /// The player claims every remaining trick for themselves.
+    Tricks { player: Uid },
+    /// Team 0 (players 0 and 2) or Team 1 (players 1 and 3) gives every remaining trick to the other team.
+    Concession { team: usize },
+}
+
+/// What happened when a player answered a pending claim, see
+/// [`Game::respond_to_claim`](struct.Game.html#method.respond_to_claim).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum ClaimResponse {
+    /// The answer was recorded; other players still have to answer.
+    Awaiting,
+    /// The claim was rejected and play carries on as before.
+    Rejected,
+    /// Everyone accepted, so the remaining tricks were awarded and the round was scored.
+    Accepted(PlayCardResult),
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub(crate) struct PendingClaim {
+    claim: Claim,
+    /// The index of the player who will be credited with the remaining tricks.
+    winner: usize,
+    /// Which players still have to accept.
+    awaiting: [bool; 4],
+}
+
+impl Game {
+    /// The current player claims every remaining trick of the round.
+    ///
+    /// A claim can only be made by the player on lead, before any card of the trick has been played, and
+    /// only if they are certain to win every trick however the others play: each of their spades must beat
+    /// every spade still held by the others, they must hold at least as many spades as any other player, and
+    /// each of their other cards must be the highest left in its suit.  Both opponents then have to
+    /// [accept](#method.respond_to_claim) the claim.
+    pub fn claim_remaining<P: PlayerId + ?Sized>(
+        &mut self,
+        player_id: &P,
+    ) -> Result<(), SpadesError> {
+        let player = player_id.to_uid();
+        self.can_claim()?;
+        if self.player[self.current_player_index].id != player {
+            return Err(self
+                .player_index(player)
+                .err()
+                .unwrap_or(SpadesError::ImproperGameStage));
+        }
+        if !self.claim_is_provable(self.current_player_index) {
+            return Err(SpadesError::ClaimNotProvable);
+        }
+        let claimant = Seat::from_index(self.current_player_index);
+        let mut awaiting = [false; 4];
+        awaiting[claimant.left().index()] = true;
+        awaiting[claimant.right().index()] = true;
+        self.pending_claim = Some(PendingClaim {
+            claim: Claim::Tricks { player },
+            winner: claimant.index(),
+            awaiting,
+        });
+        Ok(())
+    }
+
+    /// `team` offers every remaining trick of the round to the other team, who both have to
+    /// [accept](#method.respond_to_claim).  Like a claim, this can only be done between tricks.
+    ///
+    /// The tricks are credited to a player of the receiving team who did not bet nil, if there is one.
+    pub fn concede(&mut self, team: usize) -> Result<(), SpadesError> {
+        check_team(team)?;
+        self.can_claim()?;
+        let receiving = 1 - team;
+        let bets = self.scoring.bets();
+        let winner = if !bets[receiving].is_nil() {
+            receiving
+        } else if !bets[receiving + 2].is_nil() {
+            receiving + 2
+        } else {
+            receiving
+        };
+        let mut awaiting = [false; 4];
+        awaiting[receiving] = true;
+        awaiting[receiving + 2] = true;
+        self.pending_claim = Some(PendingClaim {
+            claim: Claim::Concession { team },
+            winner,
+            awaiting,
+        });
+        Ok(())
+    }
+
+    /// The claim or concession waiting to be answered, if any.  No cards can be played until it is.
+    pub fn pending_claim(&self) -> Option<Claim> {
+        self.pending_claim.map(|pending| pending.claim)
+    }
+
+    /// The players who still have to answer the pending claim.
+    pub fn awaiting_claim_response(&self) -> Vec<Uid> {
+        match self.pending_claim {
+            Some(pending) => (0..4)
+                .filter(|index| pending.awaiting[*index])
+                .map(|index| self.player[index].id)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Accept or reject the pending claim on behalf of `player_id`.
+    ///
+    /// A single rejection withdraws the claim.  Once every player it was put to has accepted, the remaining
+    /// tricks are awarded and the round is scored, exactly as if they had been played out.
+    pub fn respond_to_claim<P: PlayerId + ?Sized>(
+        &mut self,
+        player_id: &P,
+        accept: bool,
+    ) -> Result<ClaimResponse, SpadesError> {
+        let index = self.player_index(player_id.to_uid())?;
+        let mut pending = self.pending_claim.ok_or(SpadesError::NoPendingClaim)?;
+        if let State::Paused = self.state {
+            return Err(SpadesError::GamePaused);
+        }
+        if !pending.awaiting[index] {
+            return Err(SpadesError::ImproperGameStage);
+        }
+        if !accept {
+            self.pending_claim = None;
+            return Ok(ClaimResponse::Rejected);
+        }
+        pending.awaiting[index] = false;
+        if pending.awaiting.iter().any(|awaiting| *awaiting) {
+            self.pending_claim = Some(pending);
+            return Ok(ClaimResponse::Awaiting);
+        }
+
+        self.pending_claim = None;
+        self.turn_started = None;
+        self.record_event(GameEvent::ClaimAccepted(pending.claim));
+        let outcome = self.scoring.award_remaining_tricks(pending.winner);
+        for player in self.player.iter_mut() {
+            self.deck.extend_from_slice(&player.hand);
+            player.hand.clear();
+        }
+        let result = self.finish_trick(pending.winner, Some(outcome));
+        self.check_invariants();
+        Ok(ClaimResponse::Accepted(result))
+    }
+
+    fn can_claim(&self) -> Result<(), SpadesError> {
+        match self.state {
+            State::GameNotStarted => Err(SpadesError::GameNotStarted),
+            State::GameCompleted => Err(SpadesError::GameCompleted),
+            State::Paused => Err(SpadesError::GamePaused),
+            State::Abandoned => Err(SpadesError::GameAbandoned),
+            State::Betting(_) | State::Exchange | State::BidReview | State::Trick(1..=3) => {
+                Err(SpadesError::ImproperGameStage)
+            }
+            State::Trick(_) if self.pending_claim.is_some() => Err(SpadesError::ClaimPending),
+            State::Trick(_) => Ok(()),
+        }
+    }
+
+    fn player_index(&self, player: Uid) -> Result<usize, SpadesError> {
+        self.player
+            .iter()
+            .position(|p| p.id == player)
+            .ok_or(SpadesError::InvalidUuid)
+    }
+
+    /// Whether the player at `index`, about to lead, wins every remaining trick however the others play.
+    fn claim_is_provable(&self, index: usize) -> bool {
+        let hand = &self.player[index].hand;
+        let others: Vec<&Card> = (1..4)
+            .flat_map(|offset| self.player[(index + offset) % 4].hand.iter())
+            .collect();
+        let beats_others = |card: &Card| {
+            others
+                .iter()
+                .all(|other| other.suit != card.suit || other.rank < card.rank)
+        };
+        if !hand.iter().all(beats_others) {
+            return false;
+        }
+
+        // the claimant leads spades until nobody else has any left, so they can't trump the other suits
+        let spades = |hand: &[Card]| hand.iter().filter(|card| card.suit == Suit::Spades).count();
+        let most_spades_held = (1..4)
+            .map(|offset| spades(&self.player[(index + offset) % 4].hand))
+            .max()
+            .unwrap_or(0);
+        let own_spades = spades(hand);
+        if own_spades < most_spades_held {
+            return false;
+        }
+        most_spades_held == 0 || own_spades == hand.len() || self.spades_broken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Claim, ClaimResponse};
+    use cards::{Card, Rank, Suit};
+    use test_support::started_game;
+    use {Bet, Game, PlayCardResult, SpadesError, State, Uid};
+
+    fn game_in_trick_stage() -> Game {
+        let mut g = started_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        g
+    }
+
+    fn card(rank: Rank, suit: Suit) -> Card {
+        Card { suit, rank }
+    }
+
+    #[test]
+    fn test_unprovable_claim_is_refused() {
+        let mut g = game_in_trick_stage();
+        g.rig_round(
+            [
+                vec![card(Rank::Ace, Suit::Hearts), card(Rank::Two, Suit::Clubs)],
+                vec![
+                    card(Rank::Two, Suit::Hearts),
+                    card(Rank::Three, Suit::Clubs),
+                ],
+                vec![
+                    card(Rank::Three, Suit::Hearts),
+                    card(Rank::Four, Suit::Clubs),
+                ],
+                vec![
+                    card(Rank::Four, Suit::Hearts),
+                    card(Rank::Five, Suit::Clubs),
+                ],
+            ],
+            &[],
+        );
+        assert_eq!(
+            Err(SpadesError::ClaimNotProvable),
+            g.claim_remaining(&Uid(10))
+        );
+        assert_eq!(None, g.pending_claim());
+    }
+
+    #[test]
+    fn test_claim_requires_player_on_lead() {
+        let mut g = game_in_trick_stage();
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            g.claim_remaining(&Uid(11))
+        );
+        assert_eq!(Err(SpadesError::InvalidUuid), g.claim_remaining(&Uid(99)));
+        let lead = g.playable_cards().next().unwrap();
+        g.play_card(lead);
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            g.claim_remaining(&Uid(11))
+        );
+    }
+
+    #[test]
+    fn test_accepted_claim_scores_round() {
+        let mut g = game_in_trick_stage();
+        g.rig_round(
+            [
+                vec![
+                    card(Rank::Ace, Suit::Spades),
+                    card(Rank::King, Suit::Spades),
+                ],
+                vec![card(Rank::Two, Suit::Spades), card(Rank::Two, Suit::Hearts)],
+                vec![
+                    card(Rank::Three, Suit::Hearts),
+                    card(Rank::Four, Suit::Hearts),
+                ],
+                vec![
+                    card(Rank::Five, Suit::Hearts),
+                    card(Rank::Six, Suit::Hearts),
+                ],
+            ],
+            &[],
+        );
+
+        assert_eq!(Ok(()), g.claim_remaining(&Uid(10)));
+        assert_eq!(Some(Claim::Tricks { player: Uid(10) }), g.pending_claim());
+        assert_eq!(vec![Uid(11), Uid(13)], g.awaiting_claim_response());
+        assert_eq!(
+            Some(SpadesError::ClaimPending),
+            g.can_play_card(card(Rank::Ace, Suit::Spades))
+        );
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            g.respond_to_claim(&Uid(12), true)
+        );
+
+        assert_eq!(
+            Ok(ClaimResponse::Awaiting),
+            g.respond_to_claim(&Uid(11), true)
+        );
+        assert_eq!(
+            Ok(ClaimResponse::Accepted(PlayCardResult::TrickCompleted)),
+            g.respond_to_claim(&Uid(13), true)
+        );
+        assert_eq!(None, g.pending_claim());
+        assert_eq!(State::Betting(0), g.state());
+        // every trick of the round went to player 0
+        assert_eq!(Ok(67), g.team_all_rounds_score(0));
+        assert_eq!(Ok(-60), g.team_all_rounds_score(1));
+        assert_eq!(13, g.current_hand().unwrap().len());
+    }
+
+    #[test]
+    fn test_rejected_claim_resumes_play() {
+        let mut g = game_in_trick_stage();
+        g.rig_round(
+            [
+                vec![card(Rank::Ace, Suit::Hearts)],
+                vec![card(Rank::Two, Suit::Hearts)],
+                vec![card(Rank::Three, Suit::Hearts)],
+                vec![card(Rank::Four, Suit::Hearts)],
+            ],
+            &[],
+        );
+
+        g.claim_remaining(&Uid(10)).unwrap();
+        assert_eq!(Err(SpadesError::ClaimPending), g.concede(1));
+        assert_eq!(
+            Ok(ClaimResponse::Rejected),
+            g.respond_to_claim(&Uid(13), false)
+        );
+        assert_eq!(None, g.pending_claim());
+        assert_eq!(
+            Err(SpadesError::NoPendingClaim),
+            g.respond_to_claim(&Uid(11), true)
+        );
+        assert_eq!(
+            Some(PlayCardResult::CardPlayed),
+            g.play_card(card(Rank::Ace, Suit::Hearts))
+        );
+    }
+
+    #[test]
+    fn test_claim_needs_spades_drawn_first() {
+        let mut g = game_in_trick_stage();
+        g.rig_round(
+            [
+                vec![card(Rank::Ace, Suit::Spades), card(Rank::Ace, Suit::Hearts)],
+                vec![card(Rank::Two, Suit::Spades), card(Rank::Two, Suit::Hearts)],
+                vec![
+                    card(Rank::Three, Suit::Clubs),
+                    card(Rank::Four, Suit::Clubs),
+                ],
+                vec![card(Rank::Five, Suit::Clubs), card(Rank::Six, Suit::Clubs)],
+            ],
+            &[],
+        );
+        // spades can't be led yet, so player 1 could trump the heart
+        assert_eq!(
+            Err(SpadesError::ClaimNotProvable),
+            g.claim_remaining(&Uid(10))
+        );
+        g.spades_broken = true;
+        assert_eq!(Ok(()), g.claim_remaining(&Uid(10)));
+    }
+
+    #[test]
+    fn test_concession_goes_to_other_team() {
+        let mut g = game_in_trick_stage();
+        assert_eq!(Err(SpadesError::InvalidTeam(2)), g.concede(2));
+        assert_eq!(Ok(()), g.concede(0));
+        assert_eq!(Some(Claim::Concession { team: 0 }), g.pending_claim());
+        assert_eq!(vec![Uid(11), Uid(13)], g.awaiting_claim_response());
+        g.respond_to_claim(&Uid(11), true).unwrap();
+        g.respond_to_claim(&Uid(13), true).unwrap();
+        assert_eq!(Ok(-60), g.team_all_rounds_score(0));
+        assert_eq!(Ok(67), g.team_all_rounds_score(1));
+        assert_eq!(State::Betting(0), g.state());
+    }
+
+    #[test]
+    fn test_concession_goes_to_the_board_bidder_of_a_nil_partnership() {
+        let mut g = started_game();
+        for bet in [Bet::Amount(3), Bet::Nil, Bet::Amount(3), Bet::Board] {
+            g.place_bet(bet);
+        }
+        assert_eq!(Ok(()), g.concede(0));
+        assert_eq!(Some(3), g.pending_claim.map(|pending| pending.winner));
+    }
+}
@@ -0,0 +1,236 @@
+use card_display::CardStyle;
+use cards::{Card, Rank, Suit};
+use ml::Action;
+use result::SpadesError;
+use scoring::Bet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Something a player typed to a chat bot, parsed from text such as `bid 4`, `play QS`, `hand` or `score`.
+///
+/// Commands are read case-insensitively and may start with `!` or `/`, as bot commands often do.  Text that
+/// isn't a command gives `SpadesError::InvalidCommand`, with a message fit to show the player.
+///
+/// ```
+/// use spades::ml::Action;
+/// use spades::{Bet, Command};
+///
+/// let command: Command = "!bid 4".parse().unwrap();
+/// assert_eq!(Command::Act(Action::Bet(Bet::Amount(4))), command);
+/// assert!("play 1Z".parse::<Command>().unwrap_err().to_string().contains("1Z"));
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum Command {
+    /// Bet or play a card.
+    Act(Action),
+    /// Show the player their hand.
+    Hand,
+    /// Show the [`scoreboard`](struct.Game.html#method.scoreboard).
+    Score,
+    /// Show the cards played to the trick so far.
+    Trick,
+    /// Show [`Command::usage`](#method.usage).
+    Help,
+}
+
+const USAGE: &str = "bid <1-13|nil|blind nil|board|10for200>: place your bet\n\
+                     play <card>: play a card, such as QS, 10H or 7\u{2666}\n\
+                     hand: show your hand\n\
+                     score: show the scores\n\
+                     trick: show the cards played to the trick\n\
+                     help: show this list";
+
+impl Command {
+    /// A line for each command, explaining it.
+    pub fn usage() -> &'static str {
+        USAGE
+    }
+}
+
+fn invalid(message: String) -> SpadesError {
+    SpadesError::InvalidCommand(message)
+}
+
+fn parse_bet(words: &[String]) -> Result<Bet, SpadesError> {
+    let text = words.join(" ");
+    match text.as_str() {
+        "" => Err(invalid("bid what? try `bid 4` or `bid nil`".to_string())),
+        "nil" => Ok(Bet::Nil),
+        "blind" | "blind nil" | "blindnil" => Ok(Bet::BlindNil),
+        "board" => Ok(Bet::Board),
+        "10for200" | "10 for 200" => Ok(Bet::TenForTwoHundred),
+        _ => match text.parse::<u8>() {
+            Ok(tricks) => Bet::amount(tricks).map_err(|_| {
+                invalid(format!(
+                    "can't bid {}: bid nil or between 1 and 13 tricks",
+                    tricks
+                ))
+            }),
+            Err(_) => Err(invalid(format!(
+                "`{}` isn't a bid; try a number of tricks, or nil",
+                text
+            ))),
+        },
+    }
+}
+
+/// Reads a card written rank first, as in `QS`, `10h`, `TD` or `7♦`.
+fn parse_card(text: &str) -> Option<Card> {
+    let mut chars: Vec<char> = text.chars().collect();
+    let suit = match chars.pop()?.to_ascii_lowercase() {
+        'c' | '\u{2663}' | '\u{2667}' => Suit::Clubs,
+        'd' | '\u{2666}' | '\u{2662}' => Suit::Diamonds,
+        'h' | '\u{2665}' | '\u{2661}' => Suit::Hearts,
+        's' | '\u{2660}' | '\u{2664}' => Suit::Spades,
+        _ => return None,
+    };
+    let rank: String = chars.iter().collect::<String>().to_ascii_lowercase();
+    let rank = match rank.as_str() {
+        "a" => Rank::Ace,
+        "k" => Rank::King,
+        "q" => Rank::Queen,
+        "j" => Rank::Jack,
+        "t" => Rank::Ten,
+        number => match number.parse::<u8>() {
+            Ok(n) if (2..=10).contains(&n) => Rank::from(n),
+            _ => return None,
+        },
+    };
+    Some(Card { suit, rank })
+}
+
+impl FromStr for Command {
+    type Err = SpadesError;
+
+    fn from_str(s: &str) -> Result<Command, SpadesError> {
+        let text = s.trim();
+        let text = text
+            .strip_prefix('!')
+            .or_else(|| text.strip_prefix('/'))
+            .unwrap_or(text);
+        let words: Vec<String> = text.split_whitespace().map(str::to_lowercase).collect();
+        let (name, args) = match words.split_first() {
+            Some((name, args)) => (name.as_str(), args),
+            None => {
+                return Err(invalid(
+                    "type a command, such as `bid 4` or `play QS`; `help` lists them all"
+                        .to_string(),
+                ))
+            }
+        };
+        match name {
+            "bid" | "bet" => Ok(Command::Act(Action::Bet(parse_bet(args)?))),
+            "play" => match args {
+                [] => Err(invalid(
+                    "play which card? try `play QS` or `play 10H`".to_string(),
+                )),
+                [card] => parse_card(card)
+                    .map(|card| Command::Act(Action::Play(card)))
+                    .ok_or_else(|| {
+                        invalid(format!(
+                            "`{}` isn't a card; write the rank then the suit, such as QS, 10H or 7\u{2666}",
+                            // show the card as it was typed
+                            s.split_whitespace().last().unwrap_or(card)
+                        ))
+                    }),
+                _ => Err(invalid("play one card at a time".to_string())),
+            },
+            "hand" | "cards" => Ok(Command::Hand),
+            "score" | "scores" => Ok(Command::Score),
+            "trick" => Ok(Command::Trick),
+            "help" | "?" => Ok(Command::Help),
+            _ => Err(invalid(format!(
+                "unknown command `{}`; try bid, play, hand, score, trick or help",
+                name
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Command {
+    /// Writes the command so that it parses back to itself.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Command::Act(Action::Bet(bet)) => match bet {
+                Bet::Amount(tricks) => write!(f, "bid {}", tricks),
+                Bet::Nil => write!(f, "bid nil"),
+                Bet::BlindNil => write!(f, "bid blind nil"),
+                Bet::Board => write!(f, "bid board"),
+                Bet::TenForTwoHundred => write!(f, "bid 10for200"),
+            },
+            Command::Act(Action::Play(card)) => {
+                write!(f, "play {}", card.fmt_with(CardStyle::Ascii))
+            }
+            Command::Hand => write!(f, "hand"),
+            Command::Score => write!(f, "score"),
+            Command::Trick => write!(f, "trick"),
+            Command::Help => write!(f, "help"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Command;
+    use cards::{Card, Rank, Suit};
+    use ml::Action;
+    use {Bet, SpadesError};
+
+    fn play(suit: Suit, rank: Rank) -> Command {
+        Command::Act(Action::Play(Card { suit, rank }))
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            Ok(Command::Act(Action::Bet(Bet::Amount(4)))),
+            "bid 4".parse()
+        );
+        assert_eq!(Ok(Command::Act(Action::Bet(Bet::Nil))), "!BET Nil".parse());
+        assert_eq!(
+            Ok(Command::Act(Action::Bet(Bet::BlindNil))),
+            "/bid blind nil".parse()
+        );
+        assert_eq!(Ok(play(Suit::Spades, Rank::Queen)), "play QS".parse());
+        assert_eq!(Ok(play(Suit::Hearts, Rank::Ten)), "play 10h".parse());
+        assert_eq!(Ok(play(Suit::Hearts, Rank::Ten)), "play TH".parse());
+        assert_eq!(
+            Ok(play(Suit::Diamonds, Rank::Seven)),
+            "play 7\u{2666}".parse()
+        );
+        assert_eq!(Ok(Command::Hand), " hand ".parse());
+        assert_eq!(Ok(Command::Score), "scores".parse());
+        assert_eq!(Ok(Command::Help), "?".parse());
+    }
+
+    #[test]
+    fn test_parse_errors_explain() {
+        let message = |text: &str| match text.parse::<Command>() {
+            Err(SpadesError::InvalidCommand(message)) => message,
+            other => panic!("{:?}", other),
+        };
+        assert!(message("bid 14").contains("between 1 and 13"));
+        assert!(message("bid").contains("bid what?"));
+        assert!(message("play 1Z").contains("`1Z` isn't a card"));
+        assert!(message("play").contains("which card"));
+        assert!(message("fold").contains("unknown command `fold`"));
+        assert!(message("").contains("help"));
+    }
+
+    #[test]
+    fn test_display_parses_back() {
+        let commands = [
+            Command::Act(Action::Bet(Bet::Amount(13))),
+            Command::Act(Action::Bet(Bet::BlindNil)),
+            Command::Act(Action::Bet(Bet::TenForTwoHundred)),
+            play(Suit::Clubs, Rank::Ten),
+            Command::Trick,
+        ];
+        for command in commands.iter() {
+            assert_eq!(Ok(*command), command.to_string().parse());
+        }
+        assert!(Command::usage().lines().count() >= 6);
+    }
+}
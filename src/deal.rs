@@ -0,0 +1,301 @@
+use cards::{Card, Rank, Suit};
+use result::SpadesError;
+use seat::Seat;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+use {Game, Hand, Uid};
+
+/// The four hands of one deal, for playing the same cards again, as in duplicate Spades.
+///
+/// Get the hands a game was dealt with [`Game::dealt_hands`](struct.Game.html#method.dealt_hands), and have a
+/// game deal them with [`Game::queue_deal`](struct.Game.html#method.queue_deal).
+///
+/// A deal is written the way bridge's PBN files write them, so it can be shared as a short string: the seat
+/// of the first hand, then each hand clockwise from there, as its spades, hearts, diamonds and clubs from
+/// the highest card down, with ten written `T`.
+///
+/// ```
+/// use spades::Deal;
+///
+/// let text = "N:AKQJT98765432... .AKQJT98765432.. ..AKQJT98765432. ...AKQJT98765432";
+/// let deal: Deal = text.parse().unwrap();
+/// assert_eq!(text, deal.to_string());
+/// ```
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct Deal {
+    hands: [Vec<Card>; 4],
+}
+
+impl Deal {
+    /// The deal with `hands[i]` dealt to player `i`.  Returns `SpadesError::InvalidDeal` unless the hands
+    /// hold 13 cards each and every card of the deck between them.
+    pub fn new(hands: [Vec<Card>; 4]) -> Result<Deal, SpadesError> {
+        let cards: BTreeSet<Card> = hands.iter().flat_map(|hand| hand.iter().cloned()).collect();
+        if cards.len() != 52 || hands.iter().any(|hand| hand.len() != 13) {
+            return Err(SpadesError::InvalidDeal);
+        }
+        let mut hands = hands;
+        for hand in hands.iter_mut() {
+            hand.sort();
+        }
+        Ok(Deal { hands })
+    }
+
+    pub fn hand(&self, seat: Seat) -> &[Card] {
+        &self.hands[seat.index()]
+    }
+
+    pub fn hands(&self) -> &[Vec<Card>; 4] {
+        &self.hands
+    }
+}
+
+/// The suits in the order a hand is written.
+const SUIT_ORDER: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+pub(crate) const RANK_CHARS: [(Rank, char); 13] = [
+    (Rank::Ace, 'A'),
+    (Rank::King, 'K'),
+    (Rank::Queen, 'Q'),
+    (Rank::Jack, 'J'),
+    (Rank::Ten, 'T'),
+    (Rank::Nine, '9'),
+    (Rank::Eight, '8'),
+    (Rank::Seven, '7'),
+    (Rank::Six, '6'),
+    (Rank::Five, '5'),
+    (Rank::Four, '4'),
+    (Rank::Three, '3'),
+    (Rank::Two, '2'),
+];
+
+const SEAT_CHARS: [char; 4] = ['N', 'E', 'S', 'W'];
+
+impl fmt::Display for Deal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "N:")?;
+        for (seat, hand) in self.hands.iter().enumerate() {
+            if seat > 0 {
+                write!(f, " ")?;
+            }
+            for (i, suit) in SUIT_ORDER.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ".")?;
+                }
+                for (rank, c) in RANK_CHARS.iter() {
+                    if hand.contains(&Card {
+                        suit: *suit,
+                        rank: *rank,
+                    }) {
+                        write!(f, "{}", c)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Deal {
+    type Err = SpadesError;
+
+    /// Reads a deal written as [`Display`](#impl-Display-for-Deal) writes it, starting from any seat.
+    fn from_str(s: &str) -> Result<Deal, SpadesError> {
+        let mut chars = s.trim().chars();
+        let first = chars
+            .next()
+            .and_then(|c| SEAT_CHARS.iter().position(|seat| *seat == c))
+            .ok_or(SpadesError::InvalidDeal)?;
+        if chars.next() != Some(':') {
+            return Err(SpadesError::InvalidDeal);
+        }
+        let texts: Vec<&str> = chars.as_str().split_whitespace().collect();
+        if texts.len() != 4 {
+            return Err(SpadesError::InvalidDeal);
+        }
+        let mut hands = [vec![], vec![], vec![], vec![]];
+        for (i, text) in texts.iter().enumerate() {
+            let suits: Vec<&str> = text.split('.').collect();
+            if suits.len() != 4 {
+                return Err(SpadesError::InvalidDeal);
+            }
+            let hand = &mut hands[(first + i) % 4];
+            for (suit, ranks) in SUIT_ORDER.iter().zip(suits) {
+                for c in ranks.chars() {
+                    let rank = RANK_CHARS
+                        .iter()
+                        .find(|(_, rank_char)| *rank_char == c.to_ascii_uppercase())
+                        .ok_or(SpadesError::InvalidDeal)?
+                        .0;
+                    hand.push(Card { suit: *suit, rank });
+                }
+            }
+        }
+        Deal::new(hands)
+    }
+}
+
+impl Game {
+    /// A game between these players whose first round is dealt `deal`.
+    pub fn new_with_hands(id: Uid, player_ids: [Uid; 4], deal: Deal) -> Game {
+        let mut game = Game::default();
+        game.assign_players(id, player_ids);
+        game.queue_deal(deal);
+        game
+    }
+
+    /// Deal `deal` instead of shuffling, at the first deal that has no deal queued before it.  A queued deal
+    /// is dealt without a kitty, whatever the [`KittyConfig`](struct.KittyConfig.html).
+    pub fn queue_deal(&mut self, deal: Deal) {
+        self.queued_deals.push_back(deal);
+    }
+
+    /// How many queued deals are still to be dealt.
+    pub fn queued_deals(&self) -> usize {
+        self.queued_deals.len()
+    }
+
+    /// The hands as they were dealt this round, before any kitty was picked up or card played.
+    pub fn dealt_hands(&self) -> Option<&Deal> {
+        self.round_deal.as_ref()
+    }
+
+    /// Deals the next queued deal, if there is one.
+    pub(crate) fn deal_queued(&mut self) -> bool {
+        let deal = match self.queued_deals.pop_front() {
+            Some(deal) => deal,
+            None => return false,
+        };
+        // every card goes to a hand
+        self.deck.clear();
+        for (player, hand) in self.player.iter_mut().zip(deal.hands.iter()) {
+            player.hand = Hand::from(&hand[..]);
+        }
+        self.scoring.set_tricks_per_round(13);
+        true
+    }
+
+    /// Copies the hands just dealt into the last round's record, reusing its storage.
+    pub(crate) fn record_deal(&mut self) {
+        let deal = self.round_deal.get_or_insert_with(|| Deal {
+            hands: [vec![], vec![], vec![], vec![]],
+        });
+        for (recorded, player) in deal.hands.iter_mut().zip(self.player.iter()) {
+            recorded.clear();
+            recorded.extend_from_slice(&player.hand);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Deal;
+    use cards::new_deck;
+    use {Bet, Game, KittyConfig, KittyTaker, Seat, SpadesError, State, Uid};
+
+    fn sorted_deal() -> Deal {
+        let mut deck = new_deck();
+        deck.sort();
+        let hands = [
+            deck[0..13].to_vec(),
+            deck[13..26].to_vec(),
+            deck[26..39].to_vec(),
+            deck[39..52].to_vec(),
+        ];
+        Deal::new(hands).unwrap()
+    }
+
+    #[test]
+    fn test_deal_needs_thirteen_cards_each() {
+        let deal = sorted_deal();
+        let mut hands = deal.hands().clone();
+        let card = hands[0].pop().unwrap();
+        assert_eq!(Err(SpadesError::InvalidDeal), Deal::new(hands.clone()));
+        hands[1].push(card);
+        assert_eq!(Err(SpadesError::InvalidDeal), Deal::new(hands.clone()));
+        hands[1].pop();
+        hands[0].push(hands[1][0]);
+        assert_eq!(Err(SpadesError::InvalidDeal), Deal::new(hands));
+    }
+
+    #[test]
+    fn test_deal_string_round_trip() {
+        let mut g = Game::default();
+        g.start_game();
+        let deal = g.dealt_hands().unwrap().clone();
+        let text = deal.to_string();
+        assert!(text.starts_with("N:"));
+        assert_eq!(Ok(deal), text.parse());
+    }
+
+    #[test]
+    fn test_deal_string_from_other_seat() {
+        let deal = sorted_deal();
+        assert_eq!(
+            "N:...AKQJT98765432 ..AKQJT98765432. .AKQJT98765432.. AKQJT98765432...",
+            deal.to_string()
+        );
+        let text = deal.to_string();
+        let hands: Vec<&str> = text[2..].split(' ').collect();
+        let from_south = format!("S:{} {} {} {}", hands[2], hands[3], hands[0], hands[1]);
+        assert_eq!(Ok(deal), from_south.parse());
+    }
+
+    #[test]
+    fn test_bad_deal_strings() {
+        let deal = sorted_deal().to_string();
+        for text in &[
+            "",
+            &deal[2..],
+            &deal.replace("N:", "X:"),
+            &deal.replace("N:", "N"),
+            &deal.replacen(" ", "", 1),
+            &deal.replacen("K", "X", 1),
+            &deal.replacen("K", "", 1),
+            &deal.replacen(".", "", 1),
+        ] {
+            assert_eq!(Err(SpadesError::InvalidDeal), text.parse::<Deal>());
+        }
+    }
+
+    #[test]
+    fn test_queued_deals_are_dealt_in_order() {
+        let deal = sorted_deal();
+        let mut g =
+            Game::new_with_hands(Uid(1), [Uid(10), Uid(11), Uid(12), Uid(13)], deal.clone());
+        g.set_kitty_config(Some(KittyConfig {
+            size: 4,
+            taker: KittyTaker::Dealer,
+        }))
+        .unwrap();
+        g.start_game();
+        assert_eq!(State::Betting(0), g.state());
+        assert_eq!(Some(&deal), g.dealt_hands());
+        assert_eq!(deal.hand(Seat::North), g.hand_of_current_player().unwrap());
+        assert_eq!(0, g.queued_deals());
+
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let card = g.playable_cards().next().unwrap();
+        g.play_card(card);
+        assert_eq!(Some(&deal), g.dealt_hands());
+    }
+
+    #[test]
+    fn test_same_deal_at_two_tables() {
+        let mut first = Game::default();
+        first.start_game();
+        let deal = first.dealt_hands().unwrap().clone();
+        let mut second = Game::new_with_hands(Uid(2), [Uid(20), Uid(21), Uid(22), Uid(23)], deal);
+        second.start_game();
+        assert_eq!(first.dealt_hands(), second.dealt_hands());
+        assert_eq!(
+            first.hand_of_current_player(),
+            second.hand_of_current_player()
+        );
+    }
+}
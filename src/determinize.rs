@@ -0,0 +1,201 @@
+extern crate rand;
+
+use self::rand::Rng;
+use cards::{Card, Suit};
+use diagnostics::GameEvent;
+use result::SpadesError;
+use {Game, PlayerId};
+
+/// How many times a determinization is tried before the hidden cards are left where they are.
+const DETERMINIZE_ATTEMPTS: usize = 100;
+
+impl Game {
+    /// The suits each seat has shown this round that they hold none of, by not following them when they
+    /// were led, indexed by seat and then by suit in the order clubs, diamonds, hearts, spades.
+    pub fn known_voids(&self) -> [[bool; 4]; 4] {
+        let mut voids = [[false; 4]; 4];
+        if let Some(plays) = self.round_plays().last() {
+            for (index, (seat, card)) in plays.iter().enumerate() {
+                let led = plays[index - index % 4].1.suit;
+                if card.suit != led {
+                    voids[seat.index()][led as usize] = true;
+                }
+            }
+        }
+        voids
+    }
+
+    /// A copy of the game as `perspective` might imagine it: the cards they can't see, in the other players'
+    /// hands, the kitty and the other players' discards, are shared out at random among those places, each
+    /// keeping as many cards as it has and no player getting a suit they are
+    /// [known to be void in](#method.known_voids).  The copy is a [fork](#method.fork), ready to be searched.
+    ///
+    /// Returns `SpadesError::InvalidUuid` if `perspective` isn't playing.
+    pub fn determinize<P: PlayerId + ?Sized, R: Rng>(
+        &self,
+        perspective: &P,
+        rng: &mut R,
+    ) -> Result<Game, SpadesError> {
+        let seat = self.seat_of(perspective)?.index();
+        let uid = perspective.to_uid();
+        let discards: Vec<Card> = self
+            .history()
+            .iter()
+            .rev()
+            .take_while(|event| **event != GameEvent::Dealt)
+            .filter_map(|event| match event {
+                GameEvent::Discarded { player, cards } if *player != uid => Some(cards.clone()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+
+        // the places hidden cards can be: the other seats' hands, then the kitty, then the discards
+        let mut capacity = [0; 6];
+        let mut hidden = vec![];
+        for (index, player) in self.player.iter().enumerate() {
+            if index != seat {
+                capacity[index] = player.hand.len();
+                hidden.extend_from_slice(&player.hand);
+            }
+        }
+        capacity[4] = self.kitty.len();
+        hidden.extend_from_slice(&self.kitty);
+        capacity[5] = discards.len();
+        hidden.extend_from_slice(&discards);
+
+        let voids = self.known_voids();
+        let fits = |place: usize, suit: Suit| place >= 4 || !voids[place][suit as usize];
+        let places_for = |suit: Suit| (0..6).filter(|place| fits(*place, suit)).count();
+
+        let mut fork = self.fork();
+        for _ in 0..DETERMINIZE_ATTEMPTS {
+            rng.shuffle(&mut hidden);
+            // the cards with the fewest places to go are placed first
+            hidden.sort_by_key(|card| places_for(card.suit));
+            let mut left = capacity;
+            let mut placed: [Vec<Card>; 6] = Default::default();
+            let mut stuck = false;
+            for card in hidden.iter() {
+                let room: usize = (0..6)
+                    .filter(|place| fits(*place, card.suit))
+                    .map(|place| left[place])
+                    .sum();
+                if room == 0 {
+                    stuck = true;
+                    break;
+                }
+                // weighted by the room left, so big hands get their share
+                let mut pick = rng.gen_range(0, room);
+                for place in (0..6).filter(|place| fits(*place, card.suit)) {
+                    if pick < left[place] {
+                        left[place] -= 1;
+                        placed[place].push(*card);
+                        break;
+                    }
+                    pick -= left[place];
+                }
+            }
+            if stuck {
+                continue;
+            }
+            let [north, east, south, west, kitty, discarded] = placed;
+            for (index, mut hand) in IntoIterator::into_iter([north, east, south, west]).enumerate()
+            {
+                if index != seat {
+                    hand.sort();
+                    fork.player[index].hand.clear();
+                    fork.player[index].hand.extend_from_slice(&hand);
+                }
+            }
+            fork.kitty = kitty;
+            fork.deck.retain(|card| !discards.contains(card));
+            fork.deck.extend(discarded);
+            break;
+        }
+        // the real deal would give the game away
+        fork.round_deal = None;
+        fork.deal_audit = None;
+        Ok(fork)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cards::{seeded_rng, Card, Rank, Suit};
+    use test_support::{seeded_game, started_game};
+    use {Bet, Game, SpadesError, Uid};
+
+    fn card(suit: Suit, rank: u8) -> Card {
+        Card {
+            suit,
+            rank: Rank::from(rank),
+        }
+    }
+
+    /// North leads a heart that West can't follow.
+    fn game_with_void() -> Game {
+        let mut g = seeded_game(8);
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let hands = [
+            (2..=14).map(|rank| card(Suit::Hearts, rank)).collect(),
+            (2..=14).map(|rank| card(Suit::Diamonds, rank)).collect(),
+            (2..=14).map(|rank| card(Suit::Spades, rank)).collect(),
+            (2..=14).map(|rank| card(Suit::Clubs, rank)).collect(),
+        ];
+        g.rig_round(hands, &[]);
+        g.play_card(card(Suit::Hearts, 2));
+        g.play_card(card(Suit::Diamonds, 2));
+        g.play_card(card(Suit::Spades, 2));
+        g
+    }
+
+    #[test]
+    fn test_known_voids() {
+        let g = game_with_void();
+        let voids = g.known_voids();
+        assert_eq!([false, false, false, false], voids[0]);
+        assert!(voids[1][Suit::Hearts as usize]);
+        assert!(voids[2][Suit::Hearts as usize]);
+        assert!(!voids[3][Suit::Hearts as usize]);
+    }
+
+    #[test]
+    fn test_determinize_keeps_what_is_known() {
+        let g = game_with_void();
+        assert_eq!(
+            Err(SpadesError::InvalidUuid),
+            g.determinize(&Uid(99), &mut seeded_rng(1))
+        );
+        let mut rng = seeded_rng(1);
+        for _ in 0..20 {
+            let d = g.determinize(&Uid(13), &mut rng).unwrap();
+            assert!(d.invariant_violations().is_empty());
+            assert_eq!(g.player[3].hand, d.player[3].hand);
+            for seat in 0..4 {
+                assert_eq!(g.player[seat].hand.len(), d.player[seat].hand.len());
+            }
+            // East and South have shown they have no hearts
+            for seat in 1..3 {
+                assert!(!d.player[seat].hand.iter().any(|c| c.suit == Suit::Hearts));
+            }
+            assert_eq!(g.current_trick, d.current_trick);
+            assert_eq!(None, d.dealt_hands());
+        }
+    }
+
+    #[test]
+    fn test_determinize_shuffles_unconstrained_cards() {
+        let g = started_game();
+        let mut rng = seeded_rng(2);
+        let d = g.determinize(&Uid(10), &mut rng).unwrap();
+        assert_eq!(g.player[0].hand, d.player[0].hand);
+        assert_ne!(g.player[1].hand, d.player[1].hand);
+        let mut all: Vec<Card> = d.player.iter().flat_map(|p| p.hand.clone()).collect();
+        all.sort();
+        all.dedup();
+        assert_eq!(52, all.len());
+    }
+}
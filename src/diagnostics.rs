@@ -0,0 +1,572 @@
+use adjustment::Adjustment;
+#[cfg(feature = "annotations")]
+use annotations::Annotation;
+use cards::Card;
+use claim::Claim;
+use fairness::{DealCommitment, DealReveal};
+use game_state::State;
+use options::GameOptions;
+use player_info::PlayerInfo;
+use result::SpadesError;
+use revoke::Revoke;
+use scoring::{Bet, TeamRoundScore};
+use snapshot::GameSnapshot;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use {Game, PlayerId, Uid};
+
+/// How many of the latest events [`Game::diagnostics`](struct.Game.html#method.diagnostics) reports.
+pub const RECENT_EVENT_COUNT: usize = 32;
+
+/// Something that changed the game, as kept in its [`history`](struct.Game.html#method.history) and reported
+/// in [`Diagnostics`](struct.Diagnostics.html).
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum GameEvent {
+    /// Cards were dealt for a new round.
+    Dealt,
+    Bet {
+        player: Uid,
+        bet: Bet,
+    },
+    Discarded {
+        player: Uid,
+        cards: Vec<Card>,
+    },
+    CardPlayed {
+        player: Uid,
+        card: Card,
+    },
+    /// A claim was accepted and the rest of the round's tricks awarded.
+    ClaimAccepted(Claim),
+    /// The round is over: the tricks each player took, and what each team scored for them.
+    RoundScored {
+        tricks: [u8; 4],
+        scores: [TeamRoundScore; 2],
+    },
+    /// The last bidder's partner changed their bet while reviewing the bets.
+    BetAmended {
+        player: Uid,
+        from: Bet,
+        to: Bet,
+    },
+    /// Info was attached to a player with [`Game::set_player_info`](struct.Game.html#method.set_player_info).
+    PlayerInfoChanged {
+        player: Uid,
+        info: PlayerInfo,
+    },
+    /// A misdeal was declared, and the cards are about to be dealt again.
+    Misdeal {
+        player: Uid,
+    },
+    /// A revoke was penalized, changing each team's score for its round by `points`.
+    RevokePenalized {
+        revoke: Revoke,
+        points: [i32; 2],
+    },
+    /// The table options were changed between rounds.
+    OptionsAmended {
+        from: GameOptions,
+        to: GameOptions,
+    },
+    /// A team's score was adjusted by a ruling.
+    ScoreAdjusted(Adjustment),
+    /// Each team's bags from earlier games were carried into this one, see
+    /// [`Game::carry_over_bags`](struct.Game.html#method.carry_over_bags).
+    BagsCarried([u8; 2]),
+    /// The deal just made was committed to, see [`fairness`](fairness/index.html).
+    DealCommitted(DealCommitment),
+    /// The deal of the round just over, or thrown in by a misdeal, was revealed.
+    DealRevealed(DealReveal),
+    /// The deal just made was shuffled from this [shuffle seed](struct.Game.html#method.set_shuffle_seed),
+    /// so [`Game::replay`](struct.Game.html#method.replay) can deal it again.
+    ShuffleSeed(u64),
+}
+
+impl GameEvent {
+    /// The player whose action this event records, if it records one: a bet, discard, card played, bet
+    /// amended or misdeal declared.
+    pub fn acting_player(&self) -> Option<Uid> {
+        match self {
+            GameEvent::Bet { player, .. }
+            | GameEvent::Discarded { player, .. }
+            | GameEvent::CardPlayed { player, .. }
+            | GameEvent::BetAmended { player, .. }
+            | GameEvent::Misdeal { player } => Some(*player),
+            GameEvent::Dealt
+            | GameEvent::ClaimAccepted(_)
+            | GameEvent::RoundScored { .. }
+            | GameEvent::PlayerInfoChanged { .. }
+            | GameEvent::RevokePenalized { .. }
+            | GameEvent::OptionsAmended { .. }
+            | GameEvent::ScoreAdjusted(_)
+            | GameEvent::BagsCarried(_)
+            | GameEvent::DealCommitted(_)
+            | GameEvent::DealRevealed(_)
+            | GameEvent::ShuffleSeed(_) => None,
+        }
+    }
+}
+
+/// An event numbered by its place in the game, as returned by
+/// [`Game::events_since`](struct.Game.html#method.events_since).
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct SequencedEvent {
+    /// Counting from 1 for the game's first event.
+    pub seq: u64,
+    pub event: GameEvent,
+}
+
+/// Every event of a game, oldest first, and what was known when it last went wrong.  They tell how a game
+/// got where it is rather than where it is, so they are left out when games are compared or hashed.
+///
+/// The events are shared between clones until one of them records another.
+#[derive(Debug)]
+pub(crate) struct EventLog {
+    pub(crate) events: Arc<Vec<GameEvent>>,
+    last_good: Option<GameSnapshot>,
+    // behind a lock because internal errors also turn up in methods that only borrow the game
+    internal_error: Mutex<Option<Box<Diagnostics>>>,
+    /// The sequence number of the first event kept.
+    pub(crate) first_seq: u64,
+    /// How many actions each player has taken.
+    pub(crate) player_seqs: BTreeMap<Uid, u64>,
+    /// Bets, discards and cards played dropped by [checkpoints](struct.Game.html#method.checkpoint).
+    pub(crate) compacted_actions: usize,
+    /// Rounds scored whose events were dropped by checkpoints.
+    pub(crate) compacted_rounds: usize,
+    /// Off in [forks](struct.Game.html#method.fork), which skip taking a snapshot after every change.
+    keep_last_good: bool,
+    #[cfg(feature = "annotations")]
+    pub(crate) annotations: Vec<Annotation>,
+}
+
+/// How an [`EventLog`](struct.EventLog.html) is saved with a game.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct SavedEventLog {
+    events: Vec<GameEvent>,
+    last_good: Option<GameSnapshot>,
+    internal_error: Option<Box<Diagnostics>>,
+    first_seq: u64,
+    player_seqs: BTreeMap<Uid, u64>,
+    compacted_actions: usize,
+    compacted_rounds: usize,
+    #[cfg(feature = "annotations")]
+    #[serde(default)]
+    annotations: Vec<Annotation>,
+}
+
+impl Default for EventLog {
+    fn default() -> EventLog {
+        EventLog {
+            events: Arc::default(),
+            last_good: None,
+            internal_error: Mutex::default(),
+            first_seq: 1,
+            player_seqs: BTreeMap::new(),
+            compacted_actions: 0,
+            compacted_rounds: 0,
+            keep_last_good: true,
+            #[cfg(feature = "annotations")]
+            annotations: Vec::new(),
+        }
+    }
+}
+
+impl EventLog {
+    fn push(&mut self, event: GameEvent) {
+        if let Some(player) = event.acting_player() {
+            *self.player_seqs.entry(player).or_insert(0) += 1;
+        }
+        Arc::make_mut(&mut self.events).push(event);
+    }
+
+    fn last_seq(&self) -> u64 {
+        self.first_seq + self.events.len() as u64 - 1
+    }
+
+    pub(crate) fn internal_error(&self) -> Option<Box<Diagnostics>> {
+        self.internal_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+}
+
+impl Clone for EventLog {
+    fn clone(&self) -> EventLog {
+        EventLog {
+            events: self.events.clone(),
+            last_good: self.last_good.clone(),
+            internal_error: Mutex::new(self.internal_error()),
+            first_seq: self.first_seq,
+            player_seqs: self.player_seqs.clone(),
+            compacted_actions: self.compacted_actions,
+            compacted_rounds: self.compacted_rounds,
+            keep_last_good: self.keep_last_good,
+            #[cfg(feature = "annotations")]
+            annotations: self.annotations.clone(),
+        }
+    }
+}
+
+impl From<EventLog> for SavedEventLog {
+    fn from(log: EventLog) -> SavedEventLog {
+        SavedEventLog {
+            internal_error: log.internal_error(),
+            events: Arc::try_unwrap(log.events).unwrap_or_else(|events| (*events).clone()),
+            last_good: log.last_good,
+            first_seq: log.first_seq,
+            player_seqs: log.player_seqs,
+            compacted_actions: log.compacted_actions,
+            compacted_rounds: log.compacted_rounds,
+            #[cfg(feature = "annotations")]
+            annotations: log.annotations,
+        }
+    }
+}
+
+impl From<SavedEventLog> for EventLog {
+    fn from(saved: SavedEventLog) -> EventLog {
+        EventLog {
+            events: Arc::new(saved.events),
+            last_good: saved.last_good,
+            internal_error: Mutex::new(saved.internal_error),
+            first_seq: saved.first_seq,
+            player_seqs: saved.player_seqs,
+            compacted_actions: saved.compacted_actions,
+            compacted_rounds: saved.compacted_rounds,
+            keep_last_good: true,
+            #[cfg(feature = "annotations")]
+            annotations: saved.annotations,
+        }
+    }
+}
+
+impl PartialEq for EventLog {
+    fn eq(&self, _other: &EventLog) -> bool {
+        true
+    }
+}
+
+impl Eq for EventLog {}
+
+impl PartialOrd for EventLog {
+    fn partial_cmp(&self, other: &EventLog) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EventLog {
+    fn cmp(&self, _other: &EventLog) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl Hash for EventLog {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+/// What a server operator can attach to a bug report when a game goes wrong, for instance after a
+/// `SpadesError::InternalError`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Diagnostics {
+    pub state: State,
+    /// The state a paused game will resume in.
+    pub paused_state: Option<State>,
+    pub current_player_index: usize,
+    pub hand_sizes: [usize; 4],
+    /// Cards played this round, including the current trick, and any discards.
+    pub played_count: usize,
+    pub kitty_size: usize,
+    /// Tricks taken by each player this round.
+    pub tricks: [u8; 4],
+    /// The latest events, oldest first; at most `RECENT_EVENT_COUNT` of them.
+    pub recent_events: Vec<GameEvent>,
+    /// Everything found inconsistent about the game; empty if nothing is.
+    pub problems: Vec<String>,
+    pub snapshot: GameSnapshot,
+    /// The table after the latest change that left the game consistent.
+    pub last_good: Option<GameSnapshot>,
+    /// The diagnostics taken when the game last ran into a `SpadesError::InternalError`, if it has.
+    pub internal_error: Option<Box<Diagnostics>>,
+}
+
+impl Game {
+    /// A description of the game's internals, and of the latest changes to it.
+    pub fn diagnostics(&self) -> Diagnostics {
+        let events = &self.event_log.events;
+        Diagnostics {
+            state: self.state,
+            paused_state: self.paused_state,
+            current_player_index: self.current_player_index,
+            hand_sizes: [
+                self.player[0].hand.len(),
+                self.player[1].hand.len(),
+                self.player[2].hand.len(),
+                self.player[3].hand.len(),
+            ],
+            played_count: self.deck.len(),
+            kitty_size: self.kitty.len(),
+            tricks: self.scoring.player_tricks(),
+            recent_events: events[events.len().saturating_sub(RECENT_EVENT_COUNT)..].to_vec(),
+            problems: self.invariant_violations(),
+            snapshot: self.snapshot(),
+            last_good: self.event_log.last_good.clone(),
+            internal_error: self.event_log.internal_error(),
+        }
+    }
+
+    /// Keeps the diagnostics for [`Diagnostics::internal_error`](struct.Diagnostics.html#structfield.internal_error)
+    /// and returns `SpadesError::InternalError`.
+    pub(crate) fn internal_error(&self) -> SpadesError {
+        let mut diagnostics = self.diagnostics();
+        diagnostics.internal_error = None;
+        *self
+            .event_log
+            .internal_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Box::new(diagnostics));
+        SpadesError::InternalError
+    }
+
+    /// Remembers the table as the last known good one, if the game is consistent.
+    pub(crate) fn record_good_state(&mut self) {
+        if self.event_log.keep_last_good && self.invariant_violations().is_empty() {
+            let mut last_good = self.event_log.last_good.take().unwrap_or_default();
+            self.update_snapshot(&mut last_good);
+            self.event_log.last_good = Some(last_good);
+        }
+    }
+
+    pub(crate) fn record_event(&mut self, event: GameEvent) {
+        self.event_log.push(event);
+    }
+
+    /// The sequence number of the game's latest event, counting from 1; 0 before anything has happened.
+    pub fn last_seq(&self) -> u64 {
+        self.event_log.last_seq()
+    }
+
+    /// The events after the one numbered `seq`, for a client catching up after reconnecting.
+    pub fn events_since(&self, seq: u64) -> Vec<SequencedEvent> {
+        let log = &self.event_log;
+        let skip = (seq + 1).saturating_sub(log.first_seq) as usize;
+        log.events
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .map(|(index, event)| SequencedEvent {
+                seq: log.first_seq + index as u64,
+                event: event.clone(),
+            })
+            .collect()
+    }
+
+    /// How many actions have been taken over the game.
+    pub(crate) fn actions_taken(&self) -> u64 {
+        self.event_log.player_seqs.values().sum()
+    }
+
+    /// How many actions this player has taken in the game, see
+    /// [`GameEvent::acting_player`](enum.GameEvent.html#method.acting_player).  Returns
+    /// `SpadesError::InvalidUuid` if the player isn't in the game.
+    pub fn player_seq<P: PlayerId + ?Sized>(&self, player_id: &P) -> Result<u64, SpadesError> {
+        self.seat_of(player_id)?;
+        Ok(self
+            .event_log
+            .player_seqs
+            .get(&player_id.to_uid())
+            .cloned()
+            .unwrap_or(0))
+    }
+
+    /// A copy of the game for searching ahead, cheaper to make and to play on than a clone: it shares the
+    /// game's history until it records an event of its own, leaves out the diagnostics, and doesn't keep
+    /// [`Diagnostics::last_good`](struct.Diagnostics.html#structfield.last_good) up to date as it is played.
+    pub fn fork(&self) -> Game {
+        let mut fork = Game {
+            event_log: EventLog::default(),
+            ..self.clone()
+        };
+        fork.event_log.events = Arc::clone(&self.event_log.events);
+        fork.event_log.first_seq = self.event_log.first_seq;
+        fork.event_log.player_seqs = self.event_log.player_seqs.clone();
+        fork.event_log.compacted_actions = self.event_log.compacted_actions;
+        fork.event_log.compacted_rounds = self.event_log.compacted_rounds;
+        fork.event_log.keep_last_good = false;
+        fork
+    }
+
+    /// A copy of the game for simulation only, such as playing moves out in a search, that costs about as
+    /// much to make and to play on however long the game has run.  It is a [`fork`](#method.fork) that keeps
+    /// only the events of the round being played, as a [`checkpoint`](#method.checkpoint) does, so
+    /// [`round_history`](#method.round_history), [`events_since`](#method.events_since) and the like only go
+    /// back to the start of that round.
+    pub fn clone_without_history(&self) -> Game {
+        let mut copy = self.fork();
+        copy.drop_earlier_rounds();
+        copy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GameEvent, SequencedEvent, RECENT_EVENT_COUNT};
+    use test_support::{seated_game, started_game};
+    use {Bet, Game, SpadesError, State, Uid};
+
+    #[test]
+    fn test_diagnostics_keep_latest_events() {
+        let mut g = started_game();
+        g.place_bet(Bet::Nil);
+        let diagnostics = g.diagnostics();
+        assert_eq!(
+            vec![
+                GameEvent::Dealt,
+                GameEvent::Bet {
+                    player: Uid(10),
+                    bet: Bet::Nil
+                }
+            ],
+            diagnostics.recent_events
+        );
+        assert_eq!([13; 4], diagnostics.hand_sizes);
+        assert!(diagnostics.problems.is_empty());
+
+        for _ in 0..3 {
+            g.place_bet(Bet::Amount(3));
+        }
+        for _ in 0..40 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+        let diagnostics = g.diagnostics();
+        assert_eq!(RECENT_EVENT_COUNT, diagnostics.recent_events.len());
+        assert_eq!(40, diagnostics.played_count);
+    }
+
+    #[test]
+    fn test_events_do_not_tell_games_apart() {
+        let mut g = Game::default();
+        g.start_game();
+        let mut other = g.clone();
+        other.record_event(GameEvent::Dealt);
+        assert_eq!(g, other);
+        assert_eq!(std::cmp::Ordering::Equal, g.cmp(&other));
+    }
+
+    #[test]
+    fn test_internal_error_keeps_diagnostics() {
+        let mut g = started_game();
+        g.place_bet(Bet::Nil);
+        let last_good = g.diagnostics().last_good;
+        assert_eq!(Some(g.snapshot()), last_good);
+        assert_eq!(None, g.diagnostics().internal_error);
+
+        // a trick that was never played; a broken deck couldn't be saved and loaded again
+        g.current_trick.clear();
+        g.current_trick.push(g.player[1].hand[0]);
+        g.state = State::Paused;
+        assert_eq!(Err(SpadesError::InternalError), g.resume());
+        let diagnostics = g.diagnostics();
+        let internal_error = diagnostics.internal_error.unwrap();
+        assert_eq!(State::Paused, internal_error.state);
+        assert_eq!(last_good, internal_error.last_good);
+        assert!(!internal_error.problems.is_empty());
+        assert_eq!(None, internal_error.internal_error);
+
+        let json = serde_json::to_string(&g).unwrap();
+        let loaded: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(Some(internal_error), loaded.diagnostics().internal_error);
+    }
+
+    #[test]
+    fn test_fork_shares_history_until_it_moves_on() {
+        let mut g = started_game();
+        g.place_bet(Bet::Nil);
+        let mut fork = g.fork();
+        assert_eq!(g, fork);
+        assert_eq!(g.history(), fork.history());
+        assert!(std::ptr::eq(g.history(), fork.history()));
+        assert_eq!(None, fork.diagnostics().last_good);
+
+        fork.place_bet(Bet::Amount(3));
+        assert_ne!(g, fork);
+        assert_eq!(2, g.history().len());
+        assert_eq!(3, fork.history().len());
+        assert_eq!(None, fork.diagnostics().last_good);
+        assert!(g.diagnostics().last_good.is_some());
+    }
+
+    #[test]
+    fn test_clone_without_history_keeps_only_this_round() {
+        let mut g = started_game();
+        while g.round_scores().is_empty() {
+            if g.state().is_betting() {
+                g.place_bet(Bet::Amount(3));
+            } else {
+                let card = g.playable_cards().next().unwrap();
+                g.play_card(card);
+            }
+        }
+        g.place_bet(Bet::Nil);
+        let mut copy = g.clone_without_history();
+        assert_eq!(g, copy);
+        assert_eq!(g.last_seq(), copy.last_seq());
+        assert_eq!(Some(&GameEvent::Dealt), copy.history().first());
+        assert_eq!(2, copy.history().len());
+        assert_eq!(g.round_history()[1..], copy.round_history()[..]);
+        assert_eq!(None, copy.diagnostics().last_good);
+
+        g.place_bet(Bet::Amount(3));
+        copy.place_bet(Bet::Amount(3));
+        assert_eq!(g, copy);
+        assert_eq!(g.events_since(0).last(), copy.events_since(0).last());
+        assert_eq!(3, copy.history().len());
+    }
+
+    #[test]
+    fn test_events_are_numbered_in_sequence() {
+        let mut g = seated_game();
+        assert_eq!(0, g.last_seq());
+        assert!(g.events_since(0).is_empty());
+        g.start_game();
+        g.place_bet(Bet::Nil);
+        g.place_bet(Bet::Amount(3));
+        assert_eq!(3, g.last_seq());
+        assert_eq!(
+            vec![SequencedEvent {
+                seq: 3,
+                event: GameEvent::Bet {
+                    player: Uid(11),
+                    bet: Bet::Amount(3)
+                }
+            }],
+            g.events_since(2)
+        );
+        assert_eq!(3, g.events_since(0).len());
+        assert!(g.events_since(3).is_empty());
+        assert_eq!(Ok(1), g.player_seq(&Uid(10)));
+        assert_eq!(Ok(0), g.player_seq(&Uid(12)));
+        assert_eq!(Err(SpadesError::InvalidUuid), g.player_seq(&Uid(99)));
+
+        let json = serde_json::to_string(&g).unwrap();
+        let loaded: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(3, loaded.last_seq());
+        assert_eq!(Ok(1), loaded.player_seq(&Uid(11)));
+    }
+
+    #[test]
+    fn test_diagnostics_report_problems() {
+        let mut g = Game::default();
+        g.start_game();
+        g.player[1].hand.clear();
+        assert!(!g.diagnostics().problems.is_empty());
+    }
+}
@@ -0,0 +1,163 @@
+//! Duplicate Spades: the same deals, or boards, played at several tables, so that partnerships are compared
+//! on how they played their cards rather than on which cards they were dealt.
+
+use deal::Deal;
+use {Game, Uid};
+
+/// Tables that play the same boards in the same order, and how each board went at each of them.
+///
+/// Each table is an ordinary [`Game`](struct.Game.html), reached with
+/// [`table_mut`](#method.table_mut) and started and played as usual.  Seating the same players in other seats
+/// at another table has them play the other side's cards.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct DuplicateSession {
+    boards: Vec<Deal>,
+    tables: Vec<Game>,
+}
+
+/// How one board went at every table of a [`DuplicateSession`](struct.DuplicateSession.html).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct BoardResult {
+    pub board: usize,
+    /// The points Team 0 (North and South) and Team 1 (East and West) made on the board at each table, or
+    /// `None` where the board hasn't been played out, or won't be because the game ended first.
+    pub scores: Vec<Option<[i32; 2]>>,
+    /// At each table that played the board, how many points more Team 0 gained over Team 1 than the average
+    /// over those tables.
+    pub versus_field: Vec<Option<i32>>,
+}
+
+impl DuplicateSession {
+    pub fn new(boards: Vec<Deal>) -> DuplicateSession {
+        DuplicateSession {
+            boards,
+            tables: vec![],
+        }
+    }
+
+    /// Adds a table where these players play every board, and returns its number.
+    pub fn add_table(&mut self, id: Uid, player_ids: [Uid; 4]) -> usize {
+        let mut game = Game::default();
+        game.assign_players(id, player_ids);
+        for board in self.boards.iter() {
+            game.queue_deal(board.clone());
+        }
+        self.tables.push(game);
+        self.tables.len() - 1
+    }
+
+    pub fn boards(&self) -> &[Deal] {
+        &self.boards
+    }
+
+    pub fn table(&self, table: usize) -> Option<&Game> {
+        self.tables.get(table)
+    }
+
+    pub fn table_mut(&mut self, table: usize) -> Option<&mut Game> {
+        self.tables.get_mut(table)
+    }
+
+    /// How every board has gone so far, in the order they are played.
+    pub fn results(&self) -> Vec<BoardResult> {
+        (0..self.boards.len())
+            .map(|board| {
+                let scores: Vec<Option<[i32; 2]>> = self
+                    .tables
+                    .iter()
+                    .map(|game| game.round_scores().get(board).cloned())
+                    .collect();
+                let nets: Vec<i32> = scores.iter().flatten().map(|s| s[0] - s[1]).collect();
+                let average = if nets.is_empty() {
+                    0
+                } else {
+                    nets.iter().sum::<i32>() / nets.len() as i32
+                };
+                BoardResult {
+                    board,
+                    versus_field: scores
+                        .iter()
+                        .map(|score| score.map(|s| s[0] - s[1] - average))
+                        .collect(),
+                    scores,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DuplicateSession;
+    use agent::{run_game, run_game_with_round_limit, PlayerAgent, PlayerView};
+    use {Bet, Card, Game, Uid};
+
+    /// Bets `bet` and plays its first legal card.
+    struct Fixed(u8);
+
+    impl PlayerAgent for Fixed {
+        fn bet(&mut self, _view: &PlayerView) -> Bet {
+            Bet::Amount(self.0)
+        }
+
+        fn play_card(&mut self, view: &PlayerView) -> Card {
+            view.playable[0]
+        }
+    }
+
+    fn session() -> DuplicateSession {
+        let boards = (0..3)
+            .map(|seed| {
+                let mut g = Game::default();
+                g.set_shuffle_seed(Some(seed));
+                g.start_game();
+                g.dealt_hands().unwrap().clone()
+            })
+            .collect();
+        DuplicateSession::new(boards)
+    }
+
+    #[test]
+    fn test_tables_play_the_same_boards() {
+        let mut session = session();
+        for table in 0..2 {
+            let base = 10 * (table + 1);
+            let id = session.add_table(
+                Uid(table),
+                [Uid(base), Uid(base + 1), Uid(base + 2), Uid(base + 3)],
+            );
+            let game = session.table_mut(id).unwrap();
+            let (mut a, mut b, mut c, mut d) = (Fixed(3), Fixed(3), Fixed(3), Fixed(3));
+            run_game_with_round_limit(game, [&mut a, &mut b, &mut c, &mut d], 2).unwrap();
+        }
+
+        let results = session.results();
+        assert_eq!(3, results.len());
+        for board in results.iter().take(2) {
+            assert!(board.scores[0].is_some());
+            assert_eq!(board.scores[0], board.scores[1]);
+            assert_eq!(vec![Some(0), Some(0)], board.versus_field);
+        }
+        assert_eq!(vec![None, None], results[2].scores);
+    }
+
+    #[test]
+    fn test_results_compare_tables() {
+        let mut session = session();
+        for (table, bet) in [3, 5].iter().enumerate() {
+            let id = session.add_table(Uid(table as u64), [Uid(1), Uid(2), Uid(3), Uid(4)]);
+            let game = session.table_mut(id).unwrap();
+            let (mut a, mut c) = (Fixed(*bet), Fixed(*bet));
+            let (mut b, mut d) = (Fixed(3), Fixed(3));
+            run_game(game, [&mut a, &mut b, &mut c, &mut d]).unwrap();
+        }
+        let board = &session.results()[0];
+        let scores = [board.scores[0].unwrap(), board.scores[1].unwrap()];
+        let nets = [scores[0][0] - scores[0][1], scores[1][0] - scores[1][1]];
+        assert_ne!(nets[0], nets[1]);
+        assert_eq!(
+            Some(nets[0] - (nets[0] + nets[1]) / 2),
+            board.versus_field[0]
+        );
+    }
+}
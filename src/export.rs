@@ -0,0 +1,397 @@
+//! Game statistics as CSV or JSON, for pulling results into a spreadsheet.
+//!
+//! [`RoundSummary`](struct.RoundSummary.html), [`PlayerStats`](struct.PlayerStats.html) and
+//! [`GameResult`](../struct.GameResult.html) each flatten into a fixed list of
+//! [`columns`](trait.Record.html#tymethod.columns), the same for every game, so exports from many games can be
+//! appended to one another.
+//!
+//! ```
+//! use spades::export::{to_csv, PlayerStats};
+//! use spades::{Game, Uid};
+//!
+//! let mut g = Game::default();
+//! g.assign_players(Uid(1), [Uid(10), Uid(11), Uid(12), Uid(13)]);
+//! g.start_game();
+//! let csv = to_csv(&g.player_stats());
+//! assert!(csv.starts_with("game,player,seat,team,"));
+//! assert_eq!(5, csv.lines().count());
+//! ```
+
+extern crate serde_json;
+
+use self::serde_json::{Map, Value};
+use game_result::GameResult;
+use lifecycle::PrematureEnd;
+use scoring::Bet;
+use std::time::Duration;
+use {Game, Uid};
+
+/// Something exported as one row of a CSV file or one object of a JSON array.
+pub trait Record {
+    /// The column names, in order.  They don't change from one record to the next.
+    fn columns() -> Vec<String>;
+
+    /// The record's value in each column, in the order of [`columns`](#tymethod.columns).  `Value::Null` is
+    /// an empty cell.
+    fn values(&self) -> Vec<Value>;
+}
+
+/// One finished round of a game.  Seats are numbered 0 to 3, and Team 0 is seats 0 and 2.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct RoundSummary {
+    pub game: Uid,
+    /// The round's number, counting from 1.
+    pub round: usize,
+    pub bets: [Bet; 4],
+    /// Tricks each seat took.
+    pub tricks: [u8; 4],
+    /// Points each team scored this round, including any bag penalty.
+    pub points: [i32; 2],
+    /// Bags each team took this round.
+    pub bags: [u8; 2],
+    /// Each team's score after the round.
+    pub scores: [i32; 2],
+}
+
+/// How one player did over a game.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct PlayerStats {
+    pub game: Uid,
+    pub player: Uid,
+    pub seat: usize,
+    pub team: usize,
+    /// Rounds the player finished.
+    pub rounds: usize,
+    /// Tricks bet over those rounds, nil bets counting as none.
+    pub tricks_bet: u32,
+    pub tricks_taken: u32,
+    /// Nil and blind nil bets.
+    pub nil_bets: u32,
+    /// Nil and blind nil bets that took no tricks.
+    pub nils_made: u32,
+    /// Actions taken, counted as by [`Game::player_seq`](../struct.Game.html#method.player_seq).
+    pub actions: u64,
+    pub think_time: Duration,
+}
+
+impl Game {
+    /// The rounds finished since the last [checkpoint](#method.checkpoint), oldest first.
+    pub fn round_summaries(&self) -> Vec<RoundSummary> {
+        let finished: Vec<_> = self
+            .round_history()
+            .into_iter()
+            .filter_map(|round| {
+                let (tricks, scores) = round.result?;
+                Some((round.bets, tricks, scores))
+            })
+            .collect();
+        let scores = self.score_history();
+        // rounds before a checkpoint are only counted in the scores
+        let skipped = scores.len().saturating_sub(finished.len());
+        finished
+            .into_iter()
+            .enumerate()
+            .map(|(i, (bets, tricks, round_scores))| RoundSummary {
+                game: *self.id(),
+                round: skipped + i + 1,
+                bets: bets.map(|bet| bet.unwrap_or_default()),
+                tricks,
+                points: [round_scores[0].points, round_scores[1].points],
+                bags: [round_scores[0].bags, round_scores[1].bags],
+                scores: scores.get(skipped + i).cloned().unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// How each player has done so far, in seat order, over the rounds in
+    /// [`round_summaries`](#method.round_summaries).
+    pub fn player_stats(&self) -> [PlayerStats; 4] {
+        let rounds = self.round_summaries();
+        let result = self.game_result();
+        [0, 1, 2, 3].map(|seat| {
+            let mut stats = PlayerStats {
+                game: *self.id(),
+                player: self.player[seat].id,
+                seat,
+                team: seat % 2,
+                rounds: rounds.len(),
+                tricks_bet: 0,
+                tricks_taken: 0,
+                nil_bets: 0,
+                nils_made: 0,
+                actions: result.player_actions[seat],
+                think_time: result.think_time[seat],
+            };
+            for round in &rounds {
+                let (bet, tricks) = (round.bets[seat], round.tricks[seat]);
+                stats.tricks_bet += bet.tricks_required() as u32;
+                stats.tricks_taken += tricks as u32;
+                if bet.is_nil() {
+                    stats.nil_bets += 1;
+                    if tricks == 0 {
+                        stats.nils_made += 1;
+                    }
+                }
+            }
+            stats
+        })
+    }
+}
+
+fn per<T: Into<Value> + Copy>(values: &[T]) -> impl Iterator<Item = Value> + '_ {
+    values.iter().map(|value| (*value).into())
+}
+
+fn numbered<'a>(
+    prefix: &'a str,
+    count: usize,
+    suffix: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    (0..count).map(move |i| format!("{}{}_{}", prefix, i, suffix))
+}
+
+/// A bet as a cell: the number of tricks for an amount, otherwise its name in snake case.
+fn bet_value(bet: Bet) -> Value {
+    match bet {
+        Bet::Amount(tricks) => tricks.into(),
+        Bet::Nil => "nil".into(),
+        Bet::BlindNil => "blind_nil".into(),
+        Bet::Board => "board".into(),
+        Bet::TenForTwoHundred => "ten_for_two_hundred".into(),
+    }
+}
+
+fn millis(duration: Duration) -> Value {
+    (duration.as_millis() as u64).into()
+}
+
+impl Record for RoundSummary {
+    fn columns() -> Vec<String> {
+        let mut columns = vec!["game".to_string(), "round".to_string()];
+        columns.extend(numbered("seat", 4, "bet"));
+        columns.extend(numbered("seat", 4, "tricks"));
+        columns.extend(numbered("team", 2, "points"));
+        columns.extend(numbered("team", 2, "bags"));
+        columns.extend(numbered("team", 2, "score"));
+        columns
+    }
+
+    fn values(&self) -> Vec<Value> {
+        let mut values = vec![self.game.0.into(), self.round.into()];
+        values.extend(self.bets.iter().map(|bet| bet_value(*bet)));
+        values.extend(per(&self.tricks));
+        values.extend(per(&self.points));
+        values.extend(per(&self.bags));
+        values.extend(per(&self.scores));
+        values
+    }
+}
+
+impl Record for PlayerStats {
+    fn columns() -> Vec<String> {
+        [
+            "game",
+            "player",
+            "seat",
+            "team",
+            "rounds",
+            "tricks_bet",
+            "tricks_taken",
+            "nil_bets",
+            "nils_made",
+            "actions",
+            "think_time_ms",
+        ]
+        .iter()
+        .map(|column| column.to_string())
+        .collect()
+    }
+
+    fn values(&self) -> Vec<Value> {
+        vec![
+            self.game.0.into(),
+            self.player.0.into(),
+            self.seat.into(),
+            self.team.into(),
+            self.rounds.into(),
+            self.tricks_bet.into(),
+            self.tricks_taken.into(),
+            self.nil_bets.into(),
+            self.nils_made.into(),
+            self.actions.into(),
+            millis(self.think_time),
+        ]
+    }
+}
+
+/// The per-round score history is left out; export the game's
+/// [`round_summaries`](../struct.Game.html#method.round_summaries) for that.
+impl Record for GameResult {
+    fn columns() -> Vec<String> {
+        let mut columns: Vec<String> = ["winner_0", "winner_1", "winning_team"]
+            .iter()
+            .map(|column| column.to_string())
+            .collect();
+        columns.extend(numbered("team", 2, "score"));
+        columns.extend(numbered("team", 2, "total_bags"));
+        columns.extend(
+            ["ended", "forfeiting_team", "rounds_played", "actions"]
+                .iter()
+                .map(|column| column.to_string()),
+        );
+        columns.extend(numbered("seat", 4, "actions"));
+        columns.extend(numbered("seat", 4, "think_time_ms"));
+        columns
+    }
+
+    fn values(&self) -> Vec<Value> {
+        let (ended, forfeiting_team) = match self.premature_end {
+            None => ("played_out", Value::Null),
+            Some(PrematureEnd::Forfeit { team }) => ("forfeit", team.into()),
+            Some(PrematureEnd::Abandoned(_)) => ("abandoned", Value::Null),
+        };
+        let mut values = vec![
+            self.winners
+                .map_or(Value::Null, |(first, _)| first.0.into()),
+            self.winners
+                .map_or(Value::Null, |(_, second)| second.0.into()),
+            self.winning_team.map_or(Value::Null, |team| team.0.into()),
+        ];
+        values.extend(per(&self.scores));
+        values.extend(per(&self.total_bags));
+        values.extend(vec![
+            ended.into(),
+            forfeiting_team,
+            self.rounds_played.into(),
+            self.actions.into(),
+        ]);
+        values.extend(per(&self.player_actions));
+        values.extend(self.think_time.iter().map(|time| millis(*time)));
+        values
+    }
+}
+
+/// The records as CSV: a header line of their columns, then a line for each record.
+pub fn to_csv<R: Record>(records: &[R]) -> String {
+    let mut out = csv_line(R::columns().into_iter().map(Value::String));
+    for record in records {
+        out += &csv_line(record.values().into_iter());
+    }
+    out
+}
+
+fn csv_line<I: Iterator<Item = Value>>(values: I) -> String {
+    let cells: Vec<String> = values
+        .map(|value| match value {
+            Value::Null => String::new(),
+            Value::String(text) => {
+                if text.contains([',', '"', '\n']) {
+                    format!("\"{}\"", text.replace('"', "\"\""))
+                } else {
+                    text
+                }
+            }
+            other => other.to_string(),
+        })
+        .collect();
+    cells.join(",") + "\n"
+}
+
+/// The records as a JSON array of objects, each with a field for every column.
+pub fn to_json<R: Record>(records: &[R]) -> String {
+    let objects: Vec<Value> = records
+        .iter()
+        .map(|record| {
+            let fields: Map<String, Value> =
+                R::columns().into_iter().zip(record.values()).collect();
+            Value::Object(fields)
+        })
+        .collect();
+    Value::Array(objects).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::serde_json::{self, Value};
+    use super::{to_csv, to_json, Record, RoundSummary};
+    use test_support::seeded_game;
+    use {Bet, Game, GameResult, Uid};
+
+    fn finished_game() -> Game {
+        let mut g = seeded_game(3);
+        while !g.is_over() {
+            g.place_bet(Bet::Nil);
+            for _ in 0..3 {
+                g.place_bet(Bet::Amount(3));
+            }
+            for _ in 0..52 {
+                let card = g.playable_cards().next().unwrap();
+                g.play_card(card);
+            }
+        }
+        g
+    }
+
+    #[test]
+    fn test_round_summaries_and_player_stats() {
+        let g = finished_game();
+        let rounds = g.round_summaries();
+        assert_eq!(g.result().unwrap().rounds_played, rounds.len());
+        assert_eq!(1, rounds[0].round);
+        assert_eq!(Bet::Nil, rounds[0].bets[0]);
+        assert_eq!(
+            g.score_history(),
+            rounds.iter().map(|r| r.scores).collect::<Vec<_>>()
+        );
+
+        let stats = g.player_stats();
+        assert_eq!(Uid(11), stats[1].player);
+        assert_eq!(rounds.len() as u32, stats[0].nil_bets);
+        assert_eq!(0, stats[0].tricks_bet);
+        assert_eq!(3 * rounds.len() as u32, stats[2].tricks_bet);
+        let taken: u32 = stats.iter().map(|s| s.tricks_taken).sum();
+        assert_eq!(13 * rounds.len() as u32, taken);
+    }
+
+    #[test]
+    fn test_csv_has_stable_columns() {
+        let g = finished_game();
+        let csv = to_csv(&g.round_summaries());
+        let mut lines = csv.lines();
+        assert_eq!(
+            "game,round,seat0_bet,seat1_bet,seat2_bet,seat3_bet,seat0_tricks,seat1_tricks,seat2_tricks,\
+             seat3_tricks,team0_points,team1_points,team0_bags,team1_bags,team0_score,team1_score",
+            lines.next().unwrap()
+        );
+        assert!(lines.next().unwrap().starts_with("1,1,nil,3,3,3,"));
+        for line in csv.lines() {
+            assert_eq!(RoundSummary::columns().len(), line.split(',').count());
+        }
+
+        let result = to_csv(&[g.result().unwrap()]);
+        let row: Vec<&str> = result.lines().nth(1).unwrap().split(',').collect();
+        assert_eq!(GameResult::columns().len(), row.len());
+        assert_eq!(
+            "played_out",
+            row[GameResult::columns()
+                .iter()
+                .position(|c| c == "ended")
+                .unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_json_matches_columns() {
+        let g = finished_game();
+        let json: Value = serde_json::from_str(&to_json(&g.player_stats())).unwrap();
+        let players = json.as_array().unwrap();
+        assert_eq!(4, players.len());
+        assert_eq!(Some(12), players[2]["player"].as_u64());
+        assert_eq!(Some(0), players[2]["team"].as_u64());
+        assert_eq!(11, players[0].as_object().unwrap().len());
+
+        let json: Value = serde_json::from_str(&to_json(&g.round_summaries())).unwrap();
+        assert_eq!(Some("nil"), json[0]["seat0_bet"].as_str());
+        assert_eq!(Some(3), json[0]["seat1_bet"].as_u64());
+    }
+}
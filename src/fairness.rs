@@ -0,0 +1,222 @@
+//! Committed deals, so players can check the cards weren't stacked once the deal was made.
+//!
+//! With [`GameOptions::audit_deals`](../struct.GameOptions.html#structfield.audit_deals) on, every deal is
+//! followed by a [`GameEvent::DealCommitted`](../enum.GameEvent.html#variant.DealCommitted) event carrying a
+//! hash of the dealt deck and a secret salt.  Once the round is over, or a misdeal declared, a
+//! [`GameEvent::DealRevealed`](../enum.GameEvent.html#variant.DealRevealed) event gives away the deck and the
+//! salt, and anyone can [`verify`](fn.verify.html) that they hash to the commitment and hold the hands that
+//! were played.
+
+extern crate rand;
+extern crate sha2;
+
+use self::rand::{thread_rng, Rng};
+use self::sha2::{Digest, Sha256};
+use cards::{seeded_rng, Card};
+use diagnostics::GameEvent;
+use seat::Seat;
+use std::collections::BTreeSet;
+use std::fmt;
+use Game;
+
+/// The SHA-256 hash of a salted deck, published when it is dealt.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct DealCommitment(pub [u8; 32]);
+
+impl fmt::Display for DealCommitment {
+    /// The hash in lowercase hex.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in self.0.iter() {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// A dealt deck and the salt it was committed with, published once its round is over.
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct DealReveal {
+    /// North's hand, then East's, South's and West's, then the kitty.
+    pub deck: Vec<Card>,
+    pub kitty_size: usize,
+    pub salt: [u8; 32],
+}
+
+impl DealReveal {
+    /// The hand dealt to `seat`.
+    pub fn hand(&self, seat: Seat) -> &[Card] {
+        let size = self.deck.len().saturating_sub(self.kitty_size) / 4;
+        &self.deck[seat.index() * size..(seat.index() + 1) * size]
+    }
+
+    pub fn kitty(&self) -> &[Card] {
+        &self.deck[self.deck.len().saturating_sub(self.kitty_size)..]
+    }
+}
+
+/// The commitment to dealing `deck`, salted with `salt`: the SHA-256 hash of the salt followed by each card's
+/// suit and rank as a byte each.
+pub fn commit(deck: &[Card], salt: &[u8; 32]) -> DealCommitment {
+    let mut data = salt.to_vec();
+    for card in deck {
+        data.push(card.suit as u8);
+        data.push(card.rank as u8);
+    }
+    DealCommitment(sha256(&data))
+}
+
+/// Whether `reveal` is a whole deck, dealt into equal hands, that hashes to `commitment`.
+pub fn verify(commitment: &DealCommitment, reveal: &DealReveal) -> bool {
+    let cards: BTreeSet<&Card> = reveal.deck.iter().collect();
+    cards.len() == 52
+        && reveal.deck.len() == 52
+        && reveal.kitty_size <= 52
+        && (52 - reveal.kitty_size).is_multiple_of(4)
+        && commit(&reveal.deck, &reveal.salt) == *commitment
+}
+
+impl Game {
+    /// The commitment to this round's deal, if [deals are audited](struct.GameOptions.html#structfield.audit_deals).
+    pub fn deal_commitment(&self) -> Option<DealCommitment> {
+        self.deal_audit
+            .as_ref()
+            .map(|reveal| commit(&reveal.deck, &reveal.salt))
+    }
+
+    /// The latest deal revealed, once its round is over or a misdeal was declared.
+    pub fn deal_reveal(&self) -> Option<&DealReveal> {
+        self.revealed_deal.as_ref()
+    }
+
+    /// Salts and commits to the deal just made, if deals are audited.
+    pub(crate) fn commit_deal(&mut self) {
+        if !self.options.audit_deals {
+            self.deal_audit = None;
+            return;
+        }
+        let mut deck: Vec<Card> = self
+            .player
+            .iter()
+            .flat_map(|player| player.hand.clone())
+            .collect();
+        deck.extend(self.kitty.iter().cloned());
+        let mut salt = [0; 32];
+        match self.shuffle_seed {
+            // so that a seeded game replays the same, see `Game::replay`
+            Some(seed) => {
+                let round = self.scoring.rounds_played() as u64;
+                let redeal = self.misdeals_this_round() << 32;
+                seeded_rng(!seed.wrapping_add(round).wrapping_add(redeal)).fill(&mut salt)
+            }
+            None => thread_rng().fill(&mut salt),
+        }
+        let reveal = DealReveal {
+            deck,
+            kitty_size: self.kitty.len(),
+            salt,
+        };
+        self.record_event(GameEvent::DealCommitted(commit(&reveal.deck, &reveal.salt)));
+        self.deal_audit = Some(reveal);
+    }
+
+    /// Reveals the deal of the round just over, if it was committed to.
+    pub(crate) fn reveal_deal(&mut self) {
+        if let Some(reveal) = self.deal_audit.take() {
+            self.record_event(GameEvent::DealRevealed(reveal.clone()));
+            self.revealed_deal = Some(reveal);
+        }
+    }
+}
+
+/// The SHA-256 hash of `data`.
+fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{commit, sha256, verify, DealCommitment};
+    use test_support::{seated_game, started_game};
+    use {Bet, Game, GameEvent, GameOptions, Seat, Uid};
+
+    fn hex(digest: [u8; 32]) -> String {
+        DealCommitment(digest).to_string()
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            hex(sha256(b""))
+        );
+        assert_eq!(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            hex(sha256(b"abc"))
+        );
+        assert_eq!(
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+            hex(sha256(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            ))
+        );
+    }
+
+    fn audited_game() -> Game {
+        let mut g = seated_game();
+        g.set_options(GameOptions {
+            audit_deals: true,
+            ..GameOptions::default()
+        })
+        .unwrap();
+        g.set_shuffle_seed(Some(6));
+        g.start_game();
+        g
+    }
+
+    #[test]
+    fn test_deals_are_committed_and_revealed() {
+        let mut g = audited_game();
+        let commitment = g.deal_commitment().unwrap();
+        assert!(g.history().contains(&GameEvent::DealCommitted(commitment)));
+        assert_eq!(None, g.deal_reveal());
+        let hands: Vec<Vec<_>> = (10..14)
+            .map(|id| g.hand_from_player_id(Uid(id)).unwrap().to_vec())
+            .collect();
+
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        for _ in 0..52 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+        let reveal = g.deal_reveal().unwrap().clone();
+        assert!(verify(&commitment, &reveal));
+        assert_eq!(hands[0], reveal.hand(Seat::North));
+        assert_eq!(hands[3], reveal.hand(Seat::West));
+        assert!(reveal.kitty().is_empty());
+        assert!(g
+            .history()
+            .contains(&GameEvent::DealRevealed(reveal.clone())));
+        assert_ne!(Some(commitment), g.deal_commitment());
+
+        let mut stacked = reveal.clone();
+        stacked.deck.swap(0, 51);
+        assert!(!verify(&commitment, &stacked));
+        assert_ne!(commitment, commit(&reveal.deck, &[0; 32]));
+    }
+
+    #[test]
+    fn test_unaudited_deals_are_not_committed() {
+        let g = started_game();
+        assert_eq!(None, g.deal_commitment());
+        assert!(!g
+            .history()
+            .iter()
+            .any(|event| matches!(event, GameEvent::DealCommitted(_))));
+    }
+}
@@ -0,0 +1,130 @@
+use diagnostics::GameEvent;
+use game_state::State;
+use lifecycle::PrematureEnd;
+use result::SpadesError;
+use std::time::Duration;
+use {Game, Uid};
+
+/// How a game turned out, as returned by [`Game::result`](struct.Game.html#method.result) and
+/// [`run_game`](fn.run_game.html).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct GameResult {
+    /// The players on the winning team, or `None` if the game ended without a winner.
+    pub winners: Option<(Uid, Uid)>,
+    /// The winning team, if the game has a winner and [named teams](struct.TeamAssignment.html).
+    pub winning_team: Option<Uid>,
+    /// Cumulative score of Team 0 (players 0 and 2) and Team 1 (players 1 and 3).
+    pub scores: [i32; 2],
+    /// Set if the game was forfeited or abandoned rather than played out.
+    pub premature_end: Option<PrematureEnd>,
+    /// Rounds played to the end.
+    pub rounds_played: usize,
+    /// Every bag each team took over the game, including those that were later penalized.
+    pub total_bags: [u32; 2],
+    /// Each team's cumulative score after each round, oldest first.
+    pub score_history: Vec<[i32; 2]>,
+    /// Bets, discards and cards played over the game.
+    pub actions: usize,
+    /// Actions each player took, in seat order, counted as by
+    /// [`Game::player_seq`](struct.Game.html#method.player_seq).
+    pub player_actions: [u64; 4],
+    /// The time each player took over the game, in seat order, counted as by
+    /// [`Game::think_time`](struct.Game.html#method.think_time).
+    pub think_time: [Duration; 4],
+}
+
+impl Game {
+    /// How the game turned out, once it is over or has been abandoned.  Returns
+    /// `SpadesError::GameNotCompleted` before then.
+    pub fn result(&self) -> Result<GameResult, SpadesError> {
+        match self.state {
+            State::GameCompleted | State::Abandoned => Ok(self.game_result()),
+            _ => Err(SpadesError::GameNotCompleted),
+        }
+    }
+
+    pub(crate) fn game_result(&self) -> GameResult {
+        let mut total_bags = [0, 0];
+        for scores in self.scoring.round_scores() {
+            total_bags[0] += scores[0].bags as u32;
+            total_bags[1] += scores[1].bags as u32;
+        }
+        GameResult {
+            winners: self.winner_ids().ok(),
+            winning_team: self.winning_team_id().ok().flatten(),
+            scores: [
+                self.scoring.team[0].cumulative_points(),
+                self.scoring.team[1].cumulative_points(),
+            ],
+            premature_end: self.premature_end(),
+            rounds_played: self.scoring.round_scores().len(),
+            total_bags,
+            score_history: self.score_history(),
+            actions: self.event_log.compacted_actions
+                + self
+                    .history()
+                    .iter()
+                    .filter(|event| {
+                        matches!(
+                            event,
+                            GameEvent::Bet { .. }
+                                | GameEvent::Discarded { .. }
+                                | GameEvent::CardPlayed { .. }
+                        )
+                    })
+                    .count(),
+            player_actions: [0, 1, 2, 3].map(|index| {
+                self.event_log
+                    .player_seqs
+                    .get(&self.player[index].id)
+                    .cloned()
+                    .unwrap_or(0)
+            }),
+            think_time: self.think_time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_support::{seeded_game, started_game};
+    use {AbandonReason, Bet, SpadesError};
+
+    #[test]
+    fn test_result_once_game_is_over() {
+        let mut g = seeded_game(3);
+        assert_eq!(Err(SpadesError::GameNotCompleted), g.result());
+        let mut rounds = 0;
+        while !g.is_over() {
+            for _ in 0..4 {
+                g.place_bet(Bet::Amount(3));
+            }
+            for _ in 0..52 {
+                let card = g.playable_cards().next().unwrap();
+                g.play_card(card);
+            }
+            rounds += 1;
+        }
+
+        let result = g.result().unwrap();
+        assert_eq!(g.winner_ids().ok(), result.winners);
+        assert_eq!(rounds, result.rounds_played);
+        assert_eq!(rounds * (4 + 52), result.actions);
+        assert_eq!(Some(&result.scores), result.score_history.last());
+        for team in 0..2 {
+            assert!(result.total_bags[team] >= g.team_all_rounds_bags(team).unwrap() as u32);
+        }
+    }
+
+    #[test]
+    fn test_result_of_abandoned_game() {
+        let mut g = started_game();
+        g.place_bet(Bet::Nil);
+        g.abandon(AbandonReason::Agreed).unwrap();
+        let result = g.result().unwrap();
+        assert_eq!(None, result.winners);
+        assert_eq!(0, result.rounds_played);
+        assert_eq!(1, result.actions);
+        assert!(result.score_history.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt::Display;
+use {Game, Uid};
 
 /// Current game stage, field of `Game`.
 ///
@@ -7,13 +8,66 @@ use std::fmt::Display;
 /// respectively.
 ///
 /// **Example:** `State::Trick(2)` means the game is in the card playing stage, and two players have played their cards.
-#[derive(Debug, Default, PartialEq, Clone, Copy, Eq, PartialOrd, Ord, Hash)]
+///
+/// See also [`DetailedState`](enum.DetailedState.html), which names the player expected to act.
+///
+/// A game in progress can be `Paused` and resumed later, or `Abandoned`, which ends it without a winner.
+#[derive(
+    Debug,
+    Default,
+    PartialEq,
+    Clone,
+    Copy,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 pub enum State {
     #[default]
     GameNotStarted,
     Betting(usize),
     Trick(usize),
+    /// The player who picked up the kitty is to discard, see [`KittyConfig`](struct.KittyConfig.html).
+    Exchange,
+    /// Everyone has bet, and the last bidder's partner may amend their bet once before play begins, see
+    /// [`GameOptions::bid_review`](struct.GameOptions.html#structfield.bid_review).
+    BidReview,
     GameCompleted,
+    Paused,
+    Abandoned,
+}
+
+impl State {
+    /// Whether a bet is awaited, see [`Betting`](#variant.Betting).
+    pub fn is_betting(&self) -> bool {
+        matches!(self, State::Betting(_))
+    }
+
+    /// Whether a card is awaited, see [`Trick`](#variant.Trick).
+    pub fn is_trick(&self) -> bool {
+        matches!(self, State::Trick(_))
+    }
+
+    /// Whether the game is under way with some player expected to act: betting, exchanging, reviewing bids or
+    /// playing a trick.
+    pub fn is_playing(&self) -> bool {
+        matches!(
+            self,
+            State::Betting(_) | State::Trick(_) | State::Exchange | State::BidReview
+        )
+    }
+
+    /// How many players have already bet or played to the trick, counting on from the first to act, in the
+    /// `Betting` and `Trick` states.
+    pub fn acting_player_offset(&self) -> Option<usize> {
+        match *self {
+            State::Betting(offset) | State::Trick(offset) => Some(offset),
+            _ => None,
+        }
+    }
 }
 
 impl Display for State {
@@ -21,3 +75,142 @@ impl Display for State {
         write!(f, "{:?}", self)
     }
 }
+
+/// Like [`State`](enum.State.html), but naming the player expected to act, as returned by
+/// [`Game::detailed_state`](struct.Game.html#method.detailed_state).
+#[derive(
+    Debug,
+    Default,
+    PartialEq,
+    Clone,
+    Copy,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum DetailedState {
+    #[default]
+    GameNotStarted,
+    /// `player` is to bet, after `bets_placed` players have already bet this round.
+    Betting {
+        player: Uid,
+        bets_placed: usize,
+    },
+    /// `player` is to play a card, after `cards_played` cards have already been played to the trick.
+    Trick {
+        player: Uid,
+        cards_played: usize,
+    },
+    /// `player` picked up the kitty and is to discard `discards` cards.
+    Exchange {
+        player: Uid,
+        discards: usize,
+    },
+    /// `player` may amend their bet before play begins.
+    BidReview {
+        player: Uid,
+    },
+    GameCompleted,
+    Paused,
+    Abandoned,
+}
+
+impl Display for DetailedState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Game {
+    /// The current [`DetailedState`](enum.DetailedState.html) of the game.
+    pub fn detailed_state(&self) -> DetailedState {
+        let player = self.player[self.current_player_index].id;
+        match self.state {
+            State::GameNotStarted => DetailedState::GameNotStarted,
+            State::Betting(bets_placed) => DetailedState::Betting {
+                player,
+                bets_placed,
+            },
+            State::Trick(cards_played) => DetailedState::Trick {
+                player,
+                cards_played,
+            },
+            State::Exchange => DetailedState::Exchange {
+                player,
+                discards: self.kitty_discards(),
+            },
+            State::BidReview => DetailedState::BidReview { player },
+            State::GameCompleted => DetailedState::GameCompleted,
+            State::Paused => DetailedState::Paused,
+            State::Abandoned => DetailedState::Abandoned,
+        }
+    }
+
+    /// The seat index of the player expected to act, or `None` while no one is, as when the game is not
+    /// under way.
+    pub fn acting_index(&self) -> Option<usize> {
+        self.current_player_id()
+            .ok()
+            .map(|_| self.current_player_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DetailedState;
+    use test_support::seated_game;
+    use {Bet, State, Uid};
+
+    #[test]
+    fn test_state_predicates() {
+        assert!(State::Betting(2).is_betting());
+        assert!(!State::Betting(2).is_trick());
+        assert!(State::Trick(0).is_trick());
+        assert!(State::Exchange.is_playing());
+        assert!(!State::Paused.is_playing());
+        assert!(!State::GameCompleted.is_playing());
+        assert_eq!(Some(3), State::Trick(3).acting_player_offset());
+        assert_eq!(None, State::BidReview.acting_player_offset());
+    }
+
+    #[test]
+    fn test_acting_index() {
+        let mut g = seated_game();
+        assert_eq!(None, g.acting_index());
+        g.start_game();
+        assert_eq!(Some(0), g.acting_index());
+        g.place_bet(Bet::Amount(3));
+        assert_eq!(Some(1), g.acting_index());
+        assert_eq!(Some(1), g.state().acting_player_offset());
+    }
+
+    #[test]
+    fn test_detailed_state_names_acting_player() {
+        let mut g = seated_game();
+        assert_eq!(DetailedState::GameNotStarted, g.detailed_state());
+        g.start_game();
+        g.place_bet(Bet::Amount(3));
+        assert_eq!(
+            DetailedState::Betting {
+                player: Uid(11),
+                bets_placed: 1
+            },
+            g.detailed_state()
+        );
+        for _ in 0..3 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let card = g.playable_cards().next().unwrap();
+        g.play_card(card);
+        assert_eq!(
+            DetailedState::Trick {
+                player: Uid(11),
+                cards_played: 1
+            },
+            g.detailed_state()
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use cards::{winning_position, Card};
+use claim::Claim;
+use diagnostics::GameEvent;
+use scoring::{Bet, TeamRoundScore};
+use seat::Seat;
+use Game;
+
+/// One trick of a [`RoundHistory`](struct.RoundHistory.html).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct TrickHistory {
+    pub leader: Seat,
+    /// In the order they were played, starting with the leader's.
+    pub cards: Vec<Card>,
+    pub winner: Seat,
+}
+
+/// One card of a trick, as replayed by [`TrickHistory::replay`](struct.TrickHistory.html#method.replay).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct TrickPlay {
+    pub seat: Seat,
+    pub card: Card,
+    /// The card was winning the trick once it had been played.
+    pub winning: bool,
+}
+
+impl TrickHistory {
+    /// The trick card by card, marking each card that took the lead as it was played.
+    pub fn replay(&self) -> Vec<TrickPlay> {
+        self.plays().collect()
+    }
+
+    /// Iterates over the trick as its [`replay`](#method.replay) does, without collecting it.
+    pub fn plays(&self) -> TrickPlays {
+        TrickPlays {
+            leader: self.leader,
+            cards: self.cards.clone(),
+            position: 0,
+        }
+    }
+}
+
+impl IntoIterator for TrickHistory {
+    type Item = TrickPlay;
+    type IntoIter = TrickPlays;
+
+    fn into_iter(self) -> TrickPlays {
+        TrickPlays {
+            leader: self.leader,
+            cards: self.cards,
+            position: 0,
+        }
+    }
+}
+
+impl IntoIterator for &TrickHistory {
+    type Item = TrickPlay;
+    type IntoIter = TrickPlays;
+
+    fn into_iter(self) -> TrickPlays {
+        self.plays()
+    }
+}
+
+/// The plays of a trick, returned by [`TrickHistory::plays`](struct.TrickHistory.html#method.plays).
+#[derive(Debug, Clone)]
+pub struct TrickPlays {
+    leader: Seat,
+    cards: Vec<Card>,
+    position: usize,
+}
+
+impl Iterator for TrickPlays {
+    type Item = TrickPlay;
+
+    fn next(&mut self) -> Option<TrickPlay> {
+        let play = trick_play(self.leader, &self.cards, self.position)?;
+        self.position += 1;
+        Some(play)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.cards.len() - self.position;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for TrickPlays {}
+
+/// The play at `position` of `cards`, a trick led by `leader` which may not be whole yet.
+pub(crate) fn trick_play(leader: Seat, cards: &[Card], position: usize) -> Option<TrickPlay> {
+    let card = *cards.get(position)?;
+    Some(TrickPlay {
+        seat: Seat::from_index((leader.index() + position) % 4),
+        card,
+        winning: winning_position(&cards[..=position]) == position,
+    })
+}
+
+/// One round of a game, as told by its [`history`](struct.Game.html#method.history).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct RoundHistory {
+    /// Each seat's bet, or `None` where betting hadn't got that far.
+    pub bets: [Option<Bet>; 4],
+    /// The tricks played out, leaving out a trick still being played and any tricks awarded by a claim.
+    pub tricks: Vec<TrickHistory>,
+    /// The claim that ended the round early, if one did.
+    pub claim: Option<Claim>,
+    /// The tricks each seat took and what each team scored, once the round is over.
+    pub result: Option<([u8; 4], [TeamRoundScore; 2])>,
+}
+
+impl RoundHistory {
+    /// Iterates over the tricks played out in the round.
+    pub fn tricks(&self) -> std::slice::Iter<'_, TrickHistory> {
+        self.tricks.iter()
+    }
+}
+
+impl IntoIterator for RoundHistory {
+    type Item = TrickHistory;
+    type IntoIter = std::vec::IntoIter<TrickHistory>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tricks.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a RoundHistory {
+    type Item = &'a TrickHistory;
+    type IntoIter = std::slice::Iter<'a, TrickHistory>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tricks.iter()
+    }
+}
+
+impl Game {
+    /// Everything that has happened in the game since the last [checkpoint](#method.checkpoint), oldest
+    /// first.
+    pub fn history(&self) -> &[GameEvent] {
+        &self.event_log.events
+    }
+
+    /// The game's history, round by round, from the last [checkpoint](#method.checkpoint) on.
+    pub fn round_history(&self) -> Vec<RoundHistory> {
+        let mut rounds: Vec<RoundHistory> = vec![];
+        let mut trick: Vec<(Seat, Card)> = vec![];
+        for event in self.history() {
+            match event {
+                GameEvent::Dealt => {
+                    rounds.push(RoundHistory::default());
+                    trick.clear();
+                    continue;
+                }
+                // the round is dealt again from the start
+                GameEvent::Misdeal { .. } => {
+                    rounds.pop();
+                    continue;
+                }
+                _ => {}
+            }
+            let round = match rounds.last_mut() {
+                Some(round) => round,
+                None => continue,
+            };
+            match event {
+                GameEvent::Bet { player, bet } => {
+                    if let Ok(seat) = self.seat_of(player) {
+                        round.bets[seat.index()] = Some(*bet);
+                    }
+                }
+                GameEvent::CardPlayed { player, card } => {
+                    if let Ok(seat) = self.seat_of(player) {
+                        trick.push((seat, *card));
+                    }
+                    if trick.len() == 4 {
+                        let cards: Vec<Card> = trick.iter().map(|(_, card)| *card).collect();
+                        let leader = trick[0].0;
+                        round.tricks.push(TrickHistory {
+                            leader,
+                            winner: Seat::from_index(
+                                (leader.index() + winning_position(&cards)) % 4,
+                            ),
+                            cards,
+                        });
+                        trick.clear();
+                    }
+                }
+                GameEvent::BetAmended { player, to, .. } => {
+                    if let Ok(seat) = self.seat_of(player) {
+                        round.bets[seat.index()] = Some(*to);
+                    }
+                }
+                GameEvent::ClaimAccepted(claim) => round.claim = Some(*claim),
+                GameEvent::RoundScored { tricks, scores } => {
+                    round.result = Some((*tricks, *scores))
+                }
+                GameEvent::Dealt
+                | GameEvent::Misdeal { .. }
+                | GameEvent::Discarded { .. }
+                | GameEvent::PlayerInfoChanged { .. }
+                | GameEvent::RevokePenalized { .. }
+                | GameEvent::OptionsAmended { .. }
+                | GameEvent::ScoreAdjusted(_)
+                | GameEvent::BagsCarried(_)
+                | GameEvent::DealCommitted(_)
+                | GameEvent::DealRevealed(_)
+                | GameEvent::ShuffleSeed(_) => {}
+            }
+        }
+        rounds
+    }
+
+    /// Iterates over the game's [`round_history`](#method.round_history).  Rounds iterate over their tricks and
+    /// tricks over their plays, so every card played since the last checkpoint is
+    /// `game.rounds().flatten().flat_map(|trick| trick.plays())`.
+    pub fn rounds(&self) -> std::vec::IntoIter<RoundHistory> {
+        self.round_history().into_iter()
+    }
+
+    /// How many cards of each suit, in the order clubs, diamonds, hearts, spades, have yet to be played to a
+    /// trick this round.  Cards in the kitty or discarded count as not yet played.
+    pub fn suit_counts_remaining(&self) -> [u8; 4] {
+        let mut counts = [13; 4];
+        let round = self
+            .history()
+            .iter()
+            .rev()
+            .take_while(|event| **event != GameEvent::Dealt);
+        for event in round {
+            if let GameEvent::CardPlayed { card, .. } = event {
+                counts[card.suit as usize] -= 1;
+            }
+        }
+        counts
+    }
+
+    /// The [replay](struct.TrickHistory.html#method.replay) of the latest trick played out, if any has been.
+    pub fn last_trick_replay(&self) -> Option<Vec<TrickPlay>> {
+        self.round_history()
+            .iter()
+            .rev()
+            .find_map(|round| round.tricks.last())
+            .map(TrickHistory::replay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cards::{Card, Rank, Suit};
+    use test_support::{seated_game, started_game};
+    use {Bet, GameEvent, Seat, State};
+
+    fn card(suit: Suit, rank: u8) -> Card {
+        Card {
+            suit,
+            rank: Rank::from(rank),
+        }
+    }
+
+    #[test]
+    fn test_round_history_follows_play() {
+        let mut g = started_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let mut winners = vec![];
+        for _ in 0..13 {
+            for _ in 0..4 {
+                let card = g.playable_cards().next().unwrap();
+                g.play_card(card);
+            }
+            if g.state() == State::Trick(0) {
+                winners.push(g.current_player_id().unwrap());
+            }
+        }
+        g.place_bet(Bet::Nil);
+
+        let rounds = g.round_history();
+        assert_eq!(2, rounds.len());
+        assert_eq!([Some(Bet::Amount(3)); 4], rounds[0].bets);
+        assert_eq!(13, rounds[0].tricks.len());
+        assert_eq!(Seat::North, rounds[0].tricks[0].leader);
+        for (trick, winner) in rounds[0].tricks.iter().zip(winners) {
+            assert_eq!(Ok(trick.winner), g.seat_of(&winner));
+        }
+        let (tricks, scores) = rounds[0].result.unwrap();
+        assert_eq!(13, tricks.iter().sum::<u8>());
+        assert_eq!(Ok(scores[0].points), g.team_all_rounds_score(0));
+        assert_eq!([Some(Bet::Nil), None, None, None], rounds[1].bets);
+        assert_eq!(None, rounds[1].result);
+
+        assert_eq!(Some(&GameEvent::Dealt), g.history().first());
+        // deal, bets, cards, score, then the next deal and bet
+        assert_eq!(1 + 4 + 52 + 1 + 1 + 1, g.history().len());
+    }
+
+    #[test]
+    fn test_suit_counts_remaining() {
+        let mut g = seated_game();
+        assert_eq!([13; 4], g.suit_counts_remaining());
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let mut played = vec![];
+        for _ in 0..6 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+            played.push(card);
+        }
+        let counts = g.suit_counts_remaining();
+        for suit in Suit::ALL.iter() {
+            let gone = played.iter().filter(|card| card.suit == *suit).count() as u8;
+            assert_eq!(13 - gone, counts[*suit as usize]);
+        }
+        assert_eq!(
+            52 - 6,
+            counts.iter().map(|count| *count as usize).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_last_trick_replay() {
+        let mut g = started_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        assert_eq!(None, g.last_trick_replay());
+        let hands = [
+            vec![card(Suit::Hearts, 5)],
+            vec![card(Suit::Hearts, 9)],
+            vec![card(Suit::Hearts, 7)],
+            vec![card(Suit::Spades, 2)],
+        ];
+        g.rig_round(hands, &[]);
+        for seat in 0..4 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+            if seat < 3 {
+                assert_eq!(None, g.last_trick_replay());
+            }
+        }
+
+        let replay = g.last_trick_replay().unwrap();
+        assert_eq!(
+            vec![Seat::North, Seat::East, Seat::South, Seat::West],
+            replay.iter().map(|play| play.seat).collect::<Vec<_>>()
+        );
+        assert_eq!(card(Suit::Hearts, 9), replay[1].card);
+        assert_eq!(
+            vec![true, true, false, true],
+            replay.iter().map(|play| play.winning).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rounds_iterate_down_to_plays() {
+        let mut g = started_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let mut played = vec![];
+        for _ in 0..13 * 4 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+            played.push(card);
+        }
+
+        // the second round has been dealt but not played
+        assert_eq!(2, g.rounds().len());
+        let cards: Vec<Card> = g
+            .rounds()
+            .flatten()
+            .flat_map(|trick| trick.plays())
+            .map(|play| play.card)
+            .collect();
+        assert_eq!(played, cards);
+        assert_eq!(
+            13,
+            g.rounds()
+                .map(|round| round.tricks().count())
+                .sum::<usize>()
+        );
+        let trick = &g.round_history()[0].tricks[0];
+        assert_eq!(4, trick.plays().len());
+        assert_eq!(trick.replay(), trick.into_iter().collect::<Vec<_>>());
+    }
+}
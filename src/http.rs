@@ -0,0 +1,394 @@
+//! A REST adapter serving games over HTTP, built with the `http` feature.
+//!
+//! It is not tied to any web framework: [`GameServer::handle`](struct.GameServer.html#method.handle) takes a
+//! request's method, path, `Authorization` header and body and returns the status and JSON body to answer
+//! with, so wiring it into axum, warp or a bare `TcpListener` is a single route.  The handler methods
+//! behind it can be called directly too.
+//!
+//! * `POST /games` with a [`CreateGame`](struct.CreateGame.html) body (`{}` for the defaults) starts a game
+//!   and answers `201` with a [`GameCreated`](struct.GameCreated.html), holding each player's token.
+//! * `GET /games/{id}/view` answers with the [`PlayerView`](../struct.PlayerView.html) of the player whose
+//!   token is sent as `Authorization: Bearer <token>`.  This counts as them seeing their hand.
+//! * `POST /games/{id}/actions` with an [`Action`](../ml/enum.Action.html) body, such as
+//!   `{"Bet": {"Amount": 3}}`, takes it for the token's player and answers with their new view.  Only the
+//!   player whose turn it is may act.
+//!
+//! Errors are answered as `{"error": "..."}`: `400` for a body that can't be read or a game that can't be
+//! started as asked, `401` for a missing or unknown token, `403` out of turn, `404` for an unknown game or
+//! path, `405` for the wrong method and `409` for an action the game doesn't allow.
+//!
+//! ```
+//! use spades::http::GameServer;
+//!
+//! let mut server = GameServer::new();
+//! let created = server.handle("POST", "/games", None, "{}");
+//! assert_eq!(201, created.status);
+//! ```
+extern crate rand;
+extern crate serde_json;
+
+use self::rand::{thread_rng, Rng};
+use agent::PlayerView;
+use ml::Action;
+use options::GameOptions;
+use result::SpadesError;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Instant;
+use {Game, Uid};
+
+/// The body of `POST /games`.  Every field may be left out.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct CreateGame {
+    /// The players' ids by seat, `Uid(1)` to `Uid(4)` if not given.
+    #[serde(default)]
+    pub players: Option<[Uid; 4]>,
+    #[serde(default)]
+    pub options: Option<GameOptions>,
+    /// Deal the same cards every time, see
+    /// [`Game::set_shuffle_seed`](../struct.Game.html#method.set_shuffle_seed).
+    #[serde(default)]
+    pub shuffle_seed: Option<u64>,
+}
+
+/// The answer to `POST /games`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct GameCreated {
+    pub game: Uid,
+    pub players: [Uid; 4],
+    /// Each player's secret, by seat, to hand out to them alone.
+    pub tokens: [String; 4],
+}
+
+/// Why a request failed, as answered by [`GameServer::handle`](struct.GameServer.html#method.handle).
+#[derive(Debug, PartialEq, Eq)]
+pub enum HttpError {
+    BadRequest(String),
+    Unauthorized,
+    NotYourTurn,
+    NotFound,
+    MethodNotAllowed,
+    /// The game doesn't allow the action.
+    Rejected(SpadesError),
+}
+
+impl HttpError {
+    pub fn status(&self) -> u16 {
+        match self {
+            HttpError::BadRequest(_) => 400,
+            HttpError::Unauthorized => 401,
+            HttpError::NotYourTurn => 403,
+            HttpError::NotFound => 404,
+            HttpError::MethodNotAllowed => 405,
+            HttpError::Rejected(_) => 409,
+        }
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HttpError::BadRequest(message) => write!(f, "bad request: {}", message),
+            HttpError::Unauthorized => write!(f, "missing or unknown player token"),
+            HttpError::NotYourTurn => write!(f, "it isn't your turn"),
+            HttpError::NotFound => write!(f, "not found"),
+            HttpError::MethodNotAllowed => write!(f, "method not allowed"),
+            HttpError::Rejected(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// A status and JSON body to answer a request with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    fn json<T: serde::Serialize>(status: u16, body: &T) -> HttpResponse {
+        match serde_json::to_string(body) {
+            Ok(body) => HttpResponse { status, body },
+            Err(err) => HttpResponse {
+                status: 500,
+                body: serde_json::json!({ "error": err.to_string() }).to_string(),
+            },
+        }
+    }
+
+    fn error(err: &HttpError) -> HttpResponse {
+        HttpResponse {
+            status: err.status(),
+            body: serde_json::json!({ "error": err.to_string() }).to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct HostedGame {
+    game: Game,
+    tokens: [String; 4],
+}
+
+/// The games being served, and the routes to them.  Wrap it in a `Mutex` to share it between a web
+/// framework's handlers.
+#[derive(Debug, Default)]
+pub struct GameServer {
+    games: HashMap<Uid, HostedGame>,
+    next_id: u64,
+}
+
+fn new_token() -> String {
+    let mut rng = thread_rng();
+    format!("{:016x}{:016x}", rng.gen::<u64>(), rng.gen::<u64>())
+}
+
+/// The token sent as `Bearer <token>`, or on its own.
+fn bearer(authorization: Option<&str>) -> Option<&str> {
+    let value = authorization?.trim();
+    Some(value.strip_prefix("Bearer ").unwrap_or(value).trim())
+}
+
+impl GameServer {
+    pub fn new() -> GameServer {
+        GameServer::default()
+    }
+
+    /// The game with this id, if it is being served.
+    pub fn game(&self, id: Uid) -> Option<&Game> {
+        self.games.get(&id).map(|hosted| &hosted.game)
+    }
+
+    /// Starts a game as `request` asks, handing out a token to each player.  Options that can't be set, and a
+    /// player seated twice, are a bad request.  So is
+    /// [`bid_review`](../struct.GameOptions.html#structfield.bid_review), since a reviewed bet can't be sent
+    /// as an [`Action`](../ml/enum.Action.html).
+    pub fn create_game(&mut self, request: CreateGame) -> Result<GameCreated, HttpError> {
+        let options = request.options.unwrap_or_default();
+        if options.bid_review {
+            return Err(HttpError::BadRequest(
+                "bid review is not served over HTTP".to_string(),
+            ));
+        }
+        let id = Uid(self.next_id + 1);
+        let players = request.players.unwrap_or([Uid(1), Uid(2), Uid(3), Uid(4)]);
+        let mut game = Game::with_options(id, options)
+            .and_then(|mut game| game.seat_players(players).map(|()| game))
+            .map_err(|err| HttpError::BadRequest(err.to_string()))?;
+        self.next_id += 1;
+        game.set_shuffle_seed(request.shuffle_seed);
+        game.start_game();
+        let tokens = [new_token(), new_token(), new_token(), new_token()];
+        self.games.insert(
+            id,
+            HostedGame {
+                game,
+                tokens: tokens.clone(),
+            },
+        );
+        Ok(GameCreated {
+            game: id,
+            players,
+            tokens,
+        })
+    }
+
+    /// The player holding `token` in the game with this id.  Unlike [`view`](#method.view), this doesn't
+    /// count as them seeing their hand.
+    pub fn player(&self, id: Uid, token: Option<&str>) -> Result<Uid, HttpError> {
+        let hosted = self.games.get(&id).ok_or(HttpError::NotFound)?;
+        let token = token.ok_or(HttpError::Unauthorized)?;
+        let seat = hosted
+            .tokens
+            .iter()
+            .position(|known| known == token)
+            .ok_or(HttpError::Unauthorized)?;
+        Ok(hosted.game.player[seat].id)
+    }
+
+    /// The game and the player whose token this is.
+    fn authenticate(
+        &mut self,
+        id: Uid,
+        token: Option<&str>,
+    ) -> Result<(&mut Game, Uid), HttpError> {
+        let player = self.player(id, token)?;
+        let hosted = self.games.get_mut(&id).ok_or(HttpError::NotFound)?;
+        Ok((&mut hosted.game, player))
+    }
+
+    /// The view of the player holding `token`, which counts as them seeing their hand.
+    pub fn view(&mut self, id: Uid, token: Option<&str>) -> Result<PlayerView<'_>, HttpError> {
+        let (game, player) = self.authenticate(id, token)?;
+        // a hand can only be seen once dealt, and not at all once the game is over
+        let _ = game.reveal_hand(&player);
+        game.player_view(&player).map_err(HttpError::Rejected)
+    }
+
+    /// Takes `action` for the player holding `token`, if it is their turn, and returns their new view.
+    pub fn act(
+        &mut self,
+        id: Uid,
+        token: Option<&str>,
+        action: Action,
+    ) -> Result<PlayerView<'_>, HttpError> {
+        let (game, player) = self.authenticate(id, token)?;
+        match game.current_player_id() {
+            Ok(current) if current != player => return Err(HttpError::NotYourTurn),
+            Ok(_) => {}
+            Err(err) => return Err(HttpError::Rejected(err)),
+        }
+        game.apply_with_time(action, Instant::now())
+            .map_err(HttpError::Rejected)?;
+        game.player_view(&player).map_err(HttpError::Rejected)
+    }
+
+    /// Routes a request to its handler.  `authorization` is the request's `Authorization` header, if any.
+    pub fn handle(
+        &mut self,
+        method: &str,
+        path: &str,
+        authorization: Option<&str>,
+        body: &str,
+    ) -> HttpResponse {
+        let segments: Vec<&str> = path
+            .split('?')
+            .next()
+            .unwrap_or("")
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let token = bearer(authorization);
+        let response = match (segments.as_slice(), method) {
+            (["games"], "POST") => {
+                let request: Result<CreateGame, _> = if body.trim().is_empty() {
+                    Ok(CreateGame::default())
+                } else {
+                    serde_json::from_str(body)
+                };
+                match request {
+                    Ok(request) => self
+                        .create_game(request)
+                        .map(|created| HttpResponse::json(201, &created)),
+                    Err(err) => Err(HttpError::BadRequest(err.to_string())),
+                }
+            }
+            (["games", id, "view"], "GET") => match id.parse() {
+                Ok(id) => self
+                    .view(Uid(id), token)
+                    .map(|view| HttpResponse::json(200, &view)),
+                Err(_) => Err(HttpError::NotFound),
+            },
+            (["games", id, "actions"], "POST") => {
+                match (id.parse(), serde_json::from_str::<Action>(body)) {
+                    (Err(_), _) => Err(HttpError::NotFound),
+                    (_, Err(err)) => Err(HttpError::BadRequest(err.to_string())),
+                    (Ok(id), Ok(action)) => self
+                        .act(Uid(id), token, action)
+                        .map(|view| HttpResponse::json(200, &view)),
+                }
+            }
+            (["games"], _) | (["games", _, "view"], _) | (["games", _, "actions"], _) => {
+                Err(HttpError::MethodNotAllowed)
+            }
+            _ => Err(HttpError::NotFound),
+        };
+        response.unwrap_or_else(|err| HttpResponse::error(&err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GameCreated, GameServer, HttpError};
+    use ml::Action;
+    use result::SpadesError;
+    use {Bet, Uid};
+
+    fn start(server: &mut GameServer) -> GameCreated {
+        let response = server.handle("POST", "/games", None, r#"{"shuffle_seed": 3}"#);
+        assert_eq!(201, response.status);
+        serde_json::from_str(&response.body).unwrap()
+    }
+
+    #[test]
+    fn test_routes() {
+        let mut server = GameServer::new();
+        let created = start(&mut server);
+        assert_eq!([Uid(1), Uid(2), Uid(3), Uid(4)], created.players);
+        let bearer = format!("Bearer {}", created.tokens[0]);
+        let path = format!("/games/{}/", created.game.0);
+
+        let view = server.handle("GET", &(path.clone() + "view"), Some(&bearer), "");
+        assert_eq!(200, view.status);
+        let view: serde_json::Value = serde_json::from_str(&view.body).unwrap();
+        assert_eq!(13, view["hand"].as_array().unwrap().len());
+
+        let bet = r#"{"Bet": {"Amount": 3}}"#;
+        let acted = server.handle("POST", &(path.clone() + "actions"), Some(&bearer), bet);
+        assert_eq!(200, acted.status);
+        assert_eq!(
+            Some(Uid(2)),
+            server
+                .game(created.game)
+                .and_then(|g| g.current_player_id().ok())
+        );
+
+        let again = server.handle("POST", &(path.clone() + "actions"), Some(&bearer), bet);
+        assert_eq!(403, again.status);
+        assert!(again.body.contains("turn"));
+        assert_eq!(
+            401,
+            server
+                .handle("GET", &(path.clone() + "view"), Some("Bearer nope"), "")
+                .status
+        );
+        assert_eq!(
+            400,
+            server
+                .handle("POST", &(path.clone() + "actions"), Some(&bearer), "bet")
+                .status
+        );
+        assert_eq!(
+            404,
+            server
+                .handle("GET", "/games/99/view", Some(&bearer), "")
+                .status
+        );
+        assert_eq!(404, server.handle("GET", "/players", None, "").status);
+        let endless = r#"{"options": {"bid_review": false, "minimum_team_bid": null,
+            "length": {"TargetScore": -1}, "bag_penalty": null, "audit_deals": false}}"#;
+        assert_eq!(400, server.handle("POST", "/games", None, endless).status);
+        let reviewed = r#"{"options": {"bid_review": true, "minimum_team_bid": null,
+            "length": {"TargetScore": 500}, "bag_penalty": null, "audit_deals": false}}"#;
+        assert_eq!(400, server.handle("POST", "/games", None, reviewed).status);
+        let twice = r#"{"players": [7, 7, 8, 9]}"#;
+        assert_eq!(400, server.handle("POST", "/games", None, twice).status);
+        assert_eq!(None, server.game(Uid(created.game.0 + 1)));
+        assert_eq!(
+            405,
+            server.handle("DELETE", &(path + "view"), None, "").status
+        );
+    }
+
+    #[test]
+    fn test_engine_rejections() {
+        let mut server = GameServer::new();
+        let created = start(&mut server);
+        let token = Some(created.tokens[0].as_str());
+        assert_eq!(
+            Err(HttpError::Rejected(SpadesError::InvalidBet)),
+            server
+                .act(created.game, token, Action::Bet(Bet::Amount(14)))
+                .map(|_| ())
+        );
+        assert_eq!(409, HttpError::Rejected(SpadesError::InvalidBet).status());
+        // seeing the hand through a view rules out a blind nil
+        server.view(created.game, token).unwrap();
+        assert_eq!(
+            Err(HttpError::Rejected(SpadesError::BetImproperSeenHand)),
+            server
+                .act(created.game, token, Action::Bet(Bet::BlindNil))
+                .map(|_| ())
+        );
+    }
+}
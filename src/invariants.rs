@@ -0,0 +1,221 @@
+//! Consistency checks, reported by [`Game::diagnostics`](../struct.Game.html#method.diagnostics) and run
+//! after every change to a game when the `debug-invariants` feature is enabled.
+
+#[cfg(test)]
+use cards::new_deck;
+use cards::{full_deck, Card};
+use game_state::State;
+use ml::card_index;
+use Game;
+#[cfg(test)]
+use {Hand, Trick};
+
+impl Game {
+    /// Replaces the hands with `hands` and the trick in progress with `trick`, as if every other card had
+    /// already been played in tricks player 0 took, so that tests can set up a round and keep the game
+    /// consistent.  The state is left for the test to set.
+    #[cfg(test)]
+    pub(crate) fn rig_round(&mut self, hands: [Vec<Card>; 4], trick: &[Card]) {
+        self.deck = new_deck();
+        self.deck
+            .retain(|card| !trick.contains(card) && !hands.iter().any(|hand| hand.contains(card)));
+        self.deck.extend_from_slice(trick);
+        self.current_trick = Trick::from(trick);
+        self.leading_suit = trick.first().map(|card| card.suit);
+        for (player, hand) in self.player.iter_mut().zip(hands.iter()) {
+            player.hand = Hand::from(&hand[..]);
+        }
+        self.scoring.betting_over();
+        let tricks_left = hands.iter().map(|hand| hand.len()).max().unwrap_or(0);
+        for _ in tricks_left..self.scoring.tricks_per_round() as usize {
+            self.scoring.award_trick(0);
+        }
+    }
+
+    /// Panics with a report of everything wrong with the game, if anything is.
+    #[cfg(any(test, feature = "debug-invariants"))]
+    pub(crate) fn assert_invariants(&self) {
+        let violations = self.invariant_violations();
+        if !violations.is_empty() {
+            panic!(
+                "game invariants violated:\n  {}\nstate: {:?}, current player index: {}\n{:?}",
+                violations.join("\n  "),
+                self.state,
+                self.current_player_index,
+                self
+            );
+        }
+    }
+
+    /// The cards of a standard deck that are nowhere in the game, and those that are in more than one place,
+    /// each in deck order.  Every card belongs in exactly one place: a hand, the kitty, or the deck (which
+    /// holds the cards already played this round, including the current trick, and any discards).
+    pub(crate) fn deck_discrepancies(&self) -> (Vec<Card>, Vec<Card>) {
+        // counted by card index rather than in a map, so that checking a consistent game doesn't allocate
+        let mut counts = [0usize; 52];
+        let hands = self.player.iter().flat_map(|player| player.hand.iter());
+        for card in self.deck.iter().chain(self.kitty.iter()).chain(hands) {
+            counts[card_index(*card)] += 1;
+        }
+        let deck = full_deck();
+        let missing = deck
+            .iter()
+            .filter(|card| counts[card_index(**card)] == 0)
+            .cloned()
+            .collect();
+        let duplicated = deck
+            .iter()
+            .filter(|card| counts[card_index(**card)] > 1)
+            .cloned()
+            .collect();
+        (missing, duplicated)
+    }
+
+    /// Describes every invariant the game breaks; empty if it is consistent.
+    pub(crate) fn invariant_violations(&self) -> Vec<String> {
+        let mut violations = vec![];
+
+        let (missing, duplicated) = self.deck_discrepancies();
+        if !missing.is_empty() || !duplicated.is_empty() {
+            let in_hand: usize = self.player.iter().map(|player| player.hand.len()).sum();
+            violations.push(format!(
+                "{} cards accounted for, missing {:?} and duplicated {:?}; expected 52",
+                self.deck.len() + self.kitty.len() + in_hand,
+                missing,
+                duplicated
+            ));
+        }
+        let played = &self.deck[self.deck.len().saturating_sub(self.current_trick.len())..];
+        if played != &self.current_trick[..] {
+            violations.push(format!(
+                "current trick {:?} is not the last cards played {:?}",
+                self.current_trick, played
+            ));
+        }
+
+        if self.current_player_index > 3 {
+            violations.push(format!(
+                "current player index {} out of range",
+                self.current_player_index
+            ));
+            return violations;
+        }
+
+        let state = self.paused_state.unwrap_or(self.state);
+        if self.paused_state.is_some() && self.state != State::Paused {
+            violations.push(format!("paused state kept while {:?}", self.state));
+        }
+        violations.extend(self.hand_size_violations());
+        match state {
+            State::Betting(placed) => {
+                if placed > 3 || self.current_player_index != placed {
+                    violations.push(format!(
+                        "{} bets placed, but player {} is betting",
+                        placed, self.current_player_index
+                    ));
+                }
+                let bets = self.round_bets.iter().filter(|bet| bet.is_some()).count();
+                if bets != placed {
+                    violations.push(format!("{} bets recorded for Betting({})", bets, placed));
+                }
+            }
+            State::BidReview => {
+                if self.round_bets.iter().any(|bet| bet.is_none()) {
+                    violations.push("bets missing while reviewing them".to_string());
+                }
+            }
+            State::Trick(cards_played) => {
+                if cards_played > 3 || self.current_trick.len() != cards_played {
+                    violations.push(format!(
+                        "{} cards in the trick for Trick({})",
+                        self.current_trick.len(),
+                        cards_played
+                    ));
+                }
+                if !self.kitty.is_empty() {
+                    violations.push("kitty not picked up during play".to_string());
+                }
+            }
+            State::Exchange => {
+                if !self.kitty.is_empty() {
+                    violations.push("kitty not picked up during the exchange".to_string());
+                }
+            }
+            State::GameNotStarted => {
+                if self.player.iter().any(|player| !player.hand.is_empty()) {
+                    violations.push("cards dealt before the game started".to_string());
+                }
+            }
+            State::GameCompleted | State::Paused | State::Abandoned => {}
+        }
+        violations
+    }
+
+    /// Describes every hand that doesn't hold a card for each trick left to play it to: all of the round's
+    /// tricks while betting, fewer as they are taken.
+    fn hand_size_violations(&self) -> Vec<String> {
+        let (tricks_left, leader, cards_played) = match self.paused_state.unwrap_or(self.state) {
+            State::Betting(_) | State::BidReview => {
+                (self.scoring.tricks_per_round() as usize, 0, 0)
+            }
+            State::Trick(cards_played) if cards_played < 4 && self.current_player_index < 4 => {
+                let tricks_taken: usize = self
+                    .scoring
+                    .player_tricks()
+                    .iter()
+                    .map(|t| *t as usize)
+                    .sum();
+                let tricks_left =
+                    (self.scoring.tricks_per_round() as usize).saturating_sub(tricks_taken);
+                let leader = (self.current_player_index + 4 - cards_played) % 4;
+                (tricks_left, leader, cards_played)
+            }
+            _ => return vec![],
+        };
+        let mut violations = vec![];
+        for seat in 0..4 {
+            let played_to_trick = (seat + 4 - leader) % 4 < cards_played;
+            let expected = tricks_left.saturating_sub(played_to_trick as usize);
+            let held = self.player[seat].hand.len();
+            if held != expected {
+                violations.push(format!(
+                    "player {} holds {} cards with {} tricks left; expected {}",
+                    seat, held, tricks_left, expected
+                ));
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_support::seated_game;
+    use {Bet, Game};
+
+    #[test]
+    fn test_game_stays_consistent() {
+        let mut g = seated_game();
+        assert!(g.invariant_violations().is_empty());
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+            assert_eq!(Vec::<String>::new(), g.invariant_violations());
+        }
+        for _ in 0..13 * 4 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+            assert_eq!(Vec::<String>::new(), g.invariant_violations());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cards accounted for")]
+    fn test_duplicated_card_is_reported() {
+        let mut g = Game::default();
+        g.start_game();
+        let card = g.player[0].hand[0];
+        g.deck.push(card);
+        g.assert_invariants();
+    }
+}
@@ -0,0 +1,299 @@
+use cards::Card;
+use diagnostics::GameEvent;
+use game_state::State;
+use result::SpadesError;
+use scoring::Bet;
+use seat::Seat;
+use Game;
+
+/// Who picks up the kitty, see [`KittyConfig`](struct.KittyConfig.html).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum KittyTaker {
+    /// The player who bid the most tricks, once betting is over.  The earlier seat wins ties; if everyone
+    /// bid nil, the dealer takes it.
+    #[default]
+    HighestBidder,
+    /// The dealer (West, who bets and plays last), before betting starts.
+    Dealer,
+}
+
+/// Cards set aside at each deal for one player to pick up, after which they discard as many cards as they
+/// picked up.  Set with [`Game::set_kitty_config`](struct.Game.html#method.set_kitty_config).
+///
+/// The rest of the deck is dealt out evenly, so a round has `(52 - size) / 4` tricks.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct KittyConfig {
+    /// How many cards are set aside.  Must be a multiple of 4, and less than 52.
+    pub size: usize,
+    pub taker: KittyTaker,
+}
+
+/// The dealer's seat.
+const DEALER: usize = 3;
+
+impl Game {
+    /// Set cards aside at each deal from the next one on, or stop doing so with `None`.
+    /// Returns `SpadesError::InvalidKittySize` if the kitty's size is not a multiple of 4 below 52.
+    pub fn set_kitty_config(&mut self, config: Option<KittyConfig>) -> Result<(), SpadesError> {
+        if let Some(config) = config {
+            check_kitty_size(config.size)?;
+        }
+        self.kitty_config = config;
+        Ok(())
+    }
+
+    pub fn kitty_config(&self) -> Option<KittyConfig> {
+        self.kitty_config
+    }
+
+    /// How many cards are set aside in the kitty, waiting to be picked up.  Once the kitty has been picked up
+    /// this is 0 until the next deal.
+    pub fn kitty_size(&self) -> usize {
+        self.kitty.len()
+    }
+
+    /// How many cards the player who picked up the kitty is to discard during the exchange: as many as they
+    /// picked up, even if the kitty's size has been changed since the deal.
+    pub(crate) fn kitty_discards(&self) -> usize {
+        let hand = &self.player[self.current_player_index].hand;
+        hand.len()
+            .saturating_sub(self.scoring.tricks_per_round() as usize)
+    }
+
+    /// The seat of the player who picks up the kitty this round, once that is known: from the deal when the
+    /// dealer takes it, otherwise from the end of betting.
+    pub fn kitty_taker(&self) -> Option<Seat> {
+        let config = self.kitty_config?;
+        match (config.taker, self.paused_state.unwrap_or(self.state)) {
+            (_, State::GameNotStarted) | (_, State::GameCompleted) | (_, State::Abandoned) => None,
+            (KittyTaker::Dealer, _) => Some(Seat::from_index(DEALER)),
+            (KittyTaker::HighestBidder, State::Betting(_))
+            | (KittyTaker::HighestBidder, State::BidReview) => None,
+            (KittyTaker::HighestBidder, _) => Some(Seat::from_index(self.highest_bidder())),
+        }
+    }
+
+    /// A method to determine whether the player who picked up the kitty may discard these cards.
+    /// If it would not be possible, the reason why not will be returned in Some(SpadesError).
+    pub fn can_discard(&self, cards: &[Card]) -> Option<SpadesError> {
+        match self.state {
+            State::GameNotStarted => Some(SpadesError::GameNotStarted),
+            State::GameCompleted => Some(SpadesError::GameCompleted),
+            State::Paused => Some(SpadesError::GamePaused),
+            State::Abandoned => Some(SpadesError::GameAbandoned),
+            State::Betting(_) | State::BidReview | State::Trick(_) => {
+                Some(SpadesError::ImproperGameStage)
+            }
+            State::Exchange => {
+                if cards.len() != self.kitty_discards() {
+                    return Some(SpadesError::WrongDiscardCount);
+                }
+                let mut hand = self.player[self.current_player_index].hand.clone();
+                for card in cards {
+                    match hand.iter().position(|x| x == card) {
+                        Some(index) => {
+                            hand.remove(index);
+                        }
+                        None => return Some(SpadesError::CardNotInHand),
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    /// Discard these cards from the hand of the player who picked up the kitty, which ends the exchange.
+    /// The game moves on to betting if the dealer took the kitty, or to the first trick otherwise.
+    pub fn discard(&mut self, cards: &[Card]) -> Result<State, SpadesError> {
+        if let Some(err) = self.can_discard(cards) {
+            return Err(err);
+        }
+        self.turn_started = None;
+        self.record_event(GameEvent::Discarded {
+            player: self.player[self.current_player_index].id,
+            cards: cards.to_vec(),
+        });
+        let hand = &mut self.player[self.current_player_index].hand;
+        for card in cards {
+            let index = hand.iter().position(|x| x == card).unwrap();
+            // discards sit out the round with the cards already played
+            self.deck.push(hand.remove(index));
+        }
+        self.state = if self.bets_placed().is_ok() {
+            self.current_player_index = self.leader(0);
+            State::Trick(0)
+        } else {
+            self.current_player_index = 0;
+            State::Betting(0)
+        };
+        self.check_invariants();
+        Ok(self.state)
+    }
+
+    /// Starts the exchange if the dealer picks up the kitty.  Called right after each deal, with the game in
+    /// `Betting(0)`.
+    pub(crate) fn start_round_exchange(&mut self) {
+        if let Some(KittyConfig {
+            taker: KittyTaker::Dealer,
+            ..
+        }) = self.kitty_config
+        {
+            self.start_exchange(DEALER);
+        }
+    }
+
+    /// Starts the exchange once betting is over, if the kitty is still to be picked up.  It goes to the highest
+    /// bidder even if the dealer was made the taker since the deal, so that it can't be left out of play.
+    pub(crate) fn start_bid_winner_exchange(&mut self) {
+        let taker = self.highest_bidder();
+        self.start_exchange(taker);
+    }
+
+    fn start_exchange(&mut self, taker: usize) {
+        if self.kitty.is_empty() {
+            return;
+        }
+        let hand = &mut self.player[taker].hand;
+        hand.extend_from_slice(&self.kitty);
+        self.kitty.clear();
+        hand.sort();
+        self.current_player_index = taker;
+        self.state = State::Exchange;
+    }
+
+    fn highest_bidder(&self) -> usize {
+        highest_bidder(&self.scoring.bets())
+    }
+}
+
+/// `SpadesError::InvalidKittySize` unless `size` cards can be set aside and the rest dealt out evenly.
+pub(crate) fn check_kitty_size(size: usize) -> Result<(), SpadesError> {
+    if size.is_multiple_of(4) && size < 52 {
+        Ok(())
+    } else {
+        Err(SpadesError::InvalidKittySize(size))
+    }
+}
+
+/// The seat of the player who bid the most tricks, the earlier seat on ties, or the dealer if everyone bid nil.
+pub(crate) fn highest_bidder(bets: &[Bet; 4]) -> usize {
+    let mut highest = None;
+    for (index, bet) in bets.iter().enumerate() {
+        let amount = bet.tricks_required();
+        if amount > 0 && highest.is_none_or(|(_, most)| amount > most) {
+            highest = Some((index, amount));
+        }
+    }
+    highest.map_or(DEALER, |(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KittyConfig, KittyTaker};
+    use test_support::seated_game;
+    use {Bet, BetResult, Game, Seat, SpadesError, State, Uid};
+
+    fn kitty_game(taker: KittyTaker) -> Game {
+        let mut g = seated_game();
+        g.set_kitty_config(Some(KittyConfig { size: 4, taker }))
+            .unwrap();
+        g.start_game();
+        g
+    }
+
+    #[test]
+    fn test_highest_bidder_takes_kitty_after_betting() {
+        let mut g = kitty_game(KittyTaker::HighestBidder);
+        assert_eq!(4, g.kitty_size());
+        assert_eq!(12, g.current_hand().unwrap().len());
+        assert_eq!(None, g.kitty_taker());
+
+        g.place_bet(Bet::Amount(3));
+        g.place_bet(Bet::Amount(4));
+        g.place_bet(Bet::Nil);
+        match g.place_bet(Bet::Amount(4)) {
+            Some(BetResult::CompletedBetting(_)) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(State::Exchange, g.state());
+        assert_eq!(Some(Seat::East), g.kitty_taker());
+        assert_eq!(Ok(Uid(11)), g.current_player_id());
+        assert_eq!(0, g.kitty_size());
+
+        let hand = g.current_hand().unwrap().to_vec();
+        assert_eq!(16, hand.len());
+        assert_eq!(
+            Some(SpadesError::WrongDiscardCount),
+            g.can_discard(&hand[..3])
+        );
+        assert_eq!(
+            Some(SpadesError::CardNotInHand),
+            g.can_discard(&[hand[0], hand[0], hand[1], hand[2]])
+        );
+        assert_eq!(Ok(State::Trick(0)), g.discard(&hand[..4]));
+        assert_eq!(Ok(Uid(10)), g.current_player_id());
+        assert_eq!(12, g.hand_from_player_id(Uid(11)).unwrap().len());
+    }
+
+    #[test]
+    fn test_dealer_takes_kitty_before_betting() {
+        let mut g = kitty_game(KittyTaker::Dealer);
+        assert_eq!(State::Exchange, g.state());
+        assert_eq!(Some(Seat::West), g.kitty_taker());
+        assert_eq!(
+            Some(SpadesError::NotBettingStage { next: Uid(13) }),
+            g.can_place_bet(Bet::Nil)
+        );
+        let hand = g.current_hand().unwrap().to_vec();
+        assert_eq!(Ok(State::Betting(0)), g.discard(&hand[12..]));
+        assert_eq!(Ok(Uid(10)), g.current_player_id());
+    }
+
+    #[test]
+    fn test_rounds_with_kitty_have_fewer_tricks() {
+        let mut g = kitty_game(KittyTaker::Dealer);
+        for _ in 0..2 {
+            let hand = g.current_hand().unwrap().to_vec();
+            g.discard(&hand[..4]).unwrap();
+            for _ in 0..4 {
+                g.place_bet(Bet::Amount(3));
+            }
+            for _ in 0..12 * 4 {
+                let card = g.playable_cards().next().unwrap();
+                g.play_card(card);
+            }
+            if g.state() == State::GameCompleted {
+                break;
+            }
+            assert_eq!(State::Exchange, g.state());
+            assert_eq!(
+                12,
+                g.team_tricks_won(0).unwrap() + g.team_tricks_won(1).unwrap()
+            );
+        }
+    }
+}
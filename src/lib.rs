@@ -11,15 +11,15 @@
 //! let player_ids = [Uid(3456), Uid(3457), Uid(3458), Uid(3459)];
 //!
 //! let mut g = Game::default();
-//! 
+//!
 //! g.assign_players(game_id, player_ids);
 //! g.start_game();
 //!
 //! while g.state() != State::GameCompleted {
 //!     let mut rng = thread_rng();
-//!     if let State::Trick(_playerindex) = g.state() {
+//!     if let State::Trick(_cards_played) = g.state() {
 //!         assert!(g.current_hand().is_ok());
-//!         let hand = g.current_hand().ok().unwrap().clone();
+//!         let hand = g.current_hand().ok().unwrap().to_vec();
 //!
 //!         let random_card = rng.choose(hand.as_slice()).unwrap();
 //!         
@@ -41,22 +41,145 @@
 //! That moves it to `State` `Betting(player_number)`.  Once all 4 players have bet, the game mvoes to
 //! `State` `Trick(player_number)`.  After 13 tricks of cards played by each of the 4 players, the round is over.
 //! The game `State` will move either back to the `Betting` state for a new round of the game, or to `GameCompleted`
-//! if one team has scored enough cumulative points to have won the game (at least as many as the `max_points`
-//! parameter given to `Game::new()`).
+//! once the game has run its [`length`](enum.GameLength.html): by default, when one team has scored at least 500
+//! cumulative points.
+//!
+//! A game in progress may also be paused with `pause()` (moving it to `State` `Paused` until `resume()`), or
+//! ended early without a winner with `abandon()` (moving it to `State` `Abandoned`).
+//!
+//! No sequence of calls to a `Game` panics, whatever the arguments: a call that isn't allowed returns a
+//! `SpadesError`, or `None` from the methods returning an `Option`, instead.  That includes games loaded from
+//! saves, which are checked for consistency as they are read.  The tests in `tests/api_misuse.rs` check this
+//! on random sequences of calls and tampered saves.
+//!
+//! Every public type is `Send` and `Sync`, which `tests/thread_safety.rs` checks.  That includes the rule
+//! sets, scoring rules and shufflers a game holds, since their traits require both.  A `Game` keeps no
+//! thread-local or global state, so:
+//! - it can be moved to whichever thread handles its next request;
+//! - it can be shared between threads behind a `Mutex` or `RwLock`.  Every method that changes it takes
+//!   `&mut self`, and the `&self` methods may run at once on many threads;
+//! - a clone, [`fork`](struct.Game.html#method.fork) or
+//!   [`clone_without_history`](struct.Game.html#method.clone_without_history) can be searched on another
+//!   thread while the game goes on.
+//!
+//! The traits a caller implements, [`PlayerAgent`](trait.PlayerAgent.html),
+//! [`GameStore`](store/trait.GameStore.html), [`CardNames`](trait.CardNames.html) and
+//! [`Record`](export/trait.Record.html), don't require `Send` or `Sync` of their own.  Add the bound where
+//! it is needed, as in `Box<dyn PlayerAgent + Send>`.  A tournament's [`AgentFactory`](type.AgentFactory.html)
+//! requires them of the factory instead, which makes each agent on the thread playing its game.
 //!
 
+mod adjustment;
+mod agent;
+pub mod analysis;
+#[cfg(feature = "annotations")]
+mod annotations;
+mod bid_review;
+mod bots;
+#[cfg(feature = "perf")]
+mod card_array;
+mod card_display;
+mod card_names;
 mod cards;
+mod checkpoint;
+mod claim;
+mod commands;
+mod deal;
+mod determinize;
+mod diagnostics;
+mod duplicate;
+pub mod export;
+pub mod fairness;
+mod game_result;
 mod game_state;
+mod history;
+#[cfg(feature = "http")]
+pub mod http;
+mod invariants;
+mod kitty;
+mod lifecycle;
+mod misdeal;
+pub mod ml;
+mod options;
+mod player_id;
+mod player_info;
+pub mod prelude;
 mod result;
+mod revoke;
+mod rubber;
+mod rules;
+mod schema;
+mod scoreboard;
 mod scoring;
+pub mod search;
+mod season;
+mod seat;
+mod seating;
+mod shuffler;
+mod snapshot;
+pub mod store;
+mod table;
+mod teams;
+mod timer;
+mod tournament;
+mod transition;
+mod two_player;
 
+#[cfg(test)]
+mod test_support;
 #[cfg(test)]
 mod tests;
 
-pub use cards::{get_trick_winner, Card, Rank, Suit};
-pub use game_state::State;
+pub use adjustment::Adjustment;
+pub use agent::{
+    run_game, run_game_with_round_limit, HandOrder, PlayerAgent, PlayerView, RelativeTable,
+    SeatView, ROUND_LIMIT,
+};
+#[cfg(feature = "annotations")]
+pub use annotations::Annotation;
+pub use bots::{BotConfig, HeuristicBot, RandomBot};
+pub use card_display::{CardDisplay, CardStyle, HandDisplay};
+pub use card_names::{CardNames, Language};
+pub use cards::{
+    full_deck, get_trick_winner, sort_by_strength, Card, Color, DealConfig, DealPattern, Deck,
+    Rank, Suit,
+};
+pub use checkpoint::Checkpoint;
+pub use claim::{Claim, ClaimResponse};
+pub use commands::Command;
+pub use deal::Deal;
+pub use diagnostics::{Diagnostics, GameEvent, SequencedEvent, RECENT_EVENT_COUNT};
+pub use duplicate::{BoardResult, DuplicateSession};
+pub use game_result::GameResult;
+pub use game_state::{DetailedState, State};
+pub use history::{RoundHistory, TrickHistory, TrickPlay, TrickPlays};
+pub use kitty::{KittyConfig, KittyTaker};
+pub use lifecycle::{AbandonReason, PrematureEnd};
+pub use misdeal::{high_card_points, MisdealConfig};
+pub use options::{BidVisibility, GameLength, GameOptions};
+pub use player_id::PlayerId;
+pub use player_info::PlayerInfo;
 pub use result::SpadesError;
-pub use scoring::Bet;
+pub use revoke::{Revoke, RevokePenalty, RevokePolicy};
+pub use rubber::{Rubber, RubberConfig, RubberGame};
+pub use rules::{CardPlayRules, MustTrumpRules, PlayContext, SpadesLeadRules, StandardRules};
+pub use schema::SCHEMA_VERSION;
+pub use scoreboard::{Scoreboard, ScoreboardRow};
+pub use scoring::{
+    score_rounds, BagSettlement, Bet, ContractStatus, NilStatus, NilTricks, RoundInput,
+    RoundOutcome, RoundProjection, ScoringRules, StandardScoring, TeamBid, TeamRoundScore,
+};
+pub use season::{Match, MatchResult, Season, Standing, StandingsConfig, Tiebreaker};
+pub use seat::Seat;
+pub use seating::{dealer_rotation, league_schedule, partnerships};
+pub use shuffler::{CryptoShuffler, SeededShuffler, Shuffler, ThreadRngShuffler};
+pub use snapshot::{GameSnapshot, SnapshotDelta};
+pub use table::{Table, TableState, TableVariant};
+pub use teams::TeamAssignment;
+pub use timer::{TimeoutOutcome, TimeoutPolicy};
+pub use tournament::{AgentFactory, EntrantStats, Tournament, TournamentResults};
+pub use transition::{transition, Effects, RulesContext};
+pub use two_player::DrawDeal;
 
 /// If a bet is made successfully, this lets one distinguish whether that bet ends the round of betting.
 #[derive(
@@ -76,8 +199,8 @@ pub enum BetResult {
     /// Bet was made successfully.
     #[default]
     MadeBet,
-    /// This bet completed the betting stage.
-    CompletedBetting,
+    /// This bet completed the betting stage.  Holds every player's bet for the round, in order of play.
+    CompletedBetting([(Uid, Bet); 4]),
 }
 
 /// If a card is played successfully, this lets one distinguish whether that card results in the completion
@@ -120,14 +243,47 @@ pub enum PlayCardResult {
 )]
 pub struct Uid(pub u64);
 
-use cards::{deal_four_players, new_deck};
-use scoring::Scoring;
+use cards::{deal_with_kitty_using, new_deck};
+use claim::PendingClaim;
+use diagnostics::EventLog;
+use fairness::DealReveal;
+use history::trick_play;
+use ml::Action;
+use rules::Rules;
+use scoring::{project_tricks, Scoring};
+use std::collections::{BTreeMap, VecDeque};
+use std::mem;
+use std::time::{Duration, Instant};
+
+/// A player's hand.  With the `perf` feature it is kept inline, with room for the whole deck.
+#[cfg(feature = "perf")]
+type Hand = card_array::CardArray<52>;
+#[cfg(not(feature = "perf"))]
+type Hand = Vec<Card>;
+
+/// The cards played to the current trick.  With the `perf` feature it is kept inline.
+#[cfg(feature = "perf")]
+type Trick = card_array::CardArray<4>;
+#[cfg(not(feature = "perf"))]
+type Trick = Vec<Card>;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 struct Player {
     id: Uid,
     seen_hand: bool,
-    hand: Vec<Card>,
+    hand: Hand,
+    info: Option<PlayerInfo>,
 }
 
 impl Player {
@@ -135,25 +291,58 @@ impl Player {
         Player {
             id,
             seen_hand: false,
-            hand: vec![],
+            hand: Hand::new(),
+            info: None,
         }
     }
 }
 
 /// Primary game state. Internally manages player rotation, scoring, and cards.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// A `Game` can be saved and loaded with any serde format; see [`SCHEMA_VERSION`](constant.SCHEMA_VERSION.html).
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(into = "schema::SavedGame", try_from = "schema::SavedGame")]
 pub struct Game {
     id: Uid,
     state: State,
     scoring: Scoring,
     current_player_index: usize,
     deck: Vec<Card>,
-    current_trick: Vec<Card>,
-    bets_placed: [Bet; 4],
+    current_trick: Trick,
+    round_bets: [Option<Bet>; 4],
+    previous_round_bets: Option<[Bet; 4]>,
     leading_suit: Option<Suit>,
     spades_broken: bool,
     //rule_blind_nil_allowed: bool,
     player: [Player; 4],
+    turn_duration: Option<Duration>,
+    timeout_policy: TimeoutPolicy,
+    turn_started: Option<Instant>,
+    think_time: [Duration; 4],
+    /// When the latest action given a time was taken, and how many actions had been taken after it.
+    last_timed_action: Option<(Instant, u64)>,
+    forfeited_team: Option<usize>,
+    paused_state: Option<State>,
+    abandon_reason: Option<AbandonReason>,
+    pending_claim: Option<PendingClaim>,
+    rules: Rules<dyn CardPlayRules>,
+    shuffler: Rules<dyn Shuffler>,
+    revoke_policy: RevokePolicy,
+    misdeal_config: MisdealConfig,
+    options: GameOptions,
+    deal_config: DealConfig,
+    kitty_config: Option<KittyConfig>,
+    kitty: Vec<Card>,
+    shuffle_seed: Option<u64>,
+    queued_deals: VecDeque<Deal>,
+    round_deal: Option<Deal>,
+    deal_audit: Option<DealReveal>,
+    revealed_deal: Option<DealReveal>,
+    adjustments: Vec<Adjustment>,
+    team_ids: Option<[Uid; 2]>,
+    event_log: EventLog,
 }
 
 impl Default for Game {
@@ -166,27 +355,110 @@ impl Default for Game {
             deck: new_deck(),
             leading_suit: None,
             spades_broken: false,
-            current_trick: Vec::new(),
-            bets_placed: [Bet::Amount(0); 4],
+            current_trick: Trick::new(),
+            round_bets: [None; 4],
+            previous_round_bets: None,
             player: [
                 Player::default(),
                 Player::default(),
                 Player::default(),
                 Player::default(),
             ],
+            turn_duration: None,
+            timeout_policy: TimeoutPolicy::default(),
+            turn_started: None,
+            think_time: [Duration::ZERO; 4],
+            last_timed_action: None,
+            forfeited_team: None,
+            paused_state: None,
+            abandon_reason: None,
+            pending_claim: None,
+            rules: Rules::default(),
+            shuffler: Rules::default(),
+            revoke_policy: RevokePolicy::default(),
+            misdeal_config: MisdealConfig::default(),
+            options: GameOptions::default(),
+            deal_config: DealConfig::default(),
+            kitty_config: None,
+            kitty: Vec::new(),
+            shuffle_seed: None,
+            queued_deals: VecDeque::new(),
+            round_deal: None,
+            deal_audit: None,
+            revealed_deal: None,
+            adjustments: Vec::new(),
+            team_ids: None,
+            event_log: EventLog::default(),
         }
     }
 }
 
 impl Game {
+    /// Gives the game the id `id` and seats `player_ids`, in seat order, replacing any players already
+    /// seated.  The ids aren't checked; [`seat_players`](#method.seat_players) checks them.
     pub fn assign_players(&mut self, id: Uid, player_ids: [Uid; 4]) {
         self.id = id;
+        self.team_ids = None;
         self.player = [
-                Player::new(player_ids[0]),
-                Player::new(player_ids[1]),
-                Player::new(player_ids[2]),
-                Player::new(player_ids[3]),
-            ];
+            Player::new(player_ids[0]),
+            Player::new(player_ids[1]),
+            Player::new(player_ids[2]),
+            Player::new(player_ids[3]),
+        ];
+    }
+
+    /// A game with the id `id` and `player_ids` seated in seat order, with the default options.  Unlike
+    /// [`assign_players`](#method.assign_players), which leaves it to the caller, this checks the ids:
+    /// returns `SpadesError::InvalidGameId` for the game id 0 and `SpadesError::DuplicatePlayer` if
+    /// a player id is given for more than one seat.
+    pub fn try_new(id: Uid, player_ids: [Uid; 4]) -> Result<Game, SpadesError> {
+        let mut game = Game::with_options(id, GameOptions::default())?;
+        game.seat_players(player_ids)?;
+        Ok(game)
+    }
+
+    /// Seats `player_ids`, in seat order, at a game made with [`with_options`](#method.with_options),
+    /// keeping its id.  Returns `SpadesError::DuplicatePlayer` if an id is given for more than one seat, and
+    /// `SpadesError::ImproperGameStage` once the game has started, leaving the seats as they were.
+    pub fn seat_players(&mut self, player_ids: [Uid; 4]) -> Result<(), SpadesError> {
+        if self.state != State::GameNotStarted {
+            return Err(SpadesError::ImproperGameStage);
+        }
+        for (index, player) in player_ids.iter().enumerate() {
+            if player_ids[..index].contains(player) {
+                return Err(SpadesError::DuplicatePlayer(*player));
+            }
+        }
+        self.assign_players(self.id, player_ids);
+        Ok(())
+    }
+
+    /// Like [`seat_players`](#method.seat_players), but with the caller's own kind of identifier for the game
+    /// and its players.  Use [`PlayerId::to_uid`](trait.PlayerId.html#tymethod.to_uid) to look players up
+    /// afterwards.  As well as the errors of `seat_players`, returns `SpadesError::PlayerIdCollision` if two
+    /// different players' ids map to the same `Uid`, since they couldn't be told apart.  Nothing is changed
+    /// on an error.
+    pub fn assign_player_ids<G: PlayerId + ?Sized, P: PlayerId + PartialEq>(
+        &mut self,
+        id: &G,
+        player_ids: &[P; 4],
+    ) -> Result<(), SpadesError> {
+        let uids = [
+            player_ids[0].to_uid(),
+            player_ids[1].to_uid(),
+            player_ids[2].to_uid(),
+            player_ids[3].to_uid(),
+        ];
+        for (index, uid) in uids.iter().enumerate() {
+            let collides = (0..index)
+                .any(|other| uids[other] == *uid && player_ids[other] != player_ids[index]);
+            if collides {
+                return Err(SpadesError::PlayerIdCollision(*uid));
+            }
+        }
+        self.seat_players(uids)?;
+        self.id = id.to_uid();
+        Ok(())
     }
 
     /// The uuid of the game itself
@@ -201,7 +473,7 @@ impl Game {
 
     /// Score for Team 0 (players 0 and 2) or Team 1 (players 1 and 3) for the round just finished, valid at the end of each round.
     pub fn team_individual_round_score(&self, team_id: usize) -> Result<i32, SpadesError> {
-        assert!(team_id == 0 || team_id == 1);
+        check_team(team_id)?;
         match self.state {
             State::GameNotStarted => Err(SpadesError::GameNotStarted),
             _ => Ok(self.scoring.team[team_id].game_points()),
@@ -210,7 +482,7 @@ impl Game {
 
     /// Score for Team 0 (players 0 and 2) or Team 1 (players 1 and 3) so far in the game, valid at the end of each round.
     pub fn team_all_rounds_score(&self, team_id: usize) -> Result<i32, SpadesError> {
-        assert!(team_id == 0 || team_id == 1);
+        check_team(team_id)?;
         match self.state {
             State::GameNotStarted => Err(SpadesError::GameNotStarted),
             _ => Ok(self.scoring.team[team_id].cumulative_points()),
@@ -219,7 +491,7 @@ impl Game {
 
     /// Number of tricks taken by Team 0 (players 0 and 2) or Team 1 (players 1 and 3) for the round just completed.
     pub fn team_tricks_won(&self, team_id: usize) -> Result<u8, SpadesError> {
-        assert!(team_id == 0 || team_id == 1);
+        check_team(team_id)?;
         match self.state {
             State::GameNotStarted => Err(SpadesError::GameNotStarted),
             _ => Ok(self.scoring.team[team_id].tricks_won()),
@@ -228,7 +500,7 @@ impl Game {
 
     /// Number of bags (overtricks) taken by Team 0 (players 0 and 2) or Team 1 (players 1 and 3) for the round just completed.
     pub fn team_individual_round_bags(&self, team_id: usize) -> Result<u8, SpadesError> {
-        assert!(team_id == 0 || team_id == 1);
+        check_team(team_id)?;
         match self.state {
             State::GameNotStarted => Err(SpadesError::GameNotStarted),
             _ => Ok(self.scoring.team[team_id].game_bags()),
@@ -238,70 +510,311 @@ impl Game {
     /// Number of bags (overtricks) taken by Team 0 (players 0 and 2) or Team 1 (players 1 and 3) for all rounds completed.
     /// Decremented by 10 when over 10, decreasing the overall score for this team.
     pub fn team_all_rounds_bags(&self, team_id: usize) -> Result<u8, SpadesError> {
-        assert!(team_id == 0 || team_id == 1);
+        check_team(team_id)?;
         match self.state {
             State::GameNotStarted => Err(SpadesError::GameNotStarted),
             _ => Ok(self.scoring.team[team_id].cumulative_bags()),
         }
     }
 
+    /// Points scored by Team 0 (players 0 and 2) and Team 1 (players 1 and 3) in each round completed, oldest first.
+    pub fn round_scores(&self) -> Vec<[i32; 2]> {
+        self.scoring
+            .round_scores()
+            .iter()
+            .map(|scores| [scores[0].points, scores[1].points])
+            .collect()
+    }
+
+    /// Score for Team 0 (players 0 and 2) and Team 1 (players 1 and 3) so far in the game after each round completed,
+    /// oldest first.
+    pub fn score_history(&self) -> Vec<[i32; 2]> {
+        let mut cumulative = [0, 0];
+        self.round_scores()
+            .iter()
+            .enumerate()
+            .map(|(round, scores)| {
+                cumulative = [cumulative[0] + scores[0], cumulative[1] + scores[1]];
+                for adjustment in self.adjustments.iter().filter(|a| a.round == round) {
+                    cumulative[adjustment.team] += adjustment.points;
+                }
+                cumulative
+            })
+            .collect()
+    }
+
+    /// Score Team 0 (players 0 and 2) or Team 1 (players 1 and 3) is on track for in the round being played.
+    ///
+    /// Without a `hypothetical`, the tricks still to be played are shared out in proportion to the tricks each
+    /// player has taken so far (evenly, before the first trick is over).  Otherwise `hypothetical` gives the
+    /// number of tricks each player ends the round with; these must add up to the tricks in a round (13, or
+    /// fewer with a [`KittyConfig`](struct.KittyConfig.html)), and can't be fewer than a player has already
+    /// taken.  Only valid during the trick stage.
+    pub fn projected_round_score(
+        &self,
+        team_id: usize,
+        hypothetical: Option<[u8; 4]>,
+    ) -> Result<RoundProjection, SpadesError> {
+        check_team(team_id)?;
+        self.check_trick_stage()?;
+        let taken = self.scoring.player_tricks();
+        let tricks = match hypothetical {
+            Some(tricks) => {
+                if tricks.iter().map(|t| *t as usize).sum::<usize>()
+                    != self.scoring.tricks_per_round() as usize
+                    || tricks.iter().zip(taken.iter()).any(|(t, taken)| t < taken)
+                {
+                    return Err(SpadesError::ImpossibleTrickCounts);
+                }
+                tricks
+            }
+            None => project_tricks(taken, self.scoring.tricks_per_round()),
+        };
+        Ok(self.scoring.project(team_id, tricks))
+    }
+
+    /// How Team 0 (players 0 and 2) or Team 1 (players 1 and 3) stands against its bid in the round being played.
+    /// Only valid during the trick stage.
+    pub fn contract_status(&self, team_id: usize) -> Result<ContractStatus, SpadesError> {
+        check_team(team_id)?;
+        self.check_trick_stage()?;
+        let bets = self.scoring.bets();
+        let taken = self.scoring.player_tricks();
+        let bid = TeamBid::new(bets[team_id], bets[team_id + 2]).tricks_required();
+        let tricks = taken[team_id] + taken[team_id + 2];
+        let remaining = self.scoring.tricks_per_round() - taken.iter().sum::<u8>();
+        let needed = bid.saturating_sub(tricks);
+        Ok(ContractStatus {
+            bid,
+            tricks,
+            needed,
+            remaining,
+            set: needed > remaining,
+            bagging: tricks > bid,
+        })
+    }
+
+    /// Checks that tricks are being played, or were when the game was paused, for the questions about the
+    /// round in play.
+    fn check_trick_stage(&self) -> Result<(), SpadesError> {
+        match self.paused_state.unwrap_or(self.state) {
+            State::GameNotStarted => Err(SpadesError::GameNotStarted),
+            State::GameCompleted => Err(SpadesError::GameCompleted),
+            State::Abandoned => Err(SpadesError::GameAbandoned),
+            state if state.is_trick() => Ok(()),
+            _ => Err(SpadesError::ImproperGameStage),
+        }
+    }
+
+    /// How this player's nil bet is going, or `None` if they haven't bet nil or blind nil this round.
+    pub fn nil_status<P: PlayerId + ?Sized>(
+        &self,
+        player_id: &P,
+    ) -> Result<Option<NilStatus>, SpadesError> {
+        let seat = self.seat_of(player_id)?.index();
+        Ok(match self.round_bets[seat] {
+            Some(bid @ Bet::Nil) | Some(bid @ Bet::BlindNil) => Some(NilStatus {
+                bid,
+                broken: !self.scoring.is_in_betting_stage()
+                    && self.scoring.player_tricks()[seat] > 0,
+            }),
+            _ => None,
+        })
+    }
+
+    /// Each player's id with the bet they placed this round, in order of play; `None` for players who haven't
+    /// bet yet.
+    pub fn bets_with_players(&self) -> [(Uid, Option<Bet>); 4] {
+        [
+            (self.player[0].id, self.round_bets[0]),
+            (self.player[1].id, self.round_bets[1]),
+            (self.player[2].id, self.round_bets[2]),
+            (self.player[3].id, self.round_bets[3]),
+        ]
+    }
+
+    /// Each player's id with the number of cards left in their hand, in order of play.  How many cards a
+    /// player holds is public, so a table can draw the backs of its opponents' cards from this alone.
+    pub fn hand_sizes(&self) -> [(Uid, u8); 4] {
+        [
+            (self.player[0].id, self.player[0].hand.len() as u8),
+            (self.player[1].id, self.player[1].hand.len() as u8),
+            (self.player[2].id, self.player[2].hand.len() as u8),
+            (self.player[3].id, self.player[3].hand.len() as u8),
+        ]
+    }
+
+    /// The bids placed so far this round that the whole table may see, by the player who placed them.  Bids
+    /// are made in turn and are public, so any player may see them while betting is still going on, unless
+    /// the [`bid_visibility`](struct.GameOptions.html#structfield.bid_visibility) option hides them until
+    /// everyone has bet.  Returns `SpadesError::GameNotStarted` before the game starts.
+    pub fn visible_bids(&self) -> Result<BTreeMap<Uid, Bet>, SpadesError> {
+        if let State::GameNotStarted = self.state {
+            return Err(SpadesError::GameNotStarted);
+        }
+        Ok(self
+            .player
+            .iter()
+            .zip(self.shown_bets(None).iter())
+            .filter_map(|(player, bet)| bet.map(|bet| (player.id, bet)))
+            .collect())
+    }
+
+    /// The bid this player's partner has placed this round, or `None` if they haven't bid yet or it is
+    /// hidden from them by the [`bid_visibility`](struct.GameOptions.html#structfield.bid_visibility) option.
+    pub fn partner_bid<P: PlayerId + ?Sized>(
+        &self,
+        player_id: &P,
+    ) -> Result<Option<Bet>, SpadesError> {
+        let seat = self.seat_of(player_id)?;
+        if let State::GameNotStarted = self.state {
+            return Err(SpadesError::GameNotStarted);
+        }
+        Ok(self.shown_bets(Some(seat))[seat.partner().index()])
+    }
+
     /// Obtain the uuid of the player expected to take the next game action.
     /// Returns `SpadesError` when the current game is not in the Betting or Trick stages.
     pub fn current_player_id(&self) -> Result<Uid, SpadesError> {
         match (&self.state, self.current_player_index) {
             (State::GameNotStarted, _) => Err(SpadesError::GameNotStarted),
             (State::GameCompleted, _) => Err(SpadesError::GameCompleted),
-            (State::Betting(_), p) | (State::Trick(_), p) => Ok(self.player[p].id),
+            (State::Paused, _) => Err(SpadesError::GamePaused),
+            (State::Abandoned, _) => Err(SpadesError::GameAbandoned),
+            (State::Betting(_), p)
+            | (State::Exchange, p)
+            | (State::BidReview, p)
+            | (State::Trick(_), p) => Ok(self.player[p].id),
         }
     }
 
     /// Obtain the set of cards in the hand of the player with the matching uuid.
     /// Returns a `SpadesError::InvalidUuid` if the game does not contain a player with the given `Uuid`.
-    pub fn hand_from_player_id(&self, player_id: Uid) -> Result<&Vec<Card>, SpadesError> {
-        if player_id == self.player[0].id {
-            return Ok(&self.player[0].hand);
-        }
-        if player_id == self.player[1].id {
-            return Ok(&self.player[1].hand);
-        }
-        if player_id == self.player[2].id {
-            return Ok(&self.player[2].hand);
-        }
-        if player_id == self.player[3].id {
-            return Ok(&self.player[3].hand);
+    pub fn hand_from_player_id(&self, player_id: Uid) -> Result<&[Card], SpadesError> {
+        let seat = self.seat_of(&player_id)?;
+        Ok(&self.player[seat.index()].hand)
+    }
+
+    /// The cards the current player may play now, without allocating.  Empty outside the trick stage.
+    pub fn playable_cards(&self) -> impl Iterator<Item = Card> + '_ {
+        let hand: &[Card] = match self.state {
+            State::Trick(_) => &self.player[self.current_player_index].hand,
+            _ => &[],
+        };
+        hand.iter()
+            .cloned()
+            .filter(move |card| self.can_play_card(*card).is_none())
+    }
+
+    /// Everything `player_id` may do now: the bets they may place while betting, or the cards they may play
+    /// during a trick, checked just as [`place_bet`](#method.place_bet) and [`play_card`](#method.play_card)
+    /// check them.  Empty when it isn't their turn, or while the game is waiting on something other than a
+    /// bet or a card, such as a discard.
+    /// Returns `SpadesError::InvalidUuid` if `player_id` isn't playing.
+    pub fn legal_actions<P: PlayerId + ?Sized>(
+        &self,
+        player_id: &P,
+    ) -> Result<Vec<Action>, SpadesError> {
+        if self.seat_of(player_id)?.index() != self.current_player_index {
+            return Ok(vec![]);
         }
-        Err(SpadesError::InvalidUuid)
+        Ok(match self.state {
+            State::Betting(_) => [Bet::Nil, Bet::BlindNil, Bet::Board, Bet::TenForTwoHundred]
+                .iter()
+                .cloned()
+                .chain((1..=13).map(Bet::Amount))
+                .filter(|bet| self.can_place_bet(*bet).is_none())
+                .map(Action::Bet)
+                .collect(),
+            _ => self.playable_cards().map(Action::Play).collect(),
+        })
     }
 
     /// Obtain the set of cards in the hand of the player expected to take the next game action.
-    /// Once this is called for a player, they may not make a blind nil bid for that round.
-    pub fn current_hand(&mut self) -> Result<Vec<Card>, SpadesError> {
+    /// Once this is called for a player, they may not make a blind nil bid for that round; it is
+    /// [`reveal_hand`](#method.reveal_hand) followed by [`hand_of_current_player`](#method.hand_of_current_player).
+    pub fn current_hand(&mut self) -> Result<&[Card], SpadesError> {
+        self.hand_of_current_player()?;
+        let player = &mut self.player[self.current_player_index];
+        player.seen_hand = true;
+        Ok(&player.hand)
+    }
+
+    /// The hand of the player expected to take the next game action.  This does not count as the player
+    /// seeing their hand; call [`reveal_hand`](#method.reveal_hand) when it is shown to them.
+    pub fn hand_of_current_player(&self) -> Result<&[Card], SpadesError> {
         match (&self.state, self.current_player_index) {
             (State::GameNotStarted, _) => Err(SpadesError::GameNotStarted),
             (State::GameCompleted, _) => Err(SpadesError::GameCompleted),
-            (State::Betting(_), p) | (State::Trick(_), p) => {
-                self.player[p].seen_hand = true;
-                Ok(self.player[p].hand.clone())
-            }
+            (State::Paused, _) => Err(SpadesError::GamePaused),
+            (State::Abandoned, _) => Err(SpadesError::GameAbandoned),
+            (State::Betting(_), p)
+            | (State::Exchange, p)
+            | (State::BidReview, p)
+            | (State::Trick(_), p) => Ok(&self.player[p].hand),
         }
     }
 
+    /// Record that the player with this id has seen their hand, so they may not bet blind nil this round.
+    pub fn reveal_hand<P: PlayerId + ?Sized>(&mut self, player_id: &P) -> Result<(), SpadesError> {
+        let seat = self.seat_of(player_id)?;
+        self.player[seat.index()].seen_hand = true;
+        Ok(())
+    }
+
     /// The suit led for the current trick.
     pub fn leading_suit(&self) -> Result<Option<Suit>, SpadesError> {
         match &self.state {
             State::GameNotStarted => Err(SpadesError::GameNotStarted),
             State::GameCompleted => Err(SpadesError::GameCompleted),
+            State::Paused => Err(SpadesError::GamePaused),
+            State::Abandoned => Err(SpadesError::GameAbandoned),
             State::Trick(_) => Ok(self.leading_suit),
-            _ => Err(SpadesError::InternalError),
+            _ => Err(self.internal_error()),
+        }
+    }
+
+    /// The uuid of the player who led (or is about to lead) the current trick.
+    pub fn current_trick_leader(&self) -> Result<Uid, SpadesError> {
+        match &self.state {
+            State::GameNotStarted => Err(SpadesError::GameNotStarted),
+            State::GameCompleted => Err(SpadesError::GameCompleted),
+            State::Paused => Err(SpadesError::GamePaused),
+            State::Abandoned => Err(SpadesError::GameAbandoned),
+            State::Betting(_) | State::Exchange | State::BidReview => {
+                Err(SpadesError::ImproperGameStage)
+            }
+            State::Trick(_) => Ok(self.player[self.current_trick_leader_index()].id),
         }
     }
 
+    /// The uuid of the player whose card is winning the trick in progress, or `None` before its first card.
+    pub fn currently_winning_player(&self) -> Result<Option<Uid>, SpadesError> {
+        self.current_trick_leader()?;
+        let winner = get_trick_winner(self.current_trick_leader_index(), &self.current_trick);
+        Ok(winner.map(|winner| self.player[winner].id))
+    }
+
+    fn current_trick_leader_index(&self) -> usize {
+        (self.current_player_index + 4 - self.current_trick.len()) % 4
+    }
+
+    /// The seat to lead the next trick in `seat`'s place, see `rules::leader`.
+    fn leader(&self, seat: usize) -> usize {
+        let hands = [0, 1, 2, 3].map(|index| &self.player[index].hand[..]);
+        rules::leader(self.rules.get(), hands, self.spades_broken, seat)
+    }
+
     // Obtain the uuids of the players on the team that won this game.
     pub fn winner_ids(&self) -> Result<(Uid, Uid), SpadesError> {
         match self.state {
             State::GameCompleted => {
+                if let Some(team) = self.forfeited_team {
+                    let winners = 1 - team;
+                    return Ok((self.player[winners].id, self.player[winners + 2].id));
+                }
                 if self.scoring.team[0].cumulative_points()
-                    <= self.scoring.team[1].cumulative_points()
+                    >= self.scoring.team[1].cumulative_points()
                 {
                     Ok((self.player[0].id, self.player[2].id))
                 } else {
@@ -312,9 +825,86 @@ impl Game {
         }
     }
 
-    // Obtain the bets that have been placed by each player for the current round.
+    /// Obtain the bets that have been placed by each player for the current round.
+    /// Returns `SpadesError::BettingNotComplete` until every player has bet.
     pub fn bets_placed(&self) -> Result<[Bet; 4], SpadesError> {
-        Ok(self.bets_placed)
+        if let State::GameNotStarted = self.state {
+            return Err(SpadesError::GameNotStarted);
+        }
+        match self.round_bets {
+            [Some(a), Some(b), Some(c), Some(d)] => Ok([a, b, c, d]),
+            _ => Err(SpadesError::BettingNotComplete),
+        }
+    }
+
+    /// The bets each player placed in the round before this one, if there was one.
+    pub fn bets_from_previous_round(&self) -> Option<[Bet; 4]> {
+        self.previous_round_bets
+    }
+
+    /// The public state of the game: everything except the players' hands.
+    /// Pair with [`GameSnapshot::diff`](struct.GameSnapshot.html#method.diff) to send only what changed.
+    pub fn snapshot(&self) -> GameSnapshot {
+        let mut snapshot = GameSnapshot::default();
+        self.update_snapshot(&mut snapshot);
+        snapshot
+    }
+
+    /// Brings `snapshot` up to date with the table, reusing the storage of its lists, so that keeping the
+    /// last good snapshot after every action doesn't allocate.
+    pub(crate) fn update_snapshot(&self, snapshot: &mut GameSnapshot) {
+        let leader = Seat::from_index(self.current_trick_leader_index());
+        let mut trick_plays = mem::take(&mut snapshot.trick_plays);
+        trick_plays.clear();
+        trick_plays.extend(
+            (0..self.current_trick.len())
+                .filter_map(|position| trick_play(leader, &self.current_trick, position)),
+        );
+        let winning = trick_plays
+            .iter()
+            .rev()
+            .find(|play| play.winning)
+            .map(|play| (play.seat, play.card));
+        let mut current_trick = mem::take(&mut snapshot.current_trick);
+        current_trick.clear();
+        current_trick.extend_from_slice(&self.current_trick);
+        *snapshot = GameSnapshot {
+            id: self.id,
+            state: self.state,
+            player_ids: [
+                self.player[0].id,
+                self.player[1].id,
+                self.player[2].id,
+                self.player[3].id,
+            ],
+            current_player: self.current_player_id().ok(),
+            bets: self.shown_bets(None),
+            current_trick,
+            trick_plays,
+            winning,
+            leading_suit: self.leading_suit,
+            spades_broken: self.spades_broken,
+            tricks: [
+                self.scoring.team[0].tricks_won(),
+                self.scoring.team[1].tricks_won(),
+            ],
+            scores: [
+                self.scoring.team[0].cumulative_points(),
+                self.scoring.team[1].cumulative_points(),
+            ],
+            bags: [
+                self.scoring.team[0].cumulative_bags(),
+                self.scoring.team[1].cumulative_bags(),
+            ],
+            max_points: self.max_points(),
+            options: self.options,
+            player_info: [
+                self.player[0].info.clone(),
+                self.player[1].info.clone(),
+                self.player[2].info.clone(),
+                self.player[3].info.clone(),
+            ],
+        };
     }
 
     /// Use this method to check whether the game is expecting start_game to be called next.
@@ -344,6 +934,7 @@ impl Game {
             // don't do anything if can't start game
         } else {
             self.execute_game_start();
+            self.check_invariants();
         }
     }
 
@@ -356,25 +947,14 @@ impl Game {
     ///    // library user error why_not of type SpadesError
     /// } else {
     ///  if let Some(bet_result) = g.place_bet(bet) {
-    ///    // bet_result either BetResult::SuccessfulBet or BetResult::SuccessfulBetCompletedBetting
+    ///    // bet_result either BetResult::MadeBet or BetResult::CompletedBetting(all_bets)
     ///  }
     /// }
     /// If you don't want check for errors:
     /// let bet: Bet = Bet::Amount(5);
     /// g.place_bet(bet);
     pub fn can_place_bet(&self, bet: Bet) -> Option<SpadesError> {
-        match self.state {
-            State::GameNotStarted => Some(SpadesError::GameNotStarted),
-            State::Trick(_) => Some(SpadesError::ImproperGameStage),
-            State::GameCompleted => Some(SpadesError::GameCompleted),
-            State::Betting(_rotation_status) => {
-                if bet == Bet::BlindNil && self.player[self.current_player_index].seen_hand {
-                    Some(SpadesError::BetImproperSeenHand)
-                } else {
-                    None
-                }
-            }
-        }
+        transition::check(&self.state, &Action::Bet(bet), &self.rules_context()).err()
     }
 
     /// Make this bet for the current player.
@@ -383,7 +963,12 @@ impl Game {
             // don't do anything if can't make the bet
             None
         } else if let State::Betting(rotation_status) = self.state {
+            #[cfg(any(test, feature = "debug-invariants"))]
+            let expected = transition(&self.state, &Action::Bet(bet), &self.rules_context());
             let bet_result = self.execute_bet(rotation_status, bet);
+            #[cfg(any(test, feature = "debug-invariants"))]
+            self.assert_transition(expected.unwrap());
+            self.check_invariants();
             Some(bet_result)
         } else {
             None
@@ -393,15 +978,7 @@ impl Game {
     /// A method to determine whether a card may be played by the current player.
     /// If it would not be possible, the reason why not will be returned in Some(SpadesError).
     pub fn can_play_card(&self, card: Card) -> Option<SpadesError> {
-        match self.state {
-            State::GameNotStarted => Some(SpadesError::GameNotStarted),
-            State::GameCompleted => Some(SpadesError::GameCompleted),
-            State::Betting(_rotation_status) => Some(SpadesError::ImproperGameStage),
-            State::Trick(rotation_status) => {
-                let player_hand = &self.player[self.current_player_index].hand;
-                self.can_play_card_from_hand(rotation_status, card, player_hand)
-            }
-        }
+        transition::check(&self.state, &Action::Play(card), &self.rules_context()).err()
     }
 
     /// Play this card for the current player.
@@ -412,21 +989,21 @@ impl Game {
             // don't do anything if can't play this card
             None
         } else if let State::Trick(rotation_status) = self.state {
+            #[cfg(any(test, feature = "debug-invariants"))]
+            let expected = transition(&self.state, &Action::Play(card), &self.rules_context());
+            let hand = &mut self.player[self.current_player_index].hand;
+            match hand.iter().position(|x| x == &card) {
+                Some(card_index) => self.deck.push(hand.remove(card_index)),
+                None => return None,
+            }
             if rotation_status == 0 {
                 self.leading_suit = Some(card.suit);
             }
-            let card_index = self.player[self.current_player_index]
-                .hand
-                .iter()
-                .position(|x| x == &card)
-                .unwrap();
-            self.deck.push(
-                self.player[self.current_player_index]
-                    .hand
-                    .remove(card_index),
-            );
 
             let card_result = self.execute_play_card(rotation_status, card);
+            #[cfg(any(test, feature = "debug-invariants"))]
+            self.assert_transition(expected.unwrap());
+            self.check_invariants();
             Some(card_result)
         } else {
             None
@@ -434,18 +1011,29 @@ impl Game {
     }
 
     fn execute_game_start(&mut self) {
+        self.turn_started = None;
         self.spades_broken = false;
         self.deal_cards();
         self.state = State::Betting(0);
+        self.start_round_exchange();
     }
 
     fn execute_bet(&mut self, rotation_status: usize, bet: Bet) -> BetResult {
+        self.turn_started = None;
+        self.record_event(GameEvent::Bet {
+            player: self.player[self.current_player_index].id,
+            bet,
+        });
         self.scoring.add_bet(self.current_player_index, bet);
-        if rotation_status == 3 {
-            self.scoring.betting_over();
-            self.state = State::Trick((rotation_status + 1) % 4);
-            self.current_player_index = 0;
-            BetResult::CompletedBetting
+        self.round_bets[self.current_player_index] = Some(bet);
+        if rotation_status == 3 && self.start_bid_review() {
+            BetResult::MadeBet
+        } else if rotation_status == 3 {
+            self.finish_betting();
+            // everyone has bet now
+            BetResult::CompletedBetting(
+                self.bets_with_players().map(|(id, bet)| (id, bet.unwrap())),
+            )
         } else {
             self.current_player_index = (self.current_player_index + 1) % 4;
             self.state = State::Betting((rotation_status + 1) % 4);
@@ -453,34 +1041,40 @@ impl Game {
         }
     }
 
+    /// Moves on from betting, once the bets are final, to the first trick or the kitty exchange.
+    pub(crate) fn finish_betting(&mut self) {
+        self.scoring.betting_over();
+        self.state = State::Trick(0);
+        self.current_player_index = self.leader(0);
+        self.start_bid_winner_exchange();
+    }
+
     fn execute_play_card(&mut self, rotation_status: usize, card: Card) -> PlayCardResult {
-        if card.suit == Suit::Spades {
+        self.turn_started = None;
+        self.record_event(GameEvent::CardPlayed {
+            player: self.player[self.current_player_index].id,
+            card,
+        });
+        let breaks_spades = {
+            let context = self.play_context(
+                rotation_status,
+                &self.player[self.current_player_index].hand,
+            );
+            self.rules.get().breaks_spades(&context, card)
+        };
+        if breaks_spades {
             self.spades_broken = true;
         }
 
         self.current_trick.push(card);
 
         if rotation_status == 3 {
-            let winner = self
+            let (winner, outcome) = self
                 .scoring
                 .trick((self.current_player_index + 1) % 4, &self.current_trick);
             self.current_trick.clear();
             self.leading_suit = None;
-            if self.scoring.is_over() {
-                self.state = State::GameCompleted;
-                return PlayCardResult::GameCompleted;
-            }
-            if self.scoring.is_in_betting_stage() {
-                self.current_player_index = 0;
-                self.spades_broken = false;
-                self.bets_placed = [Bet::Amount(0); 4];
-                self.state = State::Betting(0);
-                self.deal_cards(); // NOTE: The deal should happen when move from Start to Betting
-            } else {
-                self.current_player_index = winner; // the trick winner will lead on the next trick
-                self.state = State::Trick((rotation_status + 1) % 4); // NOTE: Why not current_player_index?
-            }
-            PlayCardResult::TrickCompleted
+            self.finish_trick(winner, outcome)
         } else {
             self.current_player_index = (self.current_player_index + 1) % 4;
             self.state = State::Trick((rotation_status + 1) % 4); // NOTE: Why not current_player_index?
@@ -488,49 +1082,178 @@ impl Game {
         }
     }
 
+    /// Moves on once a trick has been scored: to the next trick, the next round's betting, or the end of the game.
+    /// `outcome` is the round's, if the trick was its last.
+    fn finish_trick(&mut self, winner: usize, outcome: Option<RoundOutcome>) -> PlayCardResult {
+        if let Some(outcome) = outcome {
+            self.reveal_deal();
+            self.record_event(GameEvent::RoundScored {
+                tricks: outcome.tricks,
+                scores: outcome.scores,
+            });
+        }
+        if self.scoring.is_over() {
+            self.state = State::GameCompleted;
+            return PlayCardResult::GameCompleted;
+        }
+        if self.scoring.is_in_betting_stage() {
+            self.current_player_index = 0;
+            self.spades_broken = false;
+            self.previous_round_bets = self.bets_placed().ok();
+            self.round_bets = [None; 4];
+            self.state = State::Betting(0);
+            self.deal_cards(); // NOTE: The deal should happen when move from Start to Betting
+            self.start_round_exchange();
+        } else {
+            // the trick winner will lead on the next trick, if the rules leave them a card to lead
+            self.current_player_index = self.leader(winner);
+            self.state = State::Trick(0);
+        }
+        PlayCardResult::TrickCompleted
+    }
+
+    #[cfg(test)]
     fn can_play_card_from_hand(
         &self,
         rotation_status: usize,
         card: Card,
         hand: &[Card],
     ) -> Option<SpadesError> {
-        if !hand.contains(&card) {
-            return Some(SpadesError::CardNotInHand);
-        }
-        let leading_suit = self.leading_suit;
-        if rotation_status == 0 {
-            // to lead spades, spades must be broken OR only have spades in this hand
-            if card.suit == Suit::Spades {
-                if self.spades_broken || !hand.iter().any(|c| c.suit != Suit::Spades) {
-                } else {
-                    return Some(SpadesError::CardIncorrectSuit);
-                }
-            }
-        }
-        if self.leading_suit != Some(card.suit) && hand.iter().any(|x| Some(x.suit) == leading_suit)
-        {
-            return Some(SpadesError::CardIncorrectSuit);
+        let context = RulesContext {
+            hand,
+            ..self.rules_context()
+        };
+        transition::card_error(&context, rotation_status, card)
+    }
+
+    fn play_context<'a>(&'a self, rotation_status: usize, hand: &'a [Card]) -> PlayContext<'a> {
+        PlayContext {
+            position_in_trick: rotation_status,
+            trick: &self.current_trick,
+            leading_suit: self.leading_suit,
+            spades_broken: self.spades_broken,
+            hand,
         }
-        None
+    }
+
+    /// Replace the rules deciding which cards may be played.  Games start with
+    /// [`StandardRules`](struct.StandardRules.html).
+    pub fn set_card_play_rules<R: CardPlayRules + 'static>(&mut self, rules: R) {
+        self.rules = Rules::<dyn CardPlayRules>::new(rules);
+    }
+
+    /// The rules deciding which cards may be played in this game.
+    pub fn card_play_rules(&self) -> &dyn CardPlayRules {
+        self.rules.get()
+    }
+
+    /// Replace the way rounds are scored and the end of the game is decided.  Games start with
+    /// [`StandardScoring`](struct.StandardScoring.html).
+    pub fn set_scoring_rules<R: ScoringRules + 'static>(&mut self, rules: R) {
+        self.scoring
+            .set_rules(Rules::<dyn ScoringRules>::new(rules));
+    }
+
+    /// The way rounds are scored in this game.
+    pub fn scoring_rules(&self) -> &dyn ScoringRules {
+        self.scoring.rules()
+    }
+
+    /// Choose how the cards are dealt from the next deal on.
+    pub fn set_deal_config(&mut self, config: DealConfig) {
+        self.deal_config = config;
+    }
+
+    pub fn deal_config(&self) -> DealConfig {
+        self.deal_config
+    }
+
+    /// Shuffle every deal from the next one on from this seed and the number of rounds played, so that
+    /// games with the same seed and the same play are dealt the same cards; or shuffle at random again with
+    /// `None`.
+    pub fn set_shuffle_seed(&mut self, seed: Option<u64>) {
+        self.shuffle_seed = seed;
+    }
+
+    pub fn shuffle_seed(&self) -> Option<u64> {
+        self.shuffle_seed
     }
 
     fn deal_cards(&mut self) {
+        let mut shuffle_seed = None;
         //        cards::shuffle(&mut self.deck);
-        let mut hands = deal_four_players(&mut self.deck);
-
-        self.player[0].hand = hands.pop().unwrap();
-        self.player[1].hand = hands.pop().unwrap();
-        self.player[2].hand = hands.pop().unwrap();
-        self.player[3].hand = hands.pop().unwrap();
+        // a kitty nobody picked up goes back in the deck
+        self.deck.append(&mut self.kitty);
+        if !self.deal_queued() {
+            let kitty_size = self.kitty_config.map_or(0, |config| config.size);
+            // start from the same order whatever order the cards were played in, so the deal depends only
+            // on the shuffle
+            self.deck.sort();
+            shuffle_seed = self.shuffle_seed;
+            let dealt = match self.shuffle_seed {
+                Some(seed) => {
+                    let round = self.scoring.rounds_played() as u64;
+                    // a misdeal is dealt again differently
+                    let redeal = self.misdeals_this_round() << 32;
+                    let shuffler =
+                        SeededShuffler::new(seed.wrapping_add(round).wrapping_add(redeal));
+                    deal_with_kitty_using(&mut self.deck, &self.deal_config, kitty_size, &shuffler)
+                }
+                None => deal_with_kitty_using(
+                    &mut self.deck,
+                    &self.deal_config,
+                    kitty_size,
+                    self.shuffler.get(),
+                ),
+            };
+            // the deck always holds every card, and the kitty's size was checked when it was set
+            let (mut hands, kitty) = dealt.expect("a full deck and a valid kitty size");
+            self.kitty = kitty;
+            self.scoring
+                .set_tricks_per_round(((52 - kitty_size) / 4) as u8);
+
+            for player in self.player.iter_mut() {
+                player.hand.clear();
+                player.hand.extend_from_slice(&hands.pop().unwrap());
+            }
+        }
 
         self.player[0].hand.sort();
         self.player[1].hand.sort();
         self.player[2].hand.sort();
         self.player[3].hand.sort();
+        for player in self.player.iter_mut() {
+            // a new hand that nobody has looked at yet
+            player.seen_hand = false;
+        }
+        self.record_deal();
+        self.record_event(GameEvent::Dealt);
+        if let Some(seed) = shuffle_seed {
+            self.record_event(GameEvent::ShuffleSeed(seed));
+        }
+        self.commit_deal();
     }
 
     pub fn is_over(&self) -> bool {
-        self.scoring.is_over()
+        self.forfeited_team.is_some() || self.scoring.is_over()
+    }
+
+    /// With the `debug-invariants` feature, panics if a change has left the game inconsistent.  Otherwise
+    /// remembers the table for [`Diagnostics::last_good`](struct.Diagnostics.html#structfield.last_good) if it
+    /// is consistent.
+    fn check_invariants(&mut self) {
+        #[cfg(feature = "debug-invariants")]
+        self.assert_invariants();
+        self.record_good_state();
+    }
+}
+
+/// `SpadesError::InvalidTeam` unless `team_id` is team 0 or team 1.
+fn check_team(team_id: usize) -> Result<(), SpadesError> {
+    if team_id < 2 {
+        Ok(())
+    } else {
+        Err(SpadesError::InvalidTeam(team_id))
     }
 }
 
@@ -539,6 +1262,7 @@ mod game_tests {
 
     #![allow(unused_variables)]
 
+    use test_support::{seated_game, started_game};
     use Bet;
     use Card;
     use Game;
@@ -548,7 +1272,8 @@ mod game_tests {
     use Suit;
     use Uid;
 
-    use crate::{BetResult, PlayCardResult};
+    use crate::ml::Action;
+    use crate::{get_trick_winner, BetResult, DealConfig, DealPattern, NilStatus, PlayCardResult};
 
     #[test]
     fn test_play_card_can_or_cannot_play() {
@@ -593,7 +1318,7 @@ mod game_tests {
             suit: Suit::Spades,
         };
         g.state = State::Trick(0);
-        g.player[0].hand = vec![qs];
+        g.rig_round([vec![qs], vec![c3c], vec![c4c], vec![aces]], &[]);
         assert_eq!(None, g.can_play_card(qs));
         assert_eq!(Some(PlayCardResult::CardPlayed), g.play_card(qs));
     }
@@ -629,7 +1354,7 @@ mod game_tests {
 
         g.current_player_index = 1;
         g.state = State::Trick(1);
-        g.player[1].hand = vec![qs];
+        g.rig_round([vec![], vec![qs], vec![c4c], vec![aces]], &[c3c]);
         assert_eq!(None, g.can_play_card(qs));
         assert_eq!(Some(PlayCardResult::CardPlayed), g.play_card(qs));
     }
@@ -656,13 +1381,13 @@ mod game_tests {
 
         g.spades_broken = false;
         assert_eq!(PlayCardResult::CardPlayed, g.execute_play_card(1, c3c));
-        assert_eq!(false, g.spades_broken);
+        assert!(!g.spades_broken);
         assert_eq!(PlayCardResult::CardPlayed, g.execute_play_card(1, qs));
-        assert_eq!(true, g.spades_broken);
+        assert!(g.spades_broken);
 
         g.spades_broken = false;
         assert_eq!(PlayCardResult::CardPlayed, g.execute_play_card(0, qs));
-        assert_eq!(true, g.spades_broken);
+        assert!(g.spades_broken);
     }
 
     #[test]
@@ -685,7 +1410,7 @@ mod game_tests {
             suit: Suit::Clubs,
         };
 
-        g.current_trick = vec![];
+        g.current_trick.clear();
         g.state = State::Trick(0);
         g.current_player_index = 0;
         assert_eq!(PlayCardResult::CardPlayed, g.execute_play_card(0, c3c));
@@ -701,7 +1426,7 @@ mod game_tests {
         assert_eq!(0, g.current_player_index); // 2 won trick, so should be next player
         assert_eq!(Vec::<Card>::new(), g.current_trick); // cards should be cleared
 
-        assert_eq!(false, g.scoring.is_over());
+        assert!(!g.scoring.is_over());
 
         //        assert_eq!(0, g.current_player_index);
         //        assert_eq!(PlayCardResult::GameCompleted, g.execute_play_card(3, qc));
@@ -843,7 +1568,7 @@ mod game_tests {
         assert_eq!(p4_uuid, players[3].id);
         let b = g.scoring;
         let spades_broken = g.spades_broken;
-        assert_eq!(false, spades_broken);
+        assert!(!spades_broken);
         let gamestate = g.state;
         assert_eq!(State::GameNotStarted, gamestate);
     }
@@ -863,7 +1588,7 @@ mod game_tests {
         assert!(players[0].hand.is_empty());
         let b = g.scoring;
         let spades_broken = g.spades_broken;
-        assert_eq!(false, spades_broken);
+        assert!(!spades_broken);
         let gamestate = g.state;
         assert_eq!(State::GameNotStarted, gamestate);
     }
@@ -880,10 +1605,7 @@ mod game_tests {
             g.team_individual_round_score(0)
         );
         assert_eq!(Err(SpadesError::GameNotStarted), g.team_all_rounds_bags(0));
-        assert_eq!(
-            Err(SpadesError::GameNotStarted),
-            g.team_all_rounds_score(0)
-        );
+        assert_eq!(Err(SpadesError::GameNotStarted), g.team_all_rounds_score(0));
         assert_eq!(Err(SpadesError::GameNotStarted), g.team_tricks_won(0));
         assert_eq!(
             Err(SpadesError::GameNotStarted),
@@ -894,10 +1616,7 @@ mod game_tests {
             g.team_individual_round_score(1)
         );
         assert_eq!(Err(SpadesError::GameNotStarted), g.team_all_rounds_bags(1));
-        assert_eq!(
-            Err(SpadesError::GameNotStarted),
-            g.team_all_rounds_score(1)
-        );
+        assert_eq!(Err(SpadesError::GameNotStarted), g.team_all_rounds_score(1));
         assert_eq!(Err(SpadesError::GameNotStarted), g.team_tricks_won(1));
     }
 
@@ -917,7 +1636,7 @@ mod game_tests {
         cpi_response = g.current_player_id();
         assert_eq!(Ok(p1_uuid), cpi_response);
         let look_at_hand_response = g.current_hand();
-        assert_eq!(true, look_at_hand_response.is_ok());
+        assert!(look_at_hand_response.is_ok());
         assert_eq!(13, look_at_hand_response.unwrap().len());
         let mut can_bet_response = g.can_place_bet(Bet::BlindNil);
         assert_eq!(Some(SpadesError::BetImproperSeenHand), can_bet_response);
@@ -936,7 +1655,24 @@ mod game_tests {
         cpi_response = g.current_player_id();
         assert_eq!(Ok(p4_uuid), cpi_response);
         place_bet_response = g.place_bet(Bet::Amount(3));
-        assert_eq!(Some(BetResult::CompletedBetting), place_bet_response);
+        assert_eq!(
+            Some(BetResult::CompletedBetting([
+                (p1_uuid, Bet::Nil),
+                (p2_uuid, Bet::Amount(3)),
+                (p3_uuid, Bet::BlindNil),
+                (p4_uuid, Bet::Amount(3))
+            ])),
+            place_bet_response
+        );
+        assert_eq!(
+            [
+                (p1_uuid, Some(Bet::Nil)),
+                (p2_uuid, Some(Bet::Amount(3))),
+                (p3_uuid, Some(Bet::BlindNil)),
+                (p4_uuid, Some(Bet::Amount(3)))
+            ],
+            g.bets_with_players()
+        );
         cpi_response = g.current_player_id();
         assert_eq!(Ok(p1_uuid), cpi_response);
         let card_to_play = g.current_hand().unwrap()[0];
@@ -946,6 +1682,52 @@ mod game_tests {
         assert_eq!(Ok(p2_uuid), cpi_response);
     }
 
+    #[test]
+    fn test_hand_is_only_seen_once_revealed() {
+        let mut g = started_game();
+        assert_eq!(13, g.hand_of_current_player().unwrap().len());
+        assert_eq!(None, g.can_place_bet(Bet::BlindNil));
+        assert_eq!(Err(SpadesError::InvalidUuid), g.reveal_hand(&Uid(99)));
+        g.reveal_hand(&Uid(10)).unwrap();
+        assert_eq!(
+            Some(SpadesError::BetImproperSeenHand),
+            g.can_place_bet(Bet::BlindNil)
+        );
+
+        // every round is dealt a fresh, unseen hand
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        for _ in 0..13 * 4 {
+            let card = *g
+                .hand_of_current_player()
+                .unwrap()
+                .iter()
+                .find(|card| g.can_play_card(**card).is_none())
+                .unwrap();
+            g.play_card(card);
+        }
+        assert_eq!(State::Betting(0), g.state());
+        assert_eq!(None, g.can_place_bet(Bet::BlindNil));
+    }
+
+    #[test]
+    fn test_playable_cards() {
+        let mut g = started_game();
+        assert_eq!(0, g.playable_cards().count());
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let lead = g.playable_cards().next().unwrap();
+        g.play_card(lead);
+        let hand = g.hand_of_current_player().unwrap();
+        let playable: Vec<Card> = g.playable_cards().collect();
+        assert!(!playable.is_empty());
+        for card in hand.iter() {
+            assert_eq!(playable.contains(card), g.can_play_card(*card).is_none());
+        }
+    }
+
     #[test]
     fn test_hand_from_player_id() {
         let game_uuid = Uid(4);
@@ -962,7 +1744,7 @@ mod game_tests {
         if let Ok(p1_hand) = p1_hand_result {
             assert_eq!(13, p1_hand.len());
         } else {
-            assert!(false); // p1 is a valid player, so should not error
+            panic!("p1 is a valid player, so should not error");
         }
         let unknown_hand_result = g.hand_from_player_id(unknown_uuid);
         assert_eq!(Err(SpadesError::InvalidUuid), unknown_hand_result);
@@ -973,4 +1755,357 @@ mod game_tests {
             Err(_err) => {}
         }
     }
+
+    #[test]
+    fn test_same_shuffle_seed_deals_same_cards() {
+        let deal = |seed| {
+            let mut g = Game::default();
+            g.set_shuffle_seed(Some(seed));
+            g.start_game();
+            let first = g.hand_of_current_player().unwrap().to_vec();
+            for _ in 0..4 {
+                g.place_bet(Bet::Amount(3));
+            }
+            for _ in 0..13 * 4 {
+                let card = g.playable_cards().next().unwrap();
+                g.play_card(card);
+            }
+            (first, g.hand_of_current_player().unwrap().to_vec())
+        };
+        let (first, second) = deal(7);
+        assert_eq!((first.clone(), second.clone()), deal(7));
+        assert_ne!(first, second);
+        assert_ne!(first, deal(8).0);
+    }
+
+    #[test]
+    fn test_winner_ids_names_the_winning_team() {
+        let mut g = started_game();
+        while g.state() != State::GameCompleted {
+            if let State::Trick(_) = g.state() {
+                let card = g.playable_cards().next().unwrap();
+                g.play_card(card);
+            } else {
+                g.place_bet(Bet::Amount(3));
+            }
+        }
+        let scores = [
+            g.team_all_rounds_score(0).unwrap(),
+            g.team_all_rounds_score(1).unwrap(),
+        ];
+        let expected = if scores[0] >= scores[1] {
+            (Uid(10), Uid(12))
+        } else {
+            (Uid(11), Uid(13))
+        };
+        assert_eq!(Ok(expected), g.winner_ids());
+    }
+
+    #[test]
+    fn test_projected_round_score() {
+        let mut g = seated_game();
+        assert_eq!(
+            Err(SpadesError::GameNotStarted),
+            g.projected_round_score(0, None)
+        );
+        g.start_game();
+        for bet in [4, 3, 2, 1].iter() {
+            assert_eq!(
+                Err(SpadesError::ImproperGameStage),
+                g.projected_round_score(0, None)
+            );
+            g.place_bet(Bet::Amount(*bet));
+        }
+
+        // before any trick is taken, the tricks are split evenly
+        let projection = g.projected_round_score(0, None).unwrap();
+        assert_eq!(7, projection.tricks);
+        assert_eq!(61, projection.points);
+        assert_eq!(1, projection.bags);
+
+        let projection = g.projected_round_score(1, Some([5, 2, 3, 3])).unwrap();
+        assert_eq!(41, projection.points);
+        assert_eq!(
+            Err(SpadesError::ImpossibleTrickCounts),
+            g.projected_round_score(1, Some([5, 2, 3, 2]))
+        );
+        assert_eq!(Ok(0), g.team_all_rounds_score(0));
+    }
+
+    #[test]
+    fn test_current_trick_leader_and_winner() {
+        let mut g = started_game();
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            g.current_trick_leader()
+        );
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        assert_eq!(Ok(Uid(10)), g.current_trick_leader());
+        assert_eq!(Ok(None), g.currently_winning_player());
+
+        let mut played = Vec::new();
+        for _ in 0..3 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+            played.push(card);
+            let winner = get_trick_winner(0, &played).unwrap();
+            assert_eq!(Ok(Uid(10)), g.current_trick_leader());
+            assert_eq!(
+                Ok(Some(Uid(10 + winner as u64))),
+                g.currently_winning_player()
+            );
+        }
+    }
+
+    #[test]
+    fn test_bets_placed_is_round_aware() {
+        let mut g = seated_game();
+        assert_eq!(Err(SpadesError::GameNotStarted), g.bets_placed());
+        g.start_game();
+        for bet in [4, 3, 2].iter() {
+            g.place_bet(Bet::Amount(*bet));
+            assert_eq!(Err(SpadesError::BettingNotComplete), g.bets_placed());
+        }
+        g.place_bet(Bet::Nil);
+        let bets = [Bet::Amount(4), Bet::Amount(3), Bet::Amount(2), Bet::Nil];
+        assert_eq!(Ok(bets), g.bets_placed());
+        assert_eq!(None, g.bets_from_previous_round());
+
+        g.concede(1).unwrap();
+        g.respond_to_claim(&Uid(10), true).unwrap();
+        g.respond_to_claim(&Uid(12), true).unwrap();
+        assert_eq!(State::Betting(0), g.state());
+        assert_eq!(Err(SpadesError::BettingNotComplete), g.bets_placed());
+        assert_eq!(Some(bets), g.bets_from_previous_round());
+        assert_eq!([None; 4], g.snapshot().bets);
+    }
+
+    #[test]
+    fn test_deal_config_is_used_for_deals() {
+        let mut g = seated_game();
+        let config = DealConfig {
+            pattern: DealPattern::Blocks(4),
+            cut: None,
+            redeal_if_void_of_spades: true,
+        };
+        g.set_deal_config(config);
+        assert_eq!(config, g.deal_config());
+        g.start_game();
+        for id in 10..14 {
+            let hand = g.hand_from_player_id(Uid(id)).unwrap();
+            assert_eq!(13, hand.len());
+            assert!(hand.iter().any(|card| card.suit == Suit::Spades));
+        }
+    }
+
+    #[test]
+    fn test_score_history_adds_up_round_scores() {
+        let mut g = started_game();
+        assert!(g.score_history().is_empty());
+        for _ in 0..3 {
+            for _ in 0..4 {
+                g.place_bet(Bet::Amount(3));
+            }
+            for _ in 0..13 * 4 {
+                let card = g.playable_cards().next().unwrap();
+                g.play_card(card);
+            }
+        }
+        let rounds = g.round_scores();
+        let history = g.score_history();
+        assert_eq!(3, rounds.len());
+        assert_eq!(rounds[0], history[0]);
+        assert_eq!(
+            [history[1][0] + rounds[2][0], history[1][1] + rounds[2][1]],
+            history[2]
+        );
+        assert_eq!(Ok(rounds[2][1]), g.team_individual_round_score(1));
+        assert_eq!(Ok(history[2][0]), g.team_all_rounds_score(0));
+    }
+
+    #[test]
+    fn test_contract_status_during_round() {
+        let mut g = started_game();
+        assert_eq!(Err(SpadesError::ImproperGameStage), g.contract_status(0));
+        g.place_bet(Bet::Nil);
+        g.place_bet(Bet::Amount(6));
+        g.place_bet(Bet::Amount(4));
+        g.place_bet(Bet::Amount(7));
+
+        let status = g.contract_status(1).unwrap();
+        assert_eq!(
+            (13, 0, 13, 13),
+            (status.bid, status.tricks, status.needed, status.remaining)
+        );
+        assert!(!status.set && !status.bagging);
+        for _ in 0..4 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+        let other = g.contract_status(1).unwrap();
+        assert_eq!(12, other.remaining);
+        assert_eq!(other.tricks == 0, other.set);
+        let status = g.contract_status(0).unwrap();
+        assert_eq!(4, status.bid);
+        assert_eq!(1, status.tricks + other.tricks);
+        assert_eq!(4 - status.tricks, status.needed);
+    }
+
+    #[test]
+    fn test_nil_status_shows_broken_nil() {
+        let mut g = started_game();
+        assert_eq!(Ok(None), g.nil_status(&Uid(10)));
+        g.place_bet(Bet::Nil);
+        g.place_bet(Bet::Amount(3));
+        assert_eq!(
+            Ok(Some(NilStatus {
+                bid: Bet::Nil,
+                broken: false
+            })),
+            g.nil_status(&Uid(10))
+        );
+        assert_eq!(Ok(None), g.nil_status(&Uid(11)));
+        assert_eq!(Err(SpadesError::InvalidUuid), g.nil_status(&Uid(99)));
+        g.place_bet(Bet::Amount(3));
+        g.place_bet(Bet::Amount(3));
+
+        for _ in 0..4 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+        let nil_won_trick = g.current_player_id() == Ok(Uid(10));
+        assert_eq!(
+            nil_won_trick,
+            g.nil_status(&Uid(10)).unwrap().unwrap().broken
+        );
+    }
+
+    #[test]
+    fn test_bets_with_players_only_shows_this_rounds_bets() {
+        let mut g = started_game();
+        assert_eq!([None; 4], g.bets_with_players().map(|(_, bet)| bet));
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        for _ in 0..13 * 4 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+        assert_eq!(State::Betting(0), g.state());
+        g.place_bet(Bet::Nil);
+        assert_eq!(
+            [
+                (Uid(10), Some(Bet::Nil)),
+                (Uid(11), None),
+                (Uid(12), None),
+                (Uid(13), None)
+            ],
+            g.bets_with_players()
+        );
+    }
+
+    #[test]
+    fn test_visible_bids_during_betting() {
+        let mut g = seated_game();
+        assert_eq!(Err(SpadesError::GameNotStarted), g.visible_bids());
+        assert_eq!(Err(SpadesError::GameNotStarted), g.partner_bid(&Uid(12)));
+        g.start_game();
+        assert!(g.visible_bids().unwrap().is_empty());
+        g.place_bet(Bet::Amount(4));
+        g.place_bet(Bet::Nil);
+        let bids = g.visible_bids().unwrap();
+        assert_eq!(2, bids.len());
+        assert_eq!(Some(&Bet::Amount(4)), bids.get(&Uid(10)));
+        assert_eq!(Some(&Bet::Nil), bids.get(&Uid(11)));
+        // the third bidder can see their partner's bid before making their own
+        assert_eq!(Ok(Some(Bet::Amount(4))), g.partner_bid(&Uid(12)));
+        assert_eq!(Ok(None), g.partner_bid(&Uid(11)));
+        assert_eq!(Err(SpadesError::InvalidUuid), g.partner_bid(&Uid(99)));
+    }
+
+    #[test]
+    fn test_try_new_checks_ids() {
+        assert_eq!(
+            Err(SpadesError::DuplicatePlayer(Uid(10))),
+            Game::try_new(Uid(1), [Uid(10); 4])
+        );
+        assert_eq!(
+            Err(SpadesError::InvalidGameId),
+            Game::try_new(Uid(0), [Uid(10), Uid(11), Uid(12), Uid(13)])
+        );
+        let mut g = Game::try_new(Uid(1), [Uid(10), Uid(11), Uid(12), Uid(13)]).unwrap();
+        assert_eq!(&Uid(1), g.id());
+        assert_eq!(
+            [Uid(10), Uid(11), Uid(12), Uid(13)],
+            g.bets_with_players().map(|(player, _)| player)
+        );
+        g.start_game();
+        assert_eq!(13, g.hand_from_player_id(Uid(12)).unwrap().len());
+    }
+
+    #[test]
+    fn test_hand_sizes_shrink_as_cards_are_played() {
+        let mut g = seated_game();
+        assert_eq!(
+            [(Uid(10), 0), (Uid(11), 0), (Uid(12), 0), (Uid(13), 0)],
+            g.hand_sizes()
+        );
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        for _ in 0..2 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+        assert_eq!(
+            [(Uid(10), 12), (Uid(11), 12), (Uid(12), 13), (Uid(13), 13)],
+            g.hand_sizes()
+        );
+    }
+
+    #[test]
+    fn test_wrong_stage_errors_name_next_player() {
+        let mut g = started_game();
+        g.place_bet(Bet::Amount(3));
+        let card = g.hand_from_player_id(Uid(11)).unwrap()[0];
+        let err = g.can_play_card(card).unwrap();
+        assert_eq!(SpadesError::NotTrickStage { next: Uid(11) }, err);
+        assert_eq!(
+            "cards aren't being played now; waiting on player 11 to act",
+            err.to_string()
+        );
+        for _ in 0..3 {
+            g.place_bet(Bet::Amount(3));
+        }
+        assert_eq!(
+            Some(SpadesError::NotBettingStage { next: Uid(10) }),
+            g.can_place_bet(Bet::Amount(3))
+        );
+    }
+
+    #[test]
+    fn test_legal_actions_for_the_player_to_act() {
+        let mut g = started_game();
+        let bets = g.legal_actions(&Uid(10)).unwrap();
+        assert_eq!(17, bets.len());
+        assert!(bets.contains(&Action::Bet(Bet::BlindNil)));
+        assert_eq!(Vec::<Action>::new(), g.legal_actions(&Uid(11)).unwrap());
+        assert_eq!(Err(SpadesError::InvalidUuid), g.legal_actions(&Uid(99)));
+
+        g.current_hand().unwrap();
+        let bets = g.legal_actions(&Uid(10)).unwrap();
+        assert_eq!(16, bets.len());
+        assert!(!bets.contains(&Action::Bet(Bet::BlindNil)));
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+
+        let leader = g.current_player_id().unwrap();
+        let cards: Vec<Action> = g.playable_cards().map(Action::Play).collect();
+        assert!(!cards.is_empty());
+        assert_eq!(cards, g.legal_actions(&leader).unwrap());
+    }
 }
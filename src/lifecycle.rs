@@ -0,0 +1,175 @@
+use game_state::State;
+use result::SpadesError;
+use {Game, Uid};
+
+/// Why a game was [abandoned](struct.Game.html#method.abandon).
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum AbandonReason {
+    /// A player left the table and was not replaced.
+    PlayerLeft(Uid),
+    /// The players agreed to stop.
+    Agreed,
+    /// An agent driven by [`run_game`](fn.run_game.html) tried a bet or card that wasn't allowed.
+    IllegalAction(Uid),
+    /// [`run_game`](fn.run_game.html) played this many rounds without either team reaching the target score,
+    /// for instance because every round both teams were set.
+    RoundLimit(usize),
+    /// Anything else, described for the players.
+    Other(String),
+}
+
+/// How a game ended before a team reached the target score.
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum PrematureEnd {
+    /// Team 0 (players 0 and 2) or Team 1 (players 1 and 3) forfeited, so the other team won.
+    Forfeit { team: usize },
+    /// The game was abandoned, without a winner.
+    Abandoned(AbandonReason),
+}
+
+impl Game {
+    /// Pause a game that is in the betting, exchange or trick stage.  No bets or cards are accepted until it is
+    /// resumed.
+    pub fn pause(&mut self) -> Result<(), SpadesError> {
+        match self.state {
+            State::Betting(_) | State::Exchange | State::BidReview | State::Trick(_) => {
+                self.paused_state = Some(self.state);
+                self.state = State::Paused;
+                Ok(())
+            }
+            State::GameNotStarted => Err(SpadesError::GameNotStarted),
+            State::GameCompleted => Err(SpadesError::GameCompleted),
+            State::Paused => Err(SpadesError::GamePaused),
+            State::Abandoned => Err(SpadesError::GameAbandoned),
+        }
+    }
+
+    /// Continue a paused game from where it left off.  The current player's turn clock starts over.
+    pub fn resume(&mut self) -> Result<(), SpadesError> {
+        match (self.state, self.paused_state) {
+            (State::Paused, Some(state)) => {
+                self.state = state;
+                self.paused_state = None;
+                self.turn_started = None;
+                Ok(())
+            }
+            (State::Paused, None) => Err(self.internal_error()),
+            _ => Err(SpadesError::ImproperGameStage),
+        }
+    }
+
+    /// End the game early, without a winner.  Any game that is not already over can be abandoned.
+    pub fn abandon(&mut self, reason: AbandonReason) -> Result<(), SpadesError> {
+        match self.state {
+            State::GameCompleted => Err(SpadesError::GameCompleted),
+            State::Abandoned => Err(SpadesError::GameAbandoned),
+            _ => {
+                self.state = State::Abandoned;
+                self.paused_state = None;
+                self.turn_started = None;
+                self.abandon_reason = Some(reason);
+                Ok(())
+            }
+        }
+    }
+
+    /// Why the game was abandoned, if it was.
+    pub fn abandon_reason(&self) -> Option<&AbandonReason> {
+        self.abandon_reason.as_ref()
+    }
+
+    /// How the game ended, if it ended by forfeit or abandonment rather than by a team reaching the target
+    /// score.
+    pub fn premature_end(&self) -> Option<PrematureEnd> {
+        if let Some(reason) = &self.abandon_reason {
+            return Some(PrematureEnd::Abandoned(reason.clone()));
+        }
+        self.forfeited_team
+            .map(|team| PrematureEnd::Forfeit { team })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AbandonReason, PrematureEnd};
+    use std::time::{Duration, Instant};
+    use test_support::started_game;
+    use {Bet, Game, SpadesError, State, TimeoutPolicy, Uid};
+
+    #[test]
+    fn test_pause_blocks_actions_until_resumed() {
+        let mut g = started_game();
+        g.place_bet(Bet::Amount(3));
+        assert_eq!(Ok(()), g.pause());
+        assert_eq!(State::Paused, g.state());
+        assert_eq!(Err(SpadesError::GamePaused), g.pause());
+        assert_eq!(
+            Some(SpadesError::GamePaused),
+            g.can_place_bet(Bet::Amount(3))
+        );
+        assert_eq!(None, g.place_bet(Bet::Amount(3)));
+        assert_eq!(Err(SpadesError::GamePaused), g.current_player_id());
+
+        assert_eq!(Ok(()), g.resume());
+        assert_eq!(State::Betting(1), g.state());
+        assert_eq!(Ok(Uid(11)), g.current_player_id());
+        assert_eq!(Err(SpadesError::ImproperGameStage), g.resume());
+    }
+
+    #[test]
+    fn test_pause_requires_game_in_progress() {
+        let mut g = Game::default();
+        assert_eq!(Err(SpadesError::GameNotStarted), g.pause());
+        g.abandon(AbandonReason::Agreed).unwrap();
+        assert_eq!(Err(SpadesError::GameAbandoned), g.pause());
+    }
+
+    #[test]
+    fn test_paused_game_does_not_time_out() {
+        let mut g = started_game();
+        g.set_turn_duration(Duration::from_secs(10));
+        let now = Instant::now();
+        g.tick(now);
+        g.pause().unwrap();
+        assert_eq!(None, g.tick(now + Duration::from_secs(60)));
+        assert_eq!(State::Paused, g.state());
+    }
+
+    #[test]
+    fn test_abandon_records_reason() {
+        let mut g = started_game();
+        assert_eq!(None, g.premature_end());
+        g.pause().unwrap();
+        let reason = AbandonReason::PlayerLeft(Uid(12));
+        assert_eq!(Ok(()), g.abandon(reason.clone()));
+        assert_eq!(State::Abandoned, g.state());
+        assert_eq!(Some(&reason), g.abandon_reason());
+        assert_eq!(Some(PrematureEnd::Abandoned(reason)), g.premature_end());
+        assert_eq!(Err(SpadesError::GameNotCompleted), g.winner_ids());
+        assert_eq!(Err(SpadesError::ImproperGameStage), g.resume());
+        assert_eq!(
+            Err(SpadesError::GameAbandoned),
+            g.abandon(AbandonReason::Agreed)
+        );
+    }
+
+    #[test]
+    fn test_forfeit_is_a_premature_end() {
+        let mut g = started_game();
+        g.set_turn_duration(Duration::from_secs(1));
+        g.set_timeout_policy(TimeoutPolicy::Forfeit);
+        let now = Instant::now();
+        g.tick(now);
+        g.tick(now + Duration::from_secs(1));
+        assert_eq!(Some(PrematureEnd::Forfeit { team: 0 }), g.premature_end());
+        assert_eq!(None, g.abandon_reason());
+        assert_eq!(
+            Err(SpadesError::GameCompleted),
+            g.abandon(AbandonReason::Agreed)
+        );
+    }
+}
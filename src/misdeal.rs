@@ -0,0 +1,217 @@
+use cards::{Card, Rank, Suit};
+use diagnostics::GameEvent;
+use game_state::State;
+use result::SpadesError;
+use {Game, PlayerId};
+
+/// Which hands entitle a player to declare a misdeal, see
+/// [`Game::declare_misdeal`](struct.Game.html#method.declare_misdeal).  By default no misdeals are allowed.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct MisdealConfig {
+    /// A player holding no spades may declare a misdeal.
+    pub void_in_spades: bool,
+    /// A player holding fewer high card points than this, counting 4 for an ace, 3 for a king, 2 for a queen
+    /// and 1 for a jack, may declare a misdeal.
+    pub min_high_card_points: Option<u8>,
+}
+
+/// The high card points of `hand`: 4 for an ace, 3 for a king, 2 for a queen and 1 for a jack.
+pub fn high_card_points(hand: &[Card]) -> u8 {
+    hand.iter()
+        .map(|card| match card.rank {
+            Rank::Ace => 4,
+            Rank::King => 3,
+            Rank::Queen => 2,
+            Rank::Jack => 1,
+            _ => 0,
+        })
+        .sum()
+}
+
+impl Game {
+    /// Choose which hands entitle a player to declare a misdeal.
+    pub fn set_misdeal_config(&mut self, config: MisdealConfig) {
+        self.misdeal_config = config;
+    }
+
+    pub fn misdeal_config(&self) -> MisdealConfig {
+        self.misdeal_config
+    }
+
+    /// Whether this player may declare a misdeal now.  If not, the reason why not is returned in
+    /// `Some(SpadesError)`: `SpadesError::ImproperGameStage` once a card has been played this round, and
+    /// `SpadesError::MisdealNotAllowed` if the player's hand doesn't qualify under the
+    /// [`MisdealConfig`](struct.MisdealConfig.html).
+    pub fn can_declare_misdeal<P: PlayerId + ?Sized>(&self, player_id: &P) -> Option<SpadesError> {
+        match self.state {
+            State::GameNotStarted => return Some(SpadesError::GameNotStarted),
+            State::GameCompleted => return Some(SpadesError::GameCompleted),
+            State::Paused => return Some(SpadesError::GamePaused),
+            State::Abandoned => return Some(SpadesError::GameAbandoned),
+            State::Betting(_) | State::Exchange | State::BidReview => {}
+            State::Trick(0)
+                if self.current_trick.is_empty()
+                    && self
+                        .scoring
+                        .player_tricks()
+                        .iter()
+                        .all(|tricks| *tricks == 0) => {}
+            State::Trick(_) => return Some(SpadesError::ImproperGameStage),
+        }
+        let seat = match self.seat_of(player_id) {
+            Ok(seat) => seat,
+            Err(err) => return Some(err),
+        };
+        let hand = &self.player[seat.index()].hand;
+        let config = self.misdeal_config;
+        let void = config.void_in_spades && !hand.iter().any(|card| card.suit == Suit::Spades);
+        let weak = config
+            .min_high_card_points
+            .is_some_and(|points| high_card_points(hand) < points);
+        if void || weak {
+            None
+        } else {
+            Some(SpadesError::MisdealNotAllowed)
+        }
+    }
+
+    /// Declare a misdeal for this player: the round's bets are forgotten, the cards gathered up and dealt
+    /// again, and betting starts over.  A misdeal is always dealt again by shuffling, even if the round was a
+    /// [queued deal](#method.queue_deal).
+    pub fn declare_misdeal<P: PlayerId + ?Sized>(
+        &mut self,
+        player_id: &P,
+    ) -> Result<(), SpadesError> {
+        if let Some(err) = self.can_declare_misdeal(player_id) {
+            return Err(err);
+        }
+        self.record_event(GameEvent::Misdeal {
+            player: player_id.to_uid(),
+        });
+        self.reveal_deal();
+        for player in self.player.iter_mut() {
+            self.deck.extend_from_slice(&player.hand);
+            player.hand.clear();
+        }
+        self.turn_started = None;
+        self.pending_claim = None;
+        self.current_trick.clear();
+        self.leading_suit = None;
+        self.spades_broken = false;
+        self.round_bets = [None; 4];
+        self.current_player_index = 0;
+        self.scoring.restart_round();
+        self.state = State::Betting(0);
+        // the next queued deal is for the next round
+        let queued = std::mem::take(&mut self.queued_deals);
+        self.deal_cards();
+        self.queued_deals = queued;
+        self.start_round_exchange();
+        self.check_invariants();
+        Ok(())
+    }
+
+    /// How many misdeals have been declared in the round being played.
+    pub(crate) fn misdeals_this_round(&self) -> u64 {
+        self.history()
+            .iter()
+            .rev()
+            .take_while(|event| !matches!(event, GameEvent::RoundScored { .. }))
+            .filter(|event| matches!(event, GameEvent::Misdeal { .. }))
+            .count() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{high_card_points, MisdealConfig};
+    use cards::Suit;
+    use test_support::seated_game;
+    use {Bet, Game, GameEvent, SpadesError, State, Uid};
+
+    fn misdeal_game(config: MisdealConfig) -> Game {
+        let mut g = seated_game();
+        g.set_misdeal_config(config);
+        g.set_shuffle_seed(Some(5));
+        g.start_game();
+        g
+    }
+
+    #[test]
+    fn test_misdeals_not_allowed_by_default() {
+        let mut g = misdeal_game(MisdealConfig::default());
+        assert_eq!(
+            Err(SpadesError::MisdealNotAllowed),
+            g.declare_misdeal(&Uid(10))
+        );
+        assert_eq!(
+            Some(SpadesError::InvalidUuid),
+            g.can_declare_misdeal(&Uid(99))
+        );
+    }
+
+    #[test]
+    fn test_misdeal_redeals_and_restarts_betting() {
+        let mut g = misdeal_game(MisdealConfig {
+            void_in_spades: false,
+            min_high_card_points: Some(41),
+        });
+        let hands: Vec<Vec<_>> = (10..14)
+            .map(|id| g.hand_from_player_id(Uid(id)).unwrap().to_vec())
+            .collect();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        assert_eq!(Ok(()), g.declare_misdeal(&Uid(12)));
+        assert_eq!(State::Betting(0), g.state());
+        assert_eq!(Ok(Uid(10)), g.current_player_id());
+        assert_eq!([None; 4], g.bets_with_players().map(|(_, bet)| bet));
+        let redealt: Vec<Vec<_>> = (10..14)
+            .map(|id| g.hand_from_player_id(Uid(id)).unwrap().to_vec())
+            .collect();
+        assert_ne!(hands, redealt);
+        assert!(redealt.iter().all(|hand| hand.len() == 13));
+        assert!(g
+            .history()
+            .contains(&GameEvent::Misdeal { player: Uid(12) }));
+        assert_eq!(1, g.round_history().len());
+
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let card = g.playable_cards().next().unwrap();
+        g.play_card(card);
+        assert_eq!(
+            Some(SpadesError::ImproperGameStage),
+            g.can_declare_misdeal(&Uid(12))
+        );
+    }
+
+    #[test]
+    fn test_high_card_points() {
+        let g = misdeal_game(MisdealConfig {
+            void_in_spades: true,
+            min_high_card_points: None,
+        });
+        let mut total = 0;
+        for id in 10..14 {
+            let hand = g.hand_from_player_id(Uid(id)).unwrap();
+            total += high_card_points(hand) as u32;
+            let void = !hand.iter().any(|card| card.suit == Suit::Spades);
+            assert_eq!(void, g.can_declare_misdeal(&Uid(id)).is_none());
+        }
+        assert_eq!(40, total);
+    }
+}
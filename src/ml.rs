@@ -0,0 +1,342 @@
+//! Fixed-size numeric encodings of a game and its actions, for training and running learned players.
+//!
+//! ```
+//! use spades::ml::{decode_action, encode_action, encode_state, legal_action_mask, Action, STATE_SIZE};
+//! use spades::{Bet, Game, Uid};
+//!
+//! let mut g = Game::default();
+//! g.assign_players(Uid(1), [Uid(10), Uid(11), Uid(12), Uid(13)]);
+//! g.start_game();
+//! assert_eq!(STATE_SIZE, encode_state(&g, &Uid(10)).unwrap().len());
+//!
+//! let index = encode_action(Action::Bet(Bet::Amount(4))).unwrap();
+//! assert_eq!(Some(Action::Bet(Bet::Amount(4))), decode_action(index));
+//! assert!(legal_action_mask(&g, &Uid(10)).unwrap()[index]);
+//! ```
+//!
+//! Everything in a state encoding is seen from one player's seat, and only holds what that player may know:
+//! their own hand, the cards played, the bets and the scores.  Seats are numbered relative to that player,
+//! 0 for the player, 1 for the player to their left, 2 for their partner and 3 for the player to their right;
+//! their team comes before the other team.  A card's index is `13 * (suit as usize) + (rank as usize - 2)`,
+//! from the two of clubs at 0 to the ace of spades at 51.
+//!
+//! | Offset | Length | Contents |
+//! |---|---|---|
+//! | `HAND_OFFSET` | 52 | 1 for each card in the player's hand |
+//! | `PLAYED_OFFSET` | 52 | 1 for each card played in an earlier trick of this round |
+//! | `TRICK_OFFSET` | 4 × 52 | 1 for the card each seat has played to the current trick |
+//! | `BIDS_OFFSET` | 4 × 6 | for each seat: 1 if they have bet, 1 for nil, 1 for blind nil, 1 for the board, 1 for ten for two hundred, tricks bet / 13 |
+//! | `TRICKS_OFFSET` | 4 | tricks each seat has taken this round / 13 |
+//! | `SCORES_OFFSET` | 4 | each team's cumulative points / points to win, then each team's bags / 10 |
+//! | `POSITION_OFFSET` | 4 | 1 for how many cards were played to the trick before the player's turn |
+//! | `PHASE_OFFSET` | 4 | 1 when betting, 1 when playing tricks, 1 when it is the player's turn, 1 once spades are broken |
+
+use cards::{Card, Rank, Suit};
+use diagnostics::GameEvent;
+use game_state::State;
+use result::SpadesError;
+use scoring::Bet;
+use seat::Seat;
+use {Game, PlayerId};
+
+pub const HAND_OFFSET: usize = 0;
+pub const PLAYED_OFFSET: usize = HAND_OFFSET + 52;
+pub const TRICK_OFFSET: usize = PLAYED_OFFSET + 52;
+pub const BIDS_OFFSET: usize = TRICK_OFFSET + 4 * 52;
+pub const TRICKS_OFFSET: usize = BIDS_OFFSET + 4 * 6;
+pub const SCORES_OFFSET: usize = TRICKS_OFFSET + 4;
+pub const POSITION_OFFSET: usize = SCORES_OFFSET + 4;
+pub const PHASE_OFFSET: usize = POSITION_OFFSET + 4;
+/// The length of every encoding returned by [`encode_state`](fn.encode_state.html).
+pub const STATE_SIZE: usize = PHASE_OFFSET + 4;
+
+/// The first action index of a bet; the indexes before it are cards.
+pub const BET_OFFSET: usize = 52;
+/// The number of action indexes: the 52 cards, then nil, blind nil, bets of 1 to 13 tricks, the board and ten
+/// for two hundred.  The numbering is fixed, so indexes can be stored or sent between programs.
+pub const ACTION_COUNT: usize = BET_OFFSET + 17;
+
+/// Something a player can do, as numbered by [`encode_action`](fn.encode_action.html).
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum Action {
+    Bet(Bet),
+    Play(Card),
+}
+
+/// The index of `card`, between 0 and 51.
+pub fn card_index(card: Card) -> usize {
+    13 * card.suit as usize + (card.rank as usize - 2)
+}
+
+/// The card with this index, if it is below 52.
+pub fn card_from_index(index: usize) -> Option<Card> {
+    if index < 52 {
+        Some(Card {
+            suit: Suit::from((index / 13) as u8),
+            rank: Rank::from((index % 13 + 2) as u8),
+        })
+    } else {
+        None
+    }
+}
+
+/// The index of `action`, below `ACTION_COUNT`: cards first, by [`card_index`](fn.card_index.html), then
+/// nil, blind nil, bets of 1 to 13 tricks, `Bet::Board` and `Bet::TenForTwoHundred`.  `None` for a bet of
+/// 0 or more than 13 tricks, which can't be placed.
+pub fn encode_action(action: Action) -> Option<usize> {
+    match action {
+        Action::Play(card) => Some(card_index(card)),
+        Action::Bet(Bet::Nil) => Some(BET_OFFSET),
+        Action::Bet(Bet::BlindNil) => Some(BET_OFFSET + 1),
+        Action::Bet(Bet::Amount(tricks)) if (1..=13).contains(&tricks) => {
+            Some(BET_OFFSET + 1 + tricks as usize)
+        }
+        Action::Bet(Bet::Amount(_)) => None,
+        Action::Bet(Bet::Board) => Some(BET_OFFSET + 15),
+        Action::Bet(Bet::TenForTwoHundred) => Some(BET_OFFSET + 16),
+    }
+}
+
+/// The action with this index, if it is below `ACTION_COUNT`.
+pub fn decode_action(index: usize) -> Option<Action> {
+    match index {
+        i if i < BET_OFFSET => card_from_index(i).map(Action::Play),
+        i if i == BET_OFFSET => Some(Action::Bet(Bet::Nil)),
+        i if i == BET_OFFSET + 1 => Some(Action::Bet(Bet::BlindNil)),
+        i if i < BET_OFFSET + 15 => Some(Action::Bet(Bet::Amount((i - BET_OFFSET - 1) as u8))),
+        i if i == BET_OFFSET + 15 => Some(Action::Bet(Bet::Board)),
+        i if i == BET_OFFSET + 16 => Some(Action::Bet(Bet::TenForTwoHundred)),
+        _ => None,
+    }
+}
+
+/// Which actions `player` may take now, by action index: their
+/// [`Game::legal_actions`](../struct.Game.html#method.legal_actions).  Nothing is legal for a
+/// player whose turn it isn't, or while the game is waiting on something other than a bet or a card, such as
+/// a discard.
+/// Returns `SpadesError::InvalidUuid` if `player` isn't playing.
+pub fn legal_action_mask<P: PlayerId + ?Sized>(
+    game: &Game,
+    player: &P,
+) -> Result<[bool; ACTION_COUNT], SpadesError> {
+    let mut mask = [false; ACTION_COUNT];
+    for index in game
+        .legal_actions(player)?
+        .into_iter()
+        .filter_map(encode_action)
+    {
+        mask[index] = true;
+    }
+    Ok(mask)
+}
+
+/// The game as seen by `perspective`, laid out as described in the [module documentation](index.html).
+/// Returns `SpadesError::InvalidUuid` if `perspective` isn't playing.
+pub fn encode_state<P: PlayerId + ?Sized>(
+    game: &Game,
+    perspective: &P,
+) -> Result<Vec<f32>, SpadesError> {
+    let seat = game.seat_of(perspective)?.index();
+    let relative = |index: usize| (index + 4 - seat) % 4;
+    let mut state = vec![0.0; STATE_SIZE];
+
+    for card in game.player[seat].hand.iter() {
+        state[HAND_OFFSET + card_index(*card)] = 1.0;
+    }
+
+    let round_start = game
+        .history()
+        .iter()
+        .rposition(|event| *event == GameEvent::Dealt)
+        .unwrap_or(0);
+    for event in game.history()[round_start..].iter() {
+        if let GameEvent::CardPlayed { card, .. } = event {
+            if !game.current_trick.contains(card) {
+                state[PLAYED_OFFSET + card_index(*card)] = 1.0;
+            }
+        }
+    }
+
+    // the current player is next to play, so the trick was led by the player as many seats to their right
+    // as cards have been played
+    let leader = (game.current_player_index + 4 - game.current_trick.len()) % 4;
+    for (position, card) in game.current_trick.iter().enumerate() {
+        let player = relative(leader + position);
+        state[TRICK_OFFSET + 52 * player + card_index(*card)] = 1.0;
+    }
+
+    for (index, bet) in game
+        .shown_bets(Some(Seat::from_index(seat)))
+        .iter()
+        .enumerate()
+    {
+        let offset = BIDS_OFFSET + 6 * relative(index);
+        let bet = match bet {
+            Some(bet) => bet,
+            None => continue,
+        };
+        state[offset] = 1.0;
+        match bet {
+            Bet::Nil => state[offset + 1] = 1.0,
+            Bet::BlindNil => state[offset + 2] = 1.0,
+            Bet::Board => state[offset + 3] = 1.0,
+            Bet::TenForTwoHundred => state[offset + 4] = 1.0,
+            Bet::Amount(_) => {}
+        }
+        state[offset + 5] = bet.tricks_required() as f32 / 13.0;
+    }
+
+    for (index, tricks) in game.scoring.player_tricks().iter().enumerate() {
+        state[TRICKS_OFFSET + relative(index)] = *tricks as f32 / 13.0;
+    }
+
+    let snapshot = game.snapshot();
+    let team = seat % 2;
+    let max_points = game.scoring.max_points() as f32;
+    state[SCORES_OFFSET] = snapshot.scores[team] as f32 / max_points;
+    state[SCORES_OFFSET + 1] = snapshot.scores[1 - team] as f32 / max_points;
+    state[SCORES_OFFSET + 2] = snapshot.bags[team] as f32 / 10.0;
+    state[SCORES_OFFSET + 3] = snapshot.bags[1 - team] as f32 / 10.0;
+
+    let to_act = game.current_player_index == seat;
+    match game.state {
+        State::Betting(_) => {
+            state[PHASE_OFFSET] = 1.0;
+        }
+        State::Trick(played) => {
+            if to_act {
+                state[POSITION_OFFSET + played] = 1.0;
+            }
+            state[PHASE_OFFSET + 1] = 1.0;
+        }
+        _ => {}
+    }
+    if to_act && game.state.acting_player_offset().is_some() {
+        state[PHASE_OFFSET + 2] = 1.0;
+    }
+    if game.spades_broken {
+        state[PHASE_OFFSET + 3] = 1.0;
+    }
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cards::new_deck;
+    use test_support::{seated_game, started_game};
+    use Uid;
+
+    #[test]
+    fn test_actions_round_trip() {
+        for index in 0..ACTION_COUNT {
+            let action = decode_action(index).unwrap();
+            assert_eq!(Some(index), encode_action(action));
+        }
+        assert_eq!(None, decode_action(ACTION_COUNT));
+        for card in new_deck() {
+            assert_eq!(Some(card), card_from_index(card_index(card)));
+        }
+        assert_eq!(None, encode_action(Action::Bet(Bet::Amount(0))));
+        assert_eq!(None, encode_action(Action::Bet(Bet::Amount(14))));
+        assert_eq!(
+            Some(Action::Bet(Bet::TenForTwoHundred)),
+            decode_action(ACTION_COUNT - 1)
+        );
+    }
+
+    #[test]
+    fn test_encode_state_tells_special_bets_apart() {
+        let mut g = started_game();
+        g.place_bet(Bet::TenForTwoHundred);
+        g.place_bet(Bet::Board);
+        g.place_bet(Bet::Amount(10));
+        g.place_bet(Bet::Amount(4));
+        let state = encode_state(&g, &Uid(10)).unwrap();
+        let bid = |seat: usize| &state[BIDS_OFFSET + 6 * seat..BIDS_OFFSET + 6 * seat + 6];
+        assert_eq!(&[1.0, 0.0, 0.0, 0.0, 1.0, 10.0 / 13.0], bid(0));
+        assert_eq!(&[1.0, 0.0, 0.0, 1.0, 0.0, 4.0 / 13.0], bid(1));
+        assert_eq!(&[1.0, 0.0, 0.0, 0.0, 0.0, 10.0 / 13.0], bid(2));
+        assert_eq!(&[1.0, 0.0, 0.0, 0.0, 0.0, 4.0 / 13.0], bid(3));
+    }
+
+    #[test]
+    fn test_legal_action_mask() {
+        let mut g = started_game();
+        let mask = legal_action_mask(&g, &Uid(10)).unwrap();
+        assert!(mask[..BET_OFFSET].iter().all(|legal| !legal));
+        assert!(mask[BET_OFFSET..].iter().all(|legal| *legal));
+        assert_eq!(
+            g.legal_actions(&Uid(10)).unwrap().len(),
+            mask.iter().filter(|legal| **legal).count()
+        );
+        assert_eq!(
+            [false; ACTION_COUNT],
+            legal_action_mask(&g, &Uid(11)).unwrap()
+        );
+
+        g.current_hand().unwrap();
+        let mask = legal_action_mask(&g, &Uid(10)).unwrap();
+        assert!(!mask[encode_action(Action::Bet(Bet::BlindNil)).unwrap()]);
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+
+        let leader = g.current_player_id().unwrap();
+        let mask = legal_action_mask(&g, &leader).unwrap();
+        let legal: Vec<Card> = (0..ACTION_COUNT)
+            .filter(|index| mask[*index])
+            .map(|index| card_from_index(index).unwrap())
+            .collect();
+        assert_eq!(g.playable_cards().collect::<Vec<Card>>(), legal);
+        assert_eq!(
+            Err(SpadesError::InvalidUuid),
+            legal_action_mask(&g, &Uid(99))
+        );
+    }
+
+    #[test]
+    fn test_encode_state_from_each_seat() {
+        let mut g = seated_game();
+        assert_eq!(Err(SpadesError::InvalidUuid), encode_state(&g, &Uid(99)));
+        g.start_game();
+        g.place_bet(Bet::Nil);
+        for _ in 0..3 {
+            g.place_bet(Bet::Amount(4));
+        }
+        for _ in 0..6 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+
+        let leader = g.current_trick_leader().unwrap();
+        let led = g.current_trick[0];
+        let to_play = g.current_player_id().unwrap();
+        for player in [Uid(10), Uid(11), Uid(12), Uid(13)].iter() {
+            let state = encode_state(&g, player).unwrap();
+            assert_eq!(STATE_SIZE, state.len());
+            let hand = g.hand_from_player_id(*player).unwrap();
+            assert_eq!(
+                hand.len(),
+                state[HAND_OFFSET..PLAYED_OFFSET].iter().sum::<f32>() as usize
+            );
+            assert_eq!(4.0, state[PLAYED_OFFSET..TRICK_OFFSET].iter().sum::<f32>());
+            let seat = g.seat_of(player).unwrap().index();
+            let leader = (g.seat_of(&leader).unwrap().index() + 4 - seat) % 4;
+            assert_eq!(1.0, state[TRICK_OFFSET + 52 * leader + card_index(led)]);
+            // every bet is in, and the nil was bet by player 0
+            let nil = (4 - seat) % 4;
+            for other in 0..4 {
+                assert_eq!(1.0, state[BIDS_OFFSET + 6 * other]);
+                let expected = if other == nil { 1.0 } else { 0.0 };
+                assert_eq!(expected, state[BIDS_OFFSET + 6 * other + 1]);
+            }
+            assert_eq!(1.0, state[PHASE_OFFSET + 1]);
+            let expected = if *player == to_play { 1.0 } else { 0.0 };
+            assert_eq!(expected, state[PHASE_OFFSET + 2]);
+            assert_eq!(expected, state[POSITION_OFFSET + 2]);
+        }
+    }
+}
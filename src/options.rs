@@ -0,0 +1,494 @@
+use diagnostics::GameEvent;
+use game_state::State;
+use result::SpadesError;
+use scoring::{Bet, TeamBid, STANDARD_BAG_PENALTY};
+use seat::Seat;
+use {Game, Uid};
+
+/// The score that ends a game unless the options say otherwise.
+const DEFAULT_TARGET_SCORE: i32 = 500;
+
+/// How long a game lasts, as one of the [`GameOptions`](struct.GameOptions.html).  Once it is over the team
+/// with the higher score wins, Team 0 (players 0 and 2) on a tie.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum GameLength {
+    /// Until the end of the round in which a team reaches this score, which must be above 0.
+    TargetScore(i32),
+    /// A single round, whatever the scores.
+    SingleRound,
+    /// This many rounds, at least one, whatever the scores.
+    FixedRounds(u32),
+}
+
+impl GameLength {
+    /// Play to `points`, or `SpadesError::InvalidGameLength` unless it is above 0.
+    pub fn target_score(points: i32) -> Result<GameLength, SpadesError> {
+        let length = GameLength::TargetScore(points);
+        if length.is_valid() {
+            Ok(length)
+        } else {
+            Err(SpadesError::InvalidGameLength)
+        }
+    }
+
+    /// Play `rounds` rounds, or `SpadesError::InvalidGameLength` if that is none.
+    pub fn fixed_rounds(rounds: u32) -> Result<GameLength, SpadesError> {
+        let length = GameLength::FixedRounds(rounds);
+        if length.is_valid() {
+            Ok(length)
+        } else {
+            Err(SpadesError::InvalidGameLength)
+        }
+    }
+
+    /// Whether a game can be played to this length: a target score above 0, or at least one round.
+    pub fn is_valid(self) -> bool {
+        match self {
+            GameLength::TargetScore(points) => points > 0,
+            GameLength::SingleRound => true,
+            GameLength::FixedRounds(rounds) => rounds > 0,
+        }
+    }
+
+    /// The number of rounds the game lasts, if that is fixed.
+    pub fn rounds(self) -> Option<u32> {
+        match self {
+            GameLength::TargetScore(_) => None,
+            GameLength::SingleRound => Some(1),
+            GameLength::FixedRounds(rounds) => Some(rounds),
+        }
+    }
+}
+
+impl Default for GameLength {
+    fn default() -> Self {
+        GameLength::TargetScore(DEFAULT_TARGET_SCORE)
+    }
+}
+
+/// When the bets of a round are shown, as one of the [`GameOptions`](struct.GameOptions.html).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum BidVisibility {
+    /// Each bet as soon as it is made.
+    #[default]
+    AsPlaced,
+    /// Only once all four players have bet, so nobody can bid on a signal from their partner's bid.  Until
+    /// then each player sees their own bet alone.
+    AfterBetting,
+}
+
+/// Table options changing how betting goes, set with [`Game::set_options`](struct.Game.html#method.set_options).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct GameOptions {
+    /// Once everyone has bet, the last bidder's partner may amend their own bet once before play begins, see
+    /// [`Game::review_bet`](struct.Game.html#method.review_bet).
+    pub bid_review: bool,
+    /// The fewest tricks a team may bid between its two players.  The second of them to bet must bid enough
+    /// to make up the difference, or `SpadesError::TeamBidTooLow` is returned.
+    pub minimum_team_bid: Option<u8>,
+    /// How long the game lasts: by default, until a team reaches 500.
+    pub length: GameLength,
+    /// The points taken off a team for every 10 bags, instead of 100.
+    pub bag_penalty: Option<i32>,
+    /// Commit to every deal before play and reveal it afterwards, see the [`fairness`](fairness/index.html)
+    /// module.
+    pub audit_deals: bool,
+    /// When bets are shown by [`visible_bids`](struct.Game.html#method.visible_bids),
+    /// [`partner_bid`](struct.Game.html#method.partner_bid), [snapshots](struct.GameSnapshot.html) and
+    /// [player views](struct.PlayerView.html).  Accessors meant for the server alone, such as
+    /// [`bets_with_players`](struct.Game.html#method.bets_with_players) and the event log, hold every bet
+    /// regardless, so send clients views.  A `minimum_team_bid` still rejects a bet by what the partner bid.
+    #[serde(default)]
+    pub bid_visibility: BidVisibility,
+}
+
+impl Game {
+    /// A game with the id `id` and `options`, with no players seated yet: the first step of setting up a
+    /// table whose seats fill up later, with [`seat_players`](#method.seat_players).  Returns
+    /// `SpadesError::InvalidGameId` for the id 0, which is every default game's, and
+    /// `SpadesError::InvalidGameLength` if the options' length isn't valid.
+    pub fn with_options(id: Uid, options: GameOptions) -> Result<Game, SpadesError> {
+        if id == Uid(0) {
+            return Err(SpadesError::InvalidGameId);
+        }
+        let mut game = Game {
+            id,
+            ..Game::default()
+        };
+        game.set_options(options)?;
+        Ok(game)
+    }
+
+    /// Choose the table options, from the next bet on.  Returns `SpadesError::InvalidGameLength`, leaving
+    /// the options as they were, if the [`length`](struct.GameOptions.html#structfield.length) isn't
+    /// [valid](enum.GameLength.html#method.is_valid).
+    pub fn set_options(&mut self, options: GameOptions) -> Result<(), SpadesError> {
+        if !options.length.is_valid() {
+            return Err(SpadesError::InvalidGameLength);
+        }
+        self.options = options;
+        let max_points = match options.length {
+            GameLength::TargetScore(points) => points,
+            GameLength::SingleRound | GameLength::FixedRounds(_) => DEFAULT_TARGET_SCORE,
+        };
+        self.scoring.set_max_points(max_points);
+        self.scoring.set_round_limit(options.length.rounds());
+        self.scoring
+            .set_bag_penalty(options.bag_penalty.unwrap_or(STANDARD_BAG_PENALTY));
+        Ok(())
+    }
+
+    /// Whether the options may be [amended](#method.amend_options) now.  If not, the reason why not is
+    /// returned in `Some(SpadesError)`: `SpadesError::ImproperGameStage` once the round's first bet has been
+    /// made, until the next round is dealt.
+    pub fn can_amend_options(&self) -> Option<SpadesError> {
+        match self.state {
+            State::GameNotStarted => Some(SpadesError::GameNotStarted),
+            State::GameCompleted => Some(SpadesError::GameCompleted),
+            State::Paused => Some(SpadesError::GamePaused),
+            State::Abandoned => Some(SpadesError::GameAbandoned),
+            State::Betting(0) if self.round_bets.iter().all(Option::is_none) => None,
+            _ => Some(SpadesError::ImproperGameStage),
+        }
+    }
+
+    /// Changes the table options between rounds, as players sometimes agree to play on to a higher target
+    /// score.  Allowed only before the first bet of a round, see
+    /// [`can_amend_options`](#method.can_amend_options); use [`set_options`](#method.set_options) before the
+    /// game starts.  Whether the game is over is next decided at the end of the round about to be played.
+    pub fn amend_options(&mut self, options: GameOptions) -> Result<(), SpadesError> {
+        if let Some(err) = self.can_amend_options() {
+            return Err(err);
+        }
+        let from = self.options;
+        self.set_options(options)?;
+        self.record_event(GameEvent::OptionsAmended { from, to: options });
+        self.check_invariants();
+        Ok(())
+    }
+
+    pub fn options(&self) -> GameOptions {
+        self.options
+    }
+
+    /// The score that ends the game, once a team reaches it.  Games of a
+    /// [fixed number of rounds](enum.GameLength.html#variant.FixedRounds) give 500, though no score ends them.
+    pub fn max_points(&self) -> i32 {
+        self.scoring.max_points()
+    }
+
+    /// This round's bets as the player in `viewer` may see them, or as anyone may if `viewer` is `None`: with
+    /// [`BidVisibility::AfterBetting`](enum.BidVisibility.html#variant.AfterBetting), nobody else's until
+    /// everyone has bet.
+    pub(crate) fn shown_bets(&self, viewer: Option<Seat>) -> [Option<Bet>; 4] {
+        let mut bets = self.round_bets;
+        if self.options.bid_visibility == BidVisibility::AfterBetting
+            && bets.iter().any(Option::is_none)
+        {
+            for (index, bet) in bets.iter_mut().enumerate() {
+                if viewer.is_none_or(|seat| seat.index() != index) {
+                    *bet = None;
+                }
+            }
+        }
+        bets
+    }
+
+    /// Whether the current player betting `bet` would leave their team short of the
+    /// [`minimum_team_bid`](struct.GameOptions.html#structfield.minimum_team_bid), once their partner has bet.
+    pub(crate) fn check_team_bid(&self, bet: Bet) -> Option<SpadesError> {
+        let partner = self.round_bets[(self.current_player_index + 2) % 4];
+        team_bid_error(self.options.minimum_team_bid, partner, bet)
+    }
+}
+
+/// `SpadesError::TeamBidTooLow` if `bet` and the `partner`'s bet, once they have bet, come to fewer tricks than
+/// the `minimum`.
+pub(crate) fn team_bid_error(
+    minimum: Option<u8>,
+    partner: Option<Bet>,
+    bet: Bet,
+) -> Option<SpadesError> {
+    let minimum = minimum?;
+    if TeamBid::new(partner?, bet).tricks_required() < minimum {
+        Some(SpadesError::TeamBidTooLow(minimum))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BidVisibility, GameLength, GameOptions};
+    use test_support::seated_game;
+    use {Bet, Game, GameEvent, SpadesError, State, Uid};
+
+    #[test]
+    fn test_second_bidder_makes_up_minimum_team_bid() {
+        let mut g = seated_game();
+        g.set_options(GameOptions {
+            minimum_team_bid: Some(4),
+            ..GameOptions::default()
+        })
+        .unwrap();
+        g.start_game();
+        g.place_bet(Bet::Nil);
+        g.place_bet(Bet::Amount(5));
+        assert_eq!(
+            Some(SpadesError::TeamBidTooLow(4)),
+            g.can_place_bet(Bet::Amount(3))
+        );
+        assert_eq!(None, g.place_bet(Bet::Nil));
+        assert_eq!(None, g.can_place_bet(Bet::Amount(4)));
+        g.place_bet(Bet::Amount(4));
+        // partner's 5 covers the minimum on its own
+        assert_eq!(None, g.can_place_bet(Bet::Nil));
+        g.place_bet(Bet::Nil);
+        assert_eq!(State::Trick(0), g.state());
+    }
+
+    #[test]
+    fn test_rules_are_in_snapshots() {
+        let mut g = Game::default();
+        assert_eq!(500, g.max_points());
+        let before = g.snapshot();
+        assert_eq!(500, before.max_points);
+        let options = GameOptions {
+            bid_review: true,
+            minimum_team_bid: Some(4),
+            ..GameOptions::default()
+        };
+        g.set_options(options).unwrap();
+        assert_eq!(options, g.options());
+        let snapshot = g.snapshot();
+        assert_eq!(options, snapshot.options);
+        let delta = snapshot.diff(&before);
+        assert_eq!(Some(options), delta.options);
+        assert_eq!(None, delta.max_points);
+    }
+
+    #[test]
+    fn test_minimum_team_bid_holds_through_review() {
+        let mut g = seated_game();
+        g.set_options(GameOptions {
+            bid_review: true,
+            minimum_team_bid: Some(4),
+            ..GameOptions::default()
+        })
+        .unwrap();
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(2));
+        }
+        assert_eq!(
+            Some(SpadesError::TeamBidTooLow(4)),
+            g.can_review_bet(Some(Bet::Nil))
+        );
+        assert_eq!(None, g.can_review_bet(Some(Bet::Amount(3))));
+    }
+
+    #[test]
+    fn test_options_are_amended_between_rounds() {
+        let mut g = seated_game();
+        g.set_shuffle_seed(Some(9));
+        let extended = GameOptions {
+            length: GameLength::TargetScore(1000),
+            ..GameOptions::default()
+        };
+        assert_eq!(Err(SpadesError::GameNotStarted), g.amend_options(extended));
+        g.start_game();
+        assert_eq!(None, g.can_amend_options());
+        g.place_bet(Bet::Amount(3));
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            g.amend_options(extended)
+        );
+        for _ in 0..3 {
+            g.place_bet(Bet::Amount(3));
+        }
+        for _ in 0..52 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+        assert_eq!(Ok(()), g.amend_options(extended));
+        assert_eq!(1000, g.max_points());
+        assert_eq!(
+            Some(&GameEvent::OptionsAmended {
+                from: GameOptions::default(),
+                to: extended,
+            }),
+            g.history().last()
+        );
+    }
+
+    #[test]
+    fn test_bag_penalty_option() {
+        let mut g = Game::default();
+        let bets = [Bet::Amount(3); 4];
+        // team 0 takes 2 bags on top of the 9 it had
+        let standard = g.scoring.score_bets(&bets, &[4, 1, 4, 4], &[9, 0]);
+        g.set_options(GameOptions {
+            bag_penalty: Some(150),
+            ..GameOptions::default()
+        })
+        .unwrap();
+        let harsh = g.scoring.score_bets(&bets, &[4, 1, 4, 4], &[9, 0]);
+        assert_eq!(standard[0].points - 50, harsh[0].points);
+        assert_eq!(standard[1], harsh[1]);
+        assert_eq!(1, harsh[0].cumulative_bags);
+    }
+
+    fn play_round(g: &mut Game) {
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        for _ in 0..52 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+    }
+
+    #[test]
+    fn test_nonsensical_game_lengths_are_rejected() {
+        assert_eq!(
+            Err(SpadesError::InvalidGameLength),
+            GameLength::target_score(-1)
+        );
+        assert_eq!(
+            Err(SpadesError::InvalidGameLength),
+            GameLength::target_score(0)
+        );
+        assert_eq!(
+            Err(SpadesError::InvalidGameLength),
+            GameLength::fixed_rounds(0)
+        );
+        assert_eq!(Ok(GameLength::FixedRounds(3)), GameLength::fixed_rounds(3));
+
+        let mut g = Game::default();
+        let bad = GameOptions {
+            length: GameLength::TargetScore(-1),
+            bid_review: true,
+            ..GameOptions::default()
+        };
+        assert_eq!(Err(SpadesError::InvalidGameLength), g.set_options(bad));
+        assert_eq!(GameOptions::default(), g.options());
+        assert_eq!(500, g.max_points());
+    }
+
+    #[test]
+    fn test_two_phase_setup() {
+        let options = GameOptions {
+            length: GameLength::SingleRound,
+            ..GameOptions::default()
+        };
+        assert_eq!(
+            Err(SpadesError::InvalidGameLength),
+            Game::with_options(
+                Uid(1),
+                GameOptions {
+                    length: GameLength::FixedRounds(0),
+                    ..GameOptions::default()
+                }
+            )
+        );
+        let mut g = Game::with_options(Uid(1), options).unwrap();
+        assert_eq!(&Uid(1), g.id());
+        assert_eq!(options, g.options());
+
+        assert_eq!(
+            Err(SpadesError::DuplicatePlayer(Uid(11))),
+            g.seat_players([Uid(10), Uid(11), Uid(12), Uid(11)])
+        );
+        assert_eq!(Uid::default(), g.player[0].id);
+        g.seat_players([Uid(10), Uid(11), Uid(12), Uid(13)])
+            .unwrap();
+        assert_eq!(&Uid(1), g.id());
+        assert_eq!(Uid(13), g.player[3].id);
+        g.start_game();
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            g.seat_players([Uid(20), Uid(21), Uid(22), Uid(23)])
+        );
+    }
+
+    #[test]
+    fn test_fixed_number_of_rounds() {
+        for (length, rounds) in [
+            (GameLength::SingleRound, 1),
+            (GameLength::FixedRounds(3), 3),
+        ]
+        .iter()
+        {
+            let mut g = seated_game();
+            g.set_shuffle_seed(Some(4));
+            g.set_options(GameOptions {
+                length: *length,
+                ..GameOptions::default()
+            })
+            .unwrap();
+            g.start_game();
+            for _ in 0..*rounds {
+                assert!(!g.is_over());
+                play_round(&mut g);
+            }
+            assert_eq!(State::GameCompleted, g.state());
+            assert_eq!(*rounds, g.result().unwrap().rounds_played);
+        }
+    }
+
+    #[test]
+    fn test_bids_hidden_until_everyone_has_bet() {
+        let mut g = seated_game();
+        g.set_options(GameOptions {
+            bid_visibility: BidVisibility::AfterBetting,
+            ..GameOptions::default()
+        })
+        .unwrap();
+        g.start_game();
+        g.place_bet(Bet::Amount(4));
+        g.place_bet(Bet::Nil);
+        assert!(g.visible_bids().unwrap().is_empty());
+        assert_eq!(Ok(None), g.partner_bid(&Uid(12)));
+        assert_eq!([None; 4], g.snapshot().bets);
+        let view = g.player_view(&Uid(10)).unwrap();
+        assert_eq!([Some(Bet::Amount(4)), None, None, None], view.table.bets);
+        assert_eq!(None, view.relative().left.bet);
+        // the server still knows every bet
+        assert_eq!(Some(Bet::Nil), g.bets_with_players()[1].1);
+
+        g.place_bet(Bet::Amount(3));
+        g.place_bet(Bet::Amount(2));
+        assert_eq!(4, g.visible_bids().unwrap().len());
+        assert_eq!(Ok(Some(Bet::Amount(4))), g.partner_bid(&Uid(12)));
+        assert_eq!(
+            Some(Bet::Nil),
+            g.player_view(&Uid(10)).unwrap().relative().left.bet
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use Uid;
+
+/// An identity that can stand in for a player's [`Uid`](struct.Uid.html).
+///
+/// Servers usually already have their own user ids (database keys, names, `uuid::Uuid`s).  Implementing this
+/// trait maps them onto the `Uid`s the engine stores, so the same conversion can be used everywhere a `Uid`
+/// is expected instead of keeping a separate lookup table:
+///
+/// ```
+/// use spades::{Game, PlayerId, Uid};
+///
+/// let mut g = Game::default();
+/// g.assign_player_ids(&"table 7", &["ann", "bob", "cat", "dan"]).unwrap();
+/// g.start_game();
+/// assert_eq!(Ok("ann".to_uid()), g.current_player_id());
+/// assert!(g.hand_from_player_id("bob".to_uid()).is_ok());
+/// ```
+///
+/// Strings (and `uuid::Uuid`s, with the `uuid` feature) are hashed down to 64 bits with FNV-1a, which gives
+/// the same `Uid` on every platform and every run, but two different ids may in principle collide.
+/// [`Game::assign_player_ids`](struct.Game.html#method.assign_player_ids) refuses to seat players whose ids
+/// do, rather than mistake one for the other.
+pub trait PlayerId {
+    fn to_uid(&self) -> Uid;
+}
+
+impl PlayerId for Uid {
+    fn to_uid(&self) -> Uid {
+        *self
+    }
+}
+
+impl PlayerId for u64 {
+    fn to_uid(&self) -> Uid {
+        Uid(*self)
+    }
+}
+
+impl PlayerId for str {
+    fn to_uid(&self) -> Uid {
+        Uid(fnv1a(self.as_bytes()))
+    }
+}
+
+impl PlayerId for String {
+    fn to_uid(&self) -> Uid {
+        self.as_str().to_uid()
+    }
+}
+
+impl<T: PlayerId + ?Sized> PlayerId for &T {
+    fn to_uid(&self) -> Uid {
+        (**self).to_uid()
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl PlayerId for uuid::Uuid {
+    fn to_uid(&self) -> Uid {
+        Uid(fnv1a(self.as_bytes()))
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fnv1a, PlayerId};
+    use {Game, SpadesError, Uid};
+
+    /// An id whose `Uid` is only its table, so that players at the same table collide.
+    #[derive(PartialEq)]
+    struct Seat {
+        table: u64,
+        seat: u64,
+    }
+
+    impl PlayerId for Seat {
+        fn to_uid(&self) -> Uid {
+            Uid(self.table)
+        }
+    }
+
+    #[test]
+    fn test_uid_and_u64_are_unchanged() {
+        assert_eq!(Uid(42), Uid(42).to_uid());
+        assert_eq!(Uid(42), 42u64.to_uid());
+    }
+
+    #[test]
+    fn test_fnv1a_known_values() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, fnv1a(b""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, fnv1a(b"a"));
+        assert_eq!(0x8594_4171_f739_67e8, fnv1a(b"foobar"));
+    }
+
+    #[test]
+    fn test_strings_map_consistently() {
+        assert_eq!("alice".to_uid(), String::from("alice").to_uid());
+        assert_eq!("alice".to_uid(), (&"alice").to_uid());
+        assert_ne!("alice".to_uid(), "bob".to_uid());
+    }
+
+    #[test]
+    fn test_colliding_ids_are_refused() {
+        let mut g = Game::default();
+        let seats = [0, 1, 2, 3].map(|seat| Seat {
+            table: seat % 3 + 10,
+            seat,
+        });
+        assert_eq!(
+            Err(SpadesError::PlayerIdCollision(Uid(10))),
+            g.assign_player_ids(&1, &seats)
+        );
+        assert_eq!(&Uid(0), g.id());
+        assert_eq!(
+            Err(SpadesError::DuplicatePlayer("ann".to_uid())),
+            g.assign_player_ids(&1, &["ann", "bob", "ann", "dan"])
+        );
+        let seats = [0, 1, 2, 3].map(|seat| Seat {
+            table: seat + 10,
+            seat,
+        });
+        assert_eq!(Ok(()), g.assign_player_ids(&1, &seats));
+        assert_eq!(&Uid(1), g.id());
+        g.start_game();
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            g.assign_player_ids(&2, &seats)
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use diagnostics::GameEvent;
+use result::SpadesError;
+use std::cmp::Ordering;
+use {Game, PlayerId};
+
+/// What a server knows about a player beyond their [`Uid`](struct.Uid.html), carried by the game so clients
+/// don't have to look it up elsewhere.  Set with [`Game::set_player_info`](struct.Game.html#method.set_player_info).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct PlayerInfo {
+    pub name: String,
+    pub avatar: Option<String>,
+    /// Anything else the server wants to pass along; the engine never looks at it.
+    pub metadata: serde_json::Value,
+}
+
+impl PartialOrd for PlayerInfo {
+    fn partial_cmp(&self, other: &PlayerInfo) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PlayerInfo {
+    fn cmp(&self, other: &PlayerInfo) -> Ordering {
+        (&self.name, &self.avatar)
+            .cmp(&(&other.name, &other.avatar))
+            .then_with(|| self.metadata.to_string().cmp(&other.metadata.to_string()))
+    }
+}
+
+impl Game {
+    /// Attach `info` to this player, replacing any set before.  It is shown in every
+    /// [`snapshot`](#method.snapshot) and recorded in the game's [`history`](#method.history).
+    /// Returns `SpadesError::InvalidUuid` if the player isn't in the game.
+    pub fn set_player_info<P: PlayerId + ?Sized>(
+        &mut self,
+        player_id: &P,
+        info: PlayerInfo,
+    ) -> Result<(), SpadesError> {
+        let seat = self.seat_of(player_id)?;
+        self.record_event(GameEvent::PlayerInfoChanged {
+            player: player_id.to_uid(),
+            info: info.clone(),
+        });
+        self.player[seat.index()].info = Some(info);
+        Ok(())
+    }
+
+    /// The info attached to this player, if any.
+    pub fn player_info<P: PlayerId + ?Sized>(
+        &self,
+        player_id: &P,
+    ) -> Result<Option<&PlayerInfo>, SpadesError> {
+        let seat = self.seat_of(player_id)?;
+        Ok(self.player[seat.index()].info.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PlayerInfo;
+    use test_support::seated_game;
+    use {Game, GameEvent, SpadesError, Uid};
+
+    #[test]
+    fn test_player_info_is_carried_along() {
+        let mut g = seated_game();
+        let info = PlayerInfo {
+            name: "Ann".to_string(),
+            avatar: Some("fox".to_string()),
+            metadata: serde_json::json!({ "rating": 1500 }),
+        };
+        assert_eq!(
+            Err(SpadesError::InvalidUuid),
+            g.set_player_info(&Uid(99), info.clone())
+        );
+        assert_eq!(Ok(None), g.player_info(&Uid(11)));
+        g.set_player_info(&Uid(11), info.clone()).unwrap();
+        assert_eq!(Ok(Some(&info)), g.player_info(&Uid(11)));
+        assert_eq!(Some(info.clone()), g.snapshot().player_info[1]);
+        assert_eq!(
+            Some(&GameEvent::PlayerInfoChanged {
+                player: Uid(11),
+                info: info.clone()
+            }),
+            g.history().last()
+        );
+
+        let json = serde_json::to_string(&g).unwrap();
+        let loaded: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(Ok(Some(&info)), loaded.player_info(&Uid(11)));
+        assert_eq!(g, loaded);
+    }
+}
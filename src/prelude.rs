@@ -0,0 +1,19 @@
+//! The types needed to drive a game, importable in one line.
+//!
+//! ```
+//! use spades::prelude::*;
+//!
+//! let mut g = Game::with_options(Uid(1), GameOptions::default()).unwrap();
+//! g.assign_players(Uid(1), [Uid(2), Uid(3), Uid(4), Uid(5)]);
+//! g.start_game();
+//! assert_eq!(Some(BetResult::MadeBet), g.place_bet(Bet::Amount(3)));
+//!
+//! let bet = Action::Bet(Bet::Amount(3));
+//! assert_eq!(Some(bet), spades::ml::decode_action(spades::ml::encode_action(bet).unwrap()));
+//! ```
+
+pub use ml::Action;
+pub use {
+    Bet, BetResult, Card, Game, GameOptions, GameSnapshot, PlayCardResult, PlayerId, Rank, Seat,
+    SnapshotDelta, SpadesError, State, Suit, Uid,
+};
@@ -1,4 +1,6 @@
+use cards::Card;
 use std::fmt;
+use Uid;
 
 #[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum SpadesError {
@@ -6,10 +8,58 @@ pub enum SpadesError {
     GameNotStarted,
     GameCompleted,
     GameNotCompleted,
+    BettingNotComplete,
+    GamePaused,
+    GameAbandoned,
     BetImproperSeenHand,
     CardIncorrectSuit,
     CardNotInHand,
     ImproperGameStage,
+    /// A bet was made outside the betting stage; `next` is the player the game is waiting on instead.
+    NotBettingStage {
+        next: Uid,
+    },
+    /// A card was played outside the trick stage; `next` is the player the game is waiting on instead.
+    NotTrickStage {
+        next: Uid,
+    },
+    ClaimNotProvable,
+    ClaimPending,
+    NoPendingClaim,
+    ImpossibleTrickCounts,
+    WrongDiscardCount,
+    InvalidDeal,
+    MisdealNotAllowed,
+    NotARevoke,
+    RevokeAlreadyPenalized,
+    TeamBidTooLow(u8),
+    InvalidBet,
+    InvalidGameLength,
+    /// A team other than 0 or 1.
+    InvalidTeam(usize),
+    /// A kitty that isn't a multiple of 4 cards below 52.
+    InvalidKittySize(usize),
+    /// The same player was given more than one seat.
+    DuplicatePlayer(Uid),
+    /// Two different players' own ids map to the same `Uid`, given here.
+    PlayerIdCollision(Uid),
+    /// A game was made with the id 0, which is left to default games.
+    InvalidGameId,
+    /// Text that isn't a [`Command`](enum.Command.html), with a message saying why.
+    InvalidCommand(String),
+    ReplayMismatch(u64),
+    UnknownMatch(usize),
+    /// More bags than a team can carry into a game, which is 9.
+    TooManyBags(u8),
+    RubberOver,
+    UnsupportedSchemaVersion(u32),
+    /// A saved game whose hands, kitty and played cards aren't one standard deck between them.
+    CorruptDeck {
+        missing: Vec<Card>,
+        duplicated: Vec<Card>,
+    },
+    /// A saved game holding a value no game can reach, such as a fifth seat, named in the message.
+    CorruptSave(String),
     InternalError, // error within library
 }
 
@@ -28,6 +78,15 @@ impl fmt::Display for SpadesError {
             SpadesError::GameNotCompleted => {
                 write!(f, "game is not complete")
             }
+            SpadesError::BettingNotComplete => {
+                write!(f, "not every player has bet yet")
+            }
+            SpadesError::GamePaused => {
+                write!(f, "game is paused")
+            }
+            SpadesError::GameAbandoned => {
+                write!(f, "game was abandoned")
+            }
             SpadesError::BetImproperSeenHand => {
                 write!(f, "blind nil bet improper; seen hand")
             }
@@ -40,6 +99,108 @@ impl fmt::Display for SpadesError {
             SpadesError::ImproperGameStage => {
                 write!(f, "improper stage of game to take that action")
             }
+            SpadesError::NotBettingStage { next } => {
+                write!(
+                    f,
+                    "bets aren't being taken now; waiting on player {} to act",
+                    next.0
+                )
+            }
+            SpadesError::NotTrickStage { next } => {
+                write!(
+                    f,
+                    "cards aren't being played now; waiting on player {} to act",
+                    next.0
+                )
+            }
+            SpadesError::ClaimNotProvable => {
+                write!(f, "claim of remaining tricks is not certain to succeed")
+            }
+            SpadesError::ClaimPending => {
+                write!(f, "a claim is waiting to be answered")
+            }
+            SpadesError::NoPendingClaim => {
+                write!(f, "no claim to answer")
+            }
+            SpadesError::ImpossibleTrickCounts => {
+                write!(f, "trick counts can't happen in this round")
+            }
+            SpadesError::WrongDiscardCount => {
+                write!(f, "must discard as many cards as were picked up")
+            }
+            SpadesError::InvalidDeal => {
+                write!(
+                    f,
+                    "hands must hold 13 cards each and the whole deck between them"
+                )
+            }
+            SpadesError::MisdealNotAllowed => {
+                write!(f, "hand does not allow a misdeal")
+            }
+            SpadesError::NotARevoke => {
+                write!(f, "no such revoke in this game")
+            }
+            SpadesError::RevokeAlreadyPenalized => {
+                write!(f, "revoke has already been penalized")
+            }
+            SpadesError::TeamBidTooLow(minimum) => {
+                write!(f, "team must bid at least {} tricks between them", minimum)
+            }
+            SpadesError::InvalidBet => {
+                write!(f, "bet must be nil or between 1 and 13 tricks")
+            }
+            SpadesError::InvalidGameLength => {
+                write!(
+                    f,
+                    "a game must last at least one round, or to a target score above 0"
+                )
+            }
+            SpadesError::InvalidTeam(team) => {
+                write!(f, "no team {}; teams are 0 and 1", team)
+            }
+            SpadesError::InvalidKittySize(size) => {
+                write!(
+                    f,
+                    "a kitty of {} cards isn't a multiple of 4 below 52",
+                    size
+                )
+            }
+            SpadesError::DuplicatePlayer(player) => {
+                write!(f, "player {} can't take more than one seat", player.0)
+            }
+            SpadesError::PlayerIdCollision(player) => {
+                write!(f, "two different players' ids both map to {}", player.0)
+            }
+            SpadesError::InvalidGameId => write!(f, "a game's id can't be 0"),
+            SpadesError::InvalidCommand(message) => {
+                write!(f, "{}", message)
+            }
+            SpadesError::ReplayMismatch(seq) => {
+                write!(f, "replay went differently at event {}", seq)
+            }
+            SpadesError::UnknownMatch(index) => write!(f, "no match {} in the schedule", index),
+            SpadesError::TooManyBags(bags) => {
+                write!(f, "{} bags is too many to carry into a game", bags)
+            }
+            SpadesError::RubberOver => write!(f, "the rubber has already been won"),
+            SpadesError::UnsupportedSchemaVersion(version) => {
+                write!(f, "unsupported schema version {}", version)
+            }
+            SpadesError::CorruptDeck {
+                missing,
+                duplicated,
+            } => {
+                write!(f, "cards don't make up one deck")?;
+                for (label, cards) in [("missing", missing), ("duplicated", duplicated)] {
+                    if !cards.is_empty() {
+                        let cards: Vec<String> =
+                            cards.iter().map(|card| card.to_string()).collect();
+                        write!(f, "; {}: {}", label, cards.join(", "))?;
+                    }
+                }
+                Ok(())
+            }
+            SpadesError::CorruptSave(message) => write!(f, "corrupt saved game: {}", message),
             SpadesError::InternalError => {
                 write!(f, "spades crate internal error")
             }
@@ -0,0 +1,317 @@
+use cards::{Card, Suit};
+use diagnostics::GameEvent;
+use result::SpadesError;
+use seat::Seat;
+use {Game, Uid};
+
+/// How a game treats a revoke: a player not following the suit led while holding a card of it.  Set with
+/// [`Game::set_revoke_policy`](struct.Game.html#method.set_revoke_policy).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum RevokePolicy {
+    /// A card that doesn't follow suit when it could is refused.
+    #[default]
+    Prevent,
+    /// A card that doesn't follow suit when it could is played as if the player held none of the suit led.
+    /// Revokes are found afterwards with [`Game::revokes`](struct.Game.html#method.revokes) and can be
+    /// penalized with [`Game::penalize_revoke`](struct.Game.html#method.penalize_revoke).
+    Detect,
+}
+
+/// A card played off suit by a player who, as later play or their hand shows, could have followed suit.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct Revoke {
+    /// Counting from 0, as in [`Game::round_history`](struct.Game.html#method.round_history).
+    pub round: usize,
+    /// Counting from 0 within the round.
+    pub trick: usize,
+    pub player: Uid,
+    pub card: Card,
+    /// The suit led, which the player should have followed.
+    pub led: Suit,
+}
+
+/// What a revoke costs the revoking team, see [`Game::penalize_revoke`](struct.Game.html#method.penalize_revoke).
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum RevokePenalty {
+    /// These points are taken off the revoking team's score.
+    Points(i32),
+    /// Up to this many tricks are taken from the revoking player, then from their partner, and given to the
+    /// player on the revoking player's left, and the round is scored again.
+    Tricks(u8),
+}
+
+impl Game {
+    /// Whether cards that don't follow suit are refused, or allowed and found afterwards.
+    pub fn set_revoke_policy(&mut self, policy: RevokePolicy) {
+        self.revoke_policy = policy;
+    }
+
+    pub fn revoke_policy(&self) -> RevokePolicy {
+        self.revoke_policy
+    }
+
+    /// Every revoke that can be proven so far, oldest first: a card played off suit is a revoke once the same
+    /// player plays the suit led later in the round, or still holds a card of it.
+    pub fn revokes(&self) -> Vec<Revoke> {
+        let rounds = self.round_plays();
+        let mut revokes = vec![];
+        for (round, plays) in rounds.iter().enumerate() {
+            let current = round + 1 == rounds.len();
+            for (index, (seat, card)) in plays.iter().enumerate() {
+                let position = index % 4;
+                let led = plays[index - position].1.suit;
+                if position == 0 || card.suit == led {
+                    continue;
+                }
+                let held = plays[index + 1..]
+                    .iter()
+                    .any(|(later, card)| later == seat && card.suit == led)
+                    || (current
+                        && self.player[seat.index()]
+                            .hand
+                            .iter()
+                            .any(|card| card.suit == led));
+                if held {
+                    revokes.push(Revoke {
+                        round,
+                        trick: index / 4,
+                        player: self.player[seat.index()].id,
+                        card: *card,
+                        led,
+                    });
+                }
+            }
+        }
+        revokes
+    }
+
+    /// Charges the revoking team for `revoke`, one of [`revokes`](#method.revokes), once its round has been
+    /// scored.  Only points change: bags stay as they were scored.
+    ///
+    /// Returns `SpadesError::NotARevoke` if `revoke` isn't one of this game's revokes,
+    /// `SpadesError::RevokeAlreadyPenalized` if it has been penalized already, and
+    /// `SpadesError::ImproperGameStage` if its round is still being played.
+    pub fn penalize_revoke(
+        &mut self,
+        revoke: &Revoke,
+        penalty: RevokePenalty,
+    ) -> Result<(), SpadesError> {
+        if !self.revokes().contains(revoke) {
+            return Err(SpadesError::NotARevoke);
+        }
+        let penalized = self.history().iter().any(|event| match event {
+            GameEvent::RevokePenalized {
+                revoke: earlier, ..
+            } => earlier == revoke,
+            _ => false,
+        });
+        if penalized {
+            return Err(SpadesError::RevokeAlreadyPenalized);
+        }
+        let history = self.round_history();
+        let (tricks, scores) = history[revoke.round]
+            .result
+            .ok_or(SpadesError::ImproperGameStage)?;
+        // rounds before the last checkpoint aren't in the history
+        let round = revoke.round + self.event_log.compacted_rounds;
+        let seat = self.seat_of(&revoke.player)?;
+        let team = seat.team();
+
+        let points = match penalty {
+            RevokePenalty::Points(points) => {
+                let mut change = [0, 0];
+                change[team] = -points;
+                change
+            }
+            RevokePenalty::Tricks(count) => {
+                let mut moved = tricks;
+                let mut left = count;
+                for from in [seat, seat.partner()].iter() {
+                    let taken = left.min(moved[from.index()]);
+                    moved[from.index()] -= taken;
+                    moved[seat.left().index()] += taken;
+                    left -= taken;
+                }
+                let bets = history[revoke.round]
+                    .bets
+                    .map(|bet| bet.unwrap_or_default());
+                let bags_before = match round {
+                    0 => [0, 0],
+                    round => {
+                        let before = self.scoring.round_scores()[round - 1];
+                        [before[0].cumulative_bags, before[1].cumulative_bags]
+                    }
+                };
+                let mut rescored = self.scoring.score_bets(&bets, &moved, &bags_before);
+                if self.scoring.is_over() && round + 1 == self.scoring.round_scores().len() {
+                    self.scoring.settle_bags(&mut rescored);
+                }
+                [
+                    rescored[0].points - scores[0].points,
+                    rescored[1].points - scores[1].points,
+                ]
+            }
+        };
+        self.apply_revoke_penalty(revoke, points)
+    }
+
+    /// Changes each team's score for the round of `revoke` by `points`.
+    pub(crate) fn apply_revoke_penalty(
+        &mut self,
+        revoke: &Revoke,
+        points: [i32; 2],
+    ) -> Result<(), SpadesError> {
+        let round = revoke.round + self.event_log.compacted_rounds;
+        if round >= self.scoring.round_scores().len() {
+            return Err(SpadesError::ImproperGameStage);
+        }
+        self.scoring.adjust_round_points(round, points);
+        self.record_event(GameEvent::RevokePenalized {
+            revoke: *revoke,
+            points,
+        });
+        self.check_invariants();
+        Ok(())
+    }
+
+    /// The cards played in each round, in order, with the seat that played them.
+    pub(crate) fn round_plays(&self) -> Vec<Vec<(Seat, Card)>> {
+        let mut rounds: Vec<Vec<(Seat, Card)>> = vec![];
+        for event in self.history() {
+            match event {
+                GameEvent::Dealt => rounds.push(vec![]),
+                GameEvent::Misdeal { .. } => {
+                    rounds.pop();
+                }
+                GameEvent::CardPlayed { player, card } => {
+                    if let (Some(round), Ok(seat)) = (rounds.last_mut(), self.seat_of(player)) {
+                        round.push((seat, *card));
+                    }
+                }
+                _ => {}
+            }
+        }
+        rounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Revoke, RevokePenalty, RevokePolicy};
+    use cards::{Card, Rank, Suit};
+    use test_support::seated_game;
+    use {Bet, Game, SpadesError, State, Uid};
+
+    fn card(suit: Suit, rank: u8) -> Card {
+        Card {
+            suit,
+            rank: Rank::from(rank),
+        }
+    }
+
+    /// A round where North leads hearts and West holds a single heart among clubs.
+    fn revoking_game(policy: RevokePolicy) -> Game {
+        let mut g = seated_game();
+        g.set_revoke_policy(policy);
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let hands = [
+            (3..=14)
+                .map(|rank| card(Suit::Hearts, rank))
+                .chain(Some(card(Suit::Clubs, 2)))
+                .collect(),
+            (2..=14).map(|rank| card(Suit::Diamonds, rank)).collect(),
+            (2..=14).map(|rank| card(Suit::Spades, rank)).collect(),
+            (3..=14)
+                .map(|rank| card(Suit::Clubs, rank))
+                .chain(Some(card(Suit::Hearts, 2)))
+                .collect(),
+        ];
+        g.rig_round(hands, &[]);
+        g.play_card(card(Suit::Hearts, 3));
+        g.play_card(card(Suit::Diamonds, 2));
+        g.play_card(card(Suit::Spades, 2));
+        g
+    }
+
+    #[test]
+    fn test_revokes_are_refused_or_detected() {
+        let mut g = revoking_game(RevokePolicy::Prevent);
+        assert_eq!(RevokePolicy::Prevent, g.revoke_policy());
+        assert_eq!(
+            Some(SpadesError::CardIncorrectSuit),
+            g.can_play_card(card(Suit::Clubs, 3))
+        );
+
+        g.set_revoke_policy(RevokePolicy::Detect);
+        g.play_card(card(Suit::Clubs, 3));
+        assert_eq!(State::Trick(0), g.state());
+        assert_eq!(
+            vec![Revoke {
+                round: 0,
+                trick: 0,
+                player: Uid(13),
+                card: card(Suit::Clubs, 3),
+                led: Suit::Hearts,
+            }],
+            g.revokes()
+        );
+    }
+
+    #[test]
+    fn test_penalize_revoke_after_round() {
+        let mut g = revoking_game(RevokePolicy::Detect);
+        g.play_card(card(Suit::Clubs, 3));
+        let revoke = g.revokes()[0];
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            g.penalize_revoke(&revoke, RevokePenalty::Points(50))
+        );
+        for _ in 0..12 * 4 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+        assert!(g.revokes().contains(&revoke));
+        let scores = g.round_scores()[0];
+
+        let mut retried = g.clone();
+        assert_eq!(
+            Ok(()),
+            retried.penalize_revoke(&revoke, RevokePenalty::Tricks(1))
+        );
+        assert!(retried.round_scores()[0][1] <= scores[1]);
+
+        assert_eq!(
+            Err(SpadesError::NotARevoke),
+            g.penalize_revoke(&Revoke { trick: 5, ..revoke }, RevokePenalty::Points(50))
+        );
+        assert_eq!(
+            Ok(()),
+            g.penalize_revoke(&revoke, RevokePenalty::Points(50))
+        );
+        assert_eq!(
+            Err(SpadesError::RevokeAlreadyPenalized),
+            g.penalize_revoke(&revoke, RevokePenalty::Points(50))
+        );
+        assert_eq!([scores[0], scores[1] - 50], g.round_scores()[0]);
+        assert_eq!(Ok(scores[1] - 50), g.team_all_rounds_score(1));
+    }
+}
@@ -0,0 +1,300 @@
+use diagnostics::GameEvent;
+use game_state::State;
+use result::SpadesError;
+use {Game, Uid};
+
+/// How a [`Rubber`](struct.Rubber.html) is scored.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct RubberConfig {
+    /// Points added to the total of the first team to win two games.
+    pub bonus: i32,
+    /// Each team starts every game after the first with the bags it ended the last one with, so a bag
+    /// penalty can fall in a later game of the rubber.
+    pub carry_bags: bool,
+}
+
+impl Default for RubberConfig {
+    fn default() -> RubberConfig {
+        RubberConfig {
+            bonus: 200,
+            carry_bags: true,
+        }
+    }
+}
+
+/// How one game of a [`Rubber`](struct.Rubber.html) came out.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct RubberGame {
+    /// Team 0 or team 1, or `None` if the game ended without a winner.
+    pub winning_team: Option<usize>,
+    pub scores: [i32; 2],
+    /// The bags each team ended the game with, after any penalty.
+    pub bags: [u8; 2],
+}
+
+/// Games between the same two partnerships until one of them has won two, scored together: bags carry
+/// from one game into the next and the winners of the rubber take a bonus.
+///
+/// Team 0 is `players[0]` and `players[2]`, team 1 `players[1]` and `players[3]`; partners may change
+/// seats between games but not teams.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct Rubber {
+    players: [Uid; 4],
+    config: RubberConfig,
+    games: Vec<RubberGame>,
+}
+
+impl Rubber {
+    pub fn new(players: [Uid; 4], config: RubberConfig) -> Rubber {
+        Rubber {
+            players,
+            config,
+            games: vec![],
+        }
+    }
+
+    pub fn players(&self) -> [Uid; 4] {
+        self.players
+    }
+
+    pub fn config(&self) -> RubberConfig {
+        self.config
+    }
+
+    /// Every game recorded so far, oldest first.
+    pub fn games(&self) -> &[RubberGame] {
+        &self.games
+    }
+
+    /// The bags each team takes into the next game: those it ended the last game with, or none if bags
+    /// aren't carried.
+    pub fn bags(&self) -> [u8; 2] {
+        match self.games.last() {
+            Some(game) if self.config.carry_bags => game.bags,
+            _ => [0, 0],
+        }
+    }
+
+    pub fn games_won(&self) -> [u32; 2] {
+        let mut won = [0, 0];
+        for game in self.games.iter() {
+            if let Some(team) = game.winning_team {
+                won[team] += 1;
+            }
+        }
+        won
+    }
+
+    /// The team that has won two games, if either has.
+    pub fn winner(&self) -> Option<usize> {
+        let won = self.games_won();
+        (0..2).find(|team| won[*team] >= 2)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.winner().is_some()
+    }
+
+    /// Each team's points over every game, with the bonus to the winner of the rubber.
+    pub fn scores(&self) -> [i32; 2] {
+        let mut scores = [0, 0];
+        for game in self.games.iter() {
+            scores[0] += game.scores[0];
+            scores[1] += game.scores[1];
+        }
+        if let Some(team) = self.winner() {
+            scores[team] += self.config.bonus;
+        }
+        scores
+    }
+
+    /// Readies `game`, started but with no round played, as the next game of the rubber by
+    /// [carrying over](struct.Game.html#method.carry_over_bags) each team's bags.  Returns
+    /// `SpadesError::RubberOver` once the rubber has been won and `SpadesError::InvalidUuid` unless the
+    /// partnerships are the rubber's.
+    pub fn prepare(&self, game: &mut Game) -> Result<(), SpadesError> {
+        if self.is_over() {
+            return Err(SpadesError::RubberOver);
+        }
+        self.check_teams(game)?;
+        game.carry_over_bags(self.bags())
+    }
+
+    /// Records how `game`, the next game of the rubber, turned out.  Returns `SpadesError::RubberOver` once
+    /// the rubber has been won, `SpadesError::InvalidUuid` unless the partnerships are the rubber's, and
+    /// `SpadesError::GameNotCompleted` if the game isn't over.
+    pub fn record_game(&mut self, game: &Game) -> Result<(), SpadesError> {
+        if self.is_over() {
+            return Err(SpadesError::RubberOver);
+        }
+        self.check_teams(game)?;
+        let result = game.result()?;
+        let winning_team = result
+            .winners
+            .map(|(winner, _)| game.seat_of(&winner).map(|seat| seat.team()))
+            .transpose()?;
+        self.games.push(RubberGame {
+            winning_team,
+            scores: result.scores,
+            bags: [
+                game.scoring.team[0].cumulative_bags(),
+                game.scoring.team[1].cumulative_bags(),
+            ],
+        });
+        Ok(())
+    }
+
+    fn check_teams(&self, game: &Game) -> Result<(), SpadesError> {
+        for (index, player) in self.players.iter().enumerate() {
+            if game.seat_of(player)?.team() != index % 2 {
+                return Err(SpadesError::InvalidUuid);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Game {
+    /// Starts each team, 0 and 1, with `bags` bags from an earlier game, as a
+    /// [`Rubber`](struct.Rubber.html) does.  They count towards the first bag penalty of this game.
+    ///
+    /// Returns `SpadesError::TooManyBags` for 10 bags or more, which would already have been penalized,
+    /// `SpadesError::ImproperGameStage` once a round has been scored, and an error if the game hasn't
+    /// started, is over or has been abandoned.
+    pub fn carry_over_bags(&mut self, bags: [u8; 2]) -> Result<(), SpadesError> {
+        match self.state {
+            State::GameNotStarted => return Err(SpadesError::GameNotStarted),
+            State::GameCompleted => return Err(SpadesError::GameCompleted),
+            State::Abandoned => return Err(SpadesError::GameAbandoned),
+            _ if self.scoring.rounds_played() > 0 => return Err(SpadesError::ImproperGameStage),
+            _ => {}
+        }
+        if let Some(too_many) = bags.iter().find(|bags| **bags >= 10) {
+            return Err(SpadesError::TooManyBags(*too_many));
+        }
+        self.scoring.carry_bags(bags);
+        self.record_event(GameEvent::BagsCarried(bags));
+        self.check_invariants();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rubber, RubberConfig};
+    use test_support::PLAYERS;
+    use {run_game, Bet, Game, GameEvent, GameLength, GameOptions, HeuristicBot, SpadesError, Uid};
+
+    fn started_game(seed: u64) -> Game {
+        let mut g = Game::default();
+        g.assign_players(Uid(seed), PLAYERS);
+        g.set_shuffle_seed(Some(seed));
+        g.start_game();
+        g
+    }
+
+    #[test]
+    fn test_carried_bags_count_towards_penalty() {
+        let mut g = started_game(8);
+        assert_eq!(
+            Err(SpadesError::TooManyBags(10)),
+            g.carry_over_bags([3, 10])
+        );
+        g.carry_over_bags([9, 9]).unwrap();
+        assert_eq!(Some(&GameEvent::BagsCarried([9, 9])), g.history().last());
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(1));
+        }
+        for _ in 0..52 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+        for score in g.scoring.round_scores()[0].iter() {
+            if score.bags > 0 {
+                assert_eq!((9 + score.bags) % 10, score.cumulative_bags);
+                assert!(score.points < 0);
+            } else {
+                assert_eq!(9, score.cumulative_bags);
+            }
+        }
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            g.carry_over_bags([0, 0])
+        );
+        assert_eq!(
+            Err(SpadesError::GameNotStarted),
+            Game::default().carry_over_bags([0, 0])
+        );
+    }
+
+    #[test]
+    fn test_replay_carries_bags() {
+        let mut g = started_game(4);
+        let checkpoint = g.checkpoint();
+        g.carry_over_bags([5, 2]).unwrap();
+        g.place_bet(Bet::Amount(3));
+        let replayed = Game::replay(&checkpoint, &g.events_since(checkpoint.seq));
+        assert_eq!(g.scoring, replayed.unwrap().scoring);
+    }
+
+    #[test]
+    fn test_rubber_to_two_wins() {
+        let config = RubberConfig {
+            bonus: 150,
+            carry_bags: true,
+        };
+        let mut rubber = Rubber::new(PLAYERS, config);
+        let mut seed = 0;
+        while !rubber.is_over() {
+            seed += 1;
+            let mut g = started_game(seed);
+            g.set_options(GameOptions {
+                length: GameLength::FixedRounds(2),
+                ..GameOptions::default()
+            })
+            .unwrap();
+            assert_eq!(Err(SpadesError::GameNotCompleted), rubber.record_game(&g));
+            rubber.prepare(&mut g).unwrap();
+            assert_eq!(
+                rubber.bags(),
+                [
+                    g.scoring.team[0].cumulative_bags(),
+                    g.scoring.team[1].cumulative_bags()
+                ]
+            );
+            let mut bots = [HeuristicBot::default(); 4];
+            let [a, b, c, d] = &mut bots;
+            run_game(&mut g, [a, b, c, d]).unwrap();
+            rubber.record_game(&g).unwrap();
+            assert!(seed < 20);
+        }
+
+        let winner = rubber.winner().unwrap();
+        assert_eq!(2, rubber.games_won()[winner]);
+        assert_eq!(rubber.games().len(), seed as usize);
+        let mut totals = [0, 0];
+        for game in rubber.games() {
+            totals[0] += game.scores[0];
+            totals[1] += game.scores[1];
+        }
+        totals[winner] += 150;
+        assert_eq!(totals, rubber.scores());
+        assert_eq!(rubber.games().last().unwrap().bags, rubber.bags());
+
+        let mut g = started_game(99);
+        assert_eq!(Err(SpadesError::RubberOver), rubber.prepare(&mut g));
+    }
+
+    #[test]
+    fn test_rubber_keeps_partnerships() {
+        let rubber = Rubber::new(PLAYERS, RubberConfig::default());
+        let mut g = Game::default();
+        g.assign_players(Uid(1), [Uid(10), Uid(12), Uid(11), Uid(13)]);
+        g.start_game();
+        assert_eq!(Err(SpadesError::InvalidUuid), rubber.prepare(&mut g));
+    }
+}
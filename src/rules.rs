@@ -0,0 +1,531 @@
+use cards::{Card, Suit};
+use result::SpadesError;
+use scoring::{ScoringRules, StandardScoring};
+use shuffler::{Shuffler, ThreadRngShuffler};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// What a [`CardPlayRules`](trait.CardPlayRules.html) implementation gets to look at when a card is played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayContext<'a> {
+    /// How many cards have already been played to the trick; 0 when the player is leading.
+    pub position_in_trick: usize,
+    /// The cards already played to the trick, starting with the lead.
+    pub trick: &'a [Card],
+    /// The suit led for the trick, if a card has been led.
+    pub leading_suit: Option<Suit>,
+    /// Whether a spade has been played earlier in the round.
+    pub spades_broken: bool,
+    /// The hand of the player about to play.  The card being played is always in it.
+    pub hand: &'a [Card],
+}
+
+/// The rules deciding which cards may be played, so variants can be plugged into a
+/// [`Game`](struct.Game.html) with [`set_card_play_rules`](struct.Game.html#method.set_card_play_rules).
+///
+/// Rules are not part of a saved game; set them again after loading one.
+pub trait CardPlayRules: fmt::Debug + Send + Sync {
+    /// A short name for this rule set.  Two games compare equal only if their rules have the same name.
+    fn name(&self) -> &str;
+
+    /// Whether `card` may be played.  Returns the reason why not in `Some(SpadesError)`.
+    fn can_play_card(&self, context: &PlayContext, card: Card) -> Option<SpadesError>;
+
+    /// Whether playing `card` breaks spades, so that they may be led from then on.
+    fn breaks_spades(&self, _context: &PlayContext, card: Card) -> bool {
+        card.suit == Suit::Spades
+    }
+}
+
+/// The standard rules: players must follow the suit led if they can, and spades can't be led until they have
+/// been broken, unless the leader holds nothing but spades.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StandardRules;
+
+impl CardPlayRules for StandardRules {
+    fn name(&self) -> &str {
+        "standard"
+    }
+
+    fn can_play_card(&self, context: &PlayContext, card: Card) -> Option<SpadesError> {
+        let hand = context.hand;
+        if context.position_in_trick == 0 {
+            // to lead spades, spades must be broken OR only have spades in this hand
+            if card.suit == Suit::Spades {
+                if context.spades_broken || !hand.iter().any(|c| c.suit != Suit::Spades) {
+                } else {
+                    return Some(SpadesError::CardIncorrectSuit);
+                }
+            }
+        }
+        if context.leading_suit != Some(card.suit)
+            && hand.iter().any(|x| Some(x.suit) == context.leading_suit)
+        {
+            return Some(SpadesError::CardIncorrectSuit);
+        }
+        None
+    }
+}
+
+/// The standard rules with a knob for the exception letting a leader holding nothing but spades lead them
+/// before they have been broken, to match platforms that differ on it.
+///
+/// Without the exception, such a leader has no card they may lead, so the lead passes to the next player to
+/// their left who has one.  If nobody has, because every hand holds nothing but spades, the leader leads a
+/// spade after all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpadesLeadRules {
+    /// A leader holding only spades may lead one before spades are broken, as in
+    /// [`StandardRules`](struct.StandardRules.html).
+    pub only_spades_exception: bool,
+}
+
+impl Default for SpadesLeadRules {
+    fn default() -> SpadesLeadRules {
+        SpadesLeadRules {
+            only_spades_exception: true,
+        }
+    }
+}
+
+impl CardPlayRules for SpadesLeadRules {
+    fn name(&self) -> &str {
+        if self.only_spades_exception {
+            "standard"
+        } else {
+            "no-only-spades-exception"
+        }
+    }
+
+    fn can_play_card(&self, context: &PlayContext, card: Card) -> Option<SpadesError> {
+        if context.position_in_trick == 0
+            && card.suit == Suit::Spades
+            && !context.spades_broken
+            && !self.only_spades_exception
+        {
+            return Some(SpadesError::CardIncorrectSuit);
+        }
+        StandardRules.can_play_card(context, card)
+    }
+}
+
+/// The seat to lead the next trick: `seat`, unless `rules` leave them no card they may lead, when the lead passes
+/// to the left to the first player who has one.  If nobody has, `seat` leads after all, and may lead any card.
+pub(crate) fn leader(
+    rules: &dyn CardPlayRules,
+    hands: [&[Card]; 4],
+    spades_broken: bool,
+    seat: usize,
+) -> usize {
+    (0..4)
+        .map(|offset| (seat + offset) % 4)
+        .find(|&seat| {
+            let context = PlayContext {
+                position_in_trick: 0,
+                trick: &[],
+                leading_suit: None,
+                spades_broken,
+                hand: hands[seat],
+            };
+            hands[seat]
+                .iter()
+                .any(|card| rules.can_play_card(&context, *card).is_none())
+        })
+        .unwrap_or(seat)
+}
+
+/// The standard rules, except that a player who can't follow suit must play a spade if they hold one, whether
+/// or not spades have been broken.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MustTrumpRules;
+
+impl CardPlayRules for MustTrumpRules {
+    fn name(&self) -> &str {
+        "must-trump"
+    }
+
+    fn can_play_card(&self, context: &PlayContext, card: Card) -> Option<SpadesError> {
+        if let Some(err) = StandardRules.can_play_card(context, card) {
+            return Some(err);
+        }
+        let hand = context.hand;
+        if let Some(leading_suit) = context.leading_suit {
+            if card.suit != leading_suit
+                && card.suit != Suit::Spades
+                && hand.iter().any(|x| x.suit == Suit::Spades)
+            {
+                return Some(SpadesError::CardIncorrectSuit);
+            }
+        }
+        None
+    }
+}
+
+/// A set of rules plugged into a game, compared, ordered and hashed by name so `Game` keeps its derives.
+pub(crate) struct Rules<T: ?Sized>(Arc<T>);
+
+/// The rule set traits, seen through their names.
+pub(crate) trait Named {
+    fn rules_name(&self) -> &str;
+}
+
+impl Named for dyn CardPlayRules {
+    fn rules_name(&self) -> &str {
+        self.name()
+    }
+}
+
+impl Named for dyn ScoringRules {
+    fn rules_name(&self) -> &str {
+        self.name()
+    }
+}
+
+impl Named for dyn Shuffler {
+    fn rules_name(&self) -> &str {
+        self.name()
+    }
+}
+
+impl Rules<dyn CardPlayRules> {
+    pub(crate) fn new<R: CardPlayRules + 'static>(rules: R) -> Rules<dyn CardPlayRules> {
+        Rules(Arc::new(rules))
+    }
+}
+
+impl Default for Rules<dyn CardPlayRules> {
+    fn default() -> Rules<dyn CardPlayRules> {
+        Rules::<dyn CardPlayRules>::new(StandardRules)
+    }
+}
+
+impl Rules<dyn ScoringRules> {
+    pub(crate) fn new<R: ScoringRules + 'static>(rules: R) -> Rules<dyn ScoringRules> {
+        Rules(Arc::new(rules))
+    }
+}
+
+impl Default for Rules<dyn ScoringRules> {
+    fn default() -> Rules<dyn ScoringRules> {
+        Rules::<dyn ScoringRules>::new(StandardScoring::default())
+    }
+}
+
+impl Rules<dyn Shuffler> {
+    pub(crate) fn new<S: Shuffler + 'static>(shuffler: S) -> Rules<dyn Shuffler> {
+        Rules(Arc::new(shuffler))
+    }
+}
+
+impl Default for Rules<dyn Shuffler> {
+    fn default() -> Rules<dyn Shuffler> {
+        Rules::<dyn Shuffler>::new(ThreadRngShuffler)
+    }
+}
+
+impl<T: ?Sized> Rules<T> {
+    pub(crate) fn get(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: ?Sized> Clone for Rules<T> {
+    fn clone(&self) -> Rules<T> {
+        Rules(Arc::clone(&self.0))
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for Rules<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: ?Sized + Named> PartialEq for Rules<T> {
+    fn eq(&self, other: &Rules<T>) -> bool {
+        self.0.rules_name() == other.0.rules_name()
+    }
+}
+
+impl<T: ?Sized + Named> Eq for Rules<T> {}
+
+impl<T: ?Sized + Named> PartialOrd for Rules<T> {
+    fn partial_cmp(&self, other: &Rules<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ?Sized + Named> Ord for Rules<T> {
+    fn cmp(&self, other: &Rules<T>) -> Ordering {
+        self.0.rules_name().cmp(other.0.rules_name())
+    }
+}
+
+impl<T: ?Sized + Named> Hash for Rules<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.rules_name().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CardPlayRules, MustTrumpRules, PlayContext, SpadesLeadRules, StandardRules};
+    use agent::run_game_with_round_limit;
+    use cards::{Card, Rank, Suit};
+    use lifecycle::{AbandonReason, PrematureEnd};
+    use {Bet, Game, HeuristicBot, SpadesError, Uid};
+
+    /// Spades may be led at any time.
+    #[derive(Debug)]
+    struct NoBreakRequired;
+
+    impl CardPlayRules for NoBreakRequired {
+        fn name(&self) -> &str {
+            "no-break-required"
+        }
+
+        fn can_play_card(&self, context: &PlayContext, card: Card) -> Option<SpadesError> {
+            let context = PlayContext {
+                spades_broken: true,
+                ..*context
+            };
+            StandardRules.can_play_card(&context, card)
+        }
+    }
+
+    #[test]
+    fn test_standard_rules_follow_suit() {
+        let hand = [
+            Card {
+                suit: Suit::Hearts,
+                rank: Rank::Two,
+            },
+            Card {
+                suit: Suit::Spades,
+                rank: Rank::Ace,
+            },
+        ];
+        let context = PlayContext {
+            position_in_trick: 1,
+            trick: &[],
+            leading_suit: Some(Suit::Hearts),
+            spades_broken: true,
+            hand: &hand,
+        };
+        assert_eq!(None, StandardRules.can_play_card(&context, hand[0]));
+        assert_eq!(
+            Some(SpadesError::CardIncorrectSuit),
+            StandardRules.can_play_card(&context, hand[1])
+        );
+        assert!(StandardRules.breaks_spades(&context, hand[1]));
+    }
+
+    #[test]
+    fn test_only_spades_exception() {
+        let hand = [
+            Card {
+                suit: Suit::Spades,
+                rank: Rank::Two,
+            },
+            Card {
+                suit: Suit::Spades,
+                rank: Rank::Ace,
+            },
+        ];
+        let context = PlayContext {
+            position_in_trick: 0,
+            trick: &[],
+            leading_suit: None,
+            spades_broken: false,
+            hand: &hand,
+        };
+        let strict = SpadesLeadRules {
+            only_spades_exception: false,
+        };
+        assert_eq!(None, StandardRules.can_play_card(&context, hand[0]));
+        assert_eq!(
+            None,
+            SpadesLeadRules::default().can_play_card(&context, hand[0])
+        );
+        assert_eq!(StandardRules.name(), SpadesLeadRules::default().name());
+        assert_eq!(
+            Some(SpadesError::CardIncorrectSuit),
+            strict.can_play_card(&context, hand[0])
+        );
+        let broken = PlayContext {
+            spades_broken: true,
+            ..context
+        };
+        assert_eq!(None, strict.can_play_card(&broken, hand[1]));
+    }
+
+    #[test]
+    fn test_custom_rules_are_used_by_game() {
+        let mut g = Game::default();
+        g.assign_players(Uid(1), [Uid(2), Uid(3), Uid(4), Uid(5)]);
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let standard = g.clone();
+        g.set_card_play_rules(NoBreakRequired);
+        assert_eq!("no-break-required", g.card_play_rules().name());
+        assert_ne!(standard, g);
+
+        let hand = g.current_hand().unwrap().to_vec();
+        for card in hand {
+            assert_eq!(None, g.can_play_card(card));
+        }
+    }
+
+    #[test]
+    fn test_must_trump_when_void() {
+        let card = |suit, rank| Card {
+            suit,
+            rank: Rank::from(rank),
+        };
+        let mut g = Game::default();
+        g.assign_players(Uid(1), [Uid(2), Uid(3), Uid(4), Uid(5)]);
+        g.set_card_play_rules(MustTrumpRules);
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let hands = [
+            vec![
+                card(Suit::Hearts, 10),
+                card(Suit::Hearts, 11),
+                card(Suit::Spades, 3),
+            ],
+            vec![
+                card(Suit::Clubs, 4),
+                card(Suit::Spades, 2),
+                card(Suit::Spades, 5),
+            ],
+            vec![
+                card(Suit::Hearts, 4),
+                card(Suit::Clubs, 5),
+                card(Suit::Clubs, 7),
+            ],
+            vec![
+                card(Suit::Diamonds, 2),
+                card(Suit::Diamonds, 3),
+                card(Suit::Clubs, 6),
+            ],
+        ];
+        g.rig_round(hands, &[]);
+        assert_eq!(
+            Some(SpadesError::CardIncorrectSuit),
+            g.can_play_card(card(Suit::Spades, 3))
+        );
+        g.play_card(card(Suit::Hearts, 10));
+
+        // void in hearts and holding a spade, even though spades aren't broken yet
+        assert_eq!(
+            Some(SpadesError::CardIncorrectSuit),
+            g.can_play_card(card(Suit::Clubs, 4))
+        );
+        g.play_card(card(Suit::Spades, 2));
+        g.play_card(card(Suit::Hearts, 4));
+        // void in hearts and in spades, so anything goes
+        assert_eq!(None, g.can_play_card(card(Suit::Clubs, 6)));
+        g.play_card(card(Suit::Diamonds, 2));
+
+        // the trump broke spades, so the winner may lead one
+        assert_eq!(Ok(Uid(3)), g.current_player_id());
+        assert_eq!(None, g.can_play_card(card(Suit::Spades, 5)));
+        assert_eq!(None, g.can_play_card(card(Suit::Clubs, 4)));
+    }
+
+    #[test]
+    fn test_lead_passes_from_a_leader_holding_only_spades() {
+        let card = |suit, rank| Card {
+            suit,
+            rank: Rank::from(rank),
+        };
+        let mut g = Game::default();
+        g.assign_players(Uid(1), [Uid(2), Uid(3), Uid(4), Uid(5)]);
+        g.set_card_play_rules(SpadesLeadRules {
+            only_spades_exception: false,
+        });
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let hands = [
+            vec![
+                card(Suit::Hearts, 14),
+                card(Suit::Spades, 2),
+                card(Suit::Spades, 3),
+            ],
+            vec![
+                card(Suit::Hearts, 2),
+                card(Suit::Clubs, 4),
+                card(Suit::Clubs, 5),
+            ],
+            vec![
+                card(Suit::Hearts, 3),
+                card(Suit::Clubs, 6),
+                card(Suit::Clubs, 7),
+            ],
+            vec![
+                card(Suit::Hearts, 4),
+                card(Suit::Clubs, 8),
+                card(Suit::Clubs, 9),
+            ],
+        ];
+        g.rig_round(hands, &[]);
+        for card in [
+            card(Suit::Hearts, 14),
+            card(Suit::Hearts, 2),
+            card(Suit::Hearts, 3),
+            card(Suit::Hearts, 4),
+        ] {
+            g.play_card(card);
+        }
+
+        // the winner holds nothing but spades, and they aren't broken
+        assert_eq!(Ok(Uid(3)), g.current_player_id());
+        assert_eq!(
+            vec![card(Suit::Clubs, 4), card(Suit::Clubs, 5)],
+            g.playable_cards().collect::<Vec<_>>()
+        );
+        g.play_card(card(Suit::Clubs, 4));
+        g.play_card(card(Suit::Clubs, 6));
+        g.play_card(card(Suit::Clubs, 8));
+        g.play_card(card(Suit::Spades, 2));
+        assert_eq!(Ok(Uid(2)), g.current_player_id());
+
+        let mut bots = [HeuristicBot::default(); 4];
+        let [a, b, c, d] = &mut bots;
+        run_game_with_round_limit(&mut g, [a, b, c, d], 3).unwrap();
+        assert_eq!(
+            Some(PrematureEnd::Abandoned(AbandonReason::RoundLimit(3))),
+            g.premature_end()
+        );
+    }
+
+    #[test]
+    fn test_spades_are_led_when_every_hand_holds_only_spades() {
+        let mut g = Game::default();
+        g.assign_players(Uid(1), [Uid(2), Uid(3), Uid(4), Uid(5)]);
+        g.set_card_play_rules(SpadesLeadRules {
+            only_spades_exception: false,
+        });
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let hands = [2, 3, 4, 5].map(|rank| {
+            vec![Card {
+                suit: Suit::Spades,
+                rank: Rank::from(rank),
+            }]
+        });
+        g.rig_round(hands, &[]);
+        assert_eq!(Ok(Uid(2)), g.current_player_id());
+        assert_eq!(1, g.playable_cards().count());
+    }
+}
@@ -0,0 +1,231 @@
+//! The on-disk layout of a serialized [`Game`](../struct.Game.html).
+//!
+//! Every saved game records the `schema_version` it was written with.  When loading, older versions are
+//! upgraded one step at a time by [`migrate`](fn.migrate.html) before being turned back into a `Game`, and
+//! versions newer than this library understands are rejected instead of being misread.  So is a save whose
+//! hands, kitty and played cards aren't one standard deck between them, with the cards missing or
+//! duplicated named in a `SpadesError::CorruptDeck`, and a save holding a seat, team or trick count no game
+//! can reach, or breaking any other of the game's consistency checks, with a `SpadesError::CorruptSave`.
+
+use cards::{Card, DealConfig, Suit};
+use claim::PendingClaim;
+use deal::Deal;
+use diagnostics::SavedEventLog;
+use fairness::DealReveal;
+use game_state::State;
+use kitty::check_kitty_size;
+use result::SpadesError;
+use rules::Rules;
+use scoring::{Bet, Scoring};
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::time::Duration;
+use {
+    AbandonReason, Adjustment, Game, GameOptions, KittyConfig, MisdealConfig, Player, RevokePolicy,
+    TimeoutPolicy, Trick, Uid,
+};
+
+/// The version of the serialized `Game` layout written by this version of the crate.
+///
+/// Bump this whenever a change to `Game` (or anything it contains) changes the serialized form of a released
+/// version, and add a step to `migrate` that upgrades saves written with the previous version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct SavedGame {
+    /// Version 0 is a save written before the version was recorded.
+    #[serde(default)]
+    schema_version: u32,
+    id: Uid,
+    state: State,
+    scoring: Scoring,
+    current_player_index: usize,
+    deck: Vec<Card>,
+    current_trick: Trick,
+    leading_suit: Option<Suit>,
+    spades_broken: bool,
+    player: [Player; 4],
+    turn_duration: Option<Duration>,
+    timeout_policy: TimeoutPolicy,
+    think_time: [Duration; 4],
+    forfeited_team: Option<usize>,
+    paused_state: Option<State>,
+    abandon_reason: Option<AbandonReason>,
+    pending_claim: Option<PendingClaim>,
+    round_bets: [Option<Bet>; 4],
+    previous_round_bets: Option<[Bet; 4]>,
+    revoke_policy: RevokePolicy,
+    misdeal_config: MisdealConfig,
+    options: GameOptions,
+    deal_config: DealConfig,
+    kitty_config: Option<KittyConfig>,
+    kitty: Vec<Card>,
+    shuffle_seed: Option<u64>,
+    queued_deals: VecDeque<Deal>,
+    round_deal: Option<Deal>,
+    adjustments: Vec<Adjustment>,
+    team_ids: Option<[Uid; 2]>,
+    deal_audit: Option<DealReveal>,
+    revealed_deal: Option<DealReveal>,
+    event_log: SavedEventLog,
+}
+
+/// Upgrades a save to `SCHEMA_VERSION`, one version at a time.
+fn migrate(mut saved: SavedGame) -> Result<SavedGame, SpadesError> {
+    loop {
+        saved.schema_version = match saved.schema_version {
+            SCHEMA_VERSION => return Ok(saved),
+            // an unversioned save has version 1's layout, so only gains its version
+            0 => 1,
+            // a layout change adds an arm here that upgrades `version` to `version + 1`
+            version => return Err(SpadesError::UnsupportedSchemaVersion(version)),
+        };
+    }
+}
+
+/// Checks the seats, teams and counts that the game indexes by, so that a save edited or written by hand
+/// can't make a loaded game panic.
+fn check_ranges(saved: &SavedGame) -> Result<(), SpadesError> {
+    let corrupt = |message: String| Err(SpadesError::CorruptSave(message));
+    if saved.current_player_index >= 4 {
+        return corrupt(format!("no seat {}", saved.current_player_index));
+    }
+    for state in Some(saved.state).iter().chain(saved.paused_state.iter()) {
+        match state.acting_player_offset() {
+            Some(offset) if offset >= 4 => return corrupt(format!("state {}", state)),
+            _ => {}
+        }
+    }
+    match saved.forfeited_team {
+        Some(team) if team >= 2 => return corrupt(format!("no team {}", team)),
+        _ => {}
+    }
+    let tricks = saved.scoring.tricks_per_round();
+    if !(1..=13).contains(&tricks) {
+        return corrupt(format!("{} tricks a round", tricks));
+    }
+    // the tricks taken are only counted from the round's first card on
+    if saved.paused_state.unwrap_or(saved.state).is_trick() {
+        let taken: usize = saved
+            .scoring
+            .player_tricks()
+            .iter()
+            .map(|&taken| usize::from(taken))
+            .sum();
+        let trick = saved.scoring.trick_index();
+        if taken != trick || trick >= usize::from(tricks) {
+            return corrupt(format!(
+                "trick {} of {} with {} taken",
+                trick + 1,
+                tricks,
+                taken
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl From<Game> for SavedGame {
+    fn from(g: Game) -> SavedGame {
+        SavedGame {
+            schema_version: SCHEMA_VERSION,
+            id: g.id,
+            state: g.state,
+            scoring: g.scoring,
+            current_player_index: g.current_player_index,
+            deck: g.deck,
+            current_trick: g.current_trick,
+            leading_suit: g.leading_suit,
+            spades_broken: g.spades_broken,
+            player: g.player,
+            turn_duration: g.turn_duration,
+            timeout_policy: g.timeout_policy,
+            think_time: g.think_time,
+            forfeited_team: g.forfeited_team,
+            paused_state: g.paused_state,
+            abandon_reason: g.abandon_reason,
+            pending_claim: g.pending_claim,
+            round_bets: g.round_bets,
+            previous_round_bets: g.previous_round_bets,
+            revoke_policy: g.revoke_policy,
+            misdeal_config: g.misdeal_config,
+            options: g.options,
+            deal_config: g.deal_config,
+            kitty_config: g.kitty_config,
+            kitty: g.kitty,
+            shuffle_seed: g.shuffle_seed,
+            queued_deals: g.queued_deals,
+            round_deal: g.round_deal,
+            adjustments: g.adjustments,
+            team_ids: g.team_ids,
+            deal_audit: g.deal_audit,
+            revealed_deal: g.revealed_deal,
+            event_log: g.event_log.into(),
+        }
+    }
+}
+
+impl TryFrom<SavedGame> for Game {
+    type Error = SpadesError;
+
+    fn try_from(saved: SavedGame) -> Result<Game, SpadesError> {
+        let saved = migrate(saved)?;
+        check_ranges(&saved)?;
+        let game = Game {
+            id: saved.id,
+            state: saved.state,
+            scoring: saved.scoring,
+            current_player_index: saved.current_player_index,
+            deck: saved.deck,
+            current_trick: saved.current_trick,
+            round_bets: saved.round_bets,
+            previous_round_bets: saved.previous_round_bets,
+            revoke_policy: saved.revoke_policy,
+            misdeal_config: saved.misdeal_config,
+            options: saved.options,
+            deal_config: saved.deal_config,
+            kitty_config: saved.kitty_config,
+            kitty: saved.kitty,
+            shuffle_seed: saved.shuffle_seed,
+            queued_deals: saved.queued_deals,
+            round_deal: saved.round_deal,
+            adjustments: saved.adjustments,
+            team_ids: saved.team_ids,
+            deal_audit: saved.deal_audit,
+            revealed_deal: saved.revealed_deal,
+            event_log: saved.event_log.into(),
+            leading_suit: saved.leading_suit,
+            spades_broken: saved.spades_broken,
+            player: saved.player,
+            turn_duration: saved.turn_duration,
+            timeout_policy: saved.timeout_policy,
+            // a turn in progress when the game was saved gets a fresh clock
+            turn_started: None,
+            think_time: saved.think_time,
+            last_timed_action: None,
+            forfeited_team: saved.forfeited_team,
+            paused_state: saved.paused_state,
+            abandon_reason: saved.abandon_reason,
+            pending_claim: saved.pending_claim,
+            // rules are code, not data; the caller sets them again after loading
+            rules: Rules::default(),
+            shuffler: Rules::default(),
+        };
+        let (missing, duplicated) = game.deck_discrepancies();
+        if !missing.is_empty() || !duplicated.is_empty() {
+            return Err(SpadesError::CorruptDeck {
+                missing,
+                duplicated,
+            });
+        }
+        if let Some(config) = game.kitty_config {
+            check_kitty_size(config.size)?;
+        }
+        // a game that ran into an internal error is inconsistent already, and is kept for its diagnostics
+        let violations = game.invariant_violations();
+        if !violations.is_empty() && game.event_log.internal_error().is_none() {
+            return Err(SpadesError::CorruptSave(violations.join("; ")));
+        }
+        Ok(game)
+    }
+}
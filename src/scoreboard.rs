@@ -0,0 +1,175 @@
+use scoring::TeamBid;
+use std::fmt;
+use Game;
+
+/// One team's line of a [`Scoreboard`](struct.Scoreboard.html).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct ScoreboardRow {
+    /// The partners' bid this round, once both have bet.
+    pub bid: Option<u8>,
+    /// Tricks taken this round.
+    pub tricks: u8,
+    /// Points scored in the last round completed.
+    pub round_points: i32,
+    /// Bags taken in the last round completed.
+    pub round_bags: u8,
+    /// Score so far in the game.
+    pub points: i32,
+    /// Bags carried forward.
+    pub bags: u8,
+}
+
+/// The standings of a game, as returned by [`Game::scoreboard`](struct.Game.html#method.scoreboard).
+///
+/// `Display` writes it as an aligned text table, a line for each team:
+///
+/// ```text
+/// Team    Bid  Tricks  Round  Total  Bags
+/// Team 0    7       5     71    213     1
+/// Team 1    5       3    -50    140     0
+/// ```
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct Scoreboard {
+    pub rounds_played: usize,
+    /// Team 0 (players 0 and 2), then Team 1 (players 1 and 3).
+    pub teams: [ScoreboardRow; 2],
+}
+
+impl fmt::Display for Scoreboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:<6} {:>4} {:>7} {:>6} {:>6} {:>5}",
+            "Team", "Bid", "Tricks", "Round", "Total", "Bags"
+        )?;
+        for (team, row) in self.teams.iter().enumerate() {
+            let bid = row.bid.map_or("-".to_string(), |bid| bid.to_string());
+            write!(
+                f,
+                "\nTeam {:<1} {:>4} {:>7} {:>6} {:>6} {:>5}",
+                team, bid, row.tricks, row.round_points, row.points, row.bags
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Game {
+    /// The game's standings, for showing between tricks and rounds.
+    pub fn scoreboard(&self) -> Scoreboard {
+        let mut teams = [ScoreboardRow::default(); 2];
+        for (team, row) in teams.iter_mut().enumerate() {
+            let scores = &self.scoring.team[team];
+            *row = ScoreboardRow {
+                bid: match (self.round_bets[team], self.round_bets[team + 2]) {
+                    (Some(first), Some(second)) => {
+                        Some(TeamBid::new(first, second).tricks_required())
+                    }
+                    _ => None,
+                },
+                tricks: scores.tricks_won(),
+                round_points: scores.game_points(),
+                round_bags: scores.game_bags(),
+                points: scores.cumulative_points(),
+                bags: scores.cumulative_bags(),
+            };
+        }
+        Scoreboard {
+            rounds_played: self.scoring.rounds_played(),
+            teams,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Scoreboard, ScoreboardRow};
+    use test_support::seeded_game;
+    use Bet;
+
+    #[test]
+    fn test_scoreboard_follows_the_game() {
+        let mut g = seeded_game(7);
+        g.place_bet(Bet::Amount(4));
+        g.place_bet(Bet::Amount(2));
+        assert_eq!(None, g.scoreboard().teams[0].bid);
+        g.place_bet(Bet::Nil);
+        g.place_bet(Bet::Amount(3));
+        let board = g.scoreboard();
+        assert_eq!(Some(4), board.teams[0].bid);
+        assert_eq!(Some(5), board.teams[1].bid);
+        assert_eq!(0, board.rounds_played);
+
+        for _ in 0..52 {
+            let card = g.playable_cards().next().unwrap();
+            g.play_card(card);
+        }
+        let board = g.scoreboard();
+        assert_eq!(1, board.rounds_played);
+        for team in 0..2 {
+            assert_eq!(Ok(board.teams[team].points), g.team_all_rounds_score(team));
+            assert_eq!(
+                Ok(board.teams[team].round_points),
+                g.team_individual_round_score(team)
+            );
+            assert_eq!(Ok(board.teams[team].bags), g.team_all_rounds_bags(team));
+        }
+        let json = serde_json::to_string(&board).unwrap();
+        assert_eq!(board, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn test_scoreboard_table() {
+        let board = Scoreboard {
+            rounds_played: 3,
+            teams: [
+                ScoreboardRow {
+                    bid: Some(7),
+                    tricks: 5,
+                    round_points: 71,
+                    round_bags: 1,
+                    points: 213,
+                    bags: 1,
+                },
+                ScoreboardRow {
+                    bid: None,
+                    tricks: 3,
+                    round_points: -50,
+                    round_bags: 0,
+                    points: 140,
+                    bags: 0,
+                },
+            ],
+        };
+        assert_eq!(
+            "Team    Bid  Tricks  Round  Total  Bags\n\
+             Team 0    7       5     71    213     1\n\
+             Team 1    -       3    -50    140     0",
+            board.to_string()
+        );
+    }
+}
@@ -1,8 +1,12 @@
-use cards::{get_trick_winner, Card};
+use cards::{trick_winner, Card};
+use result::SpadesError;
+use rules::Rules;
 use std::fmt;
-use std::ops::Add;
 
 /// Used as an argument to [Game::place_bet](struct.Game.html#method.place_bet).
+///
+/// Build a bet of a number of tricks with [`Bet::amount`](#method.amount), which checks the number.  Bets
+/// serialize as they always have, so games saved before `Board` and `TenForTwoHundred` still load.
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
 )]
@@ -10,6 +14,117 @@ pub enum Bet {
     Amount(u8),
     Nil,
     BlindNil,
+    /// Bidding the board: 4 tricks, the least some tables let a player open with.
+    Board,
+    /// 10 tricks, worth 200 points made and costing 200 set, rather than 100.
+    TenForTwoHundred,
+}
+
+impl Bet {
+    /// A bet of `tricks` tricks, or `SpadesError::InvalidBet` unless it is between 1 and 13.  Bet nil with
+    /// `Bet::Nil`.
+    pub fn amount(tricks: u8) -> Result<Bet, SpadesError> {
+        if (1..=13).contains(&tricks) {
+            Ok(Bet::Amount(tricks))
+        } else {
+            Err(SpadesError::InvalidBet)
+        }
+    }
+
+    /// Whether the bet can be placed: any bet but an amount outside 1 to 13.
+    pub fn is_valid(self) -> bool {
+        match self {
+            Bet::Amount(tricks) => (1..=13).contains(&tricks),
+            _ => true,
+        }
+    }
+
+    /// Whether the bet is nil or blind nil.
+    pub fn is_nil(self) -> bool {
+        self == Bet::Nil || self == Bet::BlindNil
+    }
+
+    /// The tricks the bet counts towards its team's bid, 0 for nil and blind nil.
+    pub fn tricks_required(self) -> u8 {
+        match self {
+            Bet::Amount(tricks) => tricks,
+            Bet::Nil | Bet::BlindNil => 0,
+            Bet::Board => 4,
+            Bet::TenForTwoHundred => 10,
+        }
+    }
+
+    /// The points the bet is worth, made or set, under standard scoring, leaving out bags.
+    pub fn value(self) -> i32 {
+        match self {
+            Bet::Nil => 100,
+            Bet::BlindNil | Bet::TenForTwoHundred => 200,
+            bet => bet.tricks_required() as i32 * 10,
+        }
+    }
+}
+
+/// Two partners' bets taken together, as standard scoring sees them: nil bets add nothing to the tricks the
+/// team has to take, and are won or lost on their own.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct TeamBid {
+    bets: [Bet; 2],
+}
+
+impl TeamBid {
+    pub fn new(first: Bet, second: Bet) -> TeamBid {
+        TeamBid {
+            bets: [first, second],
+        }
+    }
+
+    pub fn bets(&self) -> [Bet; 2] {
+        self.bets
+    }
+
+    /// The tricks the team has to take between them to make its bid.
+    pub fn tricks_required(&self) -> u8 {
+        self.bets
+            .iter()
+            .fold(0, |sum: u8, bet| sum.saturating_add(bet.tricks_required()))
+    }
+
+    /// How many of the two bets are nil or blind nil.
+    pub fn nil_bets(&self) -> usize {
+        self.bets.iter().filter(|bet| bet.is_nil()).count()
+    }
+
+    /// Whether both bets can be placed and the team hasn't bid more than the 13 tricks of a round.
+    pub fn is_valid(&self) -> bool {
+        self.bets.iter().all(|bet| bet.is_valid()) && self.tricks_required() <= 13
+    }
+
+    /// The points the team's bid is worth, made or set, leaving out any nil bets and bags.
+    pub fn value(&self) -> i32 {
+        self.bets
+            .iter()
+            .filter(|bet| !bet.is_nil())
+            .map(|bet| bet.value())
+            .sum()
+    }
+}
+
+impl From<[Bet; 2]> for TeamBid {
+    fn from(bets: [Bet; 2]) -> TeamBid {
+        TeamBid { bets }
+    }
 }
 
 impl Default for Bet {
@@ -28,53 +143,71 @@ impl From<u8> for Bet {
     }
 }
 
-impl Add for Bet {
-    type Output = u8;
-    fn add(self, rhs: Self) -> u8 {
-        match self {
-            Bet::Amount(x) => { match rhs {
-                Bet::Amount(y) => x + y,
-                _ => x
-            }}
-            _ => { match rhs {
-                Bet::Amount(y) => y,
-                _ => 0
-            }}
-        }
-    }
-}
-
 impl fmt::Display for Bet {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:?}", self)
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
 struct GameConfig {
     max_points: i32,
+    bag_penalty: i32,
+    /// Set if the game ends after this many rounds, whatever the scores.
+    round_limit: Option<u32>,
 }
 
+/// The points standard scoring takes off a team for every 10 bags.
+pub(crate) const STANDARD_BAG_PENALTY: i32 = 100;
+
 impl Default for GameConfig {
     fn default() -> Self {
-        GameConfig { max_points: 500 }
+        GameConfig {
+            max_points: 500,
+            bag_penalty: STANDARD_BAG_PENALTY,
+            round_limit: None,
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 struct PlayerState {
     won_trick: [bool; 13],
 }
 
-impl Default for PlayerState {
-    fn default() -> Self {
-        PlayerState {
-            won_trick: [false; 13],
-        }
+impl PlayerState {
+    fn tricks_won(&self) -> u8 {
+        self.won_trick.iter().filter(|x| **x).count() as u8
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 pub struct TeamState {
     tricks: u8,
     game_bags: u8,
@@ -120,6 +253,7 @@ impl TeamState {
         self.cumulative_points
     }
 
+    #[cfg(test)]
     fn calculate_round_totals(
         &mut self,
         first_bet: Bet,
@@ -127,87 +261,534 @@ impl TeamState {
         second_bet: Bet,
         second_player: &PlayerState,
     ) {
-        let first_player_tricks = first_player.won_trick.iter().filter(|x| **x).count() as u8;
-        let second_player_tricks = second_player.won_trick.iter().filter(|x| **x).count() as u8;
-        self.tricks = (first_player_tricks + second_player_tricks) as u8;
-        let first_player_bet = {
-            match first_bet {
-                Bet::Amount(amount) => amount,
-                Bet::Nil => 0,
-                Bet::BlindNil => 0,
-            }
-        };
-        let second_player_bet = {
-            match second_bet {
-                Bet::Amount(amount) => amount,
-                Bet::Nil => 0,
-                Bet::BlindNil => 0,
-            }
-        };
-        let team_bets = first_player_bet + second_player_bet;
-        assert!(first_player_tricks <= 13);
-        assert!(second_player_tricks <= 13);
-        assert!(self.tricks <= 13);
+        self.score_tricks(
+            first_bet,
+            first_player.tricks_won(),
+            second_bet,
+            second_player.tricks_won(),
+            StandardScoring::default(),
+            STANDARD_BAG_PENALTY,
+        );
+    }
+
+    fn apply_round(&mut self, tricks: u8, score: TeamRoundScore) {
+        self.tricks = tricks;
+        self.game_points = score.points;
+        self.game_bags = score.bags;
+        self.cumulative_bags = score.cumulative_bags;
+        self.cumulative_points += score.points;
+    }
+
+    fn score_tricks(
+        &mut self,
+        first_bet: Bet,
+        first_player_tricks: u8,
+        second_bet: Bet,
+        second_player_tricks: u8,
+        rules: StandardScoring,
+        bag_penalty: i32,
+    ) {
+        // impossible counts are scored as given rather than rejected
+        self.tricks = first_player_tricks.saturating_add(second_player_tricks);
         self.game_points = 0;
         self.game_bags = 0;
+        match rules.nil_tricks {
+            NilTricks::CountTowardsBid => self.score_nil_tricks_towards_bid(
+                first_bet,
+                first_player_tricks,
+                second_bet,
+                second_player_tricks,
+            ),
+            NilTricks::Bags | NilTricks::Ignored => self.score_nil_tricks_apart(
+                [first_bet, second_bet],
+                [first_player_tricks, second_player_tricks],
+                rules.nil_tricks,
+            ),
+        }
+        self.cumulative_bags = self.cumulative_bags.saturating_add(self.game_bags);
+
+        if rules.bag_settlement == BagSettlement::EveryTenBags && self.cumulative_bags >= 10 {
+            self.cumulative_bags -= 10;
+            self.game_points -= bag_penalty;
+        }
+        self.cumulative_points += self.game_points;
+    }
+
+    /// Scores the round's bets when nil bidders' tricks count towards their partner's bet, see
+    /// [`NilTricks::CountTowardsBid`](enum.NilTricks.html#variant.CountTowardsBid).
+    fn score_nil_tricks_towards_bid(
+        &mut self,
+        first_bet: Bet,
+        first_player_tricks: u8,
+        second_bet: Bet,
+        second_player_tricks: u8,
+    ) {
+        let first_player_bet = first_bet.tricks_required();
+        let second_player_bet = second_bet.tricks_required();
+        let bid = TeamBid::new(first_bet, second_bet);
+        let team_bets = bid.tricks_required();
+        let team_value = bid.value();
         if self.tricks >= team_bets {
-            let game_bags = self.tricks - team_bets;
-            assert!(game_bags <= 13);
-            self.game_bags = game_bags;
+            self.game_bags = self.tricks - team_bets;
             if first_player_bet != 0 && second_player_bet != 0 {
-                self.game_points += self.tricks as i32 - team_bets as i32 + (team_bets as i32 * 10);
+                self.game_points += self.tricks as i32 - team_bets as i32 + team_value;
             }
         } else {
-            self.game_points -= (team_bets as i32) * 10;
+            self.game_points -= team_value;
         }
 
         if first_player_bet == 0 {
-            let change_amount = {
-                if first_bet == Bet::BlindNil {
-                    200
-                } else {
-                    100
-                }
-            };
+            let change_amount = first_bet.value();
             if first_player_tricks == 0 {
                 self.game_points += change_amount;
             } else {
                 self.game_points -= change_amount;
             }
             if second_player_tricks >= team_bets && second_player_bet != 0 {
-                self.game_points += self.tricks as i32 - team_bets as i32 + (team_bets as i32 * 10);
+                self.game_points += self.tricks as i32 - team_bets as i32 + team_value;
             }
         }
         if second_player_bet == 0 {
-            let change_amount = {
-                if second_bet == Bet::BlindNil {
-                    200
-                } else {
-                    100
-                }
-            };
+            let change_amount = second_bet.value();
             if second_player_tricks == 0 {
                 self.game_points += change_amount;
             } else {
                 self.game_points -= change_amount;
             }
             if first_player_tricks >= team_bets && first_player_bet != 0 {
-                self.game_points += self.tricks as i32 - team_bets as i32 + (team_bets as i32 * 10);
+                self.game_points += self.tricks as i32 - team_bets as i32 + team_value;
             }
         }
-        self.cumulative_bags += self.game_bags;
+    }
 
-        if self.cumulative_bags >= 10 {
-            self.cumulative_bags -= 10;
-            self.game_points -= 100;
+    /// Scores the round's bets when nil bidders' tricks don't count towards their partner's bet: the bet is
+    /// made or set on the other player's tricks alone, and the nil bidders' tricks become bags or are ignored.
+    fn score_nil_tricks_apart(&mut self, bets: [Bet; 2], tricks: [u8; 2], nil_tricks: NilTricks) {
+        let bid = TeamBid::from(bets);
+        let (mut bid_tricks, mut nil_taken) = (0, 0);
+        for (bet, taken) in bets.iter().zip(tricks.iter()) {
+            if bet.is_nil() {
+                nil_taken = taken.saturating_add(nil_taken);
+                let change_amount = bet.value();
+                self.game_points += if *taken == 0 {
+                    change_amount
+                } else {
+                    -change_amount
+                };
+            } else {
+                bid_tricks = taken.saturating_add(bid_tricks);
+            }
+        }
+        if bid_tricks >= bid.tricks_required() {
+            self.game_bags = bid_tricks - bid.tricks_required();
+            self.game_points += bid.value() + self.game_bags as i32;
+        } else {
+            self.game_points -= bid.value();
+        }
+        if nil_tricks == NilTricks::Bags {
+            self.game_bags = self.game_bags.saturating_add(nil_taken);
+            self.game_points += nil_taken as i32;
+        }
+    }
+}
+
+/// What one team scored in a round, as returned by
+/// [ScoringRules::score_round](trait.ScoringRules.html#tymethod.score_round).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct TeamRoundScore {
+    /// Points scored this round, including any bag penalty.
+    pub points: i32,
+    /// Bags (overtricks) taken this round.
+    pub bags: u8,
+    /// The team's bags carried forward after this round, once any penalty has been taken off.
+    pub cumulative_bags: u8,
+}
+
+/// How a round is scored and when the game is over, so other scoring systems can be plugged into a
+/// [`Game`](struct.Game.html) with [`set_scoring_rules`](struct.Game.html#method.set_scoring_rules).
+///
+/// Players 0 and 2 make up Team 0, and players 1 and 3 Team 1.  Like card play rules, scoring rules are not part
+/// of a saved game.
+pub trait ScoringRules: fmt::Debug + Send + Sync {
+    /// A short name for this scoring system.  Two games compare equal only if their scoring has the same name.
+    fn name(&self) -> &str;
+
+    /// Score a finished round for both teams, from each player's bet and tricks taken and each team's bags
+    /// carried forward from earlier rounds.  `bag_penalty` is the game's penalty for 10 bags, for rules that
+    /// take one off.
+    fn score_round(
+        &self,
+        bets: &[Bet; 4],
+        tricks: &[u8; 4],
+        cumulative_bags: &[u8; 2],
+        bag_penalty: i32,
+    ) -> [TeamRoundScore; 2];
+
+    /// When bags are turned into penalties.  Under
+    /// [`BagSettlement::EndOfGame`](enum.BagSettlement.html#variant.EndOfGame), `score_round` takes nothing
+    /// off for bags and carries every one of them forward, and the game settles them once it ends.
+    fn bag_settlement(&self) -> BagSettlement {
+        BagSettlement::EveryTenBags
+    }
+
+    /// Whether the game ends with these cumulative scores.
+    fn is_game_over(&self, cumulative_points: &[i32; 2], max_points: i32) -> bool {
+        cumulative_points.iter().any(|points| *points >= max_points)
+    }
+}
+
+/// What becomes of the tricks a player who bet nil or blind nil takes, under
+/// [`StandardScoring`](struct.StandardScoring.html).  The nil bet is lost either way.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum NilTricks {
+    /// The tricks count towards the partner's bet and any over it are bags, as the team's tricks together.
+    /// The partner's bet is set only if the team's tricks fall short of it, but scores only if the partner
+    /// makes it alone.
+    #[default]
+    CountTowardsBid,
+    /// The partner makes or is set on their own tricks, and every trick the nil bidder takes is a bag for the
+    /// team, worth a point like any other.
+    Bags,
+    /// The partner makes or is set on their own tricks, and the nil bidder's tricks count for nothing.
+    Ignored,
+}
+
+/// When the penalty for bags is taken off, see
+/// [`ScoringRules::bag_settlement`](trait.ScoringRules.html#method.bag_settlement).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum BagSettlement {
+    /// In the round a team reaches 10 bags, which are then taken off its count.
+    #[default]
+    EveryTenBags,
+    /// Once, in the round that ends the game, for every 10 bags the team has taken over the whole game.  The
+    /// game is decided on the scores after the penalty, but whether it ends is decided before it.
+    EndOfGame,
+}
+
+/// Standard partnership scoring: 10 points per trick bet plus 1 per bag when the team makes its bet, minus 10 per
+/// trick bet otherwise, 100 (200 for blind nil) won or lost on each nil bet, and 100 off for every 10 bags.
+///
+/// `StandardScoring::default()` counts a nil bidder's tricks towards their partner's bet and takes the
+/// penalty off every 10 bags as they are reached; set `nil_tricks` and `bag_settlement` to score otherwise.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StandardScoring {
+    pub nil_tricks: NilTricks,
+    pub bag_settlement: BagSettlement,
+}
+
+impl ScoringRules for StandardScoring {
+    fn name(&self) -> &str {
+        match (self.nil_tricks, self.bag_settlement) {
+            (NilTricks::CountTowardsBid, BagSettlement::EveryTenBags) => "standard",
+            (NilTricks::Bags, BagSettlement::EveryTenBags) => "standard, nil tricks as bags",
+            (NilTricks::Ignored, BagSettlement::EveryTenBags) => "standard, nil tricks ignored",
+            (NilTricks::CountTowardsBid, BagSettlement::EndOfGame) => {
+                "standard, bags settled at game end"
+            }
+            (NilTricks::Bags, BagSettlement::EndOfGame) => {
+                "standard, nil tricks as bags, bags settled at game end"
+            }
+            (NilTricks::Ignored, BagSettlement::EndOfGame) => {
+                "standard, nil tricks ignored, bags settled at game end"
+            }
         }
-        self.cumulative_points += self.game_points;
     }
+
+    fn bag_settlement(&self) -> BagSettlement {
+        self.bag_settlement
+    }
+
+    fn score_round(
+        &self,
+        bets: &[Bet; 4],
+        tricks: &[u8; 4],
+        cumulative_bags: &[u8; 2],
+        bag_penalty: i32,
+    ) -> [TeamRoundScore; 2] {
+        [
+            partnership_score(
+                [bets[0], bets[2]],
+                [tricks[0], tricks[2]],
+                cumulative_bags[0],
+                *self,
+                bag_penalty,
+            ),
+            partnership_score(
+                [bets[1], bets[3]],
+                [tricks[1], tricks[3]],
+                cumulative_bags[1],
+                *self,
+                bag_penalty,
+            ),
+        ]
+    }
+}
+
+pub(crate) fn standard_partnership_score(
+    bets: [Bet; 2],
+    tricks: [u8; 2],
+    cumulative_bags: u8,
+    bag_penalty: i32,
+) -> TeamRoundScore {
+    partnership_score(
+        bets,
+        tricks,
+        cumulative_bags,
+        StandardScoring::default(),
+        bag_penalty,
+    )
+}
+
+fn partnership_score(
+    bets: [Bet; 2],
+    tricks: [u8; 2],
+    cumulative_bags: u8,
+    rules: StandardScoring,
+    bag_penalty: i32,
+) -> TeamRoundScore {
+    let mut team = TeamState {
+        cumulative_bags,
+        ..TeamState::default()
+    };
+    team.score_tricks(bets[0], tricks[0], bets[1], tricks[1], rules, bag_penalty);
+    TeamRoundScore {
+        points: team.game_points,
+        bags: team.game_bags,
+        cumulative_bags: team.cumulative_bags,
+    }
+}
+
+/// Standard scoring for a player without a partner, as in two-handed Spades: as for a partnership, but on
+/// their own bet and tricks.
+pub(crate) fn standard_solo_score(
+    bet: Bet,
+    tricks: u8,
+    cumulative_bags: u8,
+    bag_penalty: i32,
+) -> TeamRoundScore {
+    let (mut points, bags) = match bet {
+        Bet::Nil | Bet::BlindNil => (
+            if tricks == 0 {
+                bet.value()
+            } else {
+                -bet.value()
+            },
+            tricks,
+        ),
+        _ if tricks >= bet.tricks_required() => (
+            bet.value() + (tricks - bet.tricks_required()) as i32,
+            tricks - bet.tricks_required(),
+        ),
+        _ => (-bet.value(), 0),
+    };
+    let mut cumulative_bags = cumulative_bags + bags;
+    if cumulative_bags >= 10 {
+        cumulative_bags -= 10;
+        points -= bag_penalty;
+    }
+    TeamRoundScore {
+        points,
+        bags,
+        cumulative_bags,
+    }
+}
+
+/// Standard scoring for a team of one or two players, with their bets and tricks in seat order.
+pub(crate) fn standard_team_score(
+    bets: &[Bet],
+    tricks: &[u8],
+    cumulative_bags: u8,
+    bag_penalty: i32,
+) -> TeamRoundScore {
+    match (bets, tricks) {
+        (&[bet], &[tricks]) => standard_solo_score(bet, tricks, cumulative_bags, bag_penalty),
+        (&[first, second], &[first_tricks, second_tricks]) => standard_partnership_score(
+            [first, second],
+            [first_tricks, second_tricks],
+            cumulative_bags,
+            bag_penalty,
+        ),
+        _ => unreachable!("teams are of one or two players"),
+    }
+}
+
+/// How a round turned out, as scored at the end of its last trick.  Players 0 and 2 make up Team 0, and players
+/// 1 and 3 Team 1.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct RoundOutcome {
+    /// Each player's bet.
+    pub bets: [Bet; 4],
+    /// Tricks each player took.
+    pub tricks: [u8; 4],
+    /// What each team scored, bag penalty included, and its bags.
+    pub scores: [TeamRoundScore; 2],
+    /// For each player who bet nil or blind nil, whether they made it.
+    pub nils: [Option<bool>; 4],
+    /// Whether the round ended the game.
+    pub game_over: bool,
+}
+
+impl RoundOutcome {
+    /// Tricks Team 0 (players 0 and 2) or Team 1 (players 1 and 3) took between them.
+    pub fn team_tricks(&self, team: usize) -> u8 {
+        self.tricks[team] + self.tricks[team + 2]
+    }
+
+    /// Points Team 0 or Team 1 scored, bag penalty included.
+    pub fn points(&self, team: usize) -> i32 {
+        self.scores[team].points
+    }
+}
+
+/// One round to score with [`score_rounds`](fn.score_rounds.html), without playing it out in a `Game`.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct RoundInput {
+    pub bets: [Bet; 4],
+    pub tricks: [u8; 4],
+    /// Each team's bags carried forward from earlier rounds.
+    pub cumulative_bags: [u8; 2],
+}
+
+/// Scores each round of `batch` by [`StandardScoring`](struct.StandardScoring.html), for tuning bidding over
+/// many bet and trick combinations at once.  Only the returned `Vec` is allocated.
+pub fn score_rounds(batch: &[RoundInput]) -> Vec<[TeamRoundScore; 2]> {
+    batch
+        .iter()
+        .map(|round| {
+            StandardScoring::default().score_round(
+                &round.bets,
+                &round.tricks,
+                &round.cumulative_bags,
+                STANDARD_BAG_PENALTY,
+            )
+        })
+        .collect()
+}
+
+/// The score a team would get for the current round, as returned by
+/// [Game::projected_round_score](struct.Game.html#method.projected_round_score).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct RoundProjection {
+    /// Tricks the team would take this round.
+    pub tricks: u8,
+    /// Points the team would score this round, including any penalty for reaching 10 bags.
+    pub points: i32,
+    /// Bags (overtricks) the team would take this round.
+    pub bags: u8,
+}
+
+/// How a team stands against its bid in the round being played, as returned by
+/// [Game::contract_status](struct.Game.html#method.contract_status).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct ContractStatus {
+    /// Tricks bet by the team's players together; nil bets count as 0.
+    pub bid: u8,
+    /// Tricks the team's players have taken together so far this round.
+    pub tricks: u8,
+    /// Tricks the team still has to take to make its bid.
+    pub needed: u8,
+    /// Tricks still to be played this round.
+    pub remaining: u8,
+    /// The team can no longer make its bid, even by taking every trick left.
+    pub set: bool,
+    /// The team has taken more tricks than it bid, so every further trick is a bag.
+    pub bagging: bool,
+}
+
+/// How a nil bet is going in the round being played, as returned by
+/// [Game::nil_status](struct.Game.html#method.nil_status).
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct NilStatus {
+    /// `Bet::Nil` or `Bet::BlindNil`.
+    pub bid: Bet,
+    /// The nil bidder has taken a trick, so the nil has failed.
+    pub broken: bool,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, serde::Serialize, serde::Deserialize,
+)]
 pub struct Scoring {
     config: GameConfig,
+    // rules are code, not data; the caller sets them again after loading
+    #[serde(skip)]
+    rules: Rules<dyn ScoringRules>,
     pub team: [TeamState; 2],
     players: [PlayerState; 4],
     in_betting_stage: bool,
@@ -215,6 +796,9 @@ pub struct Scoring {
     is_over: bool,
     round: usize,
     trick: usize,
+    tricks_per_round: u8,
+    /// What each team scored in each round, oldest first.
+    round_scores: Vec<[TeamRoundScore; 2]>,
 }
 
 impl Default for Scoring {
@@ -227,7 +811,10 @@ impl Default for Scoring {
             is_over: false,
             round: 0,
             trick: 0,
+            tricks_per_round: 13,
+            round_scores: vec![],
             config: GameConfig::default(),
+            rules: Rules::default(),
         }
     }
 }
@@ -240,7 +827,7 @@ impl Scoring {
     pub fn betting_over(&mut self) {
         self.trick = 0;
         self.in_betting_stage = false;
-        for mut p in &mut self.players {
+        for p in &mut self.players {
             for i in 0..13 {
                 p.won_trick[i] = false;
             }
@@ -251,71 +838,322 @@ impl Scoring {
         self.team[1].game_points = 0;
     }
 
-    pub fn trick(&mut self, starting_player_index: usize, cards: &Vec<Card>) -> usize {
-        let winner = get_trick_winner(starting_player_index, cards);
-        self.players[winner].won_trick[self.trick] = true;
+    /// Credits the trick to its winner, returned along with the round's outcome if it was the last trick.
+    pub fn trick(
+        &mut self,
+        starting_player_index: usize,
+        cards: &[Card],
+    ) -> (usize, Option<RoundOutcome>) {
+        let winner = trick_winner(starting_player_index, 4, cards);
+        (winner, self.award_trick(winner))
+    }
 
-        if self.trick == 12 {
-            // score the round
-            self.team[0].calculate_round_totals(
-                self.bets_placed[0],
-                &self.players[0],
-                self.bets_placed[2],
-                &self.players[2],
-            );
-            self.team[1].calculate_round_totals(
-                self.bets_placed[1],
-                &self.players[1],
-                self.bets_placed[3],
-                &self.players[3],
-            );
-            if self.team[0].cumulative_points >= self.config.max_points
-                || self.team[1].cumulative_points >= self.config.max_points
-            {
-                self.is_over = true;
+    /// Credits every trick not yet played this round to the player at `winner`, then scores the round.
+    pub fn award_remaining_tricks(&mut self, winner: usize) -> RoundOutcome {
+        loop {
+            if let Some(outcome) = self.award_trick(winner) {
+                return outcome;
             }
+        }
+    }
 
-            // reset structure for possible next round
-            self.in_betting_stage = true;
+    pub fn bets(&self) -> [Bet; 4] {
+        self.bets_placed
+    }
+
+    /// How many rounds have been scored.
+    pub fn rounds_played(&self) -> usize {
+        self.round
+    }
+
+    /// What each team scored in each round scored so far, oldest first.
+    pub(crate) fn round_scores(&self) -> &[[TeamRoundScore; 2]] {
+        &self.round_scores
+    }
 
-            self.round += 1;
+    /// Credits a trick to the player at `winner`.  If it was the round's last trick, the round is scored and
+    /// its outcome returned.
+    pub(crate) fn award_trick(&mut self, winner: usize) -> Option<RoundOutcome> {
+        self.players[winner].won_trick[self.trick] = true;
+
+        if self.trick + 1 >= self.tricks_per_round as usize {
+            let outcome = self.score_round();
+            self.apply_round_outcome(&outcome);
+            Some(outcome)
         } else {
             self.trick += 1;
+            None
+        }
+    }
+
+    /// Scores the round as it stands, from the bets and the tricks each player has taken, without changing
+    /// anything.
+    pub fn score_round(&self) -> RoundOutcome {
+        let tricks = self.player_tricks();
+        let cumulative_bags = [self.team[0].cumulative_bags, self.team[1].cumulative_bags];
+        let mut scores = self.score_bets(&self.bets_placed, &tricks, &cumulative_bags);
+        let cumulative_points = [
+            self.team[0].cumulative_points + scores[0].points,
+            self.team[1].cumulative_points + scores[1].points,
+        ];
+        let mut nils = [None; 4];
+        for seat in 0..4 {
+            if self.bets_placed[seat].is_nil() {
+                nils[seat] = Some(tricks[seat] == 0);
+            }
+        }
+        let game_over = match self.config.round_limit {
+            Some(rounds) => self.round + 1 >= rounds as usize,
+            None => self
+                .rules
+                .get()
+                .is_game_over(&cumulative_points, self.config.max_points),
+        };
+        if game_over {
+            self.settle_bags(&mut scores);
+        }
+        RoundOutcome {
+            bets: self.bets_placed,
+            tricks,
+            scores,
+            nils,
+            game_over,
+        }
+    }
+
+    /// Adds a scored round to both teams' totals and goes back to betting, or ends the game.
+    fn apply_round_outcome(&mut self, outcome: &RoundOutcome) {
+        for team in 0..2 {
+            self.team[team].apply_round(outcome.team_tricks(team), outcome.scores[team]);
+        }
+        self.round_scores.push(outcome.scores);
+        self.is_over = outcome.game_over;
+
+        // reset structure for possible next round
+        self.in_betting_stage = true;
+
+        self.round += 1;
+    }
+
+    /// Tricks taken by each player so far this round.
+    pub fn player_tricks(&self) -> [u8; 4] {
+        [
+            self.players[0].tricks_won(),
+            self.players[1].tricks_won(),
+            self.players[2].tricks_won(),
+            self.players[3].tricks_won(),
+        ]
+    }
+
+    /// What `team_id` would score this round if each player ended it with `tricks` tricks.
+    pub fn project(&self, team_id: usize, tricks: [u8; 4]) -> RoundProjection {
+        let cumulative_bags = [self.team[0].cumulative_bags, self.team[1].cumulative_bags];
+        let score = self.score_bets(&self.bets_placed, &tricks, &cumulative_bags)[team_id];
+        RoundProjection {
+            tricks: tricks[team_id] + tricks[team_id + 2],
+            points: score.points,
+            bags: score.bags,
         }
-        winner
+    }
+
+    /// Set how many tricks the next round to be bet has; 13 unless some cards aren't dealt.
+    pub fn set_tricks_per_round(&mut self, tricks: u8) {
+        assert!((1..=13).contains(&tricks));
+        self.tricks_per_round = tricks;
+    }
+
+    pub fn tricks_per_round(&self) -> u8 {
+        self.tricks_per_round
+    }
+
+    /// The index of the round's trick being played, from 0.
+    pub(crate) fn trick_index(&self) -> usize {
+        self.trick
+    }
+
+    pub fn set_rules(&mut self, rules: Rules<dyn ScoringRules>) {
+        self.rules = rules;
+    }
+
+    pub fn rules(&self) -> &dyn ScoringRules {
+        self.rules.get()
     }
 
     pub fn is_over(&self) -> bool {
         self.is_over
     }
 
+    /// Goes back to the betting stage of the round being played, for a misdeal.
+    pub(crate) fn restart_round(&mut self) {
+        self.in_betting_stage = true;
+        self.trick = 0;
+    }
+
+    /// Adds `points` to what each team scored in round `round`, and to their cumulative scores.
+    pub(crate) fn adjust_round_points(&mut self, round: usize, points: [i32; 2]) {
+        for (team, points) in points.iter().enumerate() {
+            self.round_scores[round][team].points += points;
+            self.team[team].cumulative_points += points;
+            // the latest round's points stay reported until the next round's betting is over
+            if self.in_betting_stage && round + 1 == self.round_scores.len() {
+                self.team[team].game_points += points;
+            }
+        }
+    }
+
+    /// Scores a round by the rules, with the game's bag penalty.
+    pub(crate) fn score_bets(
+        &self,
+        bets: &[Bet; 4],
+        tricks: &[u8; 4],
+        cumulative_bags: &[u8; 2],
+    ) -> [TeamRoundScore; 2] {
+        self.rules
+            .get()
+            .score_round(bets, tricks, cumulative_bags, self.config.bag_penalty)
+    }
+
+    /// Takes the penalty for every 10 bags off the scores of the round that ended the game, if the rules
+    /// [settle bags](enum.BagSettlement.html#variant.EndOfGame) then.
+    pub(crate) fn settle_bags(&self, scores: &mut [TeamRoundScore; 2]) {
+        if self.rules.get().bag_settlement() == BagSettlement::EndOfGame {
+            for score in scores.iter_mut() {
+                score.points -= (score.cumulative_bags / 10) as i32 * self.config.bag_penalty;
+                score.cumulative_bags %= 10;
+            }
+        }
+    }
+
+    pub(crate) fn set_max_points(&mut self, max_points: i32) {
+        self.config.max_points = max_points;
+    }
+
+    pub(crate) fn set_round_limit(&mut self, round_limit: Option<u32>) {
+        self.config.round_limit = round_limit;
+    }
+
+    pub(crate) fn set_bag_penalty(&mut self, bag_penalty: i32) {
+        self.config.bag_penalty = bag_penalty;
+    }
+
+    /// Adds `points` to a team's cumulative score, outside of any round.
+    pub(crate) fn adjust_points(&mut self, team: usize, points: i32) {
+        self.team[team].cumulative_points += points;
+    }
+
+    /// Sets each team's cumulative bags, as carried over from an earlier game.
+    pub(crate) fn carry_bags(&mut self, bags: [u8; 2]) {
+        self.team[0].cumulative_bags = bags[0];
+        self.team[1].cumulative_bags = bags[1];
+    }
+
+    /// The score a team needs to win the game.
+    pub(crate) fn max_points(&self) -> i32 {
+        self.config.max_points
+    }
+
     pub fn is_in_betting_stage(&self) -> bool {
         self.in_betting_stage
     }
 }
 
+/// Shares out the tricks left in a round of `tricks_per_round` tricks in proportion to the tricks each player
+/// has already taken.  Leftover tricks go to the players with the largest fractional shares, the earlier seat
+/// on ties.
+pub fn project_tricks(taken: [u8; 4], tricks_per_round: u8) -> [u8; 4] {
+    let played: u32 = taken.iter().map(|t| *t as u32).sum();
+    let remaining = tricks_per_round as u32 - played;
+    let (weights, total) = if played == 0 {
+        ([1; 4], 4)
+    } else {
+        (
+            [
+                taken[0] as u32,
+                taken[1] as u32,
+                taken[2] as u32,
+                taken[3] as u32,
+            ],
+            played,
+        )
+    };
+
+    let mut tricks = taken;
+    let mut given = 0;
+    for i in 0..4 {
+        let share = (remaining * weights[i] / total) as u8;
+        tricks[i] += share;
+        given += share as u32;
+    }
+    let mut by_fraction = [0, 1, 2, 3];
+    by_fraction.sort_by_key(|i| std::cmp::Reverse(remaining * weights[*i] % total));
+    for i in by_fraction.iter().take((remaining - given) as usize) {
+        tricks[*i] += 1;
+    }
+    tricks
+}
+
 #[cfg(test)]
 mod tests {
     use super::Bet;
-    use super::{PlayerState, Scoring, TeamState};
+    use super::{
+        project_tricks, score_rounds, standard_partnership_score, standard_solo_score,
+        BagSettlement, NilTricks, PlayerState, RoundInput, RoundOutcome, RoundProjection, Scoring,
+        ScoringRules, StandardScoring, TeamBid, TeamRoundScore, TeamState, STANDARD_BAG_PENALTY,
+    };
+    use test_support::{seated_game, started_game};
+    use {Game, GameLength, GameOptions, SpadesError, State, Uid};
+
+    /// Ten points a trick, no bets or bags, first team to a trick total of 10 wins.
+    #[derive(Debug)]
+    struct TrickCounting;
+
+    impl ScoringRules for TrickCounting {
+        fn name(&self) -> &str {
+            "trick-counting"
+        }
+
+        fn score_round(
+            &self,
+            _bets: &[Bet; 4],
+            tricks: &[u8; 4],
+            _cumulative_bags: &[u8; 2],
+            _bag_penalty: i32,
+        ) -> [TeamRoundScore; 2] {
+            [0, 1].map(|team| TeamRoundScore {
+                points: 10 * (tricks[team] + tricks[team + 2]) as i32,
+                bags: 0,
+                cumulative_bags: 0,
+            })
+        }
+
+        fn is_game_over(&self, cumulative_points: &[i32; 2], _max_points: i32) -> bool {
+            cumulative_points.iter().any(|points| *points >= 100)
+        }
+    }
 
     #[test]
-    fn test_add_bets() {
+    fn test_team_bids() {
         let bet3 = Bet::Amount(3);
         let bet13 = Bet::Amount(13);
         let betnil = Bet::Nil;
         let betblindnil = Bet::BlindNil;
-        assert_eq!(3, bet3 + betnil);
-        assert_eq!(3, bet3 + betblindnil);
-        assert_eq!(0, betnil + betnil);
-        assert_eq!(16, bet3 + bet13);
+        assert_eq!(3, TeamBid::new(bet3, betnil).tricks_required());
+        assert_eq!(3, TeamBid::new(bet3, betblindnil).tricks_required());
+        assert_eq!(0, TeamBid::new(betnil, betnil).tricks_required());
+        assert_eq!(2, TeamBid::new(betnil, betblindnil).nil_bets());
+        assert!(TeamBid::new(bet3, Bet::Amount(10)).is_valid());
+        assert!(!TeamBid::new(bet3, bet13).is_valid());
+        assert_eq!(30, TeamBid::new(bet3, betblindnil).value());
+        assert_eq!(100, betnil.value());
+        assert_eq!(200, betblindnil.value());
+        assert!(betnil.is_nil() && !bet3.is_nil());
     }
 
     #[test]
     fn test_playerstate_new() {
         let ps = PlayerState::default();
         for i in 0..13 {
-            assert_eq!(false, ps.won_trick[i]);
+            assert!(!ps.won_trick[i]);
         }
         assert_eq!(13, ps.won_trick.len());
     }
@@ -579,8 +1417,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "assertion failed: self.tricks <= 13")]
-    fn test_game_end_scoring_winning_14_tricks_panics() {
+    fn test_game_end_scoring_winning_14_tricks_is_scored_as_given() {
         let mut ts = TeamState::default();
         let first_bet = Bet::Nil;
         let second_bet = Bet::Nil;
@@ -593,5 +1430,404 @@ mod tests {
             second_player.won_trick[i] = true;
         }
         ts.calculate_round_totals(first_bet, &first_player, second_bet, &second_player);
+        assert_eq!(14, ts.tricks_won());
+        // both nils are set, and the 14 bags cost a penalty
+        assert_eq!(-300, ts.game_points());
+        assert_eq!(4, ts.cumulative_bags());
+    }
+
+    #[test]
+    fn test_project_tricks_shares_remaining_tricks() {
+        assert_eq!([4, 3, 3, 3], project_tricks([0, 0, 0, 0], 13));
+        assert_eq!([7, 0, 6, 0], project_tricks([1, 0, 1, 0], 13));
+        assert_eq!([7, 3, 0, 3], project_tricks([2, 1, 0, 1], 13));
+        assert_eq!([5, 3, 3, 2], project_tricks([5, 3, 3, 2], 13));
+        assert_eq!([3, 3, 3, 3], project_tricks([0, 0, 0, 0], 12));
+    }
+
+    #[test]
+    fn test_project_does_not_change_score() {
+        let mut sc = Scoring::default();
+        for (player, bet) in [4, 3, 2, 1].iter().enumerate() {
+            sc.add_bet(player, Bet::Amount(*bet));
+        }
+        sc.betting_over();
+        assert_eq!(
+            RoundProjection {
+                tricks: 8,
+                points: 62,
+                bags: 2
+            },
+            sc.project(0, [5, 2, 3, 3])
+        );
+        assert_eq!(
+            RoundProjection {
+                tricks: 5,
+                points: 41,
+                bags: 1
+            },
+            sc.project(1, [5, 2, 3, 3])
+        );
+        assert_eq!(0, sc.team[0].cumulative_points());
+        assert_eq!(0, sc.team[0].cumulative_bags());
+    }
+
+    #[test]
+    fn test_round_outcome() {
+        let mut sc = Scoring::default();
+        sc.set_max_points(100);
+        for (player, bet) in [Bet::Nil, Bet::Amount(3), Bet::Amount(6), Bet::Amount(3)]
+            .iter()
+            .enumerate()
+        {
+            sc.add_bet(player, *bet);
+        }
+        sc.betting_over();
+        let winners = [2, 2, 2, 2, 2, 2, 2, 1, 1, 1, 3, 3, 3];
+        for winner in &winners[..12] {
+            assert_eq!(None, sc.award_trick(*winner));
+        }
+        let expected = RoundOutcome {
+            bets: [Bet::Nil, Bet::Amount(3), Bet::Amount(6), Bet::Amount(3)],
+            tricks: [0, 3, 7, 3],
+            scores: [
+                TeamRoundScore {
+                    points: 161,
+                    bags: 1,
+                    cumulative_bags: 1,
+                },
+                TeamRoundScore {
+                    points: 60,
+                    bags: 0,
+                    cumulative_bags: 0,
+                },
+            ],
+            nils: [Some(true), None, None, None],
+            game_over: true,
+        };
+        sc.players[3].won_trick[12] = true;
+        // scoring the round as it stands changes nothing
+        assert_eq!(expected, sc.score_round());
+        assert!(!sc.is_over());
+        sc.players[3].won_trick[12] = false;
+
+        assert_eq!(Some(expected), sc.award_trick(3));
+        assert_eq!(7, expected.team_tricks(0));
+        assert_eq!(60, expected.points(1));
+        assert!(sc.is_over());
+        assert_eq!(161, sc.team[0].cumulative_points());
+        assert_eq!(&[expected.scores], sc.round_scores());
+    }
+
+    #[test]
+    fn test_standard_scoring_scores_both_teams() {
+        let bets = [Bet::Amount(4), Bet::Nil, Bet::Amount(3), Bet::Amount(5)];
+        let scores = StandardScoring::default().score_round(
+            &bets,
+            &[4, 0, 4, 5],
+            &[9, 0],
+            STANDARD_BAG_PENALTY,
+        );
+        assert_eq!(
+            TeamRoundScore {
+                points: -29,
+                bags: 1,
+                cumulative_bags: 0
+            },
+            scores[0]
+        );
+        assert_eq!(
+            TeamRoundScore {
+                points: 150,
+                bags: 0,
+                cumulative_bags: 0
+            },
+            scores[1]
+        );
+        let harsher = StandardScoring::default().score_round(&bets, &[4, 0, 4, 5], &[9, 0], 200);
+        assert_eq!(-129, harsher[0].points);
+        assert!(StandardScoring::default().is_game_over(&[500, 0], 500));
+        assert!(!StandardScoring::default().is_game_over(&[499, 499], 500));
+    }
+
+    #[test]
+    fn test_nil_tricks_rule() {
+        let scoring = |nil_tricks| {
+            let scores = StandardScoring {
+                nil_tricks,
+                ..StandardScoring::default()
+            }
+            .score_round(
+                &[Bet::Nil, Bet::Amount(3), Bet::Amount(4), Bet::Amount(3)],
+                &[2, 3, 3, 5],
+                &[0, 0],
+                STANDARD_BAG_PENALTY,
+            );
+            (scores[0].points, scores[0].bags)
+        };
+        // nil lost; the partner's 4 is made with the nil bidder's 2 tricks, but not paid, and 1 bag
+        assert_eq!((-100, 1), scoring(NilTricks::CountTowardsBid));
+        // the partner is set on their own 3 tricks, and the nil bidder's 2 tricks are bags
+        assert_eq!((-100 - 40 + 2, 2), scoring(NilTricks::Bags));
+        assert_eq!((-100 - 40, 0), scoring(NilTricks::Ignored));
+
+        let made = |nil_tricks| {
+            StandardScoring {
+                nil_tricks,
+                ..StandardScoring::default()
+            }
+            .score_round(
+                &[Bet::Nil, Bet::Amount(3), Bet::Amount(4), Bet::Amount(3)],
+                &[1, 3, 5, 4],
+                &[8, 0],
+                STANDARD_BAG_PENALTY,
+            )[0]
+        };
+        assert_eq!(
+            TeamRoundScore {
+                points: -100 + 42 - 100,
+                bags: 2,
+                cumulative_bags: 0
+            },
+            made(NilTricks::CountTowardsBid)
+        );
+        assert_eq!(made(NilTricks::CountTowardsBid), made(NilTricks::Bags));
+        assert_eq!(
+            TeamRoundScore {
+                points: -100 + 41,
+                bags: 1,
+                cumulative_bags: 9
+            },
+            made(NilTricks::Ignored)
+        );
+    }
+
+    #[test]
+    fn test_nil_tricks_rule_is_named() {
+        assert_eq!("standard", StandardScoring::default().name());
+        let mut g = Game::default();
+        g.set_scoring_rules(StandardScoring {
+            nil_tricks: NilTricks::Ignored,
+            ..StandardScoring::default()
+        });
+        assert_eq!("standard, nil tricks ignored", g.scoring_rules().name());
+        assert_ne!(Game::default(), g);
+    }
+
+    #[test]
+    fn test_bags_settled_at_game_end() {
+        let rules = StandardScoring {
+            bag_settlement: BagSettlement::EndOfGame,
+            ..StandardScoring::default()
+        };
+        assert_eq!("standard, bags settled at game end", rules.name());
+        let scores = rules.score_round(
+            &[
+                Bet::Amount(2),
+                Bet::Amount(3),
+                Bet::Amount(2),
+                Bet::Amount(3),
+            ],
+            &[5, 3, 2, 3],
+            &[9, 0],
+            STANDARD_BAG_PENALTY,
+        );
+        assert_eq!(
+            TeamRoundScore {
+                points: 43,
+                bags: 3,
+                cumulative_bags: 12
+            },
+            scores[0]
+        );
+
+        let mut g = seated_game();
+        g.set_options(GameOptions {
+            length: GameLength::FixedRounds(3),
+            ..GameOptions::default()
+        })
+        .unwrap();
+        g.set_scoring_rules(rules);
+        g.set_shuffle_seed(Some(2));
+        g.start_game();
+        while g.state() != State::GameCompleted {
+            for _ in 0..4 {
+                g.place_bet(Bet::Amount(1));
+            }
+            for _ in 0..52 {
+                let card = g.playable_cards().next().unwrap();
+                g.play_card(card);
+            }
+        }
+        let rounds = g.scoring.round_scores().to_vec();
+        for team in 0..2 {
+            let total_bags: u8 = rounds.iter().map(|round| round[team].bags).sum();
+            assert_eq!(rounds[0][team].bags, rounds[0][team].cumulative_bags);
+            assert_eq!(
+                rounds[0][team].bags + rounds[1][team].bags,
+                rounds[1][team].cumulative_bags
+            );
+            assert_eq!(total_bags % 10, rounds[2][team].cumulative_bags);
+            assert_eq!(
+                rounds[2][team].bags as i32 + 20 - (total_bags / 10) as i32 * 100,
+                rounds[2][team].points
+            );
+        }
+        // a penalty was taken off at the end
+        assert!((0..2).any(|team| rounds[1][team].cumulative_bags + rounds[2][team].bags >= 10));
+        let history = g.score_history();
+        assert_eq!(g.result().unwrap().scores, history[2]);
+    }
+
+    #[test]
+    fn test_solo_scoring() {
+        let made = standard_solo_score(Bet::Amount(5), 7, 8, STANDARD_BAG_PENALTY);
+        assert_eq!(
+            (52 - 100, 2, 0),
+            (made.points, made.bags, made.cumulative_bags)
+        );
+        let set = standard_solo_score(Bet::Amount(5), 4, 0, STANDARD_BAG_PENALTY);
+        assert_eq!((-50, 0), (set.points, set.bags));
+        assert_eq!(
+            200,
+            standard_solo_score(Bet::BlindNil, 0, 0, STANDARD_BAG_PENALTY).points
+        );
+        let failed_nil = standard_solo_score(Bet::Nil, 2, 0, STANDARD_BAG_PENALTY);
+        assert_eq!((-100, 2), (failed_nil.points, failed_nil.bags));
+        assert_eq!(
+            52 - 150,
+            standard_solo_score(Bet::Amount(5), 7, 8, 150).points
+        );
+    }
+
+    #[test]
+    fn test_bet_amount_is_validated() {
+        assert_eq!(Ok(Bet::Amount(1)), Bet::amount(1));
+        assert_eq!(Ok(Bet::Amount(13)), Bet::amount(13));
+        assert_eq!(Err(SpadesError::InvalidBet), Bet::amount(0));
+        assert_eq!(Err(SpadesError::InvalidBet), Bet::amount(14));
+        assert!(!Bet::Amount(0).is_valid());
+        assert!(Bet::Board.is_valid());
+
+        let g = started_game();
+        assert_eq!(
+            Some(SpadesError::InvalidBet),
+            g.can_place_bet(Bet::Amount(14))
+        );
+        assert_eq!(None, g.can_place_bet(Bet::Board));
+    }
+
+    #[test]
+    fn test_board_and_ten_for_two_hundred() {
+        assert_eq!(4, Bet::Board.tricks_required());
+        assert_eq!(10, Bet::TenForTwoHundred.tricks_required());
+        assert_eq!(
+            7,
+            TeamBid::new(Bet::Board, Bet::Amount(3)).tricks_required()
+        );
+
+        let board = StandardScoring::default().score_round(
+            &[Bet::Board, Bet::Amount(2), Bet::Amount(1), Bet::Amount(2)],
+            &[4, 3, 2, 4],
+            &[0, 0],
+            STANDARD_BAG_PENALTY,
+        );
+        assert_eq!(
+            StandardScoring::default().score_round(
+                &[
+                    Bet::Amount(4),
+                    Bet::Amount(2),
+                    Bet::Amount(1),
+                    Bet::Amount(2)
+                ],
+                &[4, 3, 2, 4],
+                &[0, 0],
+                STANDARD_BAG_PENALTY
+            ),
+            board
+        );
+
+        let made = standard_partnership_score(
+            [Bet::TenForTwoHundred, Bet::Amount(1)],
+            [9, 3],
+            0,
+            STANDARD_BAG_PENALTY,
+        );
+        assert_eq!((210 + 1, 1), (made.points, made.bags));
+        let set = standard_partnership_score(
+            [Bet::TenForTwoHundred, Bet::Amount(1)],
+            [8, 2],
+            0,
+            STANDARD_BAG_PENALTY,
+        );
+        assert_eq!(-210, set.points);
+        assert_eq!(
+            200,
+            standard_solo_score(Bet::TenForTwoHundred, 10, 0, STANDARD_BAG_PENALTY).points
+        );
+        assert_eq!(
+            -200,
+            standard_solo_score(Bet::TenForTwoHundred, 9, 0, STANDARD_BAG_PENALTY).points
+        );
+    }
+
+    #[test]
+    fn test_bets_serialize_as_before() {
+        assert_eq!(
+            "{\"Amount\":3}",
+            serde_json::to_string(&Bet::Amount(3)).unwrap()
+        );
+        assert_eq!("\"Nil\"", serde_json::to_string(&Bet::Nil).unwrap());
+        assert_eq!(
+            Bet::Board,
+            serde_json::from_str::<Bet>("\"Board\"").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_score_rounds_matches_standard_scoring() {
+        let mut batch = vec![];
+        for tricks in 0..=13 {
+            for bet in [Bet::Nil, Bet::Amount(2), Bet::Amount(6)].iter() {
+                batch.push(RoundInput {
+                    bets: [*bet, Bet::Amount(3), Bet::Amount(4), Bet::BlindNil],
+                    tricks: [tricks, 13 - tricks, 0, 0],
+                    cumulative_bags: [8, 0],
+                });
+            }
+        }
+        let scores = score_rounds(&batch);
+        assert_eq!(batch.len(), scores.len());
+        for (round, score) in batch.iter().zip(scores.iter()) {
+            assert_eq!(
+                StandardScoring::default().score_round(
+                    &round.bets,
+                    &round.tricks,
+                    &round.cumulative_bags,
+                    STANDARD_BAG_PENALTY
+                ),
+                *score
+            );
+        }
+        // nil taking nothing, partner set: 100 - 40
+        assert_eq!(60, scores[0][0].points);
+        assert_eq!(Vec::<[TeamRoundScore; 2]>::new(), score_rounds(&[]));
+    }
+
+    #[test]
+    fn test_custom_scoring_rules_are_used_by_game() {
+        let mut g = seated_game();
+        g.set_scoring_rules(TrickCounting);
+        assert_eq!("trick-counting", g.scoring_rules().name());
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        g.concede(1).unwrap();
+        g.respond_to_claim(&Uid(10), true).unwrap();
+        g.respond_to_claim(&Uid(12), true).unwrap();
+        assert_eq!(Ok(130), g.team_all_rounds_score(0));
+        assert_eq!(Ok(0), g.team_all_rounds_score(1));
+        assert_eq!(State::GameCompleted, g.state());
     }
 }
@@ -0,0 +1,245 @@
+//! Choosing a card by Monte Carlo search, and a trace of how it was chosen for debugging bots.
+//!
+//! [`search`](fn.search.html) [determinizes](../struct.Game.html#method.determinize) the game from the point
+//! of view of the player to act a number of times, and in each sample plays out the rest of the round after
+//! every card they may play, with [`HeuristicBot`](../struct.HeuristicBot.html)s in every seat.  The card
+//! whose rollouts score best for the player's team is suggested.  The returned
+//! [`SearchTrace`](struct.SearchTrace.html) holds the legal cards, the samples and the rollout statistics,
+//! and can be dumped [as JSON](struct.SearchTrace.html#method.to_json) to see why.
+//!
+//! ```
+//! use spades::{Bet, Game, Uid};
+//!
+//! let mut g = Game::default();
+//! g.assign_players(Uid(1), [Uid(10), Uid(11), Uid(12), Uid(13)]);
+//! g.set_shuffle_seed(Some(3));
+//! g.start_game();
+//! for _ in 0..4 {
+//!     g.place_bet(Bet::Amount(3));
+//! }
+//! let trace = spades::search::search(&g, 4, 7).unwrap();
+//! assert!(trace.legal.contains(&trace.suggested));
+//! println!("{}", trace.to_json());
+//! ```
+
+extern crate rand;
+extern crate serde_json;
+
+use self::rand::prng::XorShiftRng;
+use self::rand::SeedableRng;
+use agent::PlayerAgent;
+use bots::HeuristicBot;
+use cards::Card;
+use diagnostics::GameEvent;
+use game_state::State;
+use result::SpadesError;
+use seat::Seat;
+use {Game, Uid};
+
+/// One of the deals the player to act was imagined to be facing: every seat's hand, in seat order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct Sample {
+    pub hands: [Vec<Card>; 4],
+}
+
+/// How the rollouts after playing one card went, averaged over the samples.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CardStats {
+    pub card: Card,
+    /// Rollouts that reached the end of the round; at most one per sample.
+    pub rollouts: usize,
+    /// Of those, how many the player's team took the trick in progress in.
+    pub tricks_won: usize,
+    /// Tricks the player's team ended the round with.
+    pub mean_tricks: f64,
+    /// Points the player's team scored for the round.
+    pub mean_points: f64,
+}
+
+/// What [`search`](fn.search.html) looked at for one decision, and what it chose.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearchTrace {
+    pub game: Uid,
+    pub player: Uid,
+    pub seat: Seat,
+    /// The cards the player may play, as the game keeps them.
+    pub legal: Vec<Card>,
+    pub samples: Vec<Sample>,
+    /// One entry for each of `legal`, in the same order.
+    pub stats: Vec<CardStats>,
+    /// The card with the best mean points, the best mean tricks breaking ties, then the first legal card.
+    pub suggested: Card,
+}
+
+impl SearchTrace {
+    /// The trace as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a trace should serialize")
+    }
+}
+
+/// Searches `samples` determinizations of `game`, drawn from random numbers seeded by `seed`, for the card
+/// the player to act should play.
+///
+/// Returns `SpadesError::ImproperGameStage` unless the game is in the trick stage.
+pub fn search(game: &Game, samples: usize, seed: u64) -> Result<SearchTrace, SpadesError> {
+    if !matches!(game.state, State::Trick(_)) {
+        return Err(SpadesError::ImproperGameStage);
+    }
+    let player = game.current_player_id()?;
+    let seat = game.seat_of(&player)?;
+    let team = seat.index() % 2;
+    let legal: Vec<Card> = game.playable_cards().collect();
+    let mut rng = XorShiftRng::seed_from_u64(seed);
+
+    let mut sampled = Vec::with_capacity(samples);
+    let mut stats: Vec<CardStats> = legal
+        .iter()
+        .map(|card| CardStats {
+            card: *card,
+            rollouts: 0,
+            tricks_won: 0,
+            mean_tricks: 0.0,
+            mean_points: 0.0,
+        })
+        .collect();
+    for _ in 0..samples {
+        let world = game.determinize(&player, &mut rng)?;
+        sampled.push(Sample {
+            hands: [0, 1, 2, 3].map(|index| world.player[index].hand.to_vec()),
+        });
+        for stat in stats.iter_mut() {
+            if let Some((won, tricks, points)) = rollout(&world, stat.card, team) {
+                stat.rollouts += 1;
+                stat.tricks_won += won as usize;
+                stat.mean_tricks += tricks as f64;
+                stat.mean_points += points as f64;
+            }
+        }
+    }
+    for stat in stats.iter_mut().filter(|stat| stat.rollouts > 0) {
+        stat.mean_tricks /= stat.rollouts as f64;
+        stat.mean_points /= stat.rollouts as f64;
+    }
+
+    let mut suggested = legal[0];
+    let mut best = (f64::MIN, f64::MIN);
+    for stat in stats.iter().filter(|stat| stat.rollouts > 0) {
+        if (stat.mean_points, stat.mean_tricks) > best {
+            best = (stat.mean_points, stat.mean_tricks);
+            suggested = stat.card;
+        }
+    }
+    Ok(SearchTrace {
+        game: *game.id(),
+        player,
+        seat,
+        legal,
+        samples: sampled,
+        stats,
+        suggested,
+    })
+}
+
+/// Plays `card` in `world` and the rest of the round with heuristic bots.  Returns whether `team` took the
+/// trick in progress, and the tricks and points it ended the round with, or `None` if the round didn't end.
+fn rollout(world: &Game, card: Card, team: usize) -> Option<(bool, u8, i32)> {
+    let mut fork = world.clone_without_history();
+    let trick = fork.scoring.player_tricks();
+    let round = fork.scoring.rounds_played();
+    fork.play_card(card)?;
+    let mut won = None;
+    let mut bot = HeuristicBot::default();
+    while matches!(fork.state, State::Trick(_)) && fork.scoring.rounds_played() == round {
+        if won.is_none() && fork.scoring.player_tricks() != trick {
+            won = Some(taken_by(fork.scoring.player_tricks(), team) > taken_by(trick, team));
+        }
+        let player = fork.player[fork.current_player_index].id;
+        let view = fork.player_view(&player).ok()?;
+        if view.playable.is_empty() {
+            return None;
+        }
+        let card = bot.play_card(&view);
+        fork.play_card(card)?;
+    }
+    if fork.scoring.rounds_played() == round {
+        return None;
+    }
+    fork.history()
+        .iter()
+        .rev()
+        .find_map(|event| match event {
+            GameEvent::RoundScored { tricks, scores } => Some((*tricks, scores[team].points)),
+            _ => None,
+        })
+        .map(|(tricks, points)| {
+            let tricks_taken = taken_by(tricks, team);
+            // the trick in progress was the round's last
+            let won = won.unwrap_or(tricks_taken > taken_by(trick, team));
+            (won, tricks_taken, points)
+        })
+}
+
+fn taken_by(tricks: [u8; 4], team: usize) -> u8 {
+    tricks[team] + tricks[team + 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::search;
+    use super::serde_json::{self, Value};
+    use test_support::seeded_game;
+    use {Bet, Game, SpadesError, Uid};
+
+    fn game_in_trick_stage() -> Game {
+        let mut g = seeded_game(5);
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        g
+    }
+
+    #[test]
+    fn test_search_rolls_out_every_legal_card() {
+        let g = game_in_trick_stage();
+        let trace = search(&g, 3, 11).unwrap();
+        assert_eq!(Uid(10), trace.player);
+        assert_eq!(g.playable_cards().collect::<Vec<_>>(), trace.legal);
+        assert_eq!(3, trace.samples.len());
+        assert_eq!(trace.legal.len(), trace.stats.len());
+        for (card, stat) in trace.legal.iter().zip(trace.stats.iter()) {
+            assert_eq!(*card, stat.card);
+            assert_eq!(3, stat.rollouts);
+            assert!(stat.tricks_won <= stat.rollouts);
+            assert!(stat.mean_tricks <= 13.0);
+        }
+        assert!(trace.legal.contains(&trace.suggested));
+        // the searcher's own hand is never reimagined
+        for sample in trace.samples.iter() {
+            assert_eq!(
+                g.hand_from_player_id(Uid(10)).unwrap(),
+                &sample.hands[0][..]
+            );
+        }
+        assert_eq!(trace, search(&g, 3, 11).unwrap());
+    }
+
+    #[test]
+    fn test_search_trace_as_json() {
+        let trace = search(&game_in_trick_stage(), 2, 1).unwrap();
+        let json: Value = serde_json::from_str(&trace.to_json()).unwrap();
+        assert_eq!(2, json["samples"].as_array().unwrap().len());
+        assert_eq!(trace.stats.len(), json["stats"].as_array().unwrap().len());
+        assert_eq!(
+            serde_json::to_value(trace.suggested).unwrap(),
+            json["suggested"]
+        );
+    }
+
+    #[test]
+    fn test_search_only_in_trick_stage() {
+        let mut g = Game::default();
+        g.start_game();
+        assert_eq!(Err(SpadesError::ImproperGameStage), search(&g, 1, 0));
+    }
+}
@@ -0,0 +1,354 @@
+//! A league season: a schedule of games among a roster of players, the results recorded as the games are
+//! played, and the standings they add up to.
+
+use result::SpadesError;
+use seat::Seat;
+use seating::league_schedule;
+use {Game, Uid};
+
+/// One scheduled game of a [`Season`](struct.Season.html).
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct Match {
+    /// Counting from 0.  Every player plays at most once per session.
+    pub session: usize,
+    /// The players in seat order, so `seats[0]` and `seats[2]` are partners against `seats[1]` and `seats[3]`.
+    pub seats: [Uid; 4],
+}
+
+/// How a recorded [`Match`](struct.Match.html) came out.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct MatchResult {
+    /// Team 0 (seats 0 and 2) or team 1 (seats 1 and 3), or `None` if the game ended without a winner.
+    pub winning_team: Option<usize>,
+    pub scores: [i32; 2],
+}
+
+/// What decides the order of players level on league points, see
+/// [`StandingsConfig`](struct.StandingsConfig.html).
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum Tiebreaker {
+    /// More games won.
+    Wins,
+    /// More points scored than conceded, over all games.
+    PointDifference,
+    /// More points scored over all games.
+    PointsFor,
+    /// Fewer games played, for standings taken partway through a season.
+    FewerGames,
+}
+
+/// How standings are worked out, set with [`Season::set_standings_config`](struct.Season.html#method.set_standings_config).
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct StandingsConfig {
+    pub points_per_win: u32,
+    pub points_per_loss: u32,
+    /// For a game that ended without a winner.
+    pub points_per_unfinished: u32,
+    /// Applied in order to players level on league points.  Players level on all of them keep their roster
+    /// order.
+    pub tiebreakers: Vec<Tiebreaker>,
+}
+
+impl Default for StandingsConfig {
+    fn default() -> StandingsConfig {
+        StandingsConfig {
+            points_per_win: 2,
+            points_per_loss: 0,
+            points_per_unfinished: 1,
+            tiebreakers: vec![Tiebreaker::PointDifference, Tiebreaker::PointsFor],
+        }
+    }
+}
+
+/// One player's line in the [`standings`](struct.Season.html#method.standings).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct Standing {
+    pub player: Uid,
+    pub played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub unfinished: u32,
+    /// League points, as given by the [`StandingsConfig`](struct.StandingsConfig.html).
+    pub points: u32,
+    pub points_for: i64,
+    pub points_against: i64,
+}
+
+/// A season of games among a roster, scheduled by [`league_schedule`](fn.league_schedule.html) so that
+/// every player partners every other once.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct Season {
+    roster: Vec<Uid>,
+    schedule: Vec<Match>,
+    results: Vec<Option<MatchResult>>,
+    config: StandingsConfig,
+}
+
+impl Season {
+    /// The season for `roster`, its schedule drawn with `seed`.
+    pub fn new(roster: &[Uid], seed: u64) -> Season {
+        let schedule: Vec<Match> = league_schedule(roster, seed)
+            .into_iter()
+            .enumerate()
+            .flat_map(|(session, tables)| {
+                tables
+                    .into_iter()
+                    .map(move |seats| Match { session, seats })
+            })
+            .collect();
+        Season {
+            roster: roster.to_vec(),
+            results: vec![None; schedule.len()],
+            schedule,
+            config: StandingsConfig::default(),
+        }
+    }
+
+    pub fn roster(&self) -> &[Uid] {
+        &self.roster
+    }
+
+    /// Every game of the season, session by session.
+    pub fn schedule(&self) -> &[Match] {
+        &self.schedule
+    }
+
+    /// The indexes in the schedule of the games with no result recorded yet.
+    pub fn unplayed(&self) -> Vec<usize> {
+        (0..self.schedule.len())
+            .filter(|index| self.results[*index].is_none())
+            .collect()
+    }
+
+    pub fn result(&self, index: usize) -> Option<MatchResult> {
+        self.results.get(index).cloned().flatten()
+    }
+
+    pub fn set_standings_config(&mut self, config: StandingsConfig) {
+        self.config = config;
+    }
+
+    pub fn standings_config(&self) -> &StandingsConfig {
+        &self.config
+    }
+
+    /// Records how `game`, played as game `index` of the schedule, turned out, replacing any result already
+    /// recorded for it.  Returns `SpadesError::UnknownMatch` if there is no such game,
+    /// `SpadesError::InvalidUuid` unless the game was played by the scheduled players in their scheduled
+    /// seats, and `SpadesError::GameNotCompleted` if it isn't over.
+    pub fn record_result(&mut self, index: usize, game: &Game) -> Result<(), SpadesError> {
+        let scheduled = self
+            .schedule
+            .get(index)
+            .ok_or(SpadesError::UnknownMatch(index))?;
+        for (seat, player) in scheduled.seats.iter().enumerate() {
+            if game.seat_of(player)? != Seat::from_index(seat) {
+                return Err(SpadesError::InvalidUuid);
+            }
+        }
+        let result = game.result()?;
+        let winning_team = result
+            .winners
+            .map(|(winner, _)| game.seat_of(&winner).map(Seat::team))
+            .transpose()?;
+        self.results[index] = Some(MatchResult {
+            winning_team,
+            scores: result.scores,
+        });
+        Ok(())
+    }
+
+    /// Every player on the roster, best first, by league points and then the tiebreakers.
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = self
+            .roster
+            .iter()
+            .map(|player| Standing {
+                player: *player,
+                played: 0,
+                wins: 0,
+                losses: 0,
+                unfinished: 0,
+                points: 0,
+                points_for: 0,
+                points_against: 0,
+            })
+            .collect();
+        for (scheduled, result) in self.schedule.iter().zip(self.results.iter()) {
+            let result = match result {
+                Some(result) => result,
+                None => continue,
+            };
+            for (seat, player) in scheduled.seats.iter().enumerate() {
+                let standing = match standings.iter_mut().find(|s| s.player == *player) {
+                    Some(standing) => standing,
+                    None => continue,
+                };
+                let team = seat % 2;
+                standing.played += 1;
+                standing.points_for += result.scores[team] as i64;
+                standing.points_against += result.scores[1 - team] as i64;
+                match result.winning_team {
+                    Some(winner) if winner == team => {
+                        standing.wins += 1;
+                        standing.points += self.config.points_per_win;
+                    }
+                    Some(_) => {
+                        standing.losses += 1;
+                        standing.points += self.config.points_per_loss;
+                    }
+                    None => {
+                        standing.unfinished += 1;
+                        standing.points += self.config.points_per_unfinished;
+                    }
+                }
+            }
+        }
+        // a stable sort keeps players level on everything in roster order
+        standings.sort_by(|a, b| {
+            self.config
+                .tiebreakers
+                .iter()
+                .fold(b.points.cmp(&a.points), |order, tiebreaker| {
+                    order.then_with(|| match tiebreaker {
+                        Tiebreaker::Wins => b.wins.cmp(&a.wins),
+                        Tiebreaker::PointDifference => (b.points_for - b.points_against)
+                            .cmp(&(a.points_for - a.points_against)),
+                        Tiebreaker::PointsFor => b.points_for.cmp(&a.points_for),
+                        Tiebreaker::FewerGames => a.played.cmp(&b.played),
+                    })
+                })
+        });
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MatchResult, Season, StandingsConfig, Tiebreaker};
+    use {Bet, Game, SpadesError, Uid};
+
+    fn roster() -> Vec<Uid> {
+        (1..=8).map(Uid).collect()
+    }
+
+    fn play(seats: [Uid; 4], seed: u64) -> Game {
+        let mut g = Game::default();
+        g.assign_players(Uid(100), seats);
+        g.set_shuffle_seed(Some(seed));
+        g.start_game();
+        while !g.is_over() {
+            for _ in 0..4 {
+                g.place_bet(Bet::Amount(3));
+            }
+            for _ in 0..52 {
+                let card = g.playable_cards().next().unwrap();
+                g.play_card(card);
+            }
+        }
+        g
+    }
+
+    #[test]
+    fn test_schedule_partners_everyone_once() {
+        let season = Season::new(&roster(), 3);
+        // 7 sessions of 2 tables
+        assert_eq!(14, season.schedule().len());
+        assert_eq!(6, season.schedule()[13].session);
+        let mut partnered = vec![];
+        for scheduled in season.schedule() {
+            let [a, b, c, d] = scheduled.seats;
+            partnered.push((a.min(c), a.max(c)));
+            partnered.push((b.min(d), b.max(d)));
+        }
+        partnered.sort();
+        partnered.dedup();
+        assert_eq!(28, partnered.len());
+        assert_eq!(season.unplayed(), (0..14).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_results_make_standings() {
+        let mut season = Season::new(&roster(), 3);
+        let seats = season.schedule()[0].seats;
+        let unfinished = {
+            let mut g = Game::default();
+            g.assign_players(Uid(100), seats);
+            g.start_game();
+            g
+        };
+        assert_eq!(
+            Err(SpadesError::GameNotCompleted),
+            season.record_result(0, &unfinished)
+        );
+        assert_eq!(
+            Err(SpadesError::UnknownMatch(99)),
+            season.record_result(99, &unfinished)
+        );
+        assert_eq!(
+            Err(SpadesError::InvalidUuid),
+            season.record_result(1, &unfinished)
+        );
+
+        let game = play(seats, 1);
+        assert_eq!(Ok(()), season.record_result(0, &game));
+        let result = season.result(0).unwrap();
+        let winning_team = result.winning_team.unwrap();
+        assert_eq!(vec![1], season.unplayed()[..1].to_vec());
+
+        let standings = season.standings();
+        assert_eq!(8, standings.len());
+        let winners = [seats[winning_team], seats[winning_team + 2]];
+        assert!(winners.contains(&standings[0].player));
+        assert!(winners.contains(&standings[1].player));
+        assert_eq!(2, standings[0].points);
+        assert_eq!(1, standings[0].wins);
+        assert_eq!(result.scores[winning_team] as i64, standings[0].points_for);
+        assert_eq!(0, standings[2].points);
+        // the four who haven't played keep their roster order after the loser
+        let idle: Vec<Uid> = standings[2..]
+            .iter()
+            .filter(|standing| standing.played == 0)
+            .map(|standing| standing.player)
+            .collect();
+        let mut sorted = idle.clone();
+        sorted.sort();
+        assert_eq!(sorted, idle);
+    }
+
+    #[test]
+    fn test_tiebreakers() {
+        let mut season = Season::new(&roster(), 3);
+        season.results[0] = Some(MatchResult {
+            winning_team: Some(0),
+            scores: [300, 250],
+        });
+        season.results[1] = Some(MatchResult {
+            winning_team: Some(1),
+            scores: [100, 510],
+        });
+        season.set_standings_config(StandingsConfig {
+            tiebreakers: vec![Tiebreaker::PointsFor],
+            ..StandingsConfig::default()
+        });
+        let top = season.standings()[0].player;
+        let second = season.schedule()[1].seats;
+        assert!(top == second[1] || top == second[3]);
+
+        season.results[1] = None;
+        season.set_standings_config(StandingsConfig {
+            points_per_win: 0,
+            tiebreakers: vec![Tiebreaker::FewerGames],
+            ..StandingsConfig::default()
+        });
+        assert_eq!(0, season.standings()[0].played);
+    }
+}
@@ -0,0 +1,119 @@
+use result::SpadesError;
+use std::fmt;
+use {Game, PlayerId, Uid};
+
+/// A position at the table.  North and South (players 0 and 2) are Team 0, East and West (players 1 and 3)
+/// are Team 1, and play passes clockwise, to each player's left.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum Seat {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Seat {
+    /// Every seat, in order of play.
+    pub const ALL: [Seat; 4] = [Seat::North, Seat::East, Seat::South, Seat::West];
+
+    /// The seat of player `index`, as passed to [`Game::assign_players`](struct.Game.html#method.assign_players).
+    /// Panics if `index` is not between 0 and 3.
+    pub fn from_index(index: usize) -> Seat {
+        Seat::ALL[index]
+    }
+
+    /// The index of the player in this seat.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Team 0 (North and South) or Team 1 (East and West).
+    pub fn team(self) -> usize {
+        self.index() % 2
+    }
+
+    pub fn partner(self) -> Seat {
+        Seat::from_index((self.index() + 2) % 4)
+    }
+
+    /// The next seat to play.
+    pub fn left(self) -> Seat {
+        Seat::from_index((self.index() + 1) % 4)
+    }
+
+    /// The seat that played just before this one.
+    pub fn right(self) -> Seat {
+        Seat::from_index((self.index() + 3) % 4)
+    }
+}
+
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Game {
+    /// The seat of the player with this id.
+    pub fn seat_of<P: PlayerId + ?Sized>(&self, player_id: &P) -> Result<Seat, SpadesError> {
+        let player_id = player_id.to_uid();
+        self.player
+            .iter()
+            .position(|player| player.id == player_id)
+            .map(Seat::from_index)
+            .ok_or(SpadesError::InvalidUuid)
+    }
+
+    /// The id of the player sitting in `seat`.
+    pub fn player_at(&self, seat: Seat) -> Uid {
+        self.player[seat.index()].id
+    }
+
+    /// The id of this player's partner.
+    pub fn partner_of<P: PlayerId + ?Sized>(&self, player_id: &P) -> Result<Uid, SpadesError> {
+        Ok(self.player_at(self.seat_of(player_id)?.partner()))
+    }
+
+    /// The id of the player to this player's left, who plays after them.
+    pub fn left_of<P: PlayerId + ?Sized>(&self, player_id: &P) -> Result<Uid, SpadesError> {
+        Ok(self.player_at(self.seat_of(player_id)?.left()))
+    }
+
+    /// The id of the player to this player's right, who plays before them.
+    pub fn right_of<P: PlayerId + ?Sized>(&self, player_id: &P) -> Result<Uid, SpadesError> {
+        Ok(self.player_at(self.seat_of(player_id)?.right()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Seat;
+    use test_support::seated_game;
+    use {SpadesError, Uid};
+
+    #[test]
+    fn test_seat_neighbours() {
+        for seat in Seat::ALL.iter() {
+            assert_eq!(*seat, seat.partner().partner());
+            assert_eq!(*seat, seat.left().right());
+            assert_eq!(seat.team(), seat.partner().team());
+            assert_ne!(seat.team(), seat.left().team());
+            assert_eq!(*seat, Seat::from_index(seat.index()));
+        }
+        assert_eq!(Seat::East, Seat::North.left());
+        assert_eq!(Seat::West, Seat::North.right());
+    }
+
+    #[test]
+    fn test_game_seat_lookups() {
+        let g = seated_game();
+        assert_eq!(Ok(Seat::South), g.seat_of(&Uid(12)));
+        assert_eq!(Uid(13), g.player_at(Seat::West));
+        assert_eq!(Ok(Uid(12)), g.partner_of(&Uid(10)));
+        assert_eq!(Ok(Uid(10)), g.left_of(&Uid(13)));
+        assert_eq!(Ok(Uid(12)), g.right_of(&Uid(13)));
+        assert_eq!(Err(SpadesError::InvalidUuid), g.partner_of(&Uid(99)));
+    }
+}
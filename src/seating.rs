@@ -0,0 +1,141 @@
+//! Seatings for playing many games among a group of players, as arrays of player ids in seat order ready
+//! for [`Game::assign_players`](../struct.Game.html#method.assign_players): players 0 and 2 are partners
+//! against players 1 and 3, and player 3 deals.
+
+extern crate rand;
+
+use self::rand::Rng;
+use cards::seeded_rng;
+use Uid;
+
+/// The three ways four players can pair up, so that over three games each partners each of the others once.
+pub fn partnerships(players: [Uid; 4]) -> [[Uid; 4]; 3] {
+    let [a, b, c, d] = players;
+    [[a, b, c, d], [a, c, b, d], [a, b, d, c]]
+}
+
+/// The four turns of one seating at dealing, keeping the partnerships: in game `i` the player who started
+/// in seat `i` deals.
+pub fn dealer_rotation(seats: [Uid; 4]) -> [[Uid; 4]; 4] {
+    let mut rotations = [seats; 4];
+    for (i, rotation) in rotations.iter_mut().enumerate() {
+        rotation.rotate_left((i + 1) % 4);
+    }
+    rotations
+}
+
+/// A league schedule: for each session, the seatings of its tables, so that across the sessions every
+/// player is partnered with every other exactly once.  The `seed` decides who is matched with whom in
+/// which session, the same way every time.
+///
+/// There are `n - 1` sessions for an even number `n` of players, and `n` for an odd number.  Unless `n` is
+/// a multiple of four, some players sit out each session: one partnership when `n` leaves two over, and a
+/// single player and whoever they would have partnered when `n` is odd.  Those partnerships don't get to
+/// play together.  Fewer than four players can't fill a table, so they get no sessions.
+pub fn league_schedule(players: &[Uid], seed: u64) -> Vec<Vec<[Uid; 4]>> {
+    if players.len() < 4 {
+        return vec![];
+    }
+    let mut order: Vec<Option<Uid>> = players.iter().cloned().map(Some).collect();
+    seeded_rng(seed).shuffle(&mut order);
+    if order.len() % 2 == 1 {
+        // whoever is paired with the bye sits out
+        order.push(None);
+    }
+
+    // the circle method: the first player stays put while the others turn around them
+    let count = order.len();
+    let mut sessions = vec![];
+    for _ in 0..count - 1 {
+        let pairs: Vec<(Uid, Uid)> = (0..count / 2)
+            .filter_map(|i| match (order[i], order[count - 1 - i]) {
+                (Some(first), Some(second)) => Some((first, second)),
+                _ => None,
+            })
+            .collect();
+        let tables = pairs
+            .chunks(2)
+            .filter(|chunk| chunk.len() == 2)
+            .map(|chunk| [chunk[0].0, chunk[1].0, chunk[0].1, chunk[1].1])
+            .collect();
+        sessions.push(tables);
+        order[1..].rotate_right(1);
+    }
+    sessions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dealer_rotation, league_schedule, partnerships};
+    use std::collections::BTreeSet;
+    use Uid;
+
+    fn players(count: u64) -> Vec<Uid> {
+        (0..count).map(Uid).collect()
+    }
+
+    /// Every partnership seated in the schedule, with the lower id first.
+    fn partners(schedule: &[Vec<[Uid; 4]>]) -> Vec<(Uid, Uid)> {
+        let mut pairs = vec![];
+        for tables in schedule {
+            for seats in tables {
+                for (a, b) in [(seats[0], seats[2]), (seats[1], seats[3])].iter() {
+                    pairs.push((*a.min(b), *a.max(b)));
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn test_partnerships_pair_everyone_once() {
+        let seatings = partnerships([Uid(1), Uid(2), Uid(3), Uid(4)]);
+        let pairs = partners(&[seatings.to_vec()]);
+        let unique: BTreeSet<(Uid, Uid)> = pairs.iter().cloned().collect();
+        assert_eq!(6, pairs.len());
+        assert_eq!(6, unique.len());
+    }
+
+    #[test]
+    fn test_dealer_rotation_keeps_partners() {
+        let rotations = dealer_rotation([Uid(1), Uid(2), Uid(3), Uid(4)]);
+        assert_eq!([Uid(2), Uid(3), Uid(4), Uid(1)], rotations[0]);
+        for (i, seats) in rotations.iter().enumerate() {
+            // the dealer sits in seat 3
+            assert_eq!(Uid(i as u64 + 1), seats[3]);
+            let pair = (seats[0].min(seats[2]), seats[0].max(seats[2]));
+            assert!(pair == (Uid(1), Uid(3)) || pair == (Uid(2), Uid(4)));
+        }
+    }
+
+    #[test]
+    fn test_league_partners_everyone_once() {
+        let schedule = league_schedule(&players(8), 1);
+        assert_eq!(7, schedule.len());
+        for tables in schedule.iter() {
+            assert_eq!(2, tables.len());
+            let seated: BTreeSet<Uid> = tables.iter().flat_map(|t| t.iter().cloned()).collect();
+            assert_eq!(8, seated.len());
+        }
+        let pairs = partners(&schedule);
+        let unique: BTreeSet<(Uid, Uid)> = pairs.iter().cloned().collect();
+        assert_eq!(28, pairs.len());
+        assert_eq!(28, unique.len());
+        assert_eq!(schedule, league_schedule(&players(8), 1));
+        assert_ne!(schedule, league_schedule(&players(8), 2));
+    }
+
+    #[test]
+    fn test_league_with_players_left_over() {
+        for count in 5..8 {
+            let schedule = league_schedule(&players(count), 3);
+            let pairs = partners(&schedule);
+            let unique: BTreeSet<(Uid, Uid)> = pairs.iter().cloned().collect();
+            assert_eq!(pairs.len(), unique.len());
+            assert!(schedule
+                .iter()
+                .all(|tables| tables.len() == count as usize / 4));
+        }
+        assert!(league_schedule(&players(3), 0).is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+extern crate rand;
+
+use self::rand::prng::{ChaChaRng, XorShiftRng};
+use self::rand::{thread_rng, FromEntropy, Rng, SeedableRng};
+use cards::Card;
+use rules::Rules;
+use std::fmt;
+use std::sync::Mutex;
+use Game;
+
+/// Where the randomness for shuffling comes from, so it can be chosen, and documented, by whoever runs the
+/// game.  Plug one into a [`Game`](struct.Game.html) with
+/// [`set_shuffler`](struct.Game.html#method.set_shuffler).
+///
+/// Shufflers are not part of a saved game; set one again after loading.  Clones of a game share its
+/// shuffler.
+pub trait Shuffler: fmt::Debug + Send + Sync {
+    /// A short name for this shuffler.  Two games compare equal only if their shufflers have the same name.
+    fn name(&self) -> &str;
+
+    /// Shuffles `cards` in place.
+    fn shuffle(&self, cards: &mut [Card]);
+}
+
+/// Shuffles with the thread's random number generator, which is what games do unless told otherwise.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ThreadRngShuffler;
+
+impl Shuffler for ThreadRngShuffler {
+    fn name(&self) -> &str {
+        "thread-rng"
+    }
+
+    fn shuffle(&self, cards: &mut [Card]) {
+        thread_rng().shuffle(cards);
+    }
+}
+
+/// Shuffles the same way every time for the same seed, for tests and reproducible games.  Not fit for play
+/// for money: anyone who learns the seed knows every deal.
+#[derive(Debug)]
+pub struct SeededShuffler {
+    rng: Mutex<XorShiftRng>,
+}
+
+impl SeededShuffler {
+    pub fn new(seed: u64) -> SeededShuffler {
+        SeededShuffler {
+            rng: Mutex::new(XorShiftRng::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl Shuffler for SeededShuffler {
+    fn name(&self) -> &str {
+        "seeded"
+    }
+
+    fn shuffle(&self, cards: &mut [Card]) {
+        // a panic mid-shuffle leaves the generator as good as any other state
+        let mut rng = self.rng.lock().unwrap_or_else(|err| err.into_inner());
+        rng.shuffle(cards);
+    }
+}
+
+/// Shuffles with ChaCha20, a cryptographically secure generator, seeded from the operating system's
+/// entropy unless a seed is given.
+#[derive(Debug)]
+pub struct CryptoShuffler {
+    rng: Mutex<ChaChaRng>,
+}
+
+impl CryptoShuffler {
+    pub fn new() -> CryptoShuffler {
+        CryptoShuffler {
+            rng: Mutex::new(ChaChaRng::from_entropy()),
+        }
+    }
+
+    /// A shuffler starting from `seed`, such as one drawn from a certified hardware generator.
+    pub fn from_seed(seed: [u8; 32]) -> CryptoShuffler {
+        CryptoShuffler {
+            rng: Mutex::new(ChaChaRng::from_seed(seed)),
+        }
+    }
+}
+
+impl Default for CryptoShuffler {
+    fn default() -> CryptoShuffler {
+        CryptoShuffler::new()
+    }
+}
+
+impl Shuffler for CryptoShuffler {
+    fn name(&self) -> &str {
+        "chacha20"
+    }
+
+    fn shuffle(&self, cards: &mut [Card]) {
+        let mut rng = self.rng.lock().unwrap_or_else(|err| err.into_inner());
+        rng.shuffle(cards);
+    }
+}
+
+impl Game {
+    /// Replace where the randomness for dealing comes from, from the next deal on.  Games start with
+    /// [`ThreadRngShuffler`](struct.ThreadRngShuffler.html).  A [shuffle seed](#method.set_shuffle_seed), if
+    /// set, takes precedence.
+    pub fn set_shuffler<S: Shuffler + 'static>(&mut self, shuffler: S) {
+        self.shuffler = Rules::<dyn Shuffler>::new(shuffler);
+    }
+
+    /// Where the randomness for dealing comes from in this game.
+    pub fn shuffler(&self) -> &dyn Shuffler {
+        self.shuffler.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CryptoShuffler, SeededShuffler, Shuffler};
+    use cards::new_deck;
+    use test_support::seated_game;
+    use {Bet, Game};
+
+    #[test]
+    fn test_shufflers_shuffle() {
+        let mut sorted = new_deck();
+        sorted.sort();
+        let shufflers: [Box<dyn Shuffler>; 2] = [
+            Box::new(CryptoShuffler::from_seed([7; 32])),
+            Box::new(SeededShuffler::new(7)),
+        ];
+        for shuffler in shufflers.iter() {
+            let mut deck = sorted.clone();
+            shuffler.shuffle(&mut deck);
+            assert_ne!(sorted, deck);
+            let mut again = sorted.clone();
+            shuffler.shuffle(&mut again);
+            assert_ne!(deck, again);
+            deck.sort();
+            assert_eq!(sorted, deck);
+        }
+
+        let mut first = sorted.clone();
+        CryptoShuffler::from_seed([7; 32]).shuffle(&mut first);
+        let mut second = sorted.clone();
+        CryptoShuffler::from_seed([7; 32]).shuffle(&mut second);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_game_deals_with_its_shuffler() {
+        let deal = |seed| {
+            let mut g = seated_game();
+            g.set_shuffler(CryptoShuffler::from_seed(seed));
+            g.start_game();
+            g.place_bet(Bet::Amount(3));
+            g.dealt_hands().unwrap().clone()
+        };
+        assert_eq!(deal([1; 32]), deal([1; 32]));
+        assert_ne!(deal([1; 32]), deal([2; 32]));
+
+        let mut g = Game::default();
+        assert_eq!("thread-rng", g.shuffler().name());
+        let other = g.clone();
+        g.set_shuffler(CryptoShuffler::new());
+        assert_eq!("chacha20", g.shuffler().name());
+        assert_ne!(other, g);
+    }
+}
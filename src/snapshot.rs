@@ -0,0 +1,277 @@
+use cards::{Card, Suit};
+use game_state::State;
+use history::TrickPlay;
+use options::GameOptions;
+use player_info::PlayerInfo;
+use scoring::Bet;
+use seat::Seat;
+use Uid;
+
+/// The public state of a [`Game`](struct.Game.html) at one point in time, as returned by
+/// [`Game::snapshot`](struct.Game.html#method.snapshot).
+///
+/// Hands are not part of a snapshot, so it can be sent to every player and spectator alike.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct GameSnapshot {
+    pub id: Uid,
+    pub state: State,
+    pub player_ids: [Uid; 4],
+    pub current_player: Option<Uid>,
+    /// Each player's bet this round, or `None` if they haven't bet yet or it is hidden by the
+    /// [`bid_visibility`](struct.GameOptions.html#structfield.bid_visibility) option.
+    pub bets: [Option<Bet>; 4],
+    pub current_trick: Vec<Card>,
+    /// The cards of `current_trick` with the seat that played each, in the order they were played.
+    pub trick_plays: Vec<TrickPlay>,
+    /// The seat and card winning the trick in progress, or `None` before its first card.
+    pub winning: Option<(Seat, Card)>,
+    pub leading_suit: Option<Suit>,
+    pub spades_broken: bool,
+    /// Tricks won this round by Team 0 (players 0 and 2) and Team 1 (players 1 and 3).
+    pub tricks: [u8; 2],
+    /// Cumulative score of each team.
+    pub scores: [i32; 2],
+    /// Cumulative bags of each team.
+    pub bags: [u8; 2],
+    /// The score that ends the game.
+    pub max_points: i32,
+    pub options: GameOptions,
+    /// What has been attached to each player with
+    /// [`Game::set_player_info`](struct.Game.html#method.set_player_info).
+    pub player_info: [Option<PlayerInfo>; 4],
+}
+
+/// The fields that changed between two [`GameSnapshot`](struct.GameSnapshot.html)s.
+///
+/// Unchanged fields are `None` and are left out when serialized, so a delta for a single played card
+/// is only a few bytes.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct SnapshotDelta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Uid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<State>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player_ids: Option<[Uid; 4]>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "present"
+    )]
+    pub current_player: Option<Option<Uid>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bets: Option<[Option<Bet>; 4]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_trick: Option<Vec<Card>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trick_plays: Option<Vec<TrickPlay>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "present"
+    )]
+    pub winning: Option<Option<(Seat, Card)>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "present"
+    )]
+    pub leading_suit: Option<Option<Suit>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spades_broken: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tricks: Option<[u8; 2]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scores: Option<[i32; 2]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bags: Option<[u8; 2]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_points: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<GameOptions>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player_info: Option<[Option<PlayerInfo>; 4]>,
+}
+
+/// Keeps a field that is present but `null` as `Some(None)`, rather than treating it like a missing field.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    serde::Deserialize::deserialize(deserializer).map(Some)
+}
+
+fn changed<T: PartialEq + Clone>(new: &T, old: &T) -> Option<T> {
+    if new != old {
+        Some(new.clone())
+    } else {
+        None
+    }
+}
+
+fn apply<T>(field: &mut T, change: &Option<T>)
+where
+    T: Clone,
+{
+    if let Some(value) = change {
+        *field = value.clone();
+    }
+}
+
+impl GameSnapshot {
+    /// The changes needed to turn `old` into this snapshot.
+    pub fn diff(&self, old: &GameSnapshot) -> SnapshotDelta {
+        SnapshotDelta {
+            id: changed(&self.id, &old.id),
+            state: changed(&self.state, &old.state),
+            player_ids: changed(&self.player_ids, &old.player_ids),
+            current_player: changed(&self.current_player, &old.current_player),
+            bets: changed(&self.bets, &old.bets),
+            current_trick: changed(&self.current_trick, &old.current_trick),
+            trick_plays: changed(&self.trick_plays, &old.trick_plays),
+            winning: changed(&self.winning, &old.winning),
+            leading_suit: changed(&self.leading_suit, &old.leading_suit),
+            spades_broken: changed(&self.spades_broken, &old.spades_broken),
+            tricks: changed(&self.tricks, &old.tricks),
+            scores: changed(&self.scores, &old.scores),
+            bags: changed(&self.bags, &old.bags),
+            max_points: changed(&self.max_points, &old.max_points),
+            options: changed(&self.options, &old.options),
+            player_info: changed(&self.player_info, &old.player_info),
+        }
+    }
+
+    /// Brings this snapshot up to date with a delta produced by [`diff`](#method.diff).
+    pub fn apply_delta(&mut self, delta: &SnapshotDelta) {
+        apply(&mut self.id, &delta.id);
+        apply(&mut self.state, &delta.state);
+        apply(&mut self.player_ids, &delta.player_ids);
+        apply(&mut self.current_player, &delta.current_player);
+        apply(&mut self.bets, &delta.bets);
+        apply(&mut self.current_trick, &delta.current_trick);
+        apply(&mut self.trick_plays, &delta.trick_plays);
+        apply(&mut self.winning, &delta.winning);
+        apply(&mut self.leading_suit, &delta.leading_suit);
+        apply(&mut self.spades_broken, &delta.spades_broken);
+        apply(&mut self.tricks, &delta.tricks);
+        apply(&mut self.scores, &delta.scores);
+        apply(&mut self.bags, &delta.bags);
+        apply(&mut self.max_points, &delta.max_points);
+        apply(&mut self.options, &delta.options);
+        apply(&mut self.player_info, &delta.player_info);
+    }
+}
+
+impl SnapshotDelta {
+    /// Whether the two snapshots this delta was produced from were identical.
+    pub fn is_empty(&self) -> bool {
+        *self == SnapshotDelta::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GameSnapshot, SnapshotDelta};
+    use cards::{winning_position, Card};
+    use {Bet, Game, Seat, State, Uid};
+
+    #[test]
+    fn test_diff_of_identical_snapshots_is_empty() {
+        let g = Game::default();
+        let delta = g.snapshot().diff(&g.snapshot());
+        assert!(delta.is_empty());
+        assert_eq!(SnapshotDelta::default(), delta);
+    }
+
+    #[test]
+    fn test_diff_only_contains_changed_fields() {
+        let mut g = Game::default();
+        g.assign_players(Uid(1), [Uid(2), Uid(3), Uid(4), Uid(5)]);
+        g.start_game();
+        let before = g.snapshot();
+        g.place_bet(Bet::Amount(4));
+        let after = g.snapshot();
+
+        let delta = after.diff(&before);
+        assert_eq!(Some(State::Betting(1)), delta.state);
+        assert_eq!(Some(Some(Uid(3))), delta.current_player);
+        assert_eq!(None, delta.id);
+        assert_eq!(None, delta.player_ids);
+        assert_eq!(None, delta.scores);
+    }
+
+    #[test]
+    fn test_apply_delta_reproduces_new_snapshot() {
+        let mut g = Game::default();
+        g.assign_players(Uid(1), [Uid(2), Uid(3), Uid(4), Uid(5)]);
+        let mut client = g.snapshot();
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        let card = g.current_hand().unwrap()[0];
+        g.play_card(card);
+
+        let server = g.snapshot();
+        let delta = server.diff(&client);
+        client.apply_delta(&delta);
+        assert_eq!(server, client);
+        assert_eq!(vec![card], client.current_trick);
+    }
+
+    #[test]
+    fn test_snapshot_shows_plays_and_winner_of_trick() {
+        let mut g = Game::default();
+        g.assign_players(Uid(1), [Uid(2), Uid(3), Uid(4), Uid(5)]);
+        g.set_shuffle_seed(Some(3));
+        g.start_game();
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        assert!(g.snapshot().trick_plays.is_empty());
+        assert_eq!(None, g.snapshot().winning);
+
+        let mut played = vec![];
+        for _ in 0..3 {
+            let card = g.playable_cards().last().unwrap();
+            g.play_card(card);
+            played.push(card);
+        }
+        let snapshot = g.snapshot();
+        let seats: Vec<Seat> = snapshot.trick_plays.iter().map(|play| play.seat).collect();
+        assert_eq!(vec![Seat::North, Seat::East, Seat::South], seats);
+        let cards: Vec<Card> = snapshot.trick_plays.iter().map(|play| play.card).collect();
+        assert_eq!(played, cards);
+        let winner = g.currently_winning_player().unwrap().unwrap();
+        let (seat, card) = snapshot.winning.unwrap();
+        assert_eq!(winner, snapshot.player_ids[seat.index()]);
+        assert_eq!(played[winning_position(&played)], card);
+    }
+
+    #[test]
+    fn test_delta_serializes_only_changed_fields() {
+        let old = GameSnapshot::default();
+        let mut new = old.clone();
+        new.spades_broken = true;
+        let json = serde_json::to_string(&new.diff(&old)).unwrap();
+        assert_eq!("{\"spades_broken\":true}", json);
+        let delta: SnapshotDelta = serde_json::from_str(&json).unwrap();
+        assert_eq!(Some(true), delta.spades_broken);
+        assert_eq!(None, delta.state);
+    }
+
+    #[test]
+    fn test_delta_round_trips_cleared_optional_fields() {
+        let old = GameSnapshot {
+            current_player: Some(Uid(7)),
+            ..GameSnapshot::default()
+        };
+        let new = GameSnapshot::default();
+        let delta = new.diff(&old);
+        assert_eq!(Some(None), delta.current_player);
+        let json = serde_json::to_string(&delta).unwrap();
+        let decoded: SnapshotDelta = serde_json::from_str(&json).unwrap();
+        assert_eq!(delta, decoded);
+    }
+}
@@ -0,0 +1,404 @@
+//! Keeping games outside the process, so several stateless servers can drive the same game.
+//!
+//! A [`GameStore`](trait.GameStore.html) holds each game's serialized form, event log included, with the
+//! sequence number of its latest event.  [`drive`](fn.drive.html) loads a game, takes an action on it and
+//! saves it back only if no other server has saved it in the meantime, trying again if one has.
+//!
+//! With the `redis` feature, [`RedisStore`](struct.RedisStore.html) keeps them in a Redis server.
+#[cfg(feature = "redis")]
+extern crate redis;
+
+use result::SpadesError;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+#[cfg(feature = "redis")]
+use std::sync::PoisonError;
+use {Game, Uid};
+
+/// How many times [`drive`](fn.drive.html) retries an action that lost a race to another server.
+pub const MAX_STORE_RETRIES: usize = 8;
+
+/// A game as kept in a [`GameStore`](trait.GameStore.html): its serialized form, event log included, and
+/// the [sequence number](../struct.Game.html#method.last_seq) of its latest event.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct StoredGame {
+    pub seq: u64,
+    /// The game as JSON.
+    pub data: String,
+}
+
+impl StoredGame {
+    pub fn new(game: &Game) -> Result<StoredGame, StoreError> {
+        Ok(StoredGame {
+            seq: game.last_seq(),
+            data: serde_json::to_string(game)
+                .map_err(|err| StoreError::Corrupt(err.to_string()))?,
+        })
+    }
+
+    pub fn game(&self) -> Result<Game, StoreError> {
+        serde_json::from_str(&self.data).map_err(|err| StoreError::Corrupt(err.to_string()))
+    }
+}
+
+/// Why a [`GameStore`](trait.GameStore.html) couldn't load or save a game.
+#[derive(Debug, PartialEq, Eq)]
+pub enum StoreError {
+    /// The game was saved by someone else since it was loaded: its sequence number is `found`, not the
+    /// `expected` one.  `None` stands for no game at all.
+    Conflict {
+        expected: Option<u64>,
+        found: Option<u64>,
+    },
+    UnknownGame(Uid),
+    /// The stored game couldn't be read or written.
+    Corrupt(String),
+    /// The store itself failed, such as a lost connection.
+    Backend(String),
+    /// The game didn't allow the action.
+    Game(SpadesError),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StoreError::Conflict { expected, found } => write!(
+                f,
+                "game was changed by someone else: expected seq {:?}, found {:?}",
+                expected, found
+            ),
+            StoreError::UnknownGame(id) => write!(f, "no game {:?} in the store", id),
+            StoreError::Corrupt(message) => write!(f, "stored game unreadable: {}", message),
+            StoreError::Backend(message) => write!(f, "store failed: {}", message),
+            StoreError::Game(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Somewhere games are kept so several stateless servers can take turns driving the same game.
+///
+/// Saving is optimistic: a save names the sequence number the game had when it was loaded, and fails with
+/// `StoreError::Conflict` if the stored game has moved on since, so no server overwrites another's action.
+/// [`MemoryStore`](struct.MemoryStore.html) keeps games in memory and `RedisStore`, with the `redis`
+/// feature, in Redis, making the same check with [`REDIS_SAVE_SCRIPT`](constant.REDIS_SAVE_SCRIPT.html).
+pub trait GameStore {
+    fn load(&self, id: Uid) -> Result<Option<StoredGame>, StoreError>;
+
+    /// Stores `game` as game `id` if the stored game's sequence number is still `expected`, or if there
+    /// is no stored game and `expected` is `None`.
+    fn save(&self, id: Uid, expected: Option<u64>, game: &StoredGame) -> Result<(), StoreError>;
+}
+
+/// Compare-and-set for Redis, run by `RedisStore` with `EVALSHA` on a hash per game holding `seq` and `data` fields.
+/// `KEYS[1]` is the game's key; `ARGV` are the expected sequence number (empty for a new game), then the new
+/// sequence number and data.  Returns `OK`, or an error reply starting `CONFLICT` followed by the stored
+/// sequence number.
+pub const REDIS_SAVE_SCRIPT: &str = r#"
+local found = redis.call('HGET', KEYS[1], 'seq')
+if (found or '') ~= ARGV[1] then
+  return redis.error_reply('CONFLICT ' .. (found or ''))
+end
+redis.call('HSET', KEYS[1], 'seq', ARGV[2], 'data', ARGV[3])
+return redis.status_reply('OK')
+"#;
+
+/// A [`GameStore`](trait.GameStore.html) in this process's memory, for tests and single-server setups.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    games: Mutex<HashMap<Uid, StoredGame>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> MemoryStore {
+        MemoryStore::default()
+    }
+}
+
+impl GameStore for MemoryStore {
+    fn load(&self, id: Uid) -> Result<Option<StoredGame>, StoreError> {
+        let games = self
+            .games
+            .lock()
+            .map_err(|err| StoreError::Backend(err.to_string()))?;
+        Ok(games.get(&id).cloned())
+    }
+
+    fn save(&self, id: Uid, expected: Option<u64>, game: &StoredGame) -> Result<(), StoreError> {
+        let mut games = self
+            .games
+            .lock()
+            .map_err(|err| StoreError::Backend(err.to_string()))?;
+        let found = games.get(&id).map(|stored| stored.seq);
+        if found != expected {
+            return Err(StoreError::Conflict { expected, found });
+        }
+        games.insert(id, game.clone());
+        Ok(())
+    }
+}
+
+/// A [`GameStore`](trait.GameStore.html) in Redis, keeping each game in a hash holding its `seq` and `data`
+/// fields, under its id following a prefix.  Saves run [`REDIS_SAVE_SCRIPT`](constant.REDIS_SAVE_SCRIPT.html),
+/// so servers sharing the Redis server never overwrite each other's actions.
+///
+/// One connection is kept and used a call at a time, and opened again after it fails.
+#[cfg(feature = "redis")]
+pub struct RedisStore {
+    client: redis::Client,
+    prefix: String,
+    connection: Mutex<Option<redis::Connection>>,
+    script: redis::Script,
+}
+
+#[cfg(feature = "redis")]
+impl RedisStore {
+    /// A store in the Redis server at `url`, such as `redis://127.0.0.1/`, keeping games under keys
+    /// `spades:game:<id>`.  No connection is made until the first load or save.
+    pub fn open(url: &str) -> Result<RedisStore, StoreError> {
+        RedisStore::with_prefix(url, "spades:game:")
+    }
+
+    /// A store keeping games under keys made of `prefix` followed by the game's id.
+    pub fn with_prefix(url: &str, prefix: &str) -> Result<RedisStore, StoreError> {
+        Ok(RedisStore {
+            client: redis::Client::open(url).map_err(backend)?,
+            prefix: prefix.to_string(),
+            connection: Mutex::new(None),
+            script: redis::Script::new(REDIS_SAVE_SCRIPT),
+        })
+    }
+
+    /// The key game `id` is kept under.
+    pub fn key(&self, id: Uid) -> String {
+        format!("{}{}", self.prefix, id.0)
+    }
+
+    /// Runs `command` on the connection, opening it first if there isn't one and dropping it if it fails.
+    fn with_connection<T, F>(&self, command: F) -> Result<T, redis::RedisError>
+    where
+        F: FnOnce(&mut redis::Connection) -> Result<T, redis::RedisError>,
+    {
+        let mut connection = self
+            .connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut open = match connection.take() {
+            Some(open) => open,
+            None => self.client.get_connection()?,
+        };
+        let result = command(&mut open);
+        match result {
+            Err(ref err) if err.is_unrecoverable_error() => {}
+            _ => *connection = Some(open),
+        }
+        result
+    }
+}
+
+#[cfg(feature = "redis")]
+impl fmt::Debug for RedisStore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RedisStore")
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+#[cfg(feature = "redis")]
+fn backend(err: redis::RedisError) -> StoreError {
+    StoreError::Backend(err.to_string())
+}
+
+/// The conflict a failed [`REDIS_SAVE_SCRIPT`](constant.REDIS_SAVE_SCRIPT.html) reported, or the failure
+/// itself.
+#[cfg(feature = "redis")]
+fn save_error(expected: Option<u64>, err: redis::RedisError) -> StoreError {
+    if err.code() != Some("CONFLICT") {
+        return backend(err);
+    }
+    match err.detail().map(str::trim).unwrap_or("") {
+        "" => StoreError::Conflict {
+            expected,
+            found: None,
+        },
+        found => match found.parse() {
+            Ok(found) => StoreError::Conflict {
+                expected,
+                found: Some(found),
+            },
+            Err(_) => StoreError::Corrupt(format!("sequence number {:?}", found)),
+        },
+    }
+}
+
+#[cfg(feature = "redis")]
+impl GameStore for RedisStore {
+    fn load(&self, id: Uid) -> Result<Option<StoredGame>, StoreError> {
+        let (seq, data): (Option<u64>, Option<String>) = self
+            .with_connection(|connection| {
+                redis::cmd("HMGET")
+                    .arg(self.key(id))
+                    .arg("seq")
+                    .arg("data")
+                    .query(connection)
+            })
+            .map_err(backend)?;
+        match (seq, data) {
+            (Some(seq), Some(data)) => Ok(Some(StoredGame { seq, data })),
+            (None, None) => Ok(None),
+            _ => Err(StoreError::Corrupt(format!(
+                "{} is missing its seq or data",
+                self.key(id)
+            ))),
+        }
+    }
+
+    fn save(&self, id: Uid, expected: Option<u64>, game: &StoredGame) -> Result<(), StoreError> {
+        self.with_connection(|connection| {
+            self.script
+                .key(self.key(id))
+                .arg(expected.map_or(String::new(), |seq| seq.to_string()))
+                .arg(game.seq)
+                .arg(&game.data)
+                .invoke::<()>(connection)
+        })
+        .map_err(|err| save_error(expected, err))
+    }
+}
+
+/// Stores a new game under its id, failing with `StoreError::Conflict` if there is one already.
+pub fn create<S: GameStore + ?Sized>(store: &S, game: &Game) -> Result<(), StoreError> {
+    store.save(*game.id(), None, &StoredGame::new(game)?)
+}
+
+/// Loads game `id`, runs `action` on it and saves it back, loading it afresh and running `action` again
+/// whenever another server saved the game first, up to [`MAX_STORE_RETRIES`](constant.MAX_STORE_RETRIES.html)
+/// times.  Nothing is saved if `action` fails.
+pub fn drive<S, T, F>(store: &S, id: Uid, mut action: F) -> Result<T, StoreError>
+where
+    S: GameStore + ?Sized,
+    F: FnMut(&mut Game) -> Result<T, SpadesError>,
+{
+    let mut attempts = 0;
+    loop {
+        let stored = store.load(id)?.ok_or(StoreError::UnknownGame(id))?;
+        let mut game = stored.game()?;
+        let result = action(&mut game).map_err(StoreError::Game)?;
+        match store.save(id, Some(stored.seq), &StoredGame::new(&game)?) {
+            Err(StoreError::Conflict { .. }) if attempts < MAX_STORE_RETRIES => attempts += 1,
+            Err(err) => return Err(err),
+            Ok(()) => return Ok(result),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create, drive, GameStore, MemoryStore, StoreError, StoredGame};
+    use std::cell::Cell;
+    use test_support::started_game;
+    use {Bet, SpadesError, Uid};
+
+    #[test]
+    fn test_drive_saves_each_action() {
+        let store = MemoryStore::new();
+        let g = started_game();
+        create(&store, &g).unwrap();
+        assert_eq!(
+            Err(StoreError::Conflict {
+                expected: None,
+                found: Some(g.last_seq())
+            }),
+            create(&store, &g)
+        );
+        for _ in 0..4 {
+            drive(&store, Uid(1), |g| {
+                g.place_bet(Bet::Amount(3))
+                    .map(|_| ())
+                    .ok_or(SpadesError::ImproperGameStage)
+            })
+            .unwrap();
+        }
+        let stored = store.load(Uid(1)).unwrap().unwrap();
+        let g = stored.game().unwrap();
+        assert_eq!(g.last_seq(), stored.seq);
+        assert_eq!(Ok([Bet::Amount(3); 4]), g.bets_placed());
+        assert_eq!(
+            Err(StoreError::Game(SpadesError::ImproperGameStage)),
+            drive(&store, Uid(1), |g| g
+                .place_bet(Bet::Nil)
+                .map(|_| ())
+                .ok_or(SpadesError::ImproperGameStage))
+        );
+        assert_eq!(
+            Err(StoreError::UnknownGame(Uid(2))),
+            drive(&store, Uid(2), |_| Ok(()))
+        );
+    }
+
+    #[test]
+    fn test_drive_retries_after_a_conflict() {
+        let store = MemoryStore::new();
+        create(&store, &started_game()).unwrap();
+        let raced = Cell::new(false);
+        let bets = drive(&store, Uid(1), |g| {
+            if !raced.get() {
+                // another server bets first, between this load and save
+                raced.set(true);
+                let mut other = store.load(Uid(1)).unwrap().unwrap().game().unwrap();
+                let seq = other.last_seq();
+                other.place_bet(Bet::Amount(4));
+                store
+                    .save(Uid(1), Some(seq), &StoredGame::new(&other).unwrap())
+                    .unwrap();
+            }
+            g.place_bet(Bet::Amount(2));
+            Ok(g.bets_with_players())
+        })
+        .unwrap();
+        assert_eq!(Some(Bet::Amount(4)), bets[0].1);
+        assert_eq!(Some(Bet::Amount(2)), bets[1].1);
+    }
+
+    #[cfg(feature = "redis")]
+    #[test]
+    fn test_redis_conflicts_are_read_from_the_script() {
+        use super::{redis, save_error, RedisStore};
+
+        let reply = |bytes: &[u8]| {
+            redis::parse_redis_value(bytes)
+                .unwrap()
+                .extract_error()
+                .unwrap_err()
+        };
+        assert_eq!(
+            StoreError::Conflict {
+                expected: Some(3),
+                found: Some(5)
+            },
+            save_error(Some(3), reply(b"-CONFLICT 5\r\n"))
+        );
+        assert_eq!(
+            StoreError::Conflict {
+                expected: Some(3),
+                found: None
+            },
+            save_error(Some(3), reply(b"-CONFLICT \r\n"))
+        );
+        match save_error(None, reply(b"-NOSCRIPT No matching script\r\n")) {
+            StoreError::Backend(_) => {}
+            other => panic!("expected a backend error, not {:?}", other),
+        }
+
+        let store = RedisStore::with_prefix("redis://127.0.0.1/", "test:").unwrap();
+        assert_eq!("test:7", store.key(Uid(7)));
+        assert!(RedisStore::open("not a url").is_err());
+        // nothing listens on port 1
+        let unreachable = RedisStore::open("redis://127.0.0.1:1/").unwrap();
+        match unreachable.load(Uid(7)) {
+            Err(StoreError::Backend(_)) => {}
+            other => panic!("expected a backend error, not {:?}", other),
+        }
+    }
+}
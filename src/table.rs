@@ -0,0 +1,696 @@
+//! Tables for variants that don't seat four players in two partnerships, which [`Game`](../struct.Game.html)
+//! is built around.
+
+use cards::{full_deck, trick_winner, Card, Rank, Suit};
+use result::SpadesError;
+use rules::{CardPlayRules, PlayContext, Rules, StandardRules};
+use scoring::{standard_team_score, Bet, STANDARD_BAG_PENALTY};
+use shuffler::Shuffler;
+use two_player::DrawDeal;
+use {Hand, PlayCardResult, Player, Uid};
+
+/// How the players of a [`Table`](struct.Table.html) are seated and what deck they play with.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum TableVariant {
+    /// Six players in three partnerships, partners sitting opposite each other: seats 0 and 3 are Team 0,
+    /// 1 and 4 Team 1, and 2 and 5 Team 2.  The four deuces are taken out, so the other 48 cards deal out 8 to
+    /// each player.
+    SixHanded,
+    /// Two players, each playing for themselves, who draw their 13-card hands from the stock before every
+    /// round with a [`DrawDeal`](struct.DrawDeal.html); the 26 cards they discard sit out the round.
+    TwoHanded,
+}
+
+impl TableVariant {
+    /// How many players sit at the table.
+    pub fn seats(self) -> usize {
+        match self {
+            TableVariant::SixHanded => 6,
+            TableVariant::TwoHanded => 2,
+        }
+    }
+
+    /// How many teams play against each other.
+    pub fn teams(self) -> usize {
+        match self {
+            TableVariant::SixHanded => 3,
+            TableVariant::TwoHanded => 2,
+        }
+    }
+
+    /// The team the player in seat `seat` plays for.
+    pub fn team_of(self, seat: usize) -> usize {
+        seat % self.teams()
+    }
+
+    /// The seats of the players on team `team`.
+    pub fn team_seats(self, team: usize) -> Vec<usize> {
+        (0..self.seats())
+            .filter(|seat| self.team_of(*seat) == team)
+            .collect()
+    }
+
+    /// The cards dealt each round, in deck order.
+    pub fn deck(self) -> Vec<Card> {
+        match self {
+            TableVariant::SixHanded => full_deck()
+                .iter()
+                .filter(|card| card.rank != Rank::Two)
+                .cloned()
+                .collect(),
+            TableVariant::TwoHanded => full_deck().to_vec(),
+        }
+    }
+
+    /// How many tricks are played each round: as many as each player is dealt.
+    pub fn tricks_per_round(self) -> u8 {
+        match self {
+            TableVariant::SixHanded => 8,
+            TableVariant::TwoHanded => 13,
+        }
+    }
+}
+
+/// The stage of a [`Table`](struct.Table.html)'s game, like the [`State`](enum.State.html) of a `Game`.  A
+/// table is never paused or abandoned, and has no kitty or bid review.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum TableState {
+    #[default]
+    GameNotStarted,
+    /// The players of a two-handed table are drawing their hands, see [`DrawDeal`](struct.DrawDeal.html).
+    Drawing,
+    /// How many players have bet this round, from 0 to one less than the number of seats.
+    Betting(usize),
+    /// How many cards have been played to the trick, from 0 to one less than the number of seats.
+    Trick(usize),
+    GameCompleted,
+}
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+struct TableTeam {
+    points: i32,
+    bags: u8,
+}
+
+/// A game of Spades for one of the [`TableVariant`](enum.TableVariant.html)s.
+///
+/// Play works as in [`Game`](struct.Game.html): seat 0 bets first and leads the first trick of every round,
+/// play passes to the next seat up, and the game ends once a team reaches 500 points after a round.
+/// Partnerships are scored like those of `Game`, and players without a partner on their own bet and tricks.
+///
+/// At a two-handed table, every round starts in `TableState::Drawing`, with the players taking turns at
+/// [`take_drawn_card`](#method.take_drawn_card) until both hold 13 cards.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct Table {
+    id: Uid,
+    variant: TableVariant,
+    state: TableState,
+    player: Vec<Player>,
+    bets: Vec<Option<Bet>>,
+    tricks: Vec<u8>,
+    team: Vec<TableTeam>,
+    current_player_index: usize,
+    current_trick: Vec<Card>,
+    /// Cards played so far this round.
+    played: Vec<Card>,
+    spades_broken: bool,
+    max_points: i32,
+    /// The deal in progress at a two-handed table.
+    draw: Option<DrawDeal>,
+    /// Not saved with the table, like a `Game`'s.
+    #[serde(skip)]
+    shuffler: Rules<dyn Shuffler>,
+}
+
+impl Table {
+    /// A table for six players, in the seats they are given in, see
+    /// [`TableVariant::SixHanded`](enum.TableVariant.html#variant.SixHanded).
+    pub fn six_handed(id: Uid, player_ids: [Uid; 6]) -> Table {
+        Table::new(id, TableVariant::SixHanded, &player_ids)
+    }
+
+    fn new(id: Uid, variant: TableVariant, player_ids: &[Uid]) -> Table {
+        assert_eq!(variant.seats(), player_ids.len());
+        Table {
+            id,
+            variant,
+            state: TableState::GameNotStarted,
+            player: player_ids.iter().map(|id| Player::new(*id)).collect(),
+            bets: vec![None; variant.seats()],
+            tricks: vec![0; variant.seats()],
+            team: vec![TableTeam::default(); variant.teams()],
+            current_player_index: 0,
+            current_trick: vec![],
+            played: vec![],
+            spades_broken: false,
+            max_points: 500,
+            draw: None,
+            shuffler: Rules::default(),
+        }
+    }
+
+    /// A table for two players, see [`TableVariant::TwoHanded`](enum.TableVariant.html#variant.TwoHanded).
+    pub fn two_handed(id: Uid, player_ids: [Uid; 2]) -> Table {
+        Table::new(id, TableVariant::TwoHanded, &player_ids)
+    }
+
+    pub fn id(&self) -> Uid {
+        self.id
+    }
+
+    /// Replace where the randomness for dealing comes from, from the next deal on, as
+    /// [`Game::set_shuffler`](struct.Game.html#method.set_shuffler) does for a game.  Tables start with
+    /// [`ThreadRngShuffler`](struct.ThreadRngShuffler.html).
+    pub fn set_shuffler<S: Shuffler + 'static>(&mut self, shuffler: S) {
+        self.shuffler = Rules::<dyn Shuffler>::new(shuffler);
+    }
+
+    /// Where the randomness for dealing comes from at this table.
+    pub fn shuffler(&self) -> &dyn Shuffler {
+        self.shuffler.get()
+    }
+
+    pub fn variant(&self) -> TableVariant {
+        self.variant
+    }
+
+    /// The stage of the game.  Only a two-handed table is ever `Drawing`.
+    pub fn state(&self) -> TableState {
+        self.state
+    }
+
+    /// The players' ids, by seat.
+    pub fn player_ids(&self) -> Vec<Uid> {
+        self.player.iter().map(|player| player.id).collect()
+    }
+
+    /// The seat of the player with this id.
+    pub fn seat_of(&self, player_id: Uid) -> Result<usize, SpadesError> {
+        self.player
+            .iter()
+            .position(|player| player.id == player_id)
+            .ok_or(SpadesError::InvalidUuid)
+    }
+
+    pub fn current_player_id(&self) -> Result<Uid, SpadesError> {
+        self.in_progress()?;
+        Ok(self.player[self.current_player_index].id)
+    }
+
+    /// The hand of the player with this id, or while drawing the cards they have kept so far.  Unlike
+    /// [`current_hand`](#method.current_hand), this does not count as the player seeing their hand.
+    pub fn hand_from_player_id(&self, player_id: Uid) -> Result<&[Card], SpadesError> {
+        let seat = self.seat_of(player_id)?;
+        self.in_progress()?;
+        match self.draw {
+            Some(ref draw) => Ok(draw.hand(seat)),
+            None => Ok(&self.player[seat].hand),
+        }
+    }
+
+    /// The hand of the player whose turn it is.  Once they have seen it they can no longer bet blind nil.
+    pub fn current_hand(&mut self) -> Result<&[Card], SpadesError> {
+        self.in_progress()?;
+        let seat = self.current_player_index;
+        self.player[seat].seen_hand = true;
+        match self.draw {
+            Some(ref draw) => Ok(draw.hand(seat)),
+            None => Ok(&self.player[seat].hand),
+        }
+    }
+
+    /// The card the current player drew, which they are to keep or discard.
+    pub fn drawn_card(&self) -> Result<Card, SpadesError> {
+        match (self.state, &self.draw) {
+            (TableState::Drawing, Some(draw)) => {
+                draw.drawn_card().ok_or(SpadesError::InternalError)
+            }
+            _ => Err(self.stage_error()),
+        }
+    }
+
+    /// Keep or discard the card the current player drew, see
+    /// [`DrawDeal::take_drawn_card`](struct.DrawDeal.html#method.take_drawn_card), and return the next card,
+    /// which they have now seen.  Once both players hold 13 cards, betting starts.
+    pub fn take_drawn_card(&mut self, keep: bool) -> Result<Card, SpadesError> {
+        if self.state != TableState::Drawing {
+            return Err(self.stage_error());
+        }
+        let draw = self.draw.as_mut().ok_or(SpadesError::InternalError)?;
+        let next = draw.take_drawn_card(keep)?;
+        self.current_player_index = draw.current_player();
+        if draw.is_complete() {
+            let hands = self.draw.take().unwrap().into_hands()?;
+            for (player, hand) in self.player.iter_mut().zip(hands.iter()) {
+                player.hand = Hand::from(&hand[..]);
+            }
+            self.current_player_index = 0;
+            self.state = TableState::Betting(0);
+        }
+        Ok(next)
+    }
+
+    /// The suit led in the trick in progress, or `None` before its first card.
+    pub fn leading_suit(&self) -> Result<Option<Suit>, SpadesError> {
+        match self.state {
+            TableState::Trick(_) => Ok(self.current_trick.first().map(|card| card.suit)),
+            _ => Err(self.stage_error()),
+        }
+    }
+
+    /// Each seat's bet this round, `None` for those still to bet.
+    pub fn bets(&self) -> &[Option<Bet>] {
+        &self.bets
+    }
+
+    /// Tricks taken by each seat this round.
+    pub fn tricks(&self) -> &[u8] {
+        &self.tricks
+    }
+
+    /// Team `team`'s score over all rounds played.  Returns `SpadesError::InvalidTeam` if there is no such
+    /// team.
+    pub fn team_score(&self, team: usize) -> Result<i32, SpadesError> {
+        self.team_at(team).map(|team| team.points)
+    }
+
+    /// Team `team`'s bags carried forward.  Returns `SpadesError::InvalidTeam` if there is no such team.
+    pub fn team_bags(&self, team: usize) -> Result<u8, SpadesError> {
+        self.team_at(team).map(|team| team.bags)
+    }
+
+    fn team_at(&self, team: usize) -> Result<&TableTeam, SpadesError> {
+        self.team.get(team).ok_or(SpadesError::InvalidTeam(team))
+    }
+
+    /// The ids of the players on the team with the highest score, once the game is over.  The earliest seat's
+    /// team wins ties.
+    pub fn winner_ids(&self) -> Result<Vec<Uid>, SpadesError> {
+        if self.state != TableState::GameCompleted {
+            return Err(SpadesError::GameNotCompleted);
+        }
+        let mut winner = 0;
+        for team in 1..self.variant.teams() {
+            if self.team[team].points > self.team[winner].points {
+                winner = team;
+            }
+        }
+        Ok(self
+            .variant
+            .team_seats(winner)
+            .into_iter()
+            .map(|seat| self.player[seat].id)
+            .collect())
+    }
+
+    /// Deal the first round and start betting.
+    pub fn start_game(&mut self) -> Result<TableState, SpadesError> {
+        if self.state != TableState::GameNotStarted {
+            return Err(SpadesError::ImproperGameStage);
+        }
+        self.start_round();
+        Ok(self.state)
+    }
+
+    /// Whether the current player may make this bet.  No bet may call for more tricks than the round has.
+    /// Returns the reason why not in `Some(SpadesError)`.
+    pub fn can_place_bet(&self, bet: Bet) -> Option<SpadesError> {
+        match self.state {
+            TableState::Betting(_) => {
+                if !bet.is_valid() || bet.tricks_required() > self.variant.tricks_per_round() {
+                    Some(SpadesError::InvalidBet)
+                } else if bet == Bet::BlindNil && self.player[self.current_player_index].seen_hand {
+                    Some(SpadesError::BetImproperSeenHand)
+                } else {
+                    None
+                }
+            }
+            _ => Some(self.stage_error()),
+        }
+    }
+
+    /// Make this bet for the current player.  Returns the new state: `Trick(0)` once everyone has bet.
+    pub fn place_bet(&mut self, bet: Bet) -> Result<TableState, SpadesError> {
+        if let Some(err) = self.can_place_bet(bet) {
+            return Err(err);
+        }
+        self.bets[self.current_player_index] = Some(bet);
+        self.current_player_index = (self.current_player_index + 1) % self.variant.seats();
+        self.state = if self.current_player_index == 0 {
+            TableState::Trick(0)
+        } else {
+            TableState::Betting(self.current_player_index)
+        };
+        Ok(self.state)
+    }
+
+    /// Whether the current player may play `card`, by the standard rules.  Returns the reason why not in
+    /// `Some(SpadesError)`.
+    pub fn can_play_card(&self, card: Card) -> Option<SpadesError> {
+        match self.state {
+            TableState::Trick(played) => {
+                let hand = &self.player[self.current_player_index].hand;
+                if !hand.contains(&card) {
+                    return Some(SpadesError::CardNotInHand);
+                }
+                StandardRules.can_play_card(&self.play_context(played, hand), card)
+            }
+            _ => Some(self.stage_error()),
+        }
+    }
+
+    /// Play this card for the current player.
+    pub fn play_card(&mut self, card: Card) -> Result<PlayCardResult, SpadesError> {
+        if let Some(err) = self.can_play_card(card) {
+            return Err(err);
+        }
+        let seats = self.variant.seats();
+        let breaks_spades = {
+            let hand = &self.player[self.current_player_index].hand;
+            let context = self.play_context(self.current_trick.len(), hand);
+            StandardRules.breaks_spades(&context, card)
+        };
+        let hand = &mut self.player[self.current_player_index].hand;
+        let index = hand.iter().position(|x| *x == card).unwrap();
+        self.played.push(hand.remove(index));
+        if breaks_spades {
+            self.spades_broken = true;
+        }
+        self.current_trick.push(card);
+
+        if self.current_trick.len() < seats {
+            self.current_player_index = (self.current_player_index + 1) % seats;
+            self.state = TableState::Trick(self.current_trick.len());
+            return Ok(PlayCardResult::CardPlayed);
+        }
+        // the last card of the trick was played by the seat just before the leader
+        let leader = (self.current_player_index + 1) % seats;
+        let winner = trick_winner(leader, seats, &self.current_trick);
+        self.tricks[winner] += 1;
+        self.current_trick.clear();
+        if !self.player[winner].hand.is_empty() {
+            self.current_player_index = winner;
+            self.state = TableState::Trick(0);
+            return Ok(PlayCardResult::TrickCompleted);
+        }
+
+        self.score_round();
+        if self.team.iter().any(|team| team.points >= self.max_points) {
+            self.state = TableState::GameCompleted;
+            Ok(PlayCardResult::GameCompleted)
+        } else {
+            self.start_round();
+            Ok(PlayCardResult::TrickCompleted)
+        }
+    }
+
+    fn start_round(&mut self) {
+        let seats = self.variant.seats();
+        for player in self.player.iter_mut() {
+            player.hand.clear();
+            player.seen_hand = false;
+        }
+        self.bets = vec![None; seats];
+        self.tricks = vec![0; seats];
+        self.played.clear();
+        self.spades_broken = false;
+        self.current_player_index = 0;
+        if self.variant == TableVariant::TwoHanded {
+            // drawing shows the players every card they keep, so there is no betting blind nil
+            for player in self.player.iter_mut() {
+                player.seen_hand = true;
+            }
+            self.draw = Some(DrawDeal::shuffled(self.shuffler.get()));
+            self.state = TableState::Drawing;
+            return;
+        }
+
+        let mut deck = self.variant.deck();
+        self.shuffler.get().shuffle(&mut deck);
+        for (i, card) in deck.into_iter().enumerate() {
+            self.player[i % seats].hand.push(card);
+        }
+        for player in self.player.iter_mut() {
+            player.hand.sort();
+        }
+        self.state = TableState::Betting(0);
+    }
+
+    fn score_round(&mut self) {
+        for team in 0..self.variant.teams() {
+            let seats = self.variant.team_seats(team);
+            let bets: Vec<Bet> = seats.iter().map(|seat| self.bets[*seat].unwrap()).collect();
+            let tricks: Vec<u8> = seats.iter().map(|seat| self.tricks[*seat]).collect();
+            let score =
+                standard_team_score(&bets, &tricks, self.team[team].bags, STANDARD_BAG_PENALTY);
+            self.team[team].points += score.points;
+            self.team[team].bags = score.cumulative_bags;
+        }
+    }
+
+    fn play_context<'a>(&'a self, played: usize, hand: &'a [Card]) -> PlayContext<'a> {
+        PlayContext {
+            position_in_trick: played,
+            trick: &self.current_trick,
+            leading_suit: self.current_trick.first().map(|card| card.suit),
+            spades_broken: self.spades_broken,
+            hand,
+        }
+    }
+
+    fn in_progress(&self) -> Result<(), SpadesError> {
+        match self.state {
+            TableState::Drawing | TableState::Betting(_) | TableState::Trick(_) => Ok(()),
+            _ => Err(self.stage_error()),
+        }
+    }
+
+    /// Why what was asked can't be done at this stage.
+    fn stage_error(&self) -> SpadesError {
+        match self.state {
+            TableState::GameNotStarted => SpadesError::GameNotStarted,
+            TableState::GameCompleted => SpadesError::GameCompleted,
+            _ => SpadesError::ImproperGameStage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Table, TableState, TableVariant};
+    use {Bet, Card, PlayCardResult, Rank, SeededShuffler, SpadesError, Suit, Uid};
+
+    fn six_player_table() -> Table {
+        let ids = [Uid(10), Uid(11), Uid(12), Uid(13), Uid(14), Uid(15)];
+        Table::six_handed(Uid(1), ids)
+    }
+
+    fn playable_card(t: &mut Table) -> Card {
+        let hand = t.current_hand().unwrap().to_vec();
+        *hand
+            .iter()
+            .find(|card| t.can_play_card(**card).is_none())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_six_handed_deal() {
+        let variant = TableVariant::SixHanded;
+        assert_eq!(48, variant.deck().len());
+        assert_eq!(8, variant.tricks_per_round());
+        assert_eq!(vec![1, 4], variant.team_seats(1));
+
+        let mut t = six_player_table();
+        assert_eq!(Err(SpadesError::GameNotStarted), t.current_player_id());
+        assert_eq!(Ok(TableState::Betting(0)), t.start_game());
+        for id in t.player_ids() {
+            let hand = t.hand_from_player_id(id).unwrap();
+            assert_eq!(8, hand.len());
+            assert!(hand.iter().all(|card| card.rank != Rank::Two));
+        }
+        assert_eq!(
+            Err(SpadesError::InvalidUuid),
+            t.hand_from_player_id(Uid(99))
+        );
+    }
+
+    #[test]
+    fn test_seeded_tables_deal_alike() {
+        let deal = |seed| {
+            let mut t = six_player_table();
+            t.set_shuffler(SeededShuffler::new(seed));
+            t.start_game().unwrap();
+            t.player_ids()
+                .iter()
+                .map(|id| t.hand_from_player_id(*id).unwrap().to_vec())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(deal(7), deal(7));
+        assert_ne!(deal(7), deal(8));
+
+        let draws = |seed| {
+            let mut t = Table::two_handed(Uid(1), [Uid(10), Uid(11)]);
+            assert_eq!("thread-rng", t.shuffler().name());
+            t.set_shuffler(SeededShuffler::new(seed));
+            t.start_game().unwrap();
+            let mut drawn = vec![];
+            while t.state() == TableState::Drawing {
+                drawn.push(t.take_drawn_card(true).unwrap());
+            }
+            drawn
+        };
+        assert_eq!(draws(7), draws(7));
+    }
+
+    #[test]
+    fn test_six_handed_bets_are_capped_at_eight_tricks() {
+        let mut t = six_player_table();
+        t.start_game().unwrap();
+        assert_eq!(None, t.can_place_bet(Bet::Amount(8)));
+        assert_eq!(
+            Some(SpadesError::InvalidBet),
+            t.can_place_bet(Bet::Amount(9))
+        );
+        assert_eq!(
+            Some(SpadesError::InvalidBet),
+            t.can_place_bet(Bet::TenForTwoHundred)
+        );
+        assert_eq!(Err(SpadesError::InvalidBet), t.place_bet(Bet::Amount(13)));
+    }
+
+    #[test]
+    fn test_six_handed_play_passes_round_six_seats() {
+        let mut t = six_player_table();
+        t.start_game().unwrap();
+        for seat in 0..6 {
+            assert_eq!(Ok(Uid(10 + seat)), t.current_player_id());
+            t.place_bet(Bet::Amount(1)).unwrap();
+        }
+        assert_eq!(TableState::Trick(0), t.state());
+        assert_eq!(
+            Some(SpadesError::ImproperGameStage),
+            t.can_place_bet(Bet::Nil)
+        );
+
+        for played in 0..6 {
+            assert_eq!(TableState::Trick(played), t.state());
+            let card = playable_card(&mut t);
+            let result = t.play_card(card).unwrap();
+            if played == 5 {
+                assert_eq!(PlayCardResult::TrickCompleted, result);
+            }
+        }
+        assert_eq!(1, t.tricks().iter().sum::<u8>());
+        assert_eq!(7, t.hand_from_player_id(Uid(10)).unwrap().len());
+    }
+
+    #[test]
+    fn test_six_handed_game_completes() {
+        let mut t = six_player_table();
+        t.start_game().unwrap();
+        while t.state() != TableState::GameCompleted {
+            if let TableState::Betting(_) = t.state() {
+                t.place_bet(Bet::Amount(1)).unwrap();
+                continue;
+            }
+            let card = playable_card(&mut t);
+            t.play_card(card).unwrap();
+        }
+        let winners = t.winner_ids().unwrap();
+        assert_eq!(2, winners.len());
+        let team = t.variant().team_of(t.seat_of(winners[0]).unwrap());
+        let score = t.team_score(team).unwrap();
+        assert!(score >= 500);
+        assert!((0..3).all(|other| t.team_score(other).unwrap() <= score));
+        assert_eq!(Err(SpadesError::InvalidTeam(3)), t.team_score(3));
+        assert_eq!(Err(SpadesError::InvalidTeam(3)), t.team_bags(3));
+    }
+
+    #[test]
+    fn test_two_handed_players_draw_before_betting() {
+        let mut t = Table::two_handed(Uid(1), [Uid(10), Uid(11)]);
+        assert_eq!(Ok(TableState::Drawing), t.start_game());
+        assert_eq!(
+            Some(SpadesError::ImproperGameStage),
+            t.can_place_bet(Bet::Nil)
+        );
+        let mut keep = true;
+        for turn in 0..26 {
+            assert_eq!(Ok(Uid(10 + turn % 2)), t.current_player_id());
+            t.drawn_card().unwrap();
+            t.take_drawn_card(keep).unwrap();
+            keep = !keep;
+        }
+        assert_eq!(TableState::Betting(0), t.state());
+        assert_eq!(Err(SpadesError::ImproperGameStage), t.drawn_card());
+        assert_eq!(13, t.hand_from_player_id(Uid(10)).unwrap().len());
+        assert_eq!(13, t.hand_from_player_id(Uid(11)).unwrap().len());
+    }
+
+    #[test]
+    fn test_two_handed_players_cant_bet_blind_nil() {
+        let mut t = Table::two_handed(Uid(1), [Uid(10), Uid(11)]);
+        t.start_game().unwrap();
+        while t.state() == TableState::Drawing {
+            t.take_drawn_card(true).unwrap();
+        }
+        // neither player has asked for their hand, but they chose every card in it
+        for _ in 0..2 {
+            assert_eq!(
+                Err(SpadesError::BetImproperSeenHand),
+                t.place_bet(Bet::BlindNil)
+            );
+            t.place_bet(Bet::Nil).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_two_handed_game_completes() {
+        let mut t = Table::two_handed(Uid(1), [Uid(10), Uid(11)]);
+        t.start_game().unwrap();
+        while t.state() != TableState::GameCompleted {
+            match t.state() {
+                TableState::Drawing => {
+                    let keep = t.drawn_card().unwrap().suit == Suit::Spades;
+                    t.take_drawn_card(keep).unwrap();
+                }
+                TableState::Betting(_) => {
+                    t.place_bet(Bet::Amount(4)).unwrap();
+                }
+                _ => {
+                    let card = playable_card(&mut t);
+                    t.play_card(card).unwrap();
+                }
+            }
+        }
+        let winners = t.winner_ids().unwrap();
+        assert_eq!(1, winners.len());
+        assert!(t.team_score(t.seat_of(winners[0]).unwrap()).unwrap() >= 500);
+    }
+}
@@ -0,0 +1,80 @@
+use result::SpadesError;
+use {Game, Uid};
+
+/// Who plays a game as two named teams, so results name the winning team rather than its two players, and
+/// a team keeps its identity from game to game whoever sits where.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct TeamAssignment {
+    /// `team_ids[0]` is the team in seats 0 and 2, `team_ids[1]` the team in seats 1 and 3.
+    pub team_ids: [Uid; 2],
+    /// The players in seat order.
+    pub seats: [Uid; 4],
+}
+
+impl Game {
+    /// A game between these two teams.
+    pub fn new_with_teams(id: Uid, teams: TeamAssignment) -> Game {
+        let mut game = Game::default();
+        game.assign_teams(id, teams);
+        game
+    }
+
+    /// Like [`assign_players`](#method.assign_players), naming the two teams as well.
+    pub fn assign_teams(&mut self, id: Uid, teams: TeamAssignment) {
+        self.assign_players(id, teams.seats);
+        self.team_ids = Some(teams.team_ids);
+    }
+
+    /// The ids of team 0 (seats 0 and 2) and team 1 (seats 1 and 3), if the teams were named with
+    /// [`assign_teams`](#method.assign_teams).
+    pub fn team_ids(&self) -> Option<[Uid; 2]> {
+        self.team_ids
+    }
+
+    /// The id of the team that won, once the game is over, or `None` if the teams weren't named.  Returns
+    /// `SpadesError::GameNotCompleted` before then, like [`winner_ids`](#method.winner_ids).
+    pub fn winning_team_id(&self) -> Result<Option<Uid>, SpadesError> {
+        let (winner, _) = self.winner_ids()?;
+        let team = self.seat_of(&winner)?.team();
+        Ok(self.team_ids.map(|ids| ids[team]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TeamAssignment;
+    use {Bet, Game, SpadesError, Uid};
+
+    #[test]
+    fn test_winning_team_is_named() {
+        let mut g = Game::new_with_teams(
+            Uid(1),
+            TeamAssignment {
+                team_ids: [Uid(100), Uid(200)],
+                seats: [Uid(10), Uid(20), Uid(11), Uid(21)],
+            },
+        );
+        assert_eq!(Some([Uid(100), Uid(200)]), g.team_ids());
+        g.set_shuffle_seed(Some(2));
+        g.start_game();
+        assert_eq!(Err(SpadesError::GameNotCompleted), g.winning_team_id());
+        while !g.is_over() {
+            for _ in 0..4 {
+                g.place_bet(Bet::Amount(3));
+            }
+            for _ in 0..52 {
+                let card = g.playable_cards().next().unwrap();
+                g.play_card(card);
+            }
+        }
+        let (winner, _) = g.winner_ids().unwrap();
+        let team = if winner.0 < 20 { Uid(100) } else { Uid(200) };
+        assert_eq!(Ok(Some(team)), g.winning_team_id());
+        assert_eq!(Some(team), g.result().unwrap().winning_team);
+
+        g.assign_players(Uid(2), [Uid(10), Uid(20), Uid(11), Uid(21)]);
+        assert_eq!(None, g.team_ids());
+    }
+}
@@ -0,0 +1,29 @@
+//! Games set up the same way across the crate's tests.
+
+use {Game, Uid};
+
+/// The players [`seated_game`](fn.seated_game.html) seats, in seat order.
+pub const PLAYERS: [Uid; 4] = [Uid(10), Uid(11), Uid(12), Uid(13)];
+
+/// Game 1 with `PLAYERS` seated and every option left at its default, not yet started.
+pub fn seated_game() -> Game {
+    let mut g = Game::default();
+    g.assign_players(Uid(1), PLAYERS);
+    g
+}
+
+/// A [`seated_game`](fn.seated_game.html), started: the first round is dealt and the first player is to bet.
+pub fn started_game() -> Game {
+    let mut g = seated_game();
+    g.start_game();
+    g
+}
+
+/// A [`started_game`](fn.started_game.html) whose deals are shuffled from `seed`, so that every run is
+/// dealt the same cards.
+pub fn seeded_game(seed: u64) -> Game {
+    let mut g = seated_game();
+    g.set_shuffle_seed(Some(seed));
+    g.start_game();
+    g
+}
@@ -1,2 +1,3 @@
+mod serialization_unit;
 mod spades_deck_unit;
 mod spades_game_api_unit;
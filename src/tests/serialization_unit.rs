@@ -0,0 +1,107 @@
+use super::super::{Bet, Card, Game, Rank, SpadesError, Suit, SCHEMA_VERSION};
+use test_support::started_game;
+
+fn game_in_progress() -> Game {
+    let mut g = started_game();
+    for _ in 0..4 {
+        g.place_bet(Bet::Amount(3));
+    }
+    let card = g.current_hand().unwrap()[0];
+    g.play_card(card);
+    g
+}
+
+#[test]
+fn game_round_trips_through_json() {
+    let g = game_in_progress();
+    let json = serde_json::to_string(&g).unwrap();
+    let loaded: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(g, loaded);
+}
+
+#[test]
+fn saved_game_records_schema_version() {
+    let json = serde_json::to_value(game_in_progress()).unwrap();
+    assert_eq!(serde_json::json!(SCHEMA_VERSION), json["schema_version"]);
+}
+
+#[test]
+fn newer_schema_version_is_rejected() {
+    let mut json = serde_json::to_value(game_in_progress()).unwrap();
+    json["schema_version"] = serde_json::json!(SCHEMA_VERSION + 1);
+    let err = serde_json::from_value::<Game>(json).unwrap_err();
+    assert_eq!(
+        SpadesError::UnsupportedSchemaVersion(SCHEMA_VERSION + 1).to_string(),
+        err.to_string()
+    );
+}
+
+#[test]
+fn unversioned_save_is_upgraded() {
+    let g = game_in_progress();
+    let mut json = serde_json::to_value(&g).unwrap();
+    json.as_object_mut().unwrap().remove("schema_version");
+    let loaded: Game = serde_json::from_value(json).unwrap();
+    assert_eq!(g, loaded);
+    let resaved = serde_json::to_value(&loaded).unwrap();
+    assert_eq!(serde_json::json!(SCHEMA_VERSION), resaved["schema_version"]);
+}
+
+#[test]
+fn version_zero_save_is_upgraded() {
+    let g = game_in_progress();
+    let mut json = serde_json::to_value(&g).unwrap();
+    json["schema_version"] = serde_json::json!(0);
+    assert_eq!(g, serde_json::from_value::<Game>(json).unwrap());
+}
+
+#[test]
+fn save_missing_a_field_is_rejected() {
+    let mut json = serde_json::to_value(game_in_progress()).unwrap();
+    json.as_object_mut().unwrap().remove("round_bets");
+    assert!(serde_json::from_value::<Game>(json).is_err());
+}
+
+#[test]
+fn save_with_a_duplicated_card_is_rejected() {
+    let g = game_in_progress();
+    let mut json = serde_json::to_value(&g).unwrap();
+    let (replaced, copied) = (g.player[2].hand[0], g.player[3].hand[0]);
+    json["player"][2]["hand"][0] = serde_json::to_value(copied).unwrap();
+    let err = serde_json::from_value::<Game>(json).unwrap_err();
+    let expected = SpadesError::CorruptDeck {
+        missing: vec![replaced],
+        duplicated: vec![copied],
+    };
+    assert_eq!(expected.to_string(), err.to_string());
+    assert!(err.to_string().contains(&replaced.to_string()));
+}
+
+#[test]
+fn save_missing_a_card_is_rejected() {
+    let mut json = serde_json::to_value(game_in_progress()).unwrap();
+    let ace = Card {
+        suit: Suit::Spades,
+        rank: Rank::Ace,
+    };
+    let ace_json = serde_json::to_value(ace).unwrap();
+    for player in json["player"].as_array_mut().unwrap() {
+        player["hand"]
+            .as_array_mut()
+            .unwrap()
+            .retain(|card| *card != ace_json);
+    }
+    json["deck"]
+        .as_array_mut()
+        .unwrap()
+        .retain(|card| *card != ace_json);
+    let err = serde_json::from_value::<Game>(json).unwrap_err();
+    assert_eq!(
+        SpadesError::CorruptDeck {
+            missing: vec![ace],
+            duplicated: vec![],
+        }
+        .to_string(),
+        err.to_string()
+    );
+}
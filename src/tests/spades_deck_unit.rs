@@ -10,7 +10,7 @@ fn new_deck() {
 fn deal_deck_four_players() {
     let mut x = cards::new_deck();
 
-    let hands = deal_four_players(&mut x);
+    let hands = deal_four_players(&mut x).unwrap();
     assert_eq!(hands[0].len(), 13);
     assert_eq!(hands[1].len(), 13);
     assert_eq!(hands[2].len(), 13);
@@ -37,10 +37,10 @@ fn trick_winner_same_suit() {
     };
 
     let trick = vec![a, b, c, d];
-    assert_eq!(1, get_trick_winner(0, &trick));
-    assert_eq!(2, get_trick_winner(1, &trick));
-    assert_eq!(3, get_trick_winner(2, &trick));
-    assert_eq!(0, get_trick_winner(3, &trick));
+    assert_eq!(Some(1), get_trick_winner(0, &trick));
+    assert_eq!(Some(2), get_trick_winner(1, &trick));
+    assert_eq!(Some(3), get_trick_winner(2, &trick));
+    assert_eq!(Some(0), get_trick_winner(3, &trick));
 }
 
 #[test]
@@ -64,10 +64,10 @@ fn trick_winner_no_spades() {
 
     let trick = vec![a, b, c, d];
 
-    assert_eq!(3, get_trick_winner(0, &trick));
-    assert_eq!(0, get_trick_winner(1, &trick));
-    assert_eq!(1, get_trick_winner(2, &trick));
-    assert_eq!(2, get_trick_winner(3, &trick));
+    assert_eq!(Some(3), get_trick_winner(0, &trick));
+    assert_eq!(Some(0), get_trick_winner(1, &trick));
+    assert_eq!(Some(1), get_trick_winner(2, &trick));
+    assert_eq!(Some(2), get_trick_winner(3, &trick));
 }
 
 #[test]
@@ -91,8 +91,38 @@ fn trick_winner_spades() {
 
     let trick = vec![a, b, c, d];
 
-    assert_eq!(2, get_trick_winner(0, &trick));
-    assert_eq!(3, get_trick_winner(1, &trick));
-    assert_eq!(0, get_trick_winner(2, &trick));
-    assert_eq!(1, get_trick_winner(3, &trick));
+    assert_eq!(Some(2), get_trick_winner(0, &trick));
+    assert_eq!(Some(3), get_trick_winner(1, &trick));
+    assert_eq!(Some(0), get_trick_winner(2, &trick));
+    assert_eq!(Some(1), get_trick_winner(3, &trick));
+}
+
+#[test]
+fn trick_winner_partial_trick() {
+    let a = Card {
+        suit: Suit::Hearts,
+        rank: Rank::Ten,
+    };
+    let b = Card {
+        suit: Suit::Hearts,
+        rank: Rank::Queen,
+    };
+    let c = Card {
+        suit: Suit::Spades,
+        rank: Rank::Two,
+    };
+
+    assert_eq!(Some(2), get_trick_winner(2, &[a]));
+    assert_eq!(Some(3), get_trick_winner(2, &[a, b]));
+    assert_eq!(Some(0), get_trick_winner(2, &[a, b, c]));
+}
+
+#[test]
+fn trick_winner_without_a_trick() {
+    let a = Card {
+        suit: Suit::Clubs,
+        rank: Rank::Two,
+    };
+    assert_eq!(None, get_trick_winner(0, &[]));
+    assert_eq!(None, get_trick_winner(0, &[a; 5]));
 }
@@ -0,0 +1,433 @@
+use cards::Card;
+use game_state::State;
+use ml::Action;
+use result::SpadesError;
+use scoring::Bet;
+use std::time::{Duration, Instant};
+use {BetResult, Game, PlayCardResult, PlayerId, Uid};
+
+/// What the engine does when a player lets their turn clock run out, see
+/// [`Game::tick`](struct.Game.html#method.tick).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum TimeoutPolicy {
+    /// Bet `Bet::default()` or play the player's lowest legal card for them.  If `Bet::default()` isn't
+    /// allowed, as when a team minimum is left to make up, the fewest tricks they may bid are bet instead.
+    /// Picking up a kitty, they discard their lowest cards.
+    #[default]
+    AutoPlay,
+    /// The player's team forfeits, ending the game.
+    Forfeit,
+    /// Nothing is played for the player; the timeout is only reported and their clock starts over.
+    /// Spades never lets a turn be passed, so this leaves it to the caller to decide what to do.
+    Skip,
+}
+
+/// Reported by [`Game::tick`](struct.Game.html#method.tick) when the current player's time has run out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum TimeoutOutcome {
+    /// A bet was placed on the player's behalf.
+    AutoBet {
+        player: Uid,
+        bet: Bet,
+        result: BetResult,
+    },
+    /// A card was played on the player's behalf.
+    AutoPlay {
+        player: Uid,
+        card: Card,
+        result: PlayCardResult,
+    },
+    /// The player's lowest cards were discarded after they picked up the kitty.
+    AutoDiscard { player: Uid },
+    /// The player's bet was kept as it was when the bets were reviewed.
+    AutoKeepBet { player: Uid },
+    /// The player's team forfeited the game.
+    Forfeit { player: Uid },
+    /// The timeout was reported and the player's clock restarted.
+    Skip { player: Uid },
+}
+
+impl Game {
+    /// Give each player `duration` to make each bet or play each card.
+    pub fn set_turn_duration(&mut self, duration: Duration) {
+        self.turn_duration = Some(duration);
+        self.turn_started = None;
+    }
+
+    /// Turn the turn clock off again.
+    pub fn clear_turn_duration(&mut self) {
+        self.turn_duration = None;
+        self.turn_started = None;
+    }
+
+    /// Choose what happens when a player's time runs out.  Defaults to `TimeoutPolicy::AutoPlay`.
+    pub fn set_timeout_policy(&mut self, policy: TimeoutPolicy) {
+        self.timeout_policy = policy;
+    }
+
+    /// The time the current player has left at `now`, or `None` if there is no turn clock or no one is
+    /// expected to act.  A turn's clock starts at the first call to [`tick`](#method.tick) during that turn.
+    pub fn time_remaining(&self, now: Instant) -> Option<Duration> {
+        let duration = self.turn_duration?;
+        self.current_player_id().ok()?;
+        match self.turn_started {
+            Some(started) => Some(duration.saturating_sub(now.saturating_duration_since(started))),
+            None => Some(duration),
+        }
+    }
+
+    /// Advance the turn clock to `now`.
+    ///
+    /// Call this regularly (e.g. from a server's event loop).  The first call during a turn starts that turn's
+    /// clock; once the turn has lasted at least the configured duration, the game's `TimeoutPolicy` is
+    /// applied and the outcome is returned.
+    pub fn tick(&mut self, now: Instant) -> Option<TimeoutOutcome> {
+        let duration = self.turn_duration?;
+        let player = self.current_player_id().ok()?;
+        if self.pending_claim.is_some() {
+            // nobody can play until the claim is answered
+            return None;
+        }
+        let started = match self.turn_started {
+            Some(started) => started,
+            None => {
+                self.turn_started = Some(now);
+                return None;
+            }
+        };
+        if now.saturating_duration_since(started) < duration {
+            return None;
+        }
+
+        match (self.timeout_policy, self.state) {
+            (TimeoutPolicy::Skip, _) => {
+                self.turn_started = Some(now);
+                Some(TimeoutOutcome::Skip { player })
+            }
+            (TimeoutPolicy::Forfeit, _) => {
+                self.forfeited_team = Some(self.current_player_index % 2);
+                self.state = State::GameCompleted;
+                self.turn_started = None;
+                Some(TimeoutOutcome::Forfeit { player })
+            }
+            (TimeoutPolicy::AutoPlay, State::Betting(_)) => {
+                let bet = self.auto_bet(&player)?;
+                let result = self.place_bet(bet)?;
+                Some(TimeoutOutcome::AutoBet {
+                    player,
+                    bet,
+                    result,
+                })
+            }
+            (TimeoutPolicy::AutoPlay, State::Exchange) => {
+                let size = self.kitty_discards();
+                let mut hand = self.player[self.current_player_index].hand.clone();
+                hand.sort_by_key(|card| (card.rank, card.suit));
+                self.discard(&hand[..size]).ok()?;
+                Some(TimeoutOutcome::AutoDiscard { player })
+            }
+            (TimeoutPolicy::AutoPlay, State::BidReview) => {
+                self.review_bet(None).ok()?;
+                Some(TimeoutOutcome::AutoKeepBet { player })
+            }
+            (TimeoutPolicy::AutoPlay, _) => {
+                let card = self.lowest_playable_card()?;
+                let result = self.play_card(card)?;
+                Some(TimeoutOutcome::AutoPlay {
+                    player,
+                    card,
+                    result,
+                })
+            }
+        }
+    }
+
+    /// The bet made for `player` when their clock runs out: `Bet::default()` if they may make it, and
+    /// otherwise the smallest amount they may bid.
+    fn auto_bet(&self, player: &Uid) -> Option<Bet> {
+        let bets: Vec<Bet> = self
+            .legal_actions(player)
+            .ok()?
+            .into_iter()
+            .filter_map(|action| match action {
+                Action::Bet(bet) => Some(bet),
+                Action::Play(_) => None,
+            })
+            .collect();
+        if bets.contains(&Bet::default()) {
+            return Some(Bet::default());
+        }
+        bets.iter()
+            .filter(|bet| matches!(bet, Bet::Amount(_)))
+            .min()
+            .or_else(|| bets.first())
+            .cloned()
+    }
+
+    /// Bet or play a card for the current player as [`place_bet`](#method.place_bet) or
+    /// [`play_card`](#method.play_card) would, adding the time they took to their
+    /// [`think_time`](#method.think_time).  Returns why the action isn't allowed, if it isn't.
+    ///
+    /// A player's time is counted from the previous action taken this way, or from the start of their turn
+    /// clock if that is later.  Nothing is counted for a turn that started with an action taken some other
+    /// way and no turn clock running.
+    pub fn apply_with_time(&mut self, action: Action, now: Instant) -> Result<(), SpadesError> {
+        let seat = self.current_player_index;
+        let actions_taken = self.actions_taken();
+        let previous = self
+            .last_timed_action
+            .filter(|(_, actions)| *actions == actions_taken)
+            .map(|(at, _)| at);
+        let started = match (previous, self.turn_started) {
+            (Some(previous), Some(turn)) => Some(previous.max(turn)),
+            (previous, turn) => previous.or(turn),
+        };
+        match action {
+            Action::Bet(bet) => {
+                if let Some(err) = self.can_place_bet(bet) {
+                    return Err(err);
+                }
+                self.place_bet(bet);
+            }
+            Action::Play(card) => {
+                if let Some(err) = self.can_play_card(card) {
+                    return Err(err);
+                }
+                self.play_card(card);
+            }
+        }
+        if let Some(started) = started {
+            self.think_time[seat] += now.saturating_duration_since(started);
+        }
+        self.last_timed_action = Some((now, self.actions_taken()));
+        Ok(())
+    }
+
+    /// The time this player has taken over the game, as counted by
+    /// [`apply_with_time`](#method.apply_with_time).
+    pub fn think_time<P: PlayerId + ?Sized>(&self, player_id: &P) -> Result<Duration, SpadesError> {
+        Ok(self.think_time[self.seat_of(player_id)?.index()])
+    }
+
+    fn lowest_playable_card(&self) -> Option<Card> {
+        self.playable_cards()
+            .min_by_key(|card| (card.rank, card.suit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TimeoutOutcome, TimeoutPolicy};
+    use ml::Action;
+    use std::time::{Duration, Instant};
+    use test_support::{seated_game, started_game};
+    use {
+        Bet, BetResult, Game, GameOptions, KittyConfig, KittyTaker, PlayCardResult, SpadesError,
+        State, Uid,
+    };
+
+    fn timed_game() -> Game {
+        let mut g = started_game();
+        g.set_turn_duration(Duration::from_secs(30));
+        g
+    }
+
+    #[test]
+    fn test_no_clock_means_no_timeouts() {
+        let mut g = Game::default();
+        g.start_game();
+        let now = Instant::now();
+        assert_eq!(None, g.time_remaining(now));
+        assert_eq!(None, g.tick(now));
+        assert_eq!(None, g.tick(now + Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_clock_starts_at_first_tick() {
+        let mut g = timed_game();
+        let now = Instant::now();
+        assert_eq!(Some(Duration::from_secs(30)), g.time_remaining(now));
+        assert_eq!(None, g.tick(now));
+        assert_eq!(
+            Some(Duration::from_secs(20)),
+            g.time_remaining(now + Duration::from_secs(10))
+        );
+        assert_eq!(None, g.tick(now + Duration::from_secs(29)));
+        assert_eq!(
+            Some(Duration::from_secs(0)),
+            g.time_remaining(now + Duration::from_secs(45))
+        );
+    }
+
+    #[test]
+    fn test_acting_restarts_clock() {
+        let mut g = timed_game();
+        let now = Instant::now();
+        g.tick(now);
+        g.place_bet(Bet::Amount(2));
+        assert_eq!(
+            Some(Duration::from_secs(30)),
+            g.time_remaining(now + Duration::from_secs(25))
+        );
+        assert_eq!(None, g.tick(now + Duration::from_secs(35)));
+    }
+
+    #[test]
+    fn test_autoplay_bets_and_plays() {
+        let mut g = timed_game();
+        let mut now = Instant::now();
+        for i in 0..4 {
+            g.tick(now);
+            now += Duration::from_secs(30);
+            let expected_result = if i == 3 {
+                BetResult::CompletedBetting([10, 11, 12, 13].map(|id| (Uid(id), Bet::default())))
+            } else {
+                BetResult::MadeBet
+            };
+            assert_eq!(
+                Some(TimeoutOutcome::AutoBet {
+                    player: Uid(10 + i),
+                    bet: Bet::default(),
+                    result: expected_result
+                }),
+                g.tick(now)
+            );
+        }
+        assert_eq!(State::Trick(0), g.state());
+        g.tick(now);
+        match g.tick(now + Duration::from_secs(30)) {
+            Some(TimeoutOutcome::AutoPlay { player, result, .. }) => {
+                assert_eq!(Uid(10), player);
+                assert_eq!(PlayCardResult::CardPlayed, result);
+            }
+            other => panic!("expected a card to be auto-played, got {:?}", other),
+        }
+        assert_eq!(12, g.hand_from_player_id(Uid(10)).unwrap().len());
+    }
+
+    #[test]
+    fn test_autoplay_bets_enough_to_meet_the_team_minimum() {
+        let mut g = seated_game();
+        g.set_options(GameOptions {
+            minimum_team_bid: Some(4),
+            ..GameOptions::default()
+        })
+        .unwrap();
+        g.start_game();
+        g.set_turn_duration(Duration::from_secs(30));
+        g.place_bet(Bet::Nil);
+        g.place_bet(Bet::Amount(2));
+        let now = Instant::now();
+        g.tick(now);
+        match g.tick(now + Duration::from_secs(30)) {
+            Some(TimeoutOutcome::AutoBet { player, bet, .. }) => {
+                assert_eq!(Uid(12), player);
+                assert_eq!(Bet::Amount(4), bet);
+            }
+            other => panic!("expected a bet to be made, got {:?}", other),
+        }
+        assert_eq!(State::Betting(3), g.state());
+    }
+
+    #[test]
+    fn test_autoplay_discards_after_picking_up_kitty() {
+        let mut g = seated_game();
+        g.set_kitty_config(Some(KittyConfig {
+            size: 4,
+            taker: KittyTaker::Dealer,
+        }))
+        .unwrap();
+        g.start_game();
+        g.set_turn_duration(Duration::from_secs(30));
+        let now = Instant::now();
+        g.tick(now);
+        assert_eq!(
+            Some(TimeoutOutcome::AutoDiscard { player: Uid(13) }),
+            g.tick(now + Duration::from_secs(30))
+        );
+        assert_eq!(State::Betting(0), g.state());
+        assert_eq!(12, g.hand_from_player_id(Uid(13)).unwrap().len());
+    }
+
+    #[test]
+    fn test_forfeit_ends_game_for_other_team() {
+        let mut g = timed_game();
+        g.set_timeout_policy(TimeoutPolicy::Forfeit);
+        g.place_bet(Bet::Amount(3));
+        let now = Instant::now();
+        g.tick(now);
+        assert_eq!(
+            Some(TimeoutOutcome::Forfeit { player: Uid(11) }),
+            g.tick(now + Duration::from_secs(31))
+        );
+        assert_eq!(State::GameCompleted, g.state());
+        assert!(g.is_over());
+        assert_eq!(Ok((Uid(10), Uid(12))), g.winner_ids());
+        assert_eq!(None, g.tick(now + Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn test_skip_reports_and_restarts_clock() {
+        let mut g = timed_game();
+        g.set_timeout_policy(TimeoutPolicy::Skip);
+        let now = Instant::now();
+        g.tick(now);
+        let later = now + Duration::from_secs(30);
+        assert_eq!(
+            Some(TimeoutOutcome::Skip { player: Uid(10) }),
+            g.tick(later)
+        );
+        assert_eq!(State::Betting(0), g.state());
+        assert_eq!(Some(Duration::from_secs(30)), g.time_remaining(later));
+    }
+
+    #[test]
+    fn test_think_time_adds_up_per_player() {
+        let mut g = started_game();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        // nothing to count the first bet from
+        assert_eq!(
+            Ok(()),
+            g.apply_with_time(Action::Bet(Bet::Amount(3)), at(5))
+        );
+        assert_eq!(
+            Ok(()),
+            g.apply_with_time(Action::Bet(Bet::Amount(3)), at(9))
+        );
+        let card = g.hand_of_current_player().unwrap()[0];
+        assert_eq!(
+            Err(SpadesError::NotTrickStage { next: Uid(12) }),
+            g.apply_with_time(Action::Play(card), at(10))
+        );
+        assert_eq!(
+            Ok(()),
+            g.apply_with_time(Action::Bet(Bet::Amount(3)), at(10))
+        );
+        // taken without a time, so the next player's turn isn't counted
+        g.place_bet(Bet::Amount(3));
+        let card = g.playable_cards().next().unwrap();
+        assert_eq!(Ok(()), g.apply_with_time(Action::Play(card), at(30)));
+        let card = g.playable_cards().next().unwrap();
+        assert_eq!(Ok(()), g.apply_with_time(Action::Play(card), at(37)));
+
+        let think_time = |id| g.think_time(&Uid(id)).unwrap().as_secs();
+        assert_eq!([0, 4 + 7, 1, 0], [10, 11, 12, 13].map(think_time));
+        assert_eq!(Err(SpadesError::InvalidUuid), g.think_time(&Uid(99)));
+        let result = g.game_result();
+        assert_eq!(Duration::from_secs(11), result.think_time[1]);
+        assert_eq!([2, 2, 1, 1], result.player_actions);
+    }
+}
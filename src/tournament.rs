@@ -0,0 +1,223 @@
+//! Round-robin self-play between [`PlayerAgent`](../trait.PlayerAgent.html)s.  With the `rayon` feature the
+//! games are spread over a thread pool.
+//!
+//! Each game is dealt from its own shuffle seed, so a tournament of agents that don't pick at random gives
+//! the same results every time it is run with the same seed.
+
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "rayon")]
+use self::rayon::prelude::*;
+
+use agent::{run_game, PlayerAgent};
+use result::SpadesError;
+use {Game, Uid};
+
+/// Makes a fresh agent for one seat of one game.  It is given the game's number, so agents that use random
+/// numbers can seed them reproducibly.
+pub type AgentFactory = Box<dyn Fn(usize) -> Box<dyn PlayerAgent> + Send + Sync>;
+
+struct Entrant {
+    name: String,
+    factory: AgentFactory,
+}
+
+/// Every entrant plays every other as a partnership, `games_per_pairing` times from each side of the table.
+pub struct Tournament {
+    entrants: Vec<Entrant>,
+    games_per_pairing: usize,
+    seed: u64,
+}
+
+/// How one entrant did in a [`Tournament`](struct.Tournament.html).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct EntrantStats {
+    pub name: String,
+    pub games: u32,
+    pub wins: u32,
+    /// Games that ended without a winner, such as by an agent making an illegal move or nobody winning within
+    /// [`ROUND_LIMIT`](constant.ROUND_LIMIT.html) rounds.
+    pub unfinished: u32,
+    pub points_for: i64,
+    pub points_against: i64,
+}
+
+/// The outcome of [`Tournament::run`](struct.Tournament.html#method.run).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct TournamentResults {
+    /// In the order the entrants were added.
+    pub entrants: Vec<EntrantStats>,
+    /// `head_to_head[i][j]` is how many games entrant `i` won against entrant `j`.
+    pub head_to_head: Vec<Vec<u32>>,
+}
+
+/// One game of the tournament: entrant `teams[0]` in seats 0 and 2, `teams[1]` in seats 1 and 3.
+struct Pairing {
+    number: usize,
+    teams: [usize; 2],
+}
+
+/// What came of a pairing: its teams, the winning team if any, and the final scores.
+type PairingResult = ([usize; 2], Option<usize>, [i32; 2]);
+
+impl Tournament {
+    pub fn new(games_per_pairing: usize) -> Tournament {
+        Tournament {
+            entrants: vec![],
+            games_per_pairing,
+            seed: 0,
+        }
+    }
+
+    /// Deal game number `n` from the shuffle seed `seed + n`; the seed is 0 unless set.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    pub fn add_entrant<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(usize) -> Box<dyn PlayerAgent> + Send + Sync + 'static,
+    {
+        self.entrants.push(Entrant {
+            name: name.to_string(),
+            factory: Box::new(factory),
+        });
+    }
+
+    /// Plays every game and collects the results, or returns the first error a game stopped with.
+    pub fn run(&self) -> Result<TournamentResults, SpadesError> {
+        let mut pairings = vec![];
+        for first in 0..self.entrants.len() {
+            for second in 0..self.entrants.len() {
+                if first == second {
+                    continue;
+                }
+                for _ in 0..self.games_per_pairing {
+                    pairings.push(Pairing {
+                        number: pairings.len(),
+                        teams: [first, second],
+                    });
+                }
+            }
+        }
+
+        #[cfg(feature = "rayon")]
+        let outcomes: Vec<PairingResult> = pairings
+            .par_iter()
+            .map(|p| self.play(p))
+            .collect::<Result<_, _>>()?;
+        #[cfg(not(feature = "rayon"))]
+        let outcomes: Vec<PairingResult> = pairings
+            .iter()
+            .map(|p| self.play(p))
+            .collect::<Result<_, _>>()?;
+
+        let count = self.entrants.len();
+        let mut results = TournamentResults {
+            entrants: self
+                .entrants
+                .iter()
+                .map(|entrant| EntrantStats {
+                    name: entrant.name.clone(),
+                    ..EntrantStats::default()
+                })
+                .collect(),
+            head_to_head: vec![vec![0; count]; count],
+        };
+        for (teams, winner, scores) in outcomes {
+            for side in 0..2 {
+                let stats = &mut results.entrants[teams[side]];
+                stats.games += 1;
+                stats.points_for += scores[side] as i64;
+                stats.points_against += scores[1 - side] as i64;
+            }
+            match winner {
+                Some(side) => {
+                    results.entrants[teams[side]].wins += 1;
+                    results.head_to_head[teams[side]][teams[1 - side]] += 1;
+                }
+                None => {
+                    results.entrants[teams[0]].unfinished += 1;
+                    results.entrants[teams[1]].unfinished += 1;
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    fn play(&self, pairing: &Pairing) -> Result<PairingResult, SpadesError> {
+        let make = |seat: usize| (self.entrants[pairing.teams[seat % 2]].factory)(pairing.number);
+        let mut agents = [make(0), make(1), make(2), make(3)];
+        let [a, b, c, d] = &mut agents;
+
+        let base = pairing.number as u64 * 4;
+        let mut game = Game::default();
+        game.set_shuffle_seed(Some(self.seed.wrapping_add(pairing.number as u64)));
+        game.assign_players(
+            Uid(pairing.number as u64),
+            [Uid(base), Uid(base + 1), Uid(base + 2), Uid(base + 3)],
+        );
+        let result = run_game(&mut game, [a.as_mut(), b.as_mut(), c.as_mut(), d.as_mut()])?;
+        let winner = result
+            .winners
+            .map(|(first, _)| (first.0 - base) as usize % 2);
+        Ok((pairing.teams, winner, result.scores))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tournament;
+    use agent::{PlayerAgent, PlayerView};
+    use {Bet, Card};
+
+    /// Bets `bet` and plays its first legal card.
+    struct Fixed(u8);
+
+    impl PlayerAgent for Fixed {
+        fn bet(&mut self, _view: &PlayerView) -> Bet {
+            Bet::Amount(self.0)
+        }
+
+        fn play_card(&mut self, view: &PlayerView) -> Card {
+            view.playable[0]
+        }
+    }
+
+    fn two_entrants() -> Tournament {
+        let mut tournament = Tournament::new(2);
+        tournament.add_entrant("threes", |_| Box::new(Fixed(3)));
+        tournament.add_entrant("fours", |_| Box::new(Fixed(4)));
+        tournament
+    }
+
+    #[test]
+    fn test_round_robin_results() {
+        let mut tournament = two_entrants();
+        tournament.add_entrant("twos", |_| Box::new(Fixed(2)));
+        let results = tournament.run().unwrap();
+
+        assert_eq!(3, results.entrants.len());
+        assert_eq!("fours", results.entrants[1].name);
+        for (i, stats) in results.entrants.iter().enumerate() {
+            // two games from each side against each of the two others
+            assert_eq!(8, stats.games);
+            assert_eq!(0, stats.unfinished);
+            let wins: u32 = results.head_to_head[i].iter().sum();
+            assert_eq!(stats.wins, wins);
+            assert_eq!(0, results.head_to_head[i][i]);
+        }
+        let total_wins: u32 = results.entrants.iter().map(|stats| stats.wins).sum();
+        assert_eq!(12, total_wins);
+    }
+
+    #[test]
+    fn test_same_seed_same_results() {
+        let mut tournament = two_entrants();
+        tournament.set_seed(42);
+        let results = tournament.run().unwrap();
+        assert_eq!(Ok(results.clone()), tournament.run());
+        assert_eq!(4, results.entrants[0].games);
+    }
+}
@@ -0,0 +1,425 @@
+//! The moves of the game's state machine on a bet or a card, as a pure function.
+//!
+//! [`transition`](fn.transition.html) works out from a [`State`](../enum.State.html), an
+//! [`Action`](../ml/enum.Action.html) and a [`RulesContext`](struct.RulesContext.html) whether the action is
+//! allowed and, if so, the state it leads to and what else it sets off, without changing anything.  A `Game`
+//! checks every bet and card with it, so it can be used to test the state machine on its own, or to ask
+//! what an action would do without cloning the game.
+//!
+//! ```
+//! use spades::ml::Action;
+//! use spades::{transition, Bet, Game, State, Uid};
+//!
+//! let mut g = Game::default();
+//! g.assign_players(Uid(1), [Uid(10), Uid(11), Uid(12), Uid(13)]);
+//! g.start_game();
+//! let (state, effects) = transition(&g.state(), &Action::Bet(Bet::Amount(3)), &g.rules_context()).unwrap();
+//! assert_eq!(State::Betting(1), state);
+//! assert_eq!(Some(1), effects.next_player);
+//! // nothing has changed
+//! assert_eq!(State::Betting(0), g.state());
+//! ```
+
+use bid_review::REVIEWER;
+use cards::{trick_winner, Card, Suit};
+use game_state::State;
+use kitty::highest_bidder;
+use ml::Action;
+use options::team_bid_error;
+use result::SpadesError;
+use revoke::RevokePolicy;
+use rules::{leader, CardPlayRules, PlayContext};
+use scoring::{Bet, Scoring};
+use {Game, Uid};
+
+/// What [`transition`](fn.transition.html) needs to know about a game besides its state: who is to act, and
+/// the cards, bets and options deciding what they may do and where it leads.  Made from a game by
+/// [`Game::rules_context`](../struct.Game.html#method.rules_context), after which any of its public fields may
+/// be changed to ask what if.
+#[derive(Debug, Clone, Copy)]
+pub struct RulesContext<'a> {
+    /// The seat of the player to act, 0 to 3.
+    pub player: usize,
+    /// Every seat's player, named in errors about acting out of turn.
+    pub players: [Uid; 4],
+    /// The hand of the player to act.
+    pub hand: &'a [Card],
+    /// Every seat's hand, to tell who leads when a leader has no card the rules let them lead.
+    pub(crate) hands: [&'a [Card]; 4],
+    /// Whether the player to act has looked at their hand, which rules out blind nil.
+    pub seen_hand: bool,
+    /// The bets placed so far this round, by seat.
+    pub bets: [Option<Bet>; 4],
+    pub minimum_team_bid: Option<u8>,
+    /// Whether the bets are reviewed once everyone has bet, see
+    /// [`GameOptions::bid_review`](../struct.GameOptions.html#structfield.bid_review).
+    pub bid_review: bool,
+    /// The cards set aside this round and not yet picked up, which the highest bidder picks up once betting is
+    /// over.
+    pub kitty: &'a [Card],
+    /// The cards already played to the trick, starting with the lead.
+    pub trick: &'a [Card],
+    pub leading_suit: Option<Suit>,
+    pub spades_broken: bool,
+    /// Whether a claim is waiting to be answered, which holds up play.
+    pub claim_pending: bool,
+    pub revoke_policy: RevokePolicy,
+    pub rules: &'a dyn CardPlayRules,
+    /// The scores so far, to tell whether a trick ends the round or the game.
+    pub(crate) scoring: &'a Scoring,
+}
+
+/// What an action sets off besides moving the game to a new state.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct Effects {
+    /// The seat of the player to act next, or `None` once the game is over.
+    pub next_player: Option<usize>,
+    /// The bets are final, and play begins or the highest bidder picks up the kitty.
+    pub betting_completed: bool,
+    /// The card broke spades.
+    pub breaks_spades: bool,
+    /// The seat that took the trick the card completed.
+    pub trick_winner: Option<usize>,
+    /// The trick was the last of the round, which has been scored.
+    pub round_completed: bool,
+    /// The next round is to be dealt.  If the dealer picks up a kitty, that moves the game on to the exchange
+    /// once the cards are dealt.
+    pub deal: bool,
+}
+
+/// Whether `action` may be taken in `state` and, if so, the state it leads to and its effects.  Returns the
+/// error [`Game::can_place_bet`](../struct.Game.html#method.can_place_bet) or
+/// [`Game::can_play_card`](../struct.Game.html#method.can_play_card) would.
+///
+/// Only bets and cards are covered; the kitty exchange and the bid review are moved on by their own methods.
+pub fn transition(
+    state: &State,
+    action: &Action,
+    ctx: &RulesContext,
+) -> Result<(State, Effects), SpadesError> {
+    check(state, action, ctx)?;
+    let next = (ctx.player + 1) % 4;
+    Ok(match (*state, *action) {
+        (State::Betting(3), Action::Bet(bet)) => {
+            if ctx.bid_review {
+                (
+                    State::BidReview,
+                    Effects {
+                        next_player: Some(REVIEWER),
+                        ..Effects::default()
+                    },
+                )
+            } else {
+                let mut bets = ctx.bets.map(|bet| bet.unwrap_or(Bet::Nil));
+                bets[ctx.player] = bet;
+                let (state, player) = if !ctx.kitty.is_empty() {
+                    (State::Exchange, highest_bidder(&bets))
+                } else {
+                    (
+                        State::Trick(0),
+                        leader(ctx.rules, ctx.hands, ctx.spades_broken, 0),
+                    )
+                };
+                (
+                    state,
+                    Effects {
+                        next_player: Some(player),
+                        betting_completed: true,
+                        ..Effects::default()
+                    },
+                )
+            }
+        }
+        (State::Betting(placed), Action::Bet(_)) => (
+            State::Betting(placed + 1),
+            Effects {
+                next_player: Some(next),
+                ..Effects::default()
+            },
+        ),
+        (State::Trick(played), Action::Play(card)) => {
+            let rest: Vec<Card> = ctx.hand.iter().filter(|c| **c != card).cloned().collect();
+            let breaks_spades = ctx
+                .rules
+                .breaks_spades(&play_context(ctx, played, &rest), card);
+            if played < 3 {
+                return Ok((
+                    State::Trick(played + 1),
+                    Effects {
+                        next_player: Some(next),
+                        breaks_spades,
+                        ..Effects::default()
+                    },
+                ));
+            }
+            let mut trick = ctx.trick.to_vec();
+            trick.push(card);
+            let winner = trick_winner(next, 4, &trick);
+            let mut scoring = ctx.scoring.clone();
+            let round_completed = scoring.award_trick(winner).is_some();
+            let effects = Effects {
+                breaks_spades,
+                trick_winner: Some(winner),
+                round_completed,
+                ..Effects::default()
+            };
+            if scoring.is_over() {
+                (State::GameCompleted, effects)
+            } else if scoring.is_in_betting_stage() {
+                (
+                    State::Betting(0),
+                    Effects {
+                        next_player: Some(0),
+                        deal: true,
+                        ..effects
+                    },
+                )
+            } else {
+                let mut hands = ctx.hands;
+                hands[ctx.player] = &rest;
+                let spades_broken = ctx.spades_broken || breaks_spades;
+                (
+                    State::Trick(0),
+                    Effects {
+                        next_player: Some(leader(ctx.rules, hands, spades_broken, winner)),
+                        ..effects
+                    },
+                )
+            }
+        }
+        _ => return Err(SpadesError::InternalError),
+    })
+}
+
+/// The checks of [`transition`](fn.transition.html) alone, leaving out working out where the action leads.
+pub(crate) fn check(state: &State, action: &Action, ctx: &RulesContext) -> Result<(), SpadesError> {
+    let error = match (*state, *action) {
+        (State::GameNotStarted, _) => Some(SpadesError::GameNotStarted),
+        (State::GameCompleted, _) => Some(SpadesError::GameCompleted),
+        (State::Paused, _) => Some(SpadesError::GamePaused),
+        (State::Abandoned, _) => Some(SpadesError::GameAbandoned),
+        (State::Betting(_), Action::Bet(bet)) => {
+            if !bet.is_valid() {
+                Some(SpadesError::InvalidBet)
+            } else if bet == Bet::BlindNil && ctx.seen_hand {
+                Some(SpadesError::BetImproperSeenHand)
+            } else {
+                team_bid_error(ctx.minimum_team_bid, ctx.bets[(ctx.player + 2) % 4], bet)
+            }
+        }
+        (_, Action::Bet(_)) => Some(SpadesError::NotBettingStage {
+            next: ctx.players[ctx.player],
+        }),
+        (State::Trick(_), Action::Play(_)) if ctx.claim_pending => Some(SpadesError::ClaimPending),
+        (State::Trick(played), Action::Play(card)) => card_error(ctx, played, card),
+        (_, Action::Play(_)) => Some(SpadesError::NotTrickStage {
+            next: ctx.players[ctx.player],
+        }),
+    };
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Why the player to act can't play `card` from `ctx.hand` as the card after the first `played` of the trick,
+/// if they can't.
+pub(crate) fn card_error(ctx: &RulesContext, played: usize, card: Card) -> Option<SpadesError> {
+    if !ctx.hand.contains(&card) {
+        return Some(SpadesError::CardNotInHand);
+    }
+    let context = play_context(ctx, played, ctx.hand);
+    let verdict = ctx.rules.can_play_card(&context, card);
+    // rules leaving the player nothing at all to play can't hold up the game, so then any card will do
+    if verdict.is_some()
+        && ctx
+            .hand
+            .iter()
+            .all(|held| ctx.rules.can_play_card(&context, *held).is_some())
+    {
+        return None;
+    }
+    match (verdict, ctx.revoke_policy, context.leading_suit) {
+        (Some(_), RevokePolicy::Detect, Some(led)) if played > 0 && card.suit != led => {
+            // a revoke is judged as if the player held none of the suit led
+            let void: Vec<Card> = ctx.hand.iter().filter(|c| c.suit != led).cloned().collect();
+            ctx.rules
+                .can_play_card(&play_context(ctx, played, &void), card)
+        }
+        (verdict, _, _) => verdict,
+    }
+}
+
+fn play_context<'a>(ctx: &RulesContext<'a>, played: usize, hand: &'a [Card]) -> PlayContext<'a> {
+    PlayContext {
+        position_in_trick: played,
+        trick: ctx.trick,
+        leading_suit: ctx.leading_suit,
+        spades_broken: ctx.spades_broken,
+        hand,
+    }
+}
+
+impl Game {
+    /// What [`transition`](fn.transition.html) needs to know about the game besides its state.
+    pub fn rules_context(&self) -> RulesContext<'_> {
+        let current = &self.player[self.current_player_index];
+        RulesContext {
+            player: self.current_player_index,
+            players: [0, 1, 2, 3].map(|index| self.player[index].id),
+            hand: &current.hand,
+            hands: [0, 1, 2, 3].map(|index| &self.player[index].hand[..]),
+            seen_hand: current.seen_hand,
+            bets: self.round_bets,
+            minimum_team_bid: self.options.minimum_team_bid,
+            bid_review: self.options.bid_review,
+            kitty: &self.kitty,
+            trick: &self.current_trick,
+            leading_suit: self.leading_suit,
+            spades_broken: self.spades_broken,
+            claim_pending: self.pending_claim.is_some(),
+            revoke_policy: self.revoke_policy,
+            rules: self.rules.get(),
+            scoring: &self.scoring,
+        }
+    }
+
+    /// Panics unless the game has moved as [`transition`](fn.transition.html) said it would.  A new deal may
+    /// have put the dealer to the kitty exchange.
+    #[cfg(any(test, feature = "debug-invariants"))]
+    pub(crate) fn assert_transition(&self, expected: (State, Effects)) {
+        let (state, effects) = expected;
+        let player = match self.state {
+            State::GameCompleted => None,
+            _ => Some(self.current_player_index),
+        };
+        let dealt_exchange = effects.deal && self.state == State::Exchange;
+        assert!(
+            dealt_exchange || (state, effects.next_player) == (self.state, player),
+            "game moved to {:?} with player {:?} to act; transition said {:?}, {:?}",
+            self.state,
+            player,
+            state,
+            effects
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{transition, Effects};
+    use ml::Action;
+    use test_support::{seated_game, seeded_game};
+    use {Bet, KittyConfig, KittyTaker, SpadesError, State, Uid};
+
+    #[test]
+    fn test_transition_follows_a_game() {
+        let mut g = seeded_game(6);
+        for _ in 0..4 {
+            let action = Action::Bet(Bet::Amount(3));
+            let expected = transition(&g.state(), &action, &g.rules_context()).unwrap();
+            g.place_bet(Bet::Amount(3));
+            assert_eq!(expected.0, g.state());
+        }
+        let mut tricks = 0;
+        let mut rounds = 0;
+        while g.state() != State::GameCompleted && rounds < 2 {
+            if g.state().is_betting() {
+                g.place_bet(Bet::Amount(3));
+                continue;
+            }
+            let card = g.playable_cards().next().unwrap();
+            let (state, effects) =
+                transition(&g.state(), &Action::Play(card), &g.rules_context()).unwrap();
+            let before = g.scoring.player_tricks();
+            g.play_card(card);
+            assert_eq!(state, g.state());
+            if let Some(winner) = effects.trick_winner {
+                tricks += 1;
+                if !effects.round_completed {
+                    assert_eq!(before[winner] + 1, g.scoring.player_tricks()[winner]);
+                }
+            }
+            if effects.round_completed {
+                assert!(effects.deal || state == State::GameCompleted);
+                assert_eq!(13, tricks);
+                tricks = 0;
+                rounds += 1;
+            }
+        }
+        assert_eq!(2, rounds);
+    }
+
+    #[test]
+    fn test_transition_checks_like_the_game() {
+        let mut g = seeded_game(6);
+        let card = g.current_hand().unwrap()[0];
+        assert_eq!(
+            Err(SpadesError::NotTrickStage { next: Uid(10) }),
+            transition(&g.state(), &Action::Play(card), &g.rules_context())
+        );
+        assert_eq!(
+            Err(SpadesError::InvalidBet),
+            transition(
+                &g.state(),
+                &Action::Bet(Bet::Amount(14)),
+                &g.rules_context()
+            )
+        );
+        for _ in 0..4 {
+            g.place_bet(Bet::Amount(3));
+        }
+        for card in g.current_hand().unwrap().to_vec().iter() {
+            assert_eq!(
+                g.can_play_card(*card),
+                transition(&g.state(), &Action::Play(*card), &g.rules_context()).err()
+            );
+        }
+        assert_eq!(
+            Err(SpadesError::GameNotStarted),
+            transition(
+                &State::GameNotStarted,
+                &Action::Bet(Bet::Nil),
+                &g.rules_context()
+            )
+        );
+    }
+
+    #[test]
+    fn test_last_bet_leads_to_the_kitty() {
+        let mut g = seated_game();
+        g.set_kitty_config(Some(KittyConfig {
+            size: 4,
+            taker: KittyTaker::HighestBidder,
+        }))
+        .unwrap();
+        g.start_game();
+        for bet in [Bet::Amount(2), Bet::Amount(5), Bet::Nil] {
+            g.place_bet(bet);
+        }
+        let expected = (
+            State::Exchange,
+            Effects {
+                next_player: Some(1),
+                betting_completed: true,
+                ..Effects::default()
+            },
+        );
+        assert_eq!(
+            Ok(expected),
+            transition(&g.state(), &Action::Bet(Bet::Amount(4)), &g.rules_context())
+        );
+    }
+}
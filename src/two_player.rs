@@ -0,0 +1,174 @@
+use cards::{full_deck, Card};
+use result::SpadesError;
+use shuffler::{Shuffler, ThreadRngShuffler};
+
+/// The draw-and-discard deal of two-handed Spades.
+///
+/// The players take turns drawing from the stock, player 0 first.  A player looks at the top card and
+/// either keeps it, in which case they look at the next card and discard it, or discards it, in which case
+/// they must keep the next one.  Once the stock is used up each player holds 13 cards and 26 have been
+/// discarded.
+///
+/// A two-handed [`Table`](struct.Table.html) deals this way; `DrawDeal` can also be used on its own.
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct DrawDeal {
+    stock: Vec<Card>,
+    hands: [Vec<Card>; 2],
+    discards: Vec<Card>,
+    current_player: usize,
+}
+
+impl Default for DrawDeal {
+    fn default() -> Self {
+        DrawDeal::shuffled(&ThreadRngShuffler)
+    }
+}
+
+impl DrawDeal {
+    /// A deal from a whole deck, shuffled by `shuffler`.
+    pub fn shuffled(shuffler: &dyn Shuffler) -> DrawDeal {
+        let mut stock = full_deck().to_vec();
+        shuffler.shuffle(&mut stock);
+        DrawDeal::with_stock(stock)
+    }
+
+    /// A deal from `stock`, whose last card is the top of the stock.  Returns `SpadesError::InvalidDeal` unless
+    /// `stock` holds every card of the deck once.
+    pub fn from_stock(stock: Vec<Card>) -> Result<DrawDeal, SpadesError> {
+        let mut sorted = stock.clone();
+        sorted.sort();
+        let mut deck = full_deck();
+        deck.sort();
+        if sorted[..] != deck[..] {
+            return Err(SpadesError::InvalidDeal);
+        }
+        Ok(DrawDeal::with_stock(stock))
+    }
+
+    fn with_stock(stock: Vec<Card>) -> DrawDeal {
+        DrawDeal {
+            stock,
+            hands: [vec![], vec![]],
+            discards: vec![],
+            current_player: 0,
+        }
+    }
+
+    /// Player 0 or 1, whose turn it is to draw.
+    pub fn current_player(&self) -> usize {
+        self.current_player
+    }
+
+    /// The top card of the stock, which the current player is deciding whether to keep, or `None` once the
+    /// deal is complete.
+    pub fn drawn_card(&self) -> Option<Card> {
+        self.stock.last().cloned()
+    }
+
+    /// Keep or discard the drawn card.  The current player takes the next card as well, discarding it if
+    /// they kept the first, and keeping it otherwise; that card is returned, since they have seen it.
+    pub fn take_drawn_card(&mut self, keep: bool) -> Result<Card, SpadesError> {
+        if self.is_complete() {
+            return Err(SpadesError::ImproperGameStage);
+        }
+        let first = self.stock.pop().unwrap();
+        let second = self.stock.pop().unwrap();
+        let (kept, discarded) = if keep {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        self.hands[self.current_player].push(kept);
+        self.discards.push(discarded);
+        self.current_player = 1 - self.current_player;
+        Ok(second)
+    }
+
+    /// The cards player 0 or 1 has kept so far.
+    pub fn hand(&self, player: usize) -> &[Card] {
+        &self.hands[player]
+    }
+
+    /// How many cards have been discarded so far.
+    pub fn discard_count(&self) -> usize {
+        self.discards.len()
+    }
+
+    /// Whether the stock is used up.
+    pub fn is_complete(&self) -> bool {
+        self.stock.is_empty()
+    }
+
+    /// Both players' sorted hands, once the deal is complete.
+    pub fn into_hands(self) -> Result<[Vec<Card>; 2], SpadesError> {
+        if !self.is_complete() {
+            return Err(SpadesError::ImproperGameStage);
+        }
+        let mut hands = self.hands;
+        hands[0].sort();
+        hands[1].sort();
+        Ok(hands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DrawDeal;
+    use cards::new_deck;
+    use SpadesError;
+
+    #[test]
+    fn test_keeping_discards_the_next_card() {
+        let stock = new_deck();
+        let top = stock[51];
+        let next = stock[50];
+        let mut deal = DrawDeal::from_stock(stock).unwrap();
+        assert_eq!(Some(top), deal.drawn_card());
+        assert_eq!(Ok(next), deal.take_drawn_card(true));
+        assert_eq!(&[top], deal.hand(0));
+        assert_eq!(1, deal.current_player());
+
+        let top = deal.drawn_card().unwrap();
+        let next = deal.take_drawn_card(false).unwrap();
+        assert_eq!(&[next], deal.hand(1));
+        assert_ne!(top, next);
+        assert_eq!(2, deal.discard_count());
+    }
+
+    #[test]
+    fn test_deal_gives_each_player_thirteen_cards() {
+        let mut deal = DrawDeal::default();
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            deal.clone().into_hands()
+        );
+        let mut keep = true;
+        while !deal.is_complete() {
+            deal.take_drawn_card(keep).unwrap();
+            keep = !keep;
+        }
+        assert_eq!(None, deal.drawn_card());
+        assert_eq!(
+            Err(SpadesError::ImproperGameStage),
+            deal.take_drawn_card(true)
+        );
+        assert_eq!(26, deal.discard_count());
+        let hands = deal.into_hands().unwrap();
+        assert_eq!(13, hands[0].len());
+        assert_eq!(13, hands[1].len());
+    }
+
+    #[test]
+    fn test_stock_must_be_one_deck() {
+        let mut stock = new_deck();
+        stock.pop();
+        assert_eq!(
+            Err(SpadesError::InvalidDeal),
+            DrawDeal::from_stock(stock.clone())
+        );
+        stock.push(stock[0]);
+        assert_eq!(Err(SpadesError::InvalidDeal), DrawDeal::from_stock(stock));
+    }
+}
@@ -0,0 +1,301 @@
+//! No sequence of calls to a `Game`, however wrong, should panic: a call that isn't allowed returns an error
+//! or does nothing.  These tests make many seeded random sequences of calls with random arguments, mixed with
+//! enough legal bets and cards to reach every stage of the game.
+
+extern crate rand;
+extern crate serde_json;
+extern crate spades;
+
+use rand::prng::XorShiftRng;
+use rand::{Rng, SeedableRng};
+use serde_json::{json, Value};
+use spades::ml::Action;
+use spades::{
+    full_deck, score_rounds, transition, AbandonReason, Bet, Card, Game, GameLength, GameOptions,
+    KittyConfig, KittyTaker, RoundInput, Uid,
+};
+
+const PLAYERS: [Uid; 4] = [Uid(10), Uid(11), Uid(12), Uid(13)];
+
+fn any_player(rng: &mut XorShiftRng) -> Uid {
+    if rng.gen_bool(0.9) {
+        PLAYERS[rng.gen_range(0, 4)]
+    } else {
+        Uid(rng.gen_range(0, 100))
+    }
+}
+
+fn any_bet(rng: &mut XorShiftRng) -> Bet {
+    match rng.gen_range(0, 6) {
+        0 => Bet::Nil,
+        1 => Bet::BlindNil,
+        2 => Bet::Board,
+        3 => Bet::TenForTwoHundred,
+        4 => Bet::Amount(rng.gen()),
+        _ => Bet::Amount(rng.gen_range(1, 5)),
+    }
+}
+
+fn any_card(rng: &mut XorShiftRng) -> Card {
+    full_deck()[rng.gen_range(0, 52)]
+}
+
+fn any_cards(rng: &mut XorShiftRng) -> Vec<Card> {
+    (0..rng.gen_range(0, 6)).map(|_| any_card(rng)).collect()
+}
+
+fn any_options(rng: &mut XorShiftRng) -> GameOptions {
+    GameOptions {
+        bid_review: rng.gen_bool(0.3),
+        minimum_team_bid: if rng.gen_bool(0.3) {
+            Some(rng.gen_range(0, 20))
+        } else {
+            None
+        },
+        length: match rng.gen_range(0, 4) {
+            0 => GameLength::SingleRound,
+            1 => GameLength::FixedRounds(rng.gen_range(0, 3)),
+            2 => GameLength::TargetScore(rng.gen_range(-100, 300)),
+            _ => GameLength::default(),
+        },
+        ..GameOptions::default()
+    }
+}
+
+/// Makes one random call, or a legal move that keeps the game going.
+fn step(g: &mut Game, rng: &mut XorShiftRng) {
+    let team = rng.gen_range(0, 4);
+    match rng.gen_range(0, 30) {
+        0 => g.start_game(),
+        1 => {
+            g.place_bet(any_bet(rng));
+        }
+        2 => {
+            g.play_card(any_card(rng));
+        }
+        3..=9 => {
+            let playable = g.playable_cards().next();
+            if g.state().is_betting() {
+                g.place_bet(Bet::Amount(rng.gen_range(1, 5)));
+            } else if let Some(card) = playable {
+                g.play_card(card);
+            } else {
+                let _ = g.review_bet(None);
+                let hand = g.current_hand().map(|hand| hand.to_vec());
+                if let Ok(hand) = hand {
+                    let count = g.kitty_config().map_or(0, |config| config.size);
+                    let _ = g.discard(&hand[..count.min(hand.len())]);
+                }
+            }
+        }
+        10 => {
+            let _ = g.pause();
+        }
+        11 => {
+            let _ = g.resume();
+        }
+        12 if rng.gen_bool(0.01) => {
+            let _ = g.abandon(AbandonReason::PlayerLeft(any_player(rng)));
+        }
+        13 => {
+            let _ = g.team_individual_round_score(team);
+            let _ = g.team_all_rounds_score(team);
+            let _ = g.team_tricks_won(team);
+            let _ = g.team_individual_round_bags(team);
+            let _ = g.team_all_rounds_bags(team);
+            let _ = g.contract_status(team);
+            let hypothetical = if rng.gen_bool(0.5) {
+                Some([rng.gen(), rng.gen(), rng.gen(), rng.gen()])
+            } else {
+                None
+            };
+            let _ = g.projected_round_score(team, hypothetical);
+        }
+        14 => {
+            let _ = g.declare_misdeal(&any_player(rng));
+        }
+        15 => {
+            let _ = g.claim_remaining(&any_player(rng));
+        }
+        16 => {
+            let _ = g.respond_to_claim(&any_player(rng), rng.gen());
+        }
+        17 => {
+            let _ = g.concede(team);
+        }
+        18 => {
+            let _ = g.discard(&any_cards(rng));
+        }
+        19 => {
+            let amendment = if rng.gen() { Some(any_bet(rng)) } else { None };
+            let _ = g.review_bet(amendment);
+        }
+        20 => {
+            let taker = if rng.gen() {
+                KittyTaker::Dealer
+            } else {
+                KittyTaker::HighestBidder
+            };
+            let size = rng.gen_range(0, 60);
+            let _ = g.set_kitty_config(Some(KittyConfig { size, taker }));
+        }
+        21 => {
+            let _ = g.set_options(any_options(rng));
+            let _ = g.amend_options(any_options(rng));
+        }
+        22 => {
+            let json = serde_json::to_string(&*g).unwrap();
+            // whatever the calls did, the game loads again
+            *g = serde_json::from_str::<Game>(&json).unwrap();
+        }
+        23 => {
+            let player = any_player(rng);
+            let _ = g.player_view(&player);
+            let _ = g.reveal_hand(&player);
+            let _ = g.partner_bid(&player);
+            let _ = g.hand_from_player_id(player);
+            let _ = g.legal_actions(&player);
+        }
+        24 => {
+            let _ = g.snapshot();
+            let _ = g.scoreboard();
+            let _ = g.round_history();
+            let _ = g.known_voids();
+            let _ = g.currently_winning_player();
+            let _ = g.hand_sizes();
+            let _ = g.diagnostics();
+            let _ = g.result();
+            let _ = g.winner_ids();
+            let _ = g.last_trick_replay();
+        }
+        25 => {
+            let _ = g.carry_over_bags([rng.gen(), rng.gen()]);
+        }
+        26 => {
+            let _ = g.apply_adjustment(team, rng.gen_range(-1000, 1000), "ruling");
+        }
+        27 => {
+            let action = if rng.gen() {
+                Action::Bet(any_bet(rng))
+            } else {
+                Action::Play(any_card(rng))
+            };
+            let _ = transition(&g.state(), &action, &g.rules_context());
+        }
+        28 => {
+            let checkpoint = g.checkpoint();
+            let mut events = g.events_since(checkpoint.seq);
+            let _ = Game::replay(&checkpoint, &events);
+            events.reverse();
+            let _ = Game::replay(&checkpoint, &events);
+        }
+        _ => {
+            let _ = g.determinize(&any_player(rng), rng);
+        }
+    }
+}
+
+#[test]
+fn random_calls_never_panic() {
+    let mut scored = 0;
+    for seed in 0..100 {
+        let mut rng = XorShiftRng::seed_from_u64(seed);
+        let mut g = Game::default();
+        if rng.gen_bool(0.9) {
+            g.assign_players(Uid(seed + 1), PLAYERS);
+        }
+        g.set_shuffle_seed(Some(seed));
+        for _ in 0..1000 {
+            step(&mut g, &mut rng);
+        }
+        if !g.round_scores().is_empty() {
+            scored += 1;
+        }
+    }
+    // the legal moves mixed in get games through whole rounds
+    assert!(scored > 40, "only {} games scored a round", scored);
+}
+
+#[test]
+fn scoring_impossible_rounds_never_panics() {
+    let mut rng = XorShiftRng::seed_from_u64(7);
+    let batch: Vec<RoundInput> = (0..2000)
+        .map(|_| RoundInput {
+            bets: [
+                any_bet(&mut rng),
+                any_bet(&mut rng),
+                any_bet(&mut rng),
+                any_bet(&mut rng),
+            ],
+            tricks: [rng.gen(), rng.gen(), rng.gen(), rng.gen()],
+            cumulative_bags: [rng.gen(), rng.gen()],
+        })
+        .collect();
+    assert_eq!(batch.len(), score_rounds(&batch).len());
+}
+
+/// A game saved as JSON to be tampered with, after `bets` bets and `cards` cards.
+fn saved_game(bets: usize, cards: usize) -> Value {
+    let mut g = Game::default();
+    g.assign_players(Uid(1), PLAYERS);
+    g.set_shuffle_seed(Some(5));
+    g.start_game();
+    for _ in 0..bets {
+        g.place_bet(Bet::Amount(3));
+    }
+    for _ in 0..cards {
+        let card = g.playable_cards().next().unwrap();
+        g.play_card(card);
+    }
+    serde_json::to_value(&g).unwrap()
+}
+
+#[test]
+fn saves_out_of_range_are_rejected() {
+    assert!(serde_json::from_value::<Game>(saved_game(4, 1)).is_ok());
+    let tampered: [(&str, Value); 6] = [
+        ("/current_player_index", json!(9)),
+        ("/forfeited_team", json!(2)),
+        ("/state", json!({ "Trick": 7 })),
+        ("/paused_state", json!({ "Betting": 4 })),
+        ("/scoring/tricks_per_round", json!(0)),
+        ("/scoring/tricks_per_round", json!(40)),
+    ];
+    for (pointer, value) in tampered.iter() {
+        let mut json = saved_game(4, 1);
+        *json.pointer_mut(pointer).unwrap() = value.clone();
+        assert!(
+            serde_json::from_value::<Game>(json).is_err(),
+            "{} of {} was loaded",
+            pointer,
+            value
+        );
+    }
+}
+
+#[test]
+fn tampered_saves_never_panic() {
+    let mut rng = XorShiftRng::seed_from_u64(11);
+    let pointers = [
+        "/current_player_index",
+        "/forfeited_team",
+        "/scoring/tricks_per_round",
+        "/scoring/trick",
+        "/scoring/round",
+    ];
+    for _ in 0..400 {
+        let (bets, cards) = if rng.gen() {
+            (rng.gen_range(0, 4), 0)
+        } else {
+            (4, rng.gen_range(0, 52))
+        };
+        let mut json = saved_game(bets, cards);
+        let pointer = pointers[rng.gen_range(0, pointers.len())];
+        *json.pointer_mut(pointer).unwrap() = json!(rng.gen_range(0, 20));
+        if let Ok(mut g) = serde_json::from_value::<Game>(json) {
+            for _ in 0..100 {
+                step(&mut g, &mut rng);
+            }
+        }
+    }
+}
@@ -1,3 +1,13 @@
+#![allow(
+    clippy::clone_on_copy,
+    clippy::needless_range_loop,
+    clippy::ptr_arg,
+    clippy::collapsible_else_if,
+    clippy::manual_unwrap_or,
+    clippy::manual_unwrap_or_default,
+    clippy::needless_borrow
+)]
+
 extern crate spades;
 
 extern crate rand;
@@ -16,7 +26,7 @@ fn main() {
 
         if let State::Trick(_playerindex) = g.state() {
             assert!(g.current_hand().is_ok());
-            let mut hand = g.current_hand().ok().unwrap().clone();
+            let mut hand = g.current_hand().ok().unwrap().to_vec();
 
             let leading_suit_opt = g.leading_suit().unwrap();
             let x = get_valid_card_index(leading_suit_opt, &hand);
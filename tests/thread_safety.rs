@@ -0,0 +1,210 @@
+//! Every public type of the crate is `Send` and `Sync`, so games and everything around them can be moved
+//! between threads and shared behind locks, as the crate documentation promises.  The assertions only need to
+//! compile: a type losing either would fail the build here rather than in a server using it.
+
+extern crate spades;
+
+use spades::analysis::{AnalysisReport, HandAnalysis, TrickSwing};
+use spades::export::{PlayerStats, RoundSummary};
+use spades::fairness::{DealCommitment, DealReveal};
+use spades::ml::Action;
+use spades::search::{CardStats, Sample, SearchTrace};
+use spades::store::{MemoryStore, StoreError, StoredGame};
+use spades::*;
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn assert_send_sync<T: Send + Sync + ?Sized>() {}
+
+#[test]
+fn players_on_their_own_threads_share_a_game() {
+    let players = [Uid(10), Uid(11), Uid(12), Uid(13)];
+    let mut g = Game::default();
+    g.assign_players(Uid(1), players);
+    g.set_shuffle_seed(Some(3));
+    g.start_game();
+    let game = Arc::new(Mutex::new(g));
+    let seats: Vec<_> = players
+        .iter()
+        .map(|player| {
+            let (game, player) = (Arc::clone(&game), *player);
+            thread::spawn(move || loop {
+                let mut g = game.lock().unwrap();
+                if !g.round_scores().is_empty() {
+                    return;
+                }
+                if g.current_player_id() == Ok(player) {
+                    if g.state().is_betting() {
+                        g.place_bet(Bet::Amount(3));
+                    } else {
+                        let card = g.playable_cards().next().unwrap();
+                        g.play_card(card);
+                    }
+                }
+                drop(g);
+                thread::yield_now();
+            })
+        })
+        .collect();
+    for seat in seats {
+        seat.join().unwrap();
+    }
+    assert_eq!(1, game.lock().unwrap().round_scores().len());
+}
+
+#[test]
+fn game_and_its_parts_are_send_and_sync() {
+    assert_send_sync::<Game>();
+    assert_send_sync::<Uid>();
+    assert_send_sync::<State>();
+    assert_send_sync::<DetailedState>();
+    assert_send_sync::<Card>();
+    assert_send_sync::<Suit>();
+    assert_send_sync::<Rank>();
+    assert_send_sync::<Color>();
+    assert_send_sync::<Deck>();
+    assert_send_sync::<Deal>();
+    assert_send_sync::<DealConfig>();
+    assert_send_sync::<DealPattern>();
+    assert_send_sync::<DrawDeal>();
+    assert_send_sync::<Bet>();
+    assert_send_sync::<BetResult>();
+    assert_send_sync::<PlayCardResult>();
+    assert_send_sync::<Seat>();
+    assert_send_sync::<SpadesError>();
+    assert_send_sync::<GameOptions>();
+    assert_send_sync::<GameLength>();
+    assert_send_sync::<KittyConfig>();
+    assert_send_sync::<KittyTaker>();
+    assert_send_sync::<MisdealConfig>();
+    assert_send_sync::<RevokePolicy>();
+    assert_send_sync::<RevokePenalty>();
+    assert_send_sync::<TimeoutPolicy>();
+    assert_send_sync::<PlayerInfo>();
+    assert_send_sync::<TeamAssignment>();
+    assert_send_sync::<Adjustment>();
+    assert_send_sync::<AbandonReason>();
+    assert_send_sync::<PrematureEnd>();
+    assert_send_sync::<Claim>();
+    assert_send_sync::<ClaimResponse>();
+    assert_send_sync::<Command>();
+    assert_send_sync::<Action>();
+}
+
+#[test]
+fn rules_and_shufflers_are_send_and_sync() {
+    assert_send_sync::<dyn CardPlayRules>();
+    assert_send_sync::<StandardRules>();
+    assert_send_sync::<SpadesLeadRules>();
+    assert_send_sync::<MustTrumpRules>();
+    assert_send_sync::<PlayContext<'static>>();
+    assert_send_sync::<RulesContext<'static>>();
+    assert_send_sync::<Effects>();
+    assert_send_sync::<dyn ScoringRules>();
+    assert_send_sync::<StandardScoring>();
+    assert_send_sync::<dyn Shuffler>();
+    assert_send_sync::<CryptoShuffler>();
+    assert_send_sync::<SeededShuffler>();
+    assert_send_sync::<ThreadRngShuffler>();
+}
+
+#[test]
+fn results_and_records_are_send_and_sync() {
+    assert_send_sync::<GameSnapshot>();
+    assert_send_sync::<SnapshotDelta>();
+    assert_send_sync::<GameEvent>();
+    assert_send_sync::<SequencedEvent>();
+    assert_send_sync::<Diagnostics>();
+    assert_send_sync::<Checkpoint>();
+    assert_send_sync::<GameResult>();
+    assert_send_sync::<RoundHistory>();
+    assert_send_sync::<TrickHistory>();
+    assert_send_sync::<TrickPlay>();
+    assert_send_sync::<TrickPlays>();
+    assert_send_sync::<Revoke>();
+    assert_send_sync::<Scoreboard>();
+    assert_send_sync::<ScoreboardRow>();
+    assert_send_sync::<RoundInput>();
+    assert_send_sync::<RoundOutcome>();
+    assert_send_sync::<RoundProjection>();
+    assert_send_sync::<TeamRoundScore>();
+    assert_send_sync::<TeamBid>();
+    assert_send_sync::<BagSettlement>();
+    assert_send_sync::<ContractStatus>();
+    assert_send_sync::<NilStatus>();
+    assert_send_sync::<NilTricks>();
+    assert_send_sync::<RoundSummary>();
+    assert_send_sync::<PlayerStats>();
+    assert_send_sync::<DealCommitment>();
+    assert_send_sync::<DealReveal>();
+    assert_send_sync::<Sample>();
+    assert_send_sync::<CardStats>();
+    assert_send_sync::<SearchTrace>();
+    assert_send_sync::<StoredGame>();
+    assert_send_sync::<StoreError>();
+    assert_send_sync::<MemoryStore>();
+}
+
+#[test]
+fn tables_and_competitions_are_send_and_sync() {
+    assert_send_sync::<Table>();
+    assert_send_sync::<TableState>();
+    assert_send_sync::<TableVariant>();
+    assert_send_sync::<Rubber>();
+    assert_send_sync::<RubberConfig>();
+    assert_send_sync::<RubberGame>();
+    assert_send_sync::<Season>();
+    assert_send_sync::<Match>();
+    assert_send_sync::<MatchResult>();
+    assert_send_sync::<Standing>();
+    assert_send_sync::<StandingsConfig>();
+    assert_send_sync::<Tiebreaker>();
+    assert_send_sync::<DuplicateSession>();
+    assert_send_sync::<BoardResult>();
+    assert_send_sync::<Tournament>();
+    assert_send_sync::<TournamentResults>();
+    assert_send_sync::<EntrantStats>();
+    assert_send_sync::<AgentFactory>();
+}
+
+#[test]
+fn views_and_bots_are_send_and_sync() {
+    assert_send_sync::<PlayerView<'static>>();
+    assert_send_sync::<SeatView>();
+    assert_send_sync::<RelativeTable>();
+    assert_send_sync::<HandOrder>();
+    assert_send_sync::<HeuristicBot>();
+    assert_send_sync::<RandomBot>();
+    assert_send_sync::<BotConfig>();
+    assert_send_sync::<CardDisplay>();
+    assert_send_sync::<CardStyle>();
+    assert_send_sync::<HandDisplay<'static>>();
+    assert_send_sync::<Language>();
+    assert_send_sync::<HandAnalysis>();
+    assert_send_sync::<AnalysisReport>();
+    assert_send_sync::<TrickSwing>();
+}
+
+#[cfg(feature = "annotations")]
+#[test]
+fn annotations_are_send_and_sync() {
+    assert_send_sync::<Annotation>();
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn the_http_server_is_send_and_sync() {
+    use spades::http::{CreateGame, GameCreated, GameServer, HttpError, HttpResponse};
+    assert_send_sync::<GameServer>();
+    assert_send_sync::<CreateGame>();
+    assert_send_sync::<GameCreated>();
+    assert_send_sync::<HttpError>();
+    assert_send_sync::<HttpResponse>();
+}
+
+#[cfg(feature = "redis")]
+#[test]
+fn the_redis_store_is_send_and_sync() {
+    assert_send_sync::<spades::store::RedisStore>();
+}